@@ -14,31 +14,34 @@ _yx_completions() {
         return 0
     fi
 
+    # Yak names may contain spaces, so split candidates on newlines only
+    local IFS=$'\n'
+
     # Complete yak names based on command
     case "$cmd" in
         done)
             # Check if --undo flag is present
             if [[ " ${COMP_WORDS[@]} " =~ " --undo " ]]; then
                 # After --undo, complete with done yaks
-                COMPREPLY=($(compgen -W "$(yx ls --format plain --only done 2>/dev/null)" -- "$cur"))
+                COMPREPLY=($(yx __complete --only done -- "$cur" 2>/dev/null))
             elif [ "$prev" = "done" ] && [ "$cur" = "--" ]; then
                 # Offer --undo flag
                 COMPREPLY=($(compgen -W "--undo" -- "$cur"))
             else
                 # Complete with incomplete yaks
-                COMPREPLY=($(compgen -W "$(yx ls --format plain --only not-done 2>/dev/null)" -- "$cur"))
+                COMPREPLY=($(yx __complete --only not-done -- "$cur" 2>/dev/null))
             fi
             ;;
         rm|context|move|mv)
             # Complete with all yaks
-            COMPREPLY=($(compgen -W "$(yx ls --format plain 2>/dev/null)" -- "$cur"))
+            COMPREPLY=($(yx __complete -- "$cur" 2>/dev/null))
             ;;
         context)
             # Offer --show and --edit flags
             if [ "$prev" = "context" ]; then
-                COMPREPLY=($(compgen -W "--show --edit $(yx ls --format plain 2>/dev/null)" -- "$cur"))
+                COMPREPLY=($(compgen -W $'--show\n--edit' -- "$cur") $(yx __complete -- "$cur" 2>/dev/null))
             else
-                COMPREPLY=($(compgen -W "$(yx ls --format plain 2>/dev/null)" -- "$cur"))
+                COMPREPLY=($(yx __complete -- "$cur" 2>/dev/null))
             fi
             ;;
     esac
@@ -71,3 +71,46 @@ new"
 # Nested context"
   End
 End
+
+Describe 'yx context --diff=remote'
+  setup_repos() {
+    ORIGIN=$(mktemp -d)
+    setup_bare_repo "$ORIGIN"
+
+    USER1=$(mktemp -d)
+    setup_test_repo "$USER1" "user1@example.com" "User 1" "$ORIGIN"
+    echo "# Test Repo" > "$USER1/README.md"
+    git -C "$USER1" add README.md
+    git -C "$USER1" commit -m "Initial commit" --quiet
+    git -C "$USER1" push -u origin main --quiet
+  }
+
+  cleanup_repos() {
+    rm -rf "$ORIGIN" "$USER1"
+  }
+
+  BeforeEach 'setup_repos'
+  AfterEach 'cleanup_repos'
+
+  It 'diffs against the copy origin has after a sync'
+    GIT_WORK_TREE="$USER1" "yx" add "auth"
+    echo "tokens" | GIT_WORK_TREE="$USER1" "yx" context "auth"
+    sh -c "cd '$USER1' && GIT_WORK_TREE='$USER1' yx sync" 2>&1
+    printf 'tokens\nexpiry\n' | GIT_WORK_TREE="$USER1" "yx" context "auth"
+
+    When call sh -c "cd '$USER1' && GIT_WORK_TREE='$USER1' yx context auth --diff=remote"
+    The output should include " tokens"
+    The output should include "+expiry"
+  End
+
+  It 'reads the revision after a space'
+    GIT_WORK_TREE="$USER1" "yx" add "auth"
+    echo "tokens" | GIT_WORK_TREE="$USER1" "yx" context "auth"
+    sh -c "cd '$USER1' && GIT_WORK_TREE='$USER1' yx sync" 2>&1
+    echo "expiry" | GIT_WORK_TREE="$USER1" "yx" context "auth"
+
+    When call sh -c "cd '$USER1' && GIT_WORK_TREE='$USER1' yx context auth --diff remote"
+    The output should include "-tokens"
+    The output should include "+expiry"
+  End
+End
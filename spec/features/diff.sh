@@ -0,0 +1,65 @@
+# shellcheck shell=bash
+# shellcheck disable=SC1010,SC2034
+Describe 'yx diff'
+  setup_repos() {
+    ORIGIN=$(mktemp -d)
+    setup_bare_repo "$ORIGIN"
+
+    USER1=$(mktemp -d)
+    setup_test_repo "$USER1" "user1@example.com" "User 1" "$ORIGIN"
+    echo "# Test Repo" > "$USER1/README.md"
+    git -C "$USER1" add README.md
+    git -C "$USER1" commit -m "Initial commit" --quiet
+    git -C "$USER1" push -u origin main --quiet
+
+    USER2=$(mktemp -d)
+    git clone --quiet "$ORIGIN" "$USER2"
+    git -C "$USER2" config user.email "user2@example.com"
+    git -C "$USER2" config user.name "User 2"
+  }
+
+  cleanup_repos() {
+    rm -rf "$ORIGIN" "$USER1" "$USER2"
+  }
+
+  BeforeEach 'setup_repos'
+  AfterEach 'cleanup_repos'
+
+  It 'previews incoming yaks without changing anything'
+    GIT_WORK_TREE="$USER1" "yx" add "shared yak"
+    GIT_WORK_TREE="$USER1" "yx" add "finished yak"
+    sh -c "cd '$USER1' && GIT_WORK_TREE='$USER1' yx sync" 2>&1
+    sh -c "cd '$USER2' && GIT_WORK_TREE='$USER2' yx sync" 2>&1
+
+    GIT_WORK_TREE="$USER1" "yx" add "new yak"
+    GIT_WORK_TREE="$USER1" "yx" done "finished yak"
+    GIT_WORK_TREE="$USER1" "yx" rm "shared yak"
+    sh -c "cd '$USER1' && GIT_WORK_TREE='$USER1' yx sync" 2>&1
+
+    When call sh -c "cd '$USER2' && GIT_WORK_TREE='$USER2' yx diff"
+    The output should include "+ added      new yak"
+    The output should include "x completed  finished yak"
+    The output should include "- removed    shared yak"
+    The error should include "Sync would change 3 yaks"
+    The path "$USER2/.yaks/shared yak" should be directory
+  End
+
+  It 'reports nothing to do when up to date'
+    GIT_WORK_TREE="$USER1" "yx" add "shared yak"
+    sh -c "cd '$USER1' && GIT_WORK_TREE='$USER1' yx sync" 2>&1
+
+    When call sh -c "cd '$USER1' && GIT_WORK_TREE='$USER1' yx diff"
+    The error should equal "Sync would not change any yaks"
+  End
+
+  It 'leaves archived yaks out'
+    GIT_WORK_TREE="$USER1" "yx" add "one"
+    GIT_WORK_TREE="$USER1" "yx" add "two"
+    GIT_WORK_TREE="$USER1" "yx" archive "two"
+    sh -c "cd '$USER1' && GIT_WORK_TREE='$USER1' yx sync" 2>&1
+
+    When call sh -c "cd '$USER2' && GIT_WORK_TREE='$USER2' yx diff"
+    The output should equal "+ added      one"
+    The error should include "Sync would change 1 yak"
+  End
+End
@@ -10,7 +10,7 @@ Describe 'yx done'
       yx done 'Fix the bug'
       yx list
     "
-    The output should include $'\e[90m- [x] Fix the bug\e[0m'
+    The output should include "- [x] Fix the bug"
   End
 
   It 'shows error when marking non-existent yak as done'
@@ -28,7 +28,7 @@ Describe 'yx done'
       yx list
     "
     The output should include "- [ ] Fix the bug"
-    The output should include $'\e[90m- [x] Write the docs\e[0m'
+    The output should include "- [x] Write the docs"
     The output should include "- [ ] Add tests"
   End
 
@@ -46,7 +46,7 @@ Describe 'yx done'
       yx done 'x marks the spot'
       yx list
     "
-    The output should include $'\e[90m- [x] x marks the spot\e[0m'
+    The output should include "- [x] x marks the spot"
   End
 
   It 'unmarks a done yak with --undo flag'
@@ -67,7 +67,7 @@ Describe 'yx done'
       yx list
     "
     The line 1 should equal "- [ ] parent"
-    The line 2 should equal $'\e[90m  - [x] child\e[0m'
+    The line 2 should equal "  - [x] child"
   End
 
   It 'errors when marking a parent yak as done with incomplete children'
@@ -89,9 +89,43 @@ Describe 'yx done'
       yx done --recursive 'parent'
       yx list
     "
-    The output should include $'\e[90m- [x] parent\e[0m'
-    The output should include $'\e[90m  - [x] child1\e[0m'
-    The output should include $'\e[90m  - [x] child2\e[0m'
-    The output should include $'\e[90m    - [x] grandchild\e[0m'
+    The output should include "- [x] parent"
+    The output should include "  - [x] child1"
+    The output should include "  - [x] child2"
+    The output should include "    - [x] grandchild"
+  End
+
+  It 'marks several yaks done at once'
+    When run sh -c "
+      yx add 'Fix the bug'
+      yx add 'docs'
+      yx add 'tests'
+      yx done 'Fix the bug' docs,tests
+      yx list
+    "
+    The error should include "Marked 'docs' done"
+    The output should include "- [x] Fix the bug"
+    The output should include "- [x] tests"
+  End
+
+  It 'marks nothing when one of several names fails'
+    When run sh -c "
+      yx add 'docs'
+      yx done docs missing
+      yx list
+    "
+    The error should include "Error: yak 'missing' not found"
+    The error should include "Error: nothing marked - 1 of 2 yaks failed"
+    The output should include "- [ ] docs"
+  End
+
+  It 'takes a name with a comma whole'
+    When run sh -c "
+      yx add 'fix a, b'
+      yx done 'fix a, b'
+      yx list
+    "
+    The error should include "Marked 'fix a, b' done"
+    The output should include "- [x] fix a, b"
   End
 End
@@ -5,7 +5,7 @@ Describe 'yx list'
 
   It 'shows message when no yaks exist'
     When run yx list
-    The output should equal 'You have no yaks. Are you done?'
+    The error should equal 'You have no yaks. Are you done?'
   End
 
   It 'lists added yaks'
@@ -18,7 +18,7 @@ Describe 'yx list'
 
   It 'supports ls as an alias for list'
     When run yx ls
-    The output should equal 'You have no yaks. Are you done?'
+    The error should equal 'You have no yaks. Are you done?'
   End
 
   It 'supports ls as an alias for list (with yaks)'
@@ -37,7 +37,7 @@ Describe 'yx list'
       yx done "apple" &&
       yx list
     '
-    The line 1 should equal $'\e[90m- [x] apple\e[0m'
+    The line 1 should equal "- [x] apple"
     The line 2 should equal "- [ ] mango"
     The line 3 should equal "- [ ] zebra"
   End
@@ -47,7 +47,7 @@ Describe 'yx list'
       yx add "todo task"
       yx add "done task"
       yx done "done task"
-      yx list
+      yx list --color=always
     '
     The line 1 should equal $'\e[90m- [x] done task\e[0m'
     The line 2 should equal "- [ ] todo task"
@@ -73,7 +73,7 @@ Describe 'yx list'
       yx list
     "
     The line 1 should equal "- [ ] parent a"
-    The line 2 should equal $'\e[90m  - [x] child 1\e[0m'
+    The line 2 should equal "  - [x] child 1"
     The line 3 should equal "  - [ ] child 2"
     The line 4 should equal "- [ ] parent b"
   End
@@ -168,4 +168,16 @@ Describe 'yx list'
     The line 2 should equal "  - [ ] incomplete child"
   End
 
+  It 'scopes the list to a yak and its descendants'
+    When run sh -c "
+      yx add 'backend/api/auth' &&
+      yx add 'backend/db' &&
+      yx add 'frontend' &&
+      yx ls backend/api
+    "
+    The line 1 should equal "- [ ] api"
+    The line 2 should equal "  - [ ] auth"
+    The lines of output should equal 2
+  End
+
 End
@@ -26,7 +26,7 @@ Describe 'yx move'
       yx move 'old task' 'new task'
       yx list
     "
-    The output should include $'\e[90m- [x] new task\e[0m'
+    The output should include "- [x] new task"
     The output should not include "old task"
   End
 
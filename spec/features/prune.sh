@@ -20,7 +20,7 @@ Describe 'yx prune'
       yx prune
       yx list
     "
-    The output should equal "You have no yaks. Are you done?"
+    The error should equal "You have no yaks. Are you done?"
   End
 
   It 'keeps all yaks when none are done'
@@ -43,7 +43,7 @@ Describe 'yx prune'
       yx prune
       yx list
     "
-    The output should equal "You have no yaks. Are you done?"
+    The error should equal "You have no yaks. Are you done?"
   End
 
   It 'removes done child yaks'
@@ -26,7 +26,7 @@ Describe 'yx rm'
       yx rm 'Only yak'
       yx list
     "
-    The output should equal "You have no yaks. Are you done?"
+    The error should equal "You have no yaks. Are you done?"
   End
 
   It 'removes multi-word yak names without quotes'
@@ -49,4 +49,28 @@ Describe 'yx rm'
     "
     The output should equal "- [ ] parent"
   End
+
+  It 'refuses to remove a yak with children'
+    When run sh -c "
+      yx add 'parent'
+      yx add 'parent/child'
+      yx rm 'parent'
+    "
+    The status should be failure
+    The error should include "use --recursive"
+  End
+
+  It 'removes a subtree with --recursive'
+    When run sh -c "
+      yx add 'parent'
+      yx add 'parent/child'
+      yx add 'other'
+      yx rm --recursive 'parent'
+      yx list
+    "
+    The error should include "Removed 2 yak(s):"
+    The error should include "  parent/child"
+    The output should include "- [ ] other"
+    The output should not include "- [ ] parent"
+  End
 End
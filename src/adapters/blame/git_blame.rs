@@ -0,0 +1,114 @@
+// Git blame adapter - line authorship and commit authors via libgit2
+
+use crate::ports::{Author, BlamePort};
+use anyhow::{Context, Result};
+use git2::{Repository, Signature};
+use std::collections::HashMap;
+use std::path::Path;
+
+pub struct GitBlame {
+    repo: Repository,
+}
+
+impl GitBlame {
+    pub fn new() -> Result<Self> {
+        let git_work_tree = std::env::var("GIT_WORK_TREE")
+            .or_else(|_| std::env::current_dir().map(|p| p.display().to_string()))?;
+
+        let repo = Repository::open(&git_work_tree)
+            .with_context(|| format!("Failed to open git repository at {git_work_tree}"))?;
+
+        Ok(Self { repo })
+    }
+}
+
+fn author_of(signature: &Signature) -> Author {
+    Author {
+        name: signature.name().unwrap_or_default().to_string(),
+        email: signature.email().unwrap_or_default().to_string(),
+    }
+}
+
+impl BlamePort for GitBlame {
+    fn blame_file(&self, path: &str) -> Result<Vec<(Author, usize)>> {
+        let blame = self
+            .repo
+            .blame_file(Path::new(path), None)
+            .with_context(|| format!("Failed to blame {path}"))?;
+
+        // Keep authors in order of first appearance so ties stay stable
+        let mut order = Vec::new();
+        let mut lines: HashMap<Author, usize> = HashMap::new();
+        for hunk in blame.iter() {
+            let author = author_of(&hunk.final_signature());
+            if !lines.contains_key(&author) {
+                order.push(author.clone());
+            }
+            *lines.entry(author).or_default() += hunk.lines_in_hunk();
+        }
+
+        Ok(order
+            .into_iter()
+            .map(|author| {
+                let count = lines[&author];
+                (author, count)
+            })
+            .collect())
+    }
+
+    fn commit_author(&self, rev: &str) -> Result<Author> {
+        let commit = self
+            .repo
+            .revparse_single(rev)
+            .and_then(|object| object.peel_to_commit())
+            .with_context(|| format!("Failed to find commit {rev}"))?;
+        let author = author_of(&commit.author());
+        Ok(author)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn commit_file(repo: &Repository, path: &str, text: &str, who: &str) -> git2::Oid {
+        fs::write(repo.workdir().unwrap().join(path), text).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now(who, &format!("{who}@example.com")).unwrap();
+        let parent = repo.head().ok().map(|head| head.peel_to_commit().unwrap());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "change",
+            &tree,
+            &parents,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_blame_counts_lines_per_author() {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let first = commit_file(&repo, "app.rs", "a\nb\nc\n", "ann");
+        commit_file(&repo, "app.rs", "a\nb\nc\nd\n", "bob");
+        let blame = GitBlame { repo };
+
+        let lines = blame.blame_file("app.rs").unwrap();
+        let counts: Vec<(&str, usize)> = lines
+            .iter()
+            .map(|(author, count)| (author.name.as_str(), *count))
+            .collect();
+        assert_eq!(counts, vec![("ann", 3), ("bob", 1)]);
+
+        let author = blame.commit_author(&first.to_string()[..7]).unwrap();
+        assert_eq!(author.email, "ann@example.com");
+    }
+}
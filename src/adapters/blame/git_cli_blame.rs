@@ -0,0 +1,74 @@
+// Git blame adapter over the `git` command, for builds without libgit2
+
+use crate::adapters::git_cli::GitCli;
+use crate::ports::{Author, BlamePort};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::Path;
+
+pub struct GitBlame {
+    git: GitCli,
+}
+
+impl GitBlame {
+    pub fn new() -> Result<Self> {
+        let git_work_tree = std::env::var("GIT_WORK_TREE")
+            .or_else(|_| std::env::current_dir().map(|p| p.display().to_string()))?;
+
+        Ok(Self {
+            git: GitCli::open(Path::new(&git_work_tree))?,
+        })
+    }
+}
+
+impl BlamePort for GitBlame {
+    fn blame_file(&self, path: &str) -> Result<Vec<(Author, usize)>> {
+        let porcelain = self
+            .git
+            .run(&["blame", "--line-porcelain", "--", path])
+            .with_context(|| format!("Failed to blame {path}"))?;
+
+        // Keep authors in order of first appearance so ties stay stable
+        let mut order = Vec::new();
+        let mut lines: HashMap<Author, usize> = HashMap::new();
+        let mut name = "";
+        for line in porcelain.lines() {
+            if let Some(value) = line.strip_prefix("author ") {
+                name = value;
+            } else if let Some(value) = line.strip_prefix("author-mail ") {
+                let author = Author {
+                    name: name.to_string(),
+                    email: value
+                        .trim_start_matches('<')
+                        .trim_end_matches('>')
+                        .to_string(),
+                };
+                if !lines.contains_key(&author) {
+                    order.push(author.clone());
+                }
+                *lines.entry(author).or_default() += 1;
+            }
+        }
+
+        Ok(order
+            .into_iter()
+            .map(|author| {
+                let count = lines[&author];
+                (author, count)
+            })
+            .collect())
+    }
+
+    fn commit_author(&self, rev: &str) -> Result<Author> {
+        let spec = format!("{rev}^{{commit}}");
+        let text = self
+            .git
+            .run(&["log", "-1", "--format=%an%x00%ae", &spec, "--"])
+            .with_context(|| format!("Failed to find commit {rev}"))?;
+        let (name, email) = text.trim_end().split_once('\0').unwrap_or_default();
+        Ok(Author {
+            name: name.to_string(),
+            email: email.to_string(),
+        })
+    }
+}
@@ -0,0 +1,9 @@
+#[cfg(feature = "libgit2")]
+mod git_blame;
+#[cfg(not(feature = "libgit2"))]
+mod git_cli_blame;
+
+#[cfg(feature = "libgit2")]
+pub use git_blame::GitBlame;
+#[cfg(not(feature = "libgit2"))]
+pub use git_cli_blame::GitBlame;
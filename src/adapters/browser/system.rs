@@ -0,0 +1,32 @@
+// System browser adapter - opens URLs with the OS's default handler
+
+use crate::ports::BrowserPort;
+use anyhow::{Context, Result};
+use std::process::{Command, Stdio};
+
+pub struct SystemBrowser;
+
+impl BrowserPort for SystemBrowser {
+    fn open(&self, url: &str) -> Result<()> {
+        let mut command = if cfg!(target_os = "macos") {
+            Command::new("open")
+        } else if cfg!(windows) {
+            let mut command = Command::new("cmd");
+            command.args(["/C", "start", ""]);
+            command
+        } else {
+            Command::new("xdg-open")
+        };
+
+        let status = command
+            .arg(url)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .context("Failed to start a browser")?;
+        if !status.success() {
+            anyhow::bail!("Failed to open {url}");
+        }
+        Ok(())
+    }
+}
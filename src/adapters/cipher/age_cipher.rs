@@ -0,0 +1,177 @@
+// age cipher - encrypts covered yaks to the configured age keys, as ASCII armor
+
+use super::{Coverage, IDENTITY_KEY, RECIPIENT_KEY};
+use crate::ports::{CipherPort, ConfigPort};
+use age::armor::{ArmoredWriter, Format};
+use age::x25519;
+use anyhow::{Context, Result};
+use std::cell::OnceCell;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+pub struct AgeCipher {
+    coverage: Coverage,
+    identity_file: Option<PathBuf>,
+    recipients: Vec<String>,
+    /// Keys are read on first use, so commands that touch no encrypted yak
+    /// never need the key file
+    keys: OnceCell<Keys>,
+}
+
+struct Keys {
+    identity: Option<x25519::Identity>,
+    recipients: Vec<x25519::Recipient>,
+}
+
+impl AgeCipher {
+    pub fn from_config(config: &dyn ConfigPort) -> Self {
+        let identity_file = config.get(IDENTITY_KEY).map(|value| {
+            let value = value.trim();
+            match (value.strip_prefix("~/"), std::env::var("HOME")) {
+                (Some(rest), Ok(home)) => PathBuf::from(home).join(rest),
+                _ => PathBuf::from(value),
+            }
+        });
+        Self {
+            coverage: Coverage::from_config(config),
+            identity_file,
+            recipients: config.get_all(RECIPIENT_KEY),
+            keys: OnceCell::new(),
+        }
+    }
+
+    fn keys(&self) -> Result<&Keys> {
+        if let Some(keys) = self.keys.get() {
+            return Ok(keys);
+        }
+
+        let identity = match &self.identity_file {
+            Some(path) => {
+                let text = fs::read_to_string(path).with_context(|| {
+                    format!(
+                        "Failed to read the age key in {IDENTITY_KEY} ({})",
+                        path.display()
+                    )
+                })?;
+                // An age-keygen file: comments, then the secret key
+                let key = text
+                    .lines()
+                    .map(str::trim)
+                    .find(|line| line.starts_with("AGE-SECRET-KEY-"))
+                    .with_context(|| format!("No age secret key in {}", path.display()))?;
+                let identity: x25519::Identity = key
+                    .parse()
+                    .map_err(|e| anyhow::anyhow!("Invalid age key in {}: {e}", path.display()))?;
+                Some(identity)
+            }
+            None => None,
+        };
+
+        let mut recipients: Vec<x25519::Recipient> =
+            identity.iter().map(x25519::Identity::to_public).collect();
+        for value in &self.recipients {
+            let recipient = value
+                .trim()
+                .parse()
+                .map_err(|e| anyhow::anyhow!("Invalid {RECIPIENT_KEY} '{value}': {e}"))?;
+            recipients.push(recipient);
+        }
+
+        Ok(self.keys.get_or_init(|| Keys {
+            identity,
+            recipients,
+        }))
+    }
+}
+
+impl CipherPort for AgeCipher {
+    fn covers(&self, name: &str) -> bool {
+        self.coverage.covers(name)
+    }
+
+    fn encrypt(&self, text: &str) -> Result<String> {
+        let keys = self.keys()?;
+        if keys.recipients.is_empty() {
+            anyhow::bail!(
+                "no age key to encrypt with: set {IDENTITY_KEY} to your key file (see `age-keygen`)"
+            );
+        }
+
+        let recipients = keys
+            .recipients
+            .iter()
+            .map(|recipient| recipient as &dyn age::Recipient);
+        let encryptor = age::Encryptor::with_recipients(recipients)?;
+        let mut ciphertext = Vec::new();
+        let armor = ArmoredWriter::wrap_output(&mut ciphertext, Format::AsciiArmor)?;
+        let mut writer = encryptor.wrap_output(armor)?;
+        writer.write_all(text.as_bytes())?;
+        writer.finish()?.finish()?;
+        Ok(String::from_utf8(ciphertext)?)
+    }
+
+    fn decrypt(&self, ciphertext: &str) -> Result<Option<String>> {
+        let Some(identity) = &self.keys()?.identity else {
+            return Ok(None);
+        };
+        match age::decrypt(identity, ciphertext.as_bytes()) {
+            Ok(text) => Ok(Some(String::from_utf8(text)?)),
+            // Encrypted to other people only
+            Err(age::DecryptError::NoMatchingKeys) => Ok(None),
+            Err(e) => Err(e).context("Failed to decrypt a yak file"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use age::secrecy::ExposeSecret;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    struct MockConfig(HashMap<&'static str, String>);
+
+    impl ConfigPort for MockConfig {
+        fn get(&self, key: &str) -> Option<String> {
+            self.0.get(key).cloned()
+        }
+
+        fn get_all(&self, key: &str) -> Vec<String> {
+            self.get(key).into_iter().collect()
+        }
+    }
+
+    #[test]
+    fn test_encrypted_text_opens_only_with_the_key() {
+        let dir = TempDir::new().unwrap();
+        let key_file = dir.path().join("key.txt");
+        let identity = x25519::Identity::generate();
+        fs::write(
+            &key_file,
+            format!("# created: now\n{}\n", identity.to_string().expose_secret()),
+        )
+        .unwrap();
+        let other = x25519::Identity::generate().to_public().to_string();
+        let cipher = AgeCipher::from_config(&MockConfig(HashMap::from([
+            (IDENTITY_KEY, key_file.display().to_string()),
+            (RECIPIENT_KEY, other.clone()),
+        ])));
+
+        let ciphertext = cipher.encrypt("salary bands").unwrap();
+        assert!(ciphertext.starts_with("-----BEGIN AGE ENCRYPTED FILE-----"));
+        assert!(!ciphertext.contains("salary"));
+        assert_eq!(
+            cipher.decrypt(&ciphertext).unwrap().as_deref(),
+            Some("salary bands")
+        );
+
+        // A teammate's key file or none at all
+        let recipient_only =
+            AgeCipher::from_config(&MockConfig(HashMap::from([(RECIPIENT_KEY, other)])));
+        assert_eq!(recipient_only.decrypt(&ciphertext).unwrap(), None);
+        let keyless = AgeCipher::from_config(&MockConfig(HashMap::new()));
+        assert!(keyless.encrypt("salary bands").is_err());
+    }
+}
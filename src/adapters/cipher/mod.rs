@@ -0,0 +1,87 @@
+// Cipher adapters - keep the yaks named in `yaks.encrypt` as age ciphertext
+
+#[cfg(feature = "encryption")]
+mod age_cipher;
+#[cfg(not(feature = "encryption"))]
+mod no_cipher;
+
+#[cfg(feature = "encryption")]
+pub use age_cipher::AgeCipher;
+#[cfg(not(feature = "encryption"))]
+pub use no_cipher::NoCipher;
+
+use crate::domain::graph;
+use crate::ports::ConfigPort;
+
+/// Multi-valued config key naming the yaks to encrypt: a yak (with everything
+/// under it), `private/*` for only the yaks under one, or `*` for all
+const ENCRYPT_KEY: &str = "yaks.encrypt";
+
+/// Config key with the path of the age key file (from `age-keygen`) that
+/// decrypts the yaks; its public key is always a recipient
+#[cfg_attr(not(feature = "encryption"), allow(dead_code))]
+const IDENTITY_KEY: &str = "yaks.age.identity";
+
+/// Multi-valued config key with more age public keys (`age1...`) to encrypt
+/// to, e.g. the teammates who share the yaks
+#[cfg_attr(not(feature = "encryption"), allow(dead_code))]
+const RECIPIENT_KEY: &str = "yaks.age.recipient";
+
+/// The yaks `yaks.encrypt` covers
+struct Coverage {
+    patterns: Vec<String>,
+}
+
+impl Coverage {
+    fn from_config(config: &dyn ConfigPort) -> Self {
+        let patterns = config
+            .get_all(ENCRYPT_KEY)
+            .iter()
+            .map(|value| value.trim().trim_end_matches('/').to_string())
+            .filter(|value| !value.is_empty())
+            .collect();
+        Self { patterns }
+    }
+
+    fn covers(&self, name: &str) -> bool {
+        self.patterns
+            .iter()
+            .any(|pattern| match pattern.strip_suffix("/*") {
+                Some(parent) => graph::is_descendant_of(name, parent),
+                None => pattern == "*" || name == pattern || graph::is_descendant_of(name, pattern),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockConfig(Vec<String>);
+
+    impl ConfigPort for MockConfig {
+        fn get(&self, _key: &str) -> Option<String> {
+            None
+        }
+
+        fn get_all(&self, key: &str) -> Vec<String> {
+            assert_eq!(key, ENCRYPT_KEY);
+            self.0.clone()
+        }
+    }
+
+    #[test]
+    fn test_coverage_follows_names_and_namespaces() {
+        let config = MockConfig(vec!["private/*".to_string(), "hr review".to_string()]);
+        let coverage = Coverage::from_config(&config);
+
+        assert!(coverage.covers("private/salary"));
+        assert!(coverage.covers("private/salary/bands"));
+        assert!(!coverage.covers("private"));
+        assert!(!coverage.covers("privateer"));
+        assert!(coverage.covers("hr review"));
+        assert!(coverage.covers("hr review/notes"));
+        assert!(!coverage.covers("docs"));
+        assert!(Coverage::from_config(&MockConfig(vec!["*".to_string()])).covers("docs"));
+    }
+}
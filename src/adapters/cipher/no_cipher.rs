@@ -0,0 +1,34 @@
+// Cipher stand-in for builds without age - yaks can't be encrypted or read back
+
+use super::Coverage;
+use crate::ports::{CipherPort, ConfigPort};
+use anyhow::Result;
+
+const UNAVAILABLE: &str =
+    "encrypted yaks are unavailable: yx was built without the encryption feature";
+
+pub struct NoCipher {
+    coverage: Coverage,
+}
+
+impl NoCipher {
+    pub fn from_config(config: &dyn ConfigPort) -> Self {
+        Self {
+            coverage: Coverage::from_config(config),
+        }
+    }
+}
+
+impl CipherPort for NoCipher {
+    fn covers(&self, name: &str) -> bool {
+        self.coverage.covers(name)
+    }
+
+    fn encrypt(&self, _text: &str) -> Result<String> {
+        anyhow::bail!(UNAVAILABLE)
+    }
+
+    fn decrypt(&self, _ciphertext: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,188 @@
+// Crash reports - a panic hook that saves a redacted diagnostic report for `yx bug`
+
+use crate::adapters::storage::META_FOLDER;
+use std::backtrace::Backtrace;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use walkdir::WalkDir;
+
+/// Crash reports are named `yx-crash-<unix time>.txt` in the temp directory
+const REPORT_PREFIX: &str = "yx-crash-";
+
+/// Where the running command's yaks are, once it has found them; finding
+/// them runs git, which a panicking process shouldn't
+static YAKS_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Exit status for output cut short by a closed pipe, as if killed by SIGPIPE
+const BROKEN_PIPE_STATUS: i32 = 128 + 13;
+
+/// Note where the yaks are, so a crash report can count them
+pub fn set_crash_yaks_path(path: &Path) {
+    let _ = YAKS_PATH.set(path.to_path_buf());
+}
+
+/// Replace the hook that prints panics with one that also saves a crash report
+/// Yak names and other free text are left out so the report can be shared as-is.
+pub fn install_panic_hook() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    std::panic::set_hook(Box::new(move |info| {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        if is_closed_stdout(&message) {
+            std::process::exit(BROKEN_PIPE_STATUS);
+        }
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}", l.file(), l.line()))
+            .unwrap_or_default();
+
+        let mut report = String::new();
+        let _ = writeln!(report, "yx {}", env!("CARGO_PKG_VERSION"));
+        let _ = writeln!(
+            report,
+            "os: {} {}",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        );
+        let _ = writeln!(report, "command: yx {}", redact_args(&args).join(" "));
+        let yaks = YAKS_PATH
+            .get()
+            .map_or_else(|| "unknown".to_string(), |path| yak_counts(path));
+        let _ = writeln!(report, "yaks: {yaks}");
+        let _ = writeln!(report, "panic: {} at {location}", redact_message(&message));
+        let _ = writeln!(report, "\n{}", Backtrace::force_capture());
+
+        eprintln!("yx crashed: {}", redact_message(&message));
+        match write_report(&report) {
+            Ok(path) => {
+                eprintln!("A crash report was saved to {}", path.display());
+                eprintln!("Run `yx bug` to report it");
+            }
+            Err(_) => eprintln!("{report}"),
+        }
+    }));
+}
+
+/// The newest crash report, with its path, if any were saved
+pub fn latest_crash_report() -> Option<(PathBuf, String)> {
+    let newest = fs::read_dir(std::env::temp_dir())
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(REPORT_PREFIX))
+        })
+        .max()?;
+    let text = fs::read_to_string(&newest).ok()?;
+    Some((newest, text))
+}
+
+fn write_report(report: &str) -> std::io::Result<PathBuf> {
+    let seconds = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let path = std::env::temp_dir().join(format!("{REPORT_PREFIX}{seconds}.txt"));
+    fs::write(&path, report)?;
+    Ok(path)
+}
+
+/// Whether a panic came from printing after whoever read our output
+/// stopped early (`yx list | head`), which isn't a crash
+fn is_closed_stdout(message: &str) -> bool {
+    message.starts_with("failed printing to stdout")
+}
+
+/// Keep the subcommand and flags, hide everything else (yak names, messages)
+fn redact_args(args: &[String]) -> Vec<String> {
+    args.iter()
+        .enumerate()
+        .map(|(i, arg)| {
+            if i == 0 || arg.starts_with('-') {
+                arg.split('=').next().unwrap_or_default().to_string()
+            } else {
+                "<redacted>".to_string()
+            }
+        })
+        .collect()
+}
+
+/// Hide quoted text, which is how yx messages mention yak names
+fn redact_message(message: &str) -> String {
+    let mut redacted = String::new();
+    for (i, part) in message.split('\'').enumerate() {
+        if i > 0 {
+            redacted.push('\'');
+        }
+        redacted.push_str(if i % 2 == 1 { "<redacted>" } else { part });
+    }
+    redacted
+}
+
+/// How many yaks there are and how many are done, without their names
+fn yak_counts(yaks_path: &Path) -> String {
+    if !yaks_path.exists() {
+        return "none".to_string();
+    }
+    let dirs: Vec<PathBuf> = WalkDir::new(yaks_path)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|entry| entry.file_name() != META_FOLDER)
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_dir())
+        .map(|entry| entry.into_path())
+        .collect();
+    let done = dirs
+        .iter()
+        .map(|dir| dir.join(META_FOLDER))
+        .filter(|meta| {
+            meta.join("done").exists()
+                || fs::read_to_string(meta.join("state")).is_ok_and(|text| text.trim() == "done")
+        })
+        .count();
+    format!("{} ({done} done)", dirs.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_closed_stdout_is_not_a_crash() {
+        assert!(is_closed_stdout(
+            "failed printing to stdout: Broken pipe (os error 32)"
+        ));
+        assert!(!is_closed_stdout("index out of bounds"));
+    }
+
+    #[test]
+    fn test_redact_args_keeps_subcommand_and_flags() {
+        let args: Vec<String> = ["done", "--undo", "secret", "launch", "--format=plain"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        assert_eq!(
+            redact_args(&args),
+            vec!["done", "--undo", "<redacted>", "<redacted>", "--format"]
+        );
+    }
+
+    #[test]
+    fn test_redact_message_hides_quoted_names() {
+        assert_eq!(
+            redact_message("yak 'secret plan' not found"),
+            "yak '<redacted>' not found"
+        );
+        assert_eq!(redact_message("index out of bounds"), "index out of bounds");
+    }
+}
@@ -0,0 +1,103 @@
+// Highlight - colors code from fenced blocks by language, using syntect's grammars
+
+use std::sync::OnceLock;
+use syntect::easy::ScopeRangeIterator;
+use syntect::parsing::{ParseState, Scope, ScopeStack, SyntaxSet};
+use syntect::util::LinesWithEndings;
+
+/// Scope prefixes and the SGR parameters they're drawn with, most specific
+/// first; plain terminal colors so the user's palette still applies
+const SCOPE_COLORS: [(&str, &str); 10] = [
+    ("comment", "90"),
+    ("string", "32"),
+    ("constant.character.escape", "36"),
+    ("constant.numeric", "35"),
+    ("constant.language", "35"),
+    ("keyword", "34"),
+    ("storage", "34"),
+    ("entity.name.function", "33"),
+    ("support.function", "33"),
+    ("entity.name", "36"),
+];
+
+/// The bundled grammars, loaded the first time a block is highlighted
+fn syntaxes() -> &'static SyntaxSet {
+    static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+/// `code` with ANSI colors for `language` (a name or extension such as
+/// "rust", "py" or "sh"), or None when the language isn't known
+pub(super) fn highlight(code: &str, language: &str) -> Option<String> {
+    let syntaxes = syntaxes();
+    let syntax = syntaxes.find_syntax_by_token(language.trim())?;
+    let colors: Vec<(Scope, &str)> = SCOPE_COLORS
+        .iter()
+        .filter_map(|(prefix, code)| Some((Scope::new(prefix).ok()?, *code)))
+        .collect();
+
+    let mut state = ParseState::new(syntax);
+    let mut stack = ScopeStack::new();
+    let mut highlighted = String::with_capacity(code.len());
+    for line in LinesWithEndings::from(code) {
+        let ops = state.parse_line(line, syntaxes).ok()?;
+        // Runs of the same color are joined so each gets one escape
+        let mut run = String::new();
+        let mut run_color = "";
+        for (range, op) in ScopeRangeIterator::new(&ops, line) {
+            stack.apply(op).ok()?;
+            if range.is_empty() {
+                continue;
+            }
+            // The innermost scope with a color wins, e.g. a string inside a call
+            let color = stack
+                .as_slice()
+                .iter()
+                .rev()
+                .find_map(|scope| {
+                    colors
+                        .iter()
+                        .find(|(prefix, _)| prefix.is_prefix_of(*scope))
+                        .map(|(_, code)| *code)
+                })
+                .unwrap_or_default();
+            if color != run_color {
+                push_run(&mut highlighted, &run, run_color);
+                run.clear();
+                run_color = color;
+            }
+            run.push_str(&line[range]);
+        }
+        push_run(&mut highlighted, &run, run_color);
+    }
+    Some(highlighted)
+}
+
+/// Append `run` in `color`, keeping a trailing newline outside the escapes so
+/// each line resets cleanly
+fn push_run(highlighted: &mut String, run: &str, color: &str) {
+    let (body, newline) = match run.strip_suffix('\n') {
+        Some(body) => (body, "\n"),
+        None => (run, ""),
+    };
+    if !body.is_empty() {
+        highlighted.push_str(&super::ansi(body, color));
+    }
+    highlighted.push_str(newline);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_colors_known_languages() {
+        let rust = highlight("// hi\nlet x = \"a\";\n", "rust").unwrap();
+        assert!(rust.contains("\x1b[90m// hi\x1b[0m\n"));
+        assert!(rust.contains("\x1b[34mlet\x1b[0m"));
+        assert!(rust.contains("\x1b[32m\"a\"\x1b[0m;"));
+
+        assert!(highlight("echo hi", "sh").is_some());
+        assert_eq!(highlight("whatever", "no-such-language"), None);
+    }
+}
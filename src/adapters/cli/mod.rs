@@ -1,10 +1,117 @@
 // CLI adapter - implementation using clap
 
-pub struct ConsoleOutput;
+mod crash;
+#[cfg(feature = "highlight")]
+mod highlight;
+mod plugins;
+mod spinner;
+mod theme;
 
-impl crate::ports::OutputPort for ConsoleOutput {
+use crate::ports::{OutputPort, Style, Verbosity};
+use spinner::Spinner;
+
+pub use crash::{install_panic_hook, latest_crash_report, set_crash_yaks_path};
+pub use plugins::{find_plugin, plugin_names};
+pub use theme::Theme;
+
+/// Whether the terminal's locale can show non-ASCII symbols (LC_ALL, LC_CTYPE, then LANG)
+pub fn supports_unicode() -> bool {
+    if cfg!(windows) {
+        return true;
+    }
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|value| !value.is_empty()))
+        .is_some_and(|locale| {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        })
+}
+
+/// When to color output, chosen with `--color`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColorChoice {
+    /// Color a terminal, unless NO_COLOR is set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// Prints to the terminal, leaving out what the chosen verbosity doesn't want
+#[derive(Default)]
+pub struct ConsoleOutput {
+    verbosity: Verbosity,
+    color: ColorChoice,
+    theme: Theme,
+    spinner: Spinner,
+}
+
+impl ConsoleOutput {
+    pub fn new(verbosity: Verbosity) -> Self {
+        Self {
+            verbosity,
+            color: ColorChoice::Auto,
+            theme: Theme::default(),
+            spinner: Spinner::default(),
+        }
+    }
+
+    /// Color done yaks the way `theme` says
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    pub fn with_color(mut self, color: ColorChoice) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Whether text written to `stream` gets ANSI colors
+    /// An explicit `--color` wins; otherwise NO_COLOR (https://no-color.org)
+    /// turns them off, and only a terminal gets them.
+    fn colors(&self, stream: atty::Stream) -> bool {
+        match self.color {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+                    && atty::is(stream)
+            }
+        }
+    }
+}
+
+/// The SGR parameters for each style: the theme's look for done yaks,
+/// grep's magenta names, green line numbers and bold red matches, yellow warnings,
+/// and git's green additions, red removals and cyan hunk headers in diffs
+fn ansi_code(style: Style, theme: &Theme) -> String {
+    match style {
+        Style::Done => theme.done_codes(),
+        Style::Name => "35".to_string(),
+        Style::LineNumber => "32".to_string(),
+        Style::Match => "1;31".to_string(),
+        Style::Warning => "33".to_string(),
+        Style::Added => "32".to_string(),
+        Style::Removed => "31".to_string(),
+        Style::Hunk => "36".to_string(),
+    }
+}
+
+fn ansi(text: &str, code: &str) -> String {
+    if code.is_empty() {
+        return text.to_string();
+    }
+    format!("\x1b[{code}m{text}\x1b[0m")
+}
+
+impl OutputPort for ConsoleOutput {
     fn success(&self, message: &str) {
-        println!("{message}");
+        // stderr, so stdout only carries results: `yx ... | xargs` sees no chatter
+        if self.verbosity > Verbosity::Quiet {
+            eprintln!("{message}");
+        }
     }
 
     fn error(&self, message: &str) {
@@ -14,4 +121,96 @@ impl crate::ports::OutputPort for ConsoleOutput {
     fn info(&self, message: &str) {
         println!("{message}");
     }
+
+    fn note(&self, message: &str) {
+        if self.verbosity > Verbosity::Quiet {
+            eprintln!("{message}");
+        }
+    }
+
+    fn warn(&self, message: &str) {
+        // stderr, so it never ends up in piped results
+        let message = format!("warning: {message}");
+        if self.colors(atty::Stream::Stderr) {
+            eprintln!(
+                "{}",
+                ansi(&message, &ansi_code(Style::Warning, &self.theme))
+            );
+        } else {
+            eprintln!("{message}");
+        }
+    }
+
+    fn hint(&self, message: &str) {
+        // stderr keeps hints out of anything piped from stdout
+        if self.verbosity > Verbosity::Quiet {
+            eprintln!("hint: {message}");
+        }
+    }
+
+    fn detail(&self, message: &str) {
+        if self.verbosity == Verbosity::Verbose {
+            eprintln!("{message}");
+        }
+    }
+
+    fn paint(&self, text: &str, style: Style) -> String {
+        if self.colors(atty::Stream::Stdout) {
+            ansi(text, &ansi_code(style, &self.theme))
+        } else {
+            text.to_string()
+        }
+    }
+
+    #[cfg(feature = "highlight")]
+    fn highlight(&self, code: &str, language: &str) -> String {
+        if !self.colors(atty::Stream::Stdout) {
+            return code.to_string();
+        }
+        highlight::highlight(code, language).unwrap_or_else(|| code.to_string())
+    }
+
+    fn verbosity(&self) -> Verbosity {
+        self.verbosity
+    }
+
+    fn confirm(&self, question: &str) -> Option<bool> {
+        // Never block scripts and pipes on a question
+        if !atty::is(atty::Stream::Stdin) {
+            return None;
+        }
+        eprint!("{question} [y/N] ");
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer).ok()?;
+        Some(matches!(answer.trim(), "y" | "Y" | "yes"))
+    }
+
+    fn ask(&self, prompt: &str) -> Option<String> {
+        if !atty::is(atty::Stream::Stdin) {
+            return None;
+        }
+        eprint!("{prompt} ");
+        let mut answer = String::new();
+        match std::io::stdin().read_line(&mut answer) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(answer.trim_end_matches(['\r', '\n']).to_string()),
+        }
+    }
+
+    fn progress(&self, message: &str) {
+        // Only a person watching a terminal wants it; a log would fill with redraws
+        if self.verbosity > Verbosity::Quiet && atty::is(atty::Stream::Stderr) {
+            self.spinner.draw(message);
+        }
+    }
+
+    fn progress_done(&self) {
+        self.spinner.clear();
+    }
+
+    fn clear(&self) {
+        if atty::is(atty::Stream::Stdout) {
+            print!("\x1b[2J\x1b[H");
+        }
+    }
 }
@@ -0,0 +1,54 @@
+// Plugin lookup - `yx foo` runs a `yx-foo` executable from PATH, like git does
+
+use std::env;
+use std::path::PathBuf;
+
+/// Executable name prefix that marks a yx plugin
+const PLUGIN_PREFIX: &str = "yx-";
+
+/// Path of the `yx-<name>` plugin, if one is on PATH
+pub fn find_plugin(name: &str) -> Option<PathBuf> {
+    let file_name = format!("{PLUGIN_PREFIX}{name}");
+    search_path()
+        .map(|dir| dir.join(&file_name))
+        .find(|path| is_executable(path))
+}
+
+/// Names of every plugin on PATH (without the `yx-` prefix)
+pub fn plugin_names() -> Vec<String> {
+    let mut names: Vec<String> = search_path()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| is_executable(&entry.path()))
+        .filter_map(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix(PLUGIN_PREFIX))
+                .map(|name| name.to_string())
+        })
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn search_path() -> impl Iterator<Item = PathBuf> {
+    env::var_os("PATH")
+        .map(|path| env::split_paths(&path).collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+}
+
+#[cfg(unix)]
+fn is_executable(path: &std::path::Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &std::path::Path) -> bool {
+    path.is_file()
+}
@@ -0,0 +1,60 @@
+// Spinner - a progress line on stderr, redrawn in place while a step runs
+
+use std::cell::RefCell;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+/// Frames for terminals that can show braille, and for those that can't
+const FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const ASCII_FRAMES: [&str; 4] = ["|", "/", "-", "\\"];
+
+/// How often the same step is redrawn; transfers report far more often
+const INTERVAL: Duration = Duration::from_millis(80);
+
+/// What's on the progress line now
+struct Drawn {
+    at: Instant,
+    frame: usize,
+    /// The message without its counts, e.g. "Fetching from origin"
+    step: String,
+}
+
+#[derive(Default)]
+pub(super) struct Spinner {
+    drawn: RefCell<Option<Drawn>>,
+}
+
+impl Spinner {
+    /// Replace the progress line with `message`, after the next frame
+    pub(super) fn draw(&self, message: &str) {
+        let step = message.split(" (").next().unwrap_or(message);
+        let now = Instant::now();
+        let mut drawn = self.drawn.borrow_mut();
+        let frame = match drawn.as_ref() {
+            // A new step always shows at once, the same one at most every INTERVAL
+            Some(last) if last.step == step && now - last.at < INTERVAL => return,
+            Some(last) => last.frame + 1,
+            None => 0,
+        };
+        let spinner = if super::supports_unicode() {
+            FRAMES[frame % FRAMES.len()]
+        } else {
+            ASCII_FRAMES[frame % ASCII_FRAMES.len()]
+        };
+        eprint!("\r\x1b[K{spinner} {message}");
+        let _ = std::io::stderr().flush();
+        *drawn = Some(Drawn {
+            at: now,
+            frame,
+            step: step.to_string(),
+        });
+    }
+
+    /// Erase the progress line, if one is showing
+    pub(super) fn clear(&self) {
+        if self.drawn.borrow_mut().take().is_some() {
+            eprint!("\r\x1b[K");
+            let _ = std::io::stderr().flush();
+        }
+    }
+}
@@ -0,0 +1,192 @@
+// Themes - named sets of checkbox markers and colors for the console
+
+use crate::ports::ConfigPort;
+use anyhow::Result;
+
+/// Config key naming the theme, e.g. `git config yaks.theme unicode`
+const THEME_KEY: &str = "yaks.theme";
+
+/// Config key overriding the theme's color for done yaks
+const DONE_COLOR_KEY: &str = "yaks.display.doneColor";
+
+/// Config key choosing how done yaks stand out: color, strikethrough or both
+const DONE_STYLE_KEY: &str = "yaks.display.doneStyle";
+
+/// SGR parameter for crossed-out text
+const STRIKETHROUGH: &str = "9";
+
+/// Color names accepted for `yaks.display.doneColor`, with their SGR parameters
+const COLORS: [(&str, &str); 10] = [
+    ("none", ""),
+    ("gray", "90"),
+    ("black", "30"),
+    ("red", "31"),
+    ("green", "32"),
+    ("yellow", "33"),
+    ("blue", "34"),
+    ("magenta", "35"),
+    ("cyan", "36"),
+    ("white", "37"),
+];
+
+/// How the console draws yaks: markers before open and done yaks, and the
+/// color done yaks are dimmed with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    open: &'static str,
+    done: &'static str,
+    done_color: String,
+    /// Cross done yaks out, for terminal themes where gray is unreadable
+    strikethrough: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::named("default").expect("the default theme exists")
+    }
+}
+
+impl Theme {
+    /// Every built-in theme: name, open marker, done marker, done color
+    const THEMES: [(&'static str, &'static str, &'static str, &'static str); 3] = [
+        ("default", "[ ]", "[x]", "90"),
+        ("unicode", "☐", "☑", "90"),
+        // Font Awesome square and checked square, as patched into nerd fonts
+        ("nerd", "\u{f096}", "\u{f046}", "32"),
+    ];
+
+    pub fn named(name: &str) -> Result<Self> {
+        let wanted = name.trim().to_lowercase();
+        let Some((_, open, done, color)) = Self::THEMES.iter().find(|theme| theme.0 == wanted)
+        else {
+            let names: Vec<&str> = Self::THEMES.iter().map(|theme| theme.0).collect();
+            anyhow::bail!("unknown theme '{name}': use {}", names.join(", "));
+        };
+        Ok(Self {
+            open,
+            done,
+            done_color: color.to_string(),
+            strikethrough: false,
+        })
+    }
+
+    /// The theme in `yaks.theme`, with `yaks.display.doneColor` and `doneStyle` on top
+    pub fn from_config(config: &dyn ConfigPort) -> Result<Self> {
+        let mut theme = match config.get(THEME_KEY) {
+            Some(name) => Self::named(&name)?,
+            None => Self::default(),
+        };
+        if let Some(color) = config.get(DONE_COLOR_KEY) {
+            theme.done_color = parse_color(&color)?;
+        }
+        if let Some(style) = config.get(DONE_STYLE_KEY) {
+            match style.trim().to_lowercase().as_str() {
+                "color" => {}
+                "strikethrough" => {
+                    theme.done_color.clear();
+                    theme.strikethrough = true;
+                }
+                "both" => theme.strikethrough = true,
+                _ => anyhow::bail!(
+                    "unknown style '{style}' in {DONE_STYLE_KEY}: use color, strikethrough or both"
+                ),
+            }
+        }
+        Ok(theme)
+    }
+
+    /// Open and done markers, falling back to `[ ]` / `[x]` when the terminal
+    /// can't show the theme's symbols
+    pub fn markers(&self, unicode: bool) -> (&'static str, &'static str) {
+        if unicode || (self.open.is_ascii() && self.done.is_ascii()) {
+            (self.open, self.done)
+        } else {
+            ("[ ]", "[x]")
+        }
+    }
+
+    /// SGR parameters for done yaks; empty means they aren't styled
+    pub(super) fn done_codes(&self) -> String {
+        match (self.strikethrough, self.done_color.is_empty()) {
+            (false, _) => self.done_color.clone(),
+            (true, true) => STRIKETHROUGH.to_string(),
+            (true, false) => format!("{STRIKETHROUGH};{}", self.done_color),
+        }
+    }
+}
+
+/// A color name, or raw SGR parameters such as `38;5;244`
+fn parse_color(text: &str) -> Result<String> {
+    let wanted = text.trim().to_lowercase();
+    if let Some((_, code)) = COLORS.iter().find(|(name, _)| *name == wanted) {
+        return Ok(code.to_string());
+    }
+    if !wanted.is_empty() && wanted.chars().all(|c| c.is_ascii_digit() || c == ';') {
+        return Ok(wanted);
+    }
+    let names: Vec<&str> = COLORS.iter().map(|(name, _)| *name).collect();
+    anyhow::bail!(
+        "unknown color '{text}' in {DONE_COLOR_KEY}: use {} or SGR codes like 38;5;244",
+        names.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct MockConfig(HashMap<&'static str, &'static str>);
+
+    impl ConfigPort for MockConfig {
+        fn get(&self, key: &str) -> Option<String> {
+            self.0.get(key).map(|value| value.to_string())
+        }
+
+        fn get_all(&self, _key: &str) -> Vec<String> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_theme_from_config() {
+        let theme = Theme::from_config(&MockConfig(HashMap::new())).unwrap();
+        assert_eq!(theme.markers(true), ("[ ]", "[x]"));
+        assert_eq!(theme.done_codes(), "90");
+
+        let config = MockConfig(HashMap::from([
+            ("yaks.theme", "Unicode"),
+            ("yaks.display.doneColor", "blue"),
+        ]));
+        let theme = Theme::from_config(&config).unwrap();
+        assert_eq!(theme.markers(true), ("☐", "☑"));
+        assert_eq!(theme.markers(false), ("[ ]", "[x]"));
+        assert_eq!(theme.done_codes(), "34");
+    }
+
+    #[test]
+    fn test_done_style_strikes_through() {
+        let style = |style: &'static str| {
+            let config = MockConfig(HashMap::from([("yaks.display.doneStyle", style)]));
+            Theme::from_config(&config).map(|theme| theme.done_codes())
+        };
+
+        assert_eq!(style("color").unwrap(), "90");
+        assert_eq!(style("strikethrough").unwrap(), "9");
+        assert_eq!(style("Both").unwrap(), "9;90");
+        assert!(style("blink").is_err());
+    }
+
+    #[test]
+    fn test_unknown_theme_and_color_are_errors() {
+        let config = MockConfig(HashMap::from([("yaks.theme", "neon")]));
+        assert_eq!(
+            Theme::from_config(&config).unwrap_err().to_string(),
+            "unknown theme 'neon': use default, unicode, nerd"
+        );
+
+        assert_eq!(parse_color("38;5;244").unwrap(), "38;5;244");
+        assert_eq!(parse_color("none").unwrap(), "");
+        assert!(parse_color("grey-ish").is_err());
+    }
+}
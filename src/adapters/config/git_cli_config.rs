@@ -0,0 +1,57 @@
+// Git config adapter over the `git` command, for builds without libgit2
+
+use crate::ports::ConfigPort;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub struct GitConfig {
+    work_tree: PathBuf,
+}
+
+impl GitConfig {
+    /// Repo config layered over global config, or just global config
+    /// when not inside a repository (git itself falls back to global)
+    pub fn new() -> Result<Self> {
+        let git_work_tree = std::env::var("GIT_WORK_TREE")
+            .or_else(|_| std::env::current_dir().map(|p| p.display().to_string()))?;
+
+        Self::for_work_tree(Path::new(&git_work_tree))
+    }
+
+    /// Config for another repository (e.g., a configured workspace)
+    pub fn for_work_tree(work_tree: &Path) -> Result<Self> {
+        Ok(Self {
+            work_tree: work_tree.to_path_buf(),
+        })
+    }
+
+    fn read(&self, args: &[&str]) -> Vec<String> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.work_tree)
+            .arg("config")
+            .args(args)
+            .output();
+
+        // Exit code 1 means the key is unset
+        match output {
+            Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+                .split('\0')
+                .filter(|value| !value.is_empty())
+                .map(|value| value.to_string())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl ConfigPort for GitConfig {
+    fn get(&self, key: &str) -> Option<String> {
+        self.read(&["-z", "--get", key]).pop()
+    }
+
+    fn get_all(&self, key: &str) -> Vec<String> {
+        self.read(&["-z", "--get-all", key])
+    }
+}
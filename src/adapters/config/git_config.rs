@@ -0,0 +1,54 @@
+// Git config adapter - reads yak settings from git config (e.g., `git config yaks.retention.doneDays 14`)
+
+use crate::ports::ConfigPort;
+use anyhow::{Context, Result};
+use git2::{Config, Repository};
+use std::path::Path;
+
+pub struct GitConfig {
+    config: Config,
+}
+
+impl GitConfig {
+    /// Repo config layered over global config, or just global config
+    /// when not inside a repository (e.g., `yx all list` from $HOME)
+    pub fn new() -> Result<Self> {
+        let git_work_tree = std::env::var("GIT_WORK_TREE")
+            .or_else(|_| std::env::current_dir().map(|p| p.display().to_string()))?;
+
+        Self::for_work_tree(Path::new(&git_work_tree))
+    }
+
+    /// Config for another repository (e.g., a configured workspace)
+    pub fn for_work_tree(work_tree: &Path) -> Result<Self> {
+        let config = match Repository::discover(work_tree) {
+            Ok(repo) => repo.config(),
+            Err(_) => Config::open_default(),
+        };
+
+        // Snapshot so reads are consistent for the lifetime of the command
+        let config = config
+            .and_then(|mut config| config.snapshot())
+            .context("Failed to read git config")?;
+
+        Ok(Self { config })
+    }
+}
+
+impl ConfigPort for GitConfig {
+    fn get(&self, key: &str) -> Option<String> {
+        self.config.get_string(key).ok()
+    }
+
+    fn get_all(&self, key: &str) -> Vec<String> {
+        let mut values = Vec::new();
+        if let Ok(mut entries) = self.config.multivar(key, None) {
+            while let Some(Ok(entry)) = entries.next() {
+                if let Some(value) = entry.value() {
+                    values.push(value.to_string());
+                }
+            }
+        }
+        values
+    }
+}
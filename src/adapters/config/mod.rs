@@ -0,0 +1,9 @@
+#[cfg(not(feature = "libgit2"))]
+mod git_cli_config;
+#[cfg(feature = "libgit2")]
+mod git_config;
+
+#[cfg(not(feature = "libgit2"))]
+pub use git_cli_config::GitConfig;
+#[cfg(feature = "libgit2")]
+pub use git_config::GitConfig;
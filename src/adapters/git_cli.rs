@@ -0,0 +1,105 @@
+// Git command-line runner - stands in for libgit2 when built without the `libgit2` feature
+
+use anyhow::{Context, Result};
+use std::ffi::OsStr;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+pub struct GitCli {
+    work_tree: PathBuf,
+}
+
+impl GitCli {
+    /// The repository at (or containing) `work_tree`
+    pub fn open(work_tree: &Path) -> Result<Self> {
+        let git = Self {
+            work_tree: work_tree.to_path_buf(),
+        };
+        git.run(&["rev-parse", "--git-dir"])
+            .with_context(|| format!("Failed to open git repository at {}", work_tree.display()))?;
+        Ok(git)
+    }
+
+    /// Run a git command, failing with its stderr when it exits non-zero
+    pub fn run(&self, args: &[&str]) -> Result<String> {
+        self.run_with(args, &[], None)
+    }
+
+    /// Run a git command, or None when it exits non-zero (e.g., a missing ref)
+    #[cfg_attr(not(feature = "git-cli"), allow(dead_code))]
+    pub fn try_run(&self, args: &[&str]) -> Result<Option<String>> {
+        let output = self.output(args, &[], None)?;
+        Ok(output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).into_owned()))
+    }
+
+    /// Run a git command with extra environment and optional stdin
+    pub fn run_with(
+        &self,
+        args: &[&str],
+        env: &[(&str, &OsStr)],
+        input: Option<&[u8]>,
+    ) -> Result<String> {
+        let bytes = self.run_bytes(args, env, input)?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Run a git command and return its raw stdout (e.g., blob contents)
+    pub fn run_bytes(
+        &self,
+        args: &[&str],
+        env: &[(&str, &OsStr)],
+        input: Option<&[u8]>,
+    ) -> Result<Vec<u8>> {
+        let output = self.output(args, env, input)?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "git {} failed: {}",
+                args.first().unwrap_or(&""),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(output.stdout)
+    }
+
+    /// The git dir shared by all worktrees of this repository
+    pub fn common_dir(&self) -> Result<PathBuf> {
+        let dir = self.run(&["rev-parse", "--git-common-dir"])?;
+        Ok(self.work_tree.join(dir.trim()))
+    }
+
+    fn output(
+        &self,
+        args: &[&str],
+        env: &[(&str, &OsStr)],
+        input: Option<&[u8]>,
+    ) -> Result<Output> {
+        let mut command = Command::new("git");
+        command
+            .arg("-C")
+            .arg(&self.work_tree)
+            .args(args)
+            .envs(env.iter().copied())
+            .stdin(if input.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let mut child = command.spawn().context("Failed to run git")?;
+        if let Some(input) = input {
+            // Dropping stdin after writing signals end of input
+            child
+                .stdin
+                .take()
+                .context("Failed to open git stdin")?
+                .write_all(input)?;
+        }
+        child.wait_with_output().context("Failed to run git")
+    }
+}
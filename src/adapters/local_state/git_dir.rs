@@ -0,0 +1,111 @@
+// Git dir local state adapter - keeps unsynced per-repo state in .git/yaks/
+
+#[cfg(not(feature = "libgit2"))]
+use crate::adapters::git_cli::GitCli;
+use crate::adapters::workspace;
+use crate::ports::LocalStatePort;
+use anyhow::{Context, Result};
+#[cfg(feature = "libgit2")]
+use git2::Repository;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub struct GitDirState {
+    base_path: PathBuf,
+}
+
+impl GitDirState {
+    pub fn new() -> Result<Self> {
+        let git_work_tree = std::env::var("GIT_WORK_TREE")
+            .or_else(|_| std::env::current_dir().map(|p| p.display().to_string()))?;
+
+        Self::open(Path::new(&git_work_tree), workspace::selected().as_deref())
+    }
+
+    /// Local state for another repository (e.g., a configured workspace)
+    pub fn for_work_tree(work_tree: &Path) -> Result<Self> {
+        Self::open(work_tree, None)
+    }
+
+    /// The state of one of the repository's named workspaces, or of its yaks in .yaks
+    fn open(work_tree: &Path, workspace: Option<&str>) -> Result<Self> {
+        // Use the common dir so all worktrees share the same local state
+        let base_path = workspace::local_dir(&Self::common_dir(work_tree)?, workspace);
+
+        Ok(Self { base_path })
+    }
+
+    /// The git dir shared by all worktrees of the repository at `work_tree`
+    /// (linked worktrees point at it from their private git dir)
+    pub fn common_dir(work_tree: &Path) -> Result<PathBuf> {
+        #[cfg(feature = "libgit2")]
+        {
+            let repo = Repository::discover(work_tree).with_context(|| {
+                format!("Failed to open git repository at {}", work_tree.display())
+            })?;
+            let git_dir = repo.path();
+            Ok(match fs::read_to_string(git_dir.join("commondir")) {
+                Ok(relative) => git_dir.join(relative.trim()),
+                Err(_) => git_dir.to_path_buf(),
+            })
+        }
+        #[cfg(not(feature = "libgit2"))]
+        GitCli::open(work_tree)?.common_dir()
+    }
+
+    /// Creates a GitDirState with an explicit path.
+    /// This is intended for testing only.
+    #[cfg(test)]
+    fn from_path(base_path: PathBuf) -> Self {
+        Self { base_path }
+    }
+}
+
+impl LocalStatePort for GitDirState {
+    fn read(&self, key: &str) -> Result<Option<String>> {
+        let path = self.base_path.join(key);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read local state '{key}'"))?;
+        Ok(Some(text))
+    }
+
+    fn write(&self, key: &str, value: Option<&str>) -> Result<()> {
+        let path = self.base_path.join(key);
+        match value {
+            Some(text) => {
+                fs::create_dir_all(&self.base_path)
+                    .context("Failed to create local state directory")?;
+                fs::write(&path, text)
+                    .with_context(|| format!("Failed to write local state '{key}'"))
+            }
+            None => {
+                if path.is_file() {
+                    fs::remove_file(&path)
+                        .with_context(|| format!("Failed to clear local state '{key}'"))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_read_write_clear() {
+        let temp_dir = TempDir::new().unwrap();
+        let state = GitDirState::from_path(temp_dir.path().join("yaks"));
+
+        assert_eq!(state.read("seen").unwrap(), None);
+        state.write("seen", Some("abc")).unwrap();
+        assert_eq!(state.read("seen").unwrap(), Some("abc".to_string()));
+        state.write("seen", None).unwrap();
+        assert_eq!(state.read("seen").unwrap(), None);
+    }
+}
@@ -0,0 +1,3 @@
+mod git_dir;
+
+pub use git_dir::GitDirState;
@@ -0,0 +1,207 @@
+// Lock file - keeps concurrent yx commands from interleaving changes to .yaks
+
+use crate::adapters::storage::is_local_file;
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+/// The lock's file name at the top of .yaks; it is never part of a yak (see
+/// `storage::is_local_file`)
+pub const LOCK_FILE: &str = ".lock";
+
+/// How long to wait for another command to finish with the yaks
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A lock this old is taken to be left behind by a command that crashed
+const STALE_AFTER: Duration = Duration::from_secs(120);
+
+const RETRY_EVERY: Duration = Duration::from_millis(50);
+
+/// Locks this process holds, with how many guards share each
+static HELD: Mutex<BTreeMap<PathBuf, usize>> = Mutex::new(BTreeMap::new());
+
+/// Holds the lock on a .yaks folder until dropped
+#[must_use = "the lock is released as soon as it's dropped"]
+pub struct YakLock {
+    path: PathBuf,
+}
+
+impl YakLock {
+    /// Lock the yaks in `yaks_dir`, waiting while another yx command holds
+    /// them; locking again within the same process shares the held lock, so
+    /// storage, log and sync steps of one command nest freely
+    pub fn acquire(yaks_dir: &Path) -> Result<Self> {
+        Self::acquire_within(yaks_dir, TIMEOUT)
+    }
+
+    fn acquire_within(yaks_dir: &Path, timeout: Duration) -> Result<Self> {
+        fs::create_dir_all(yaks_dir)
+            .with_context(|| format!("Failed to create {}", yaks_dir.display()))?;
+        // Adapters may name the same folder differently (relative or not)
+        let yaks_dir = fs::canonicalize(yaks_dir).unwrap_or_else(|_| yaks_dir.to_path_buf());
+        let path = yaks_dir.join(LOCK_FILE);
+        let mut held = HELD.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(count) = held.get_mut(&path) {
+            *count += 1;
+            return Ok(Self { path });
+        }
+
+        let started = Instant::now();
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    // The owner's pid, so a lock left by a crash can be spotted
+                    let _ = writeln!(file, "{}", std::process::id());
+                    break;
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    if is_stale(&path) && break_stale(&path) {
+                        continue;
+                    }
+                    if started.elapsed() >= timeout {
+                        let owner = owner(&path)
+                            .map(|pid| format!(" (pid {pid})"))
+                            .unwrap_or_default();
+                        anyhow::bail!(
+                            "another yx command{owner} is changing the yaks; if none is running, remove {}",
+                            path.display()
+                        );
+                    }
+                    thread::sleep(RETRY_EVERY);
+                }
+                Err(e) => {
+                    return Err(e).with_context(|| format!("Failed to create {}", path.display()))
+                }
+            }
+        }
+
+        held.insert(path.clone(), 1);
+        Ok(Self { path })
+    }
+}
+
+impl Drop for YakLock {
+    fn drop(&mut self) {
+        let mut held = HELD.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(count) = held.get_mut(&self.path) {
+            *count -= 1;
+            if *count == 0 {
+                held.remove(&self.path);
+                let _ = fs::remove_file(&self.path);
+            }
+        }
+    }
+}
+
+/// Remove everything in `yaks_dir` but what's kept for this checkout alone
+/// (the lock, the trash, ...), for steps that replace all the yaks at once
+pub fn clear_yaks_dir(yaks_dir: &Path) -> Result<()> {
+    if !yaks_dir.exists() {
+        return fs::create_dir_all(yaks_dir).map_err(Into::into);
+    }
+    for entry in fs::read_dir(yaks_dir)? {
+        let entry = entry?;
+        if is_local_file(Path::new(&entry.file_name())) {
+            continue;
+        }
+        if entry.file_type()?.is_dir() {
+            fs::remove_dir_all(entry.path())?;
+        } else {
+            fs::remove_file(entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+/// The pid recorded in a lock file
+fn owner(path: &Path) -> Option<u32> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// A lock whose process has exited or, where that can't be told, that has
+/// been held too long to be real; a live owner may hold it as long as it runs
+fn is_stale(path: &Path) -> bool {
+    if cfg!(target_os = "linux") && Path::new("/proc/self").exists() {
+        if let Some(pid) = owner(path) {
+            return !Path::new(&format!("/proc/{pid}")).exists();
+        }
+    }
+    fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age > STALE_AFTER)
+}
+
+/// Move a stale lock out of the way, returning whether it's gone. Renaming
+/// it first means only one waiting command can take it; if the lock it took
+/// turns out to be fresh (another command broke the stale one and locked in
+/// between), it's put back rather than removed
+fn break_stale(path: &Path) -> bool {
+    let moved = path.with_extension(format!("stale-{}", std::process::id()));
+    if fs::rename(path, &moved).is_err() {
+        return false;
+    }
+    if !is_stale(&moved) {
+        // Linking fails if yet another lock appeared, which then stands
+        let _ = fs::hard_link(&moved, path);
+        let _ = fs::remove_file(&moved);
+        return false;
+    }
+    let _ = fs::remove_file(&moved);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_lock_is_shared_within_a_process_and_released() {
+        let dir = TempDir::new().unwrap();
+        let lock_file = dir.path().join(LOCK_FILE);
+        fs::create_dir(dir.path().join("sub")).unwrap();
+
+        let outer = YakLock::acquire(dir.path()).unwrap();
+        // The same folder by another path shares the lock rather than waiting on it
+        let inner = YakLock::acquire_within(&dir.path().join("sub/.."), Duration::ZERO).unwrap();
+        assert_eq!(owner(&lock_file), Some(std::process::id()));
+        drop(inner);
+        assert!(lock_file.exists());
+        drop(outer);
+        assert!(!lock_file.exists());
+    }
+
+    #[test]
+    fn test_lock_waits_for_others_and_clears_stale_ones() {
+        let dir = TempDir::new().unwrap();
+        let lock_file = dir.path().join(LOCK_FILE);
+
+        // Held by a live process (this one, from outside the lock's records)
+        fs::write(&lock_file, format!("{}\n", std::process::id())).unwrap();
+        let error = YakLock::acquire_within(dir.path(), Duration::from_millis(120))
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("another yx command (pid"));
+
+        // Left by a process that's gone: pid numbers never get this high
+        fs::write(&lock_file, "4294967295\n").unwrap();
+        if cfg!(target_os = "linux") {
+            let lock = YakLock::acquire_within(dir.path(), Duration::ZERO).unwrap();
+            drop(lock);
+            assert!(!lock_file.exists());
+
+            // A stale lock replaced by a live one before it's broken stays
+            fs::write(&lock_file, format!("{}\n", std::process::id())).unwrap();
+            assert!(!break_stale(&lock_file));
+            assert_eq!(owner(&lock_file), Some(std::process::id()));
+            assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+        }
+    }
+}
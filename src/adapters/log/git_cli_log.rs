@@ -0,0 +1,553 @@
+// Git log adapter over the `git` command, for builds without libgit2
+
+use crate::adapters::git_cli::GitCli;
+use crate::adapters::lock::YakLock;
+use crate::adapters::storage::{is_local_file, yak_file_path, yak_file_paths};
+use crate::adapters::workspace;
+use crate::ports::{
+    ChangeKind, FileChange, FileVersion, HistoryPort, LogEntry, LogPort, Revision, YakSnapshot,
+};
+use anyhow::{Context, Result};
+use chrono::DateTime;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+// Fields of one commit, NUL-separated, one commit per line
+const REVISION_FORMAT: &str = "--format=%H%x00%an%x00%ct%x00%s";
+
+pub struct GitCliLog {
+    git: GitCli,
+    yaks_path: PathBuf,
+    /// refs/notes/yaks, or the selected workspace's own ref
+    notes_ref: String,
+    /// Where `yx sync` fetches origin's yaks to
+    remote_ref: String,
+}
+
+impl GitCliLog {
+    /// Log of the yaks in `yaks_path`, as resolved by DirectoryStorage
+    pub fn new(yaks_path: &Path) -> Result<Self> {
+        let git_work_tree = std::env::var("GIT_WORK_TREE")
+            .or_else(|_| std::env::current_dir().map(|p| p.display().to_string()))?;
+
+        let git = GitCli::open(Path::new(&git_work_tree))?;
+
+        // Resolve yaks_path relative to git_work_tree if it's relative
+        let yaks_path = if yaks_path.is_absolute() {
+            yaks_path.to_path_buf()
+        } else {
+            PathBuf::from(&git_work_tree).join(yaks_path)
+        };
+
+        let workspace = workspace::selected();
+        Ok(Self {
+            git,
+            yaks_path,
+            notes_ref: workspace::notes_ref(workspace.as_deref()),
+            remote_ref: workspace::remote_ref(workspace.as_deref()),
+        })
+    }
+
+    /// Log for another repository's yaks (e.g., a configured workspace)
+    pub fn for_work_tree(work_tree: &Path, yaks_path: &Path) -> Result<Self> {
+        Ok(Self {
+            git: GitCli::open(work_tree)?,
+            yaks_path: yaks_path.to_path_buf(),
+            notes_ref: workspace::notes_ref(None),
+            remote_ref: workspace::remote_ref(None),
+        })
+    }
+
+    /// Creates a GitCliLog with an explicit work tree and yaks path.
+    /// This is intended for testing only.
+    #[cfg(test)]
+    fn from_paths(work_tree: &Path, yaks_path: PathBuf) -> Result<Self> {
+        Ok(Self {
+            git: GitCli::open(work_tree)?,
+            yaks_path,
+            notes_ref: workspace::notes_ref(None),
+            remote_ref: workspace::remote_ref(None),
+        })
+    }
+
+    // Build a tree from .yaks directory using a throwaway index
+    fn build_tree_from_yaks(&self) -> Result<String> {
+        let index_dir = tempfile::tempdir()?;
+        let index_file = index_dir.path().join("index");
+        let env = [("GIT_INDEX_FILE", index_file.as_os_str())];
+
+        let mut files = Vec::new();
+        for entry in walkdir::WalkDir::new(&self.yaks_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let relative = entry.path().strip_prefix(&self.yaks_path)?;
+            if is_local_file(relative) {
+                continue;
+            }
+            files.push((
+                entry.path().to_path_buf(),
+                relative.to_string_lossy().into_owned(),
+            ));
+        }
+
+        if !files.is_empty() {
+            let paths: String = files
+                .iter()
+                .map(|(path, _)| format!("{}\n", path.display()))
+                .collect();
+            let blobs = self.git.run_with(
+                &["hash-object", "-w", "--no-filters", "--stdin-paths"],
+                &[],
+                Some(paths.as_bytes()),
+            )?;
+
+            let index_info: String = blobs
+                .lines()
+                .zip(&files)
+                .map(|(blob, (_, relative))| format!("100644 {blob}\t{relative}\0"))
+                .collect();
+            self.git.run_with(
+                &["update-index", "--add", "-z", "--index-info"],
+                &env,
+                Some(index_info.as_bytes()),
+            )?;
+        }
+
+        let tree = self.git.run_with(&["write-tree"], &env, None)?;
+        Ok(tree.trim().to_string())
+    }
+
+    // Get the commit id of refs/notes/yaks if it exists
+    fn get_local_ref(&self) -> Result<Option<String>> {
+        Ok(self
+            .git
+            .try_run(&["rev-parse", "--verify", "-q", &self.notes_ref])?
+            .map(|id| id.trim().to_string()))
+    }
+
+    // One commit as a revision
+    fn revision(&self, rev: &str) -> Result<Revision> {
+        let text = self.git.run(&["log", "-1", REVISION_FORMAT, rev, "--"])?;
+        parse_revision(text.trim_end()).with_context(|| format!("Unknown yak revision {rev}"))
+    }
+
+    // Commits reachable from refs/notes/yaks, newest first, optionally only
+    // those touching one yak's folder
+    fn log_revisions(&self, extra: &[&str], name: Option<&str>) -> Result<Vec<Revision>> {
+        let Some(head) = self.get_local_ref()? else {
+            return Ok(Vec::new());
+        };
+
+        let pathspec = name.map(|name| format!(":(literal){name}"));
+        let mut args = vec!["log", REVISION_FORMAT];
+        args.extend_from_slice(extra);
+        args.push(&head);
+        args.push("--");
+        if let Some(pathspec) = &pathspec {
+            args.push(pathspec);
+        }
+
+        let text = self.git.run(&args)?;
+        Ok(text.lines().filter_map(parse_revision).collect())
+    }
+
+    // The blob id of one of a yak's own files in a commit, in whichever
+    // layout the commit used
+    fn file_blob(&self, rev: &str, paths: &[String]) -> Result<Option<String>> {
+        let mut args = vec!["ls-tree", "-z", rev, "--"];
+        args.extend(paths.iter().map(String::as_str));
+        // None for a revision that doesn't exist, e.g. the first one's parent
+        let Some(listing) = self.git.try_run(&args)? else {
+            return Ok(None);
+        };
+
+        // "<mode> blob <id>\t<path>" for each path the commit has
+        let blobs: BTreeMap<&str, &str> = listing
+            .split('\0')
+            .filter_map(|entry| {
+                let (meta, path) = entry.split_once('\t')?;
+                let mut fields = meta.split(' ');
+                let kind = fields.nth(1)?;
+                (kind == "blob").then_some((path, fields.next()?))
+            })
+            .collect();
+        Ok(paths
+            .iter()
+            .find_map(|path| blobs.get(path.as_str()))
+            .map(|id| id.to_string()))
+    }
+
+    fn file_version(&self, revision: Revision, blob: &str) -> Result<FileVersion> {
+        let content = self.git.run_bytes(&["cat-file", "blob", blob], &[], None)?;
+        Ok(FileVersion {
+            revision,
+            content: String::from_utf8_lossy(&content).into_owned(),
+        })
+    }
+}
+
+fn parse_revision(line: &str) -> Option<Revision> {
+    let mut fields = line.splitn(4, '\0');
+    let id = fields.next()?.to_string();
+    let author = fields.next()?.to_string();
+    let seconds = fields.next()?.parse().ok()?;
+    let command = fields.next().unwrap_or_default().trim().to_string();
+    Some(Revision {
+        id,
+        command,
+        author,
+        time: DateTime::from_timestamp(seconds, 0)?,
+    })
+}
+
+impl LogPort for GitCliLog {
+    fn log_command(&self, command: &str) -> Result<()> {
+        // Skip if not in a git repo or yaks path doesn't exist
+        if !self.yaks_path.exists() {
+            return Ok(());
+        }
+        let _lock = YakLock::acquire(&self.yaks_path)?;
+
+        let tree = self.build_tree_from_yaks()?;
+
+        // Get parent commit if refs/notes/yaks exists
+        let parent = self.get_local_ref()?;
+        let mut args = vec!["commit-tree", tree.as_str(), "-m", command];
+        if let Some(parent) = &parent {
+            args.extend(["-p", parent.as_str()]);
+        }
+
+        let commit = self.git.run(&args).context("Failed to log yak command")?;
+        self.git
+            .run(&["update-ref", &self.notes_ref, commit.trim()])?;
+
+        Ok(())
+    }
+
+    fn entries(&self) -> Result<Vec<LogEntry>> {
+        // Merge commits from sync don't represent a command
+        let mut revisions = self.log_revisions(&["--date-order", "--no-merges"], None)?;
+        revisions.reverse();
+
+        Ok(revisions
+            .into_iter()
+            .map(|revision| LogEntry {
+                command: revision.command,
+                time: revision.time,
+            })
+            .collect())
+    }
+}
+
+impl HistoryPort for GitCliLog {
+    fn revisions(&self, name: Option<&str>) -> Result<Vec<Revision>> {
+        self.log_revisions(&["--topo-order", "--full-history"], name)
+    }
+
+    fn changed_files(&self, revision: &str, name: &str) -> Result<Vec<FileChange>> {
+        let commit = format!("{revision}^{{commit}}");
+        self.git
+            .run(&["rev-parse", "--verify", "-q", &commit])
+            .with_context(|| format!("Unknown yak revision {revision}"))?;
+
+        // Compare with the first parent, or the empty tree for the first commit
+        let parent = format!("{revision}^1");
+        let before = match self
+            .git
+            .try_run(&["rev-parse", "--verify", "-q", &parent])?
+        {
+            Some(id) => id.trim().to_string(),
+            None => self
+                .git
+                .run_with(&["hash-object", "-t", "tree", "--stdin"], &[], Some(&[]))?
+                .trim()
+                .to_string(),
+        };
+
+        let pathspec = format!(":(literal){name}");
+        let text = self.git.run(&[
+            "diff-tree",
+            "-r",
+            "--no-renames",
+            "--name-status",
+            "-z",
+            &before,
+            revision,
+            "--",
+            &pathspec,
+        ])?;
+
+        let prefix = format!("{name}/");
+        let mut fields = text.split('\0');
+        let mut changes = Vec::new();
+        while let (Some(status), Some(path)) = (fields.next(), fields.next()) {
+            let kind = match status {
+                "A" => ChangeKind::Added,
+                "D" => ChangeKind::Deleted,
+                _ => ChangeKind::Modified,
+            };
+            if let Some(path) = path.strip_prefix(&prefix) {
+                changes.push(FileChange {
+                    path: yak_file_path(path),
+                    kind,
+                });
+            }
+        }
+
+        Ok(changes)
+    }
+
+    fn last_snapshot(&self, name: &str) -> Result<Option<(Revision, Vec<YakSnapshot>)>> {
+        // The newest commit touching the yak either has it, or deleted it
+        // (in which case its first parent has the last copy)
+        for revision in self.revisions(Some(name))? {
+            let candidates = [revision.id.clone(), format!("{}^1", revision.id)];
+            for candidate in candidates {
+                let folder = format!("{candidate}:{name}");
+                let kind = self.git.try_run(&["cat-file", "-t", &folder])?;
+                if kind.as_deref().map(str::trim) != Some("tree") {
+                    continue;
+                }
+
+                let listing = self.git.run(&["ls-tree", "-r", "-z", &folder])?;
+                let mut yaks: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+                for entry in listing.split('\0').filter(|entry| !entry.is_empty()) {
+                    // "<mode> blob <id>\t<path>"
+                    let Some((meta, path)) = entry.split_once('\t') else {
+                        continue;
+                    };
+                    let Some(blob) = meta.split(' ').nth(2) else {
+                        continue;
+                    };
+                    let path = yak_file_path(path);
+                    let (yak, file) = match path.rsplit_once('/') {
+                        Some((sub, file)) => (format!("{name}/{sub}"), file),
+                        None => (name.to_string(), path.as_str()),
+                    };
+                    let content = self.git.run_bytes(&["cat-file", "blob", blob], &[], None)?;
+                    yaks.entry(yak).or_default().push((
+                        file.to_string(),
+                        String::from_utf8_lossy(&content).into_owned(),
+                    ));
+                }
+
+                let revision = if candidate == revision.id {
+                    revision
+                } else {
+                    self.revision(&candidate)?
+                };
+                let snapshots = yaks
+                    .into_iter()
+                    .map(|(name, files)| YakSnapshot { name, files })
+                    .collect();
+                return Ok(Some((revision, snapshots)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn file_versions(&self, name: &str, file: &str) -> Result<Vec<FileVersion>> {
+        let Some(head) = self.get_local_ref()? else {
+            return Ok(Vec::new());
+        };
+
+        let paths = yak_file_paths(name, file);
+        let pathspecs: Vec<String> = paths
+            .iter()
+            .map(|path| format!(":(literal){path}"))
+            .collect();
+        let mut args = vec![
+            "log",
+            REVISION_FORMAT,
+            "--topo-order",
+            "--full-history",
+            &head,
+            "--",
+        ];
+        args.extend(pathspecs.iter().map(String::as_str));
+        let text = self.git.run(&args)?;
+
+        let mut versions = Vec::new();
+        for revision in text.lines().filter_map(parse_revision) {
+            let Some(blob) = self.file_blob(&revision.id, &paths)? else {
+                continue;
+            };
+            if self.file_blob(&format!("{}^1", revision.id), &paths)? == Some(blob.clone()) {
+                continue;
+            }
+            versions.push(self.file_version(revision, &blob)?);
+        }
+
+        Ok(versions)
+    }
+
+    fn remote_file_version(&self, name: &str, file: &str) -> Result<Option<FileVersion>> {
+        let Some(id) = self
+            .git
+            .try_run(&["rev-parse", "--verify", "-q", &self.remote_ref])?
+        else {
+            return Ok(None);
+        };
+        let id = id.trim();
+        match self.file_blob(id, &yak_file_paths(name, file))? {
+            Some(blob) => Ok(Some(self.file_version(self.revision(id)?, &blob)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn init_repo() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for args in [
+            &["init", "-q"][..],
+            &["config", "user.name", "ann"],
+            &["config", "user.email", "ann@example.com"],
+        ] {
+            Command::new("git")
+                .arg("-C")
+                .arg(dir.path())
+                .args(args)
+                .status()
+                .unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_last_snapshot_finds_deleted_yak() {
+        let dir = init_repo();
+        let yaks_path = dir.path().join(".yaks");
+        let log = GitCliLog::from_paths(dir.path(), yaks_path.clone()).unwrap();
+
+        fs::create_dir_all(yaks_path.join("app/.meta")).unwrap();
+        fs::create_dir_all(yaks_path.join("app/api/.meta")).unwrap();
+        fs::write(yaks_path.join("app/.meta/context.md"), "the app").unwrap();
+        fs::write(yaks_path.join("app/api/.meta/state"), "done\n").unwrap();
+        log.log_command("add app").unwrap();
+        fs::remove_dir_all(yaks_path.join("app")).unwrap();
+        log.log_command("rm app").unwrap();
+
+        let (revision, snapshots) = log.last_snapshot("app").unwrap().unwrap();
+        assert_eq!(revision.command, "add app");
+        assert_eq!(revision.author, "ann");
+        assert_eq!(
+            snapshots,
+            vec![
+                YakSnapshot {
+                    name: "app".to_string(),
+                    files: vec![("context.md".to_string(), "the app".to_string())],
+                },
+                YakSnapshot {
+                    name: "app/api".to_string(),
+                    files: vec![("state".to_string(), "done\n".to_string())],
+                },
+            ]
+        );
+        assert!(log.last_snapshot("missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_revisions_filter_to_one_yak() {
+        let dir = init_repo();
+        let yaks_path = dir.path().join(".yaks");
+        let log = GitCliLog::from_paths(dir.path(), yaks_path.clone()).unwrap();
+
+        fs::create_dir_all(yaks_path.join("app/.meta")).unwrap();
+        fs::write(yaks_path.join("app/.meta/context.md"), "").unwrap();
+        log.log_command("add app").unwrap();
+        fs::create_dir_all(yaks_path.join("docs/.meta")).unwrap();
+        fs::write(yaks_path.join("docs/.meta/context.md"), "").unwrap();
+        log.log_command("add docs").unwrap();
+        fs::write(yaks_path.join("app/.meta/state"), "done\n").unwrap();
+        log.log_command("done app").unwrap();
+
+        let commands = |name| -> Vec<String> {
+            log.revisions(name)
+                .unwrap()
+                .into_iter()
+                .map(|revision| revision.command)
+                .collect()
+        };
+        assert_eq!(commands(None), vec!["done app", "add docs", "add app"]);
+        assert_eq!(commands(Some("app")), vec!["done app", "add app"]);
+        assert_eq!(commands(Some("docs")), vec!["add docs"]);
+        assert_eq!(
+            log.entries()
+                .unwrap()
+                .into_iter()
+                .map(|entry| entry.command)
+                .collect::<Vec<_>>(),
+            vec!["add app", "add docs", "done app"]
+        );
+
+        let latest = &log.revisions(Some("app")).unwrap()[0];
+        assert_eq!(
+            log.changed_files(&latest.id, "app").unwrap(),
+            vec![FileChange {
+                path: "state".to_string(),
+                kind: ChangeKind::Added,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_file_versions_follow_one_file_across_layouts() {
+        let dir = init_repo();
+        let yaks_path = dir.path().join(".yaks");
+        let log = GitCliLog::from_paths(dir.path(), yaks_path.clone()).unwrap();
+
+        // Before format v3 the yak's files sat beside its children
+        fs::create_dir_all(yaks_path.join("app")).unwrap();
+        fs::write(yaks_path.join("app/context.md"), "first").unwrap();
+        log.log_command("add app").unwrap();
+        fs::remove_file(yaks_path.join("app/context.md")).unwrap();
+        fs::create_dir_all(yaks_path.join("app/.meta")).unwrap();
+        fs::write(yaks_path.join("app/.meta/context.md"), "first").unwrap();
+        log.log_command("migrate meta").unwrap();
+        fs::write(yaks_path.join("app/.meta/state"), "done\n").unwrap();
+        log.log_command("done app").unwrap();
+        fs::write(yaks_path.join("app/.meta/context.md"), "second").unwrap();
+        log.log_command("context app").unwrap();
+
+        let versions: Vec<(String, String)> = log
+            .file_versions("app", "context.md")
+            .unwrap()
+            .into_iter()
+            .map(|version| (version.revision.command, version.content))
+            .collect();
+        assert_eq!(
+            versions,
+            vec![
+                ("context app".to_string(), "second".to_string()),
+                ("add app".to_string(), "first".to_string()),
+            ]
+        );
+        assert!(log.file_versions("docs", "context.md").unwrap().is_empty());
+
+        // Origin's copy, once a sync has fetched it
+        assert!(log
+            .remote_file_version("app", "context.md")
+            .unwrap()
+            .is_none());
+        let head = log.get_local_ref().unwrap().unwrap();
+        log.git
+            .run(&["update-ref", &log.remote_ref, &head])
+            .unwrap();
+        let remote = log
+            .remote_file_version("app", "context.md")
+            .unwrap()
+            .unwrap();
+        assert_eq!(remote.revision.command, "context app");
+        assert_eq!(remote.content, "second");
+    }
+}
@@ -1,33 +1,74 @@
-// Git-based log adapter - commits yak operations to refs/notes/yaks
+// Git-based log adapter - commits yak operations to refs/notes/yaks and reads them back
 
-use crate::ports::LogPort;
+use crate::adapters::lock::YakLock;
+use crate::adapters::storage::{is_local_file, yak_file_path, yak_file_paths};
+use crate::adapters::workspace;
+use crate::ports::{
+    ChangeKind, FileChange, FileVersion, HistoryPort, LogEntry, LogPort, Revision, YakSnapshot,
+};
 use anyhow::{Context, Result};
+use chrono::DateTime;
 use git2::Repository;
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 pub struct GitLog {
     repo: Repository,
     yaks_path: PathBuf,
+    /// refs/notes/yaks, or the selected workspace's own ref
+    notes_ref: String,
+    /// Where `yx sync` fetches origin's yaks to
+    remote_ref: String,
 }
 
 impl GitLog {
-    pub fn new() -> Result<Self> {
+    /// Log of the yaks in `yaks_path`, as resolved by DirectoryStorage
+    pub fn new(yaks_path: &Path) -> Result<Self> {
         let git_work_tree = std::env::var("GIT_WORK_TREE")
             .or_else(|_| std::env::current_dir().map(|p| p.display().to_string()))?;
 
         let repo = Repository::open(&git_work_tree)
             .with_context(|| format!("Failed to open git repository at {git_work_tree}"))?;
 
-        let yak_path_str = std::env::var("YAK_PATH").unwrap_or_else(|_| ".yaks".to_string());
-
         // Resolve yaks_path relative to git_work_tree if it's relative
-        let yaks_path = if std::path::Path::new(&yak_path_str).is_absolute() {
-            PathBuf::from(yak_path_str)
+        let yaks_path = if yaks_path.is_absolute() {
+            yaks_path.to_path_buf()
         } else {
-            PathBuf::from(&git_work_tree).join(yak_path_str)
+            PathBuf::from(&git_work_tree).join(yaks_path)
         };
 
-        Ok(Self { repo, yaks_path })
+        let workspace = workspace::selected();
+        Ok(Self {
+            repo,
+            yaks_path,
+            notes_ref: workspace::notes_ref(workspace.as_deref()),
+            remote_ref: workspace::remote_ref(workspace.as_deref()),
+        })
+    }
+
+    /// Log for another repository's yaks (e.g., a configured workspace)
+    pub fn for_work_tree(work_tree: &Path, yaks_path: &Path) -> Result<Self> {
+        let repo = Repository::open(work_tree)
+            .with_context(|| format!("Failed to open git repository at {}", work_tree.display()))?;
+
+        Ok(Self {
+            repo,
+            yaks_path: yaks_path.to_path_buf(),
+            notes_ref: workspace::notes_ref(None),
+            remote_ref: workspace::remote_ref(None),
+        })
+    }
+
+    /// Creates a GitLog with an explicit repository and yaks path.
+    /// This is intended for testing only.
+    #[cfg(test)]
+    fn from_paths(repo: Repository, yaks_path: PathBuf) -> Self {
+        Self {
+            repo,
+            yaks_path,
+            notes_ref: workspace::notes_ref(None),
+            remote_ref: workspace::remote_ref(None),
+        }
     }
 
     // Build a tree from .yaks directory
@@ -42,6 +83,9 @@ impl GitLog {
             {
                 let path = entry.path();
                 let relative = path.strip_prefix(&self.yaks_path)?;
+                if is_local_file(relative) {
+                    continue;
+                }
                 let contents = std::fs::read(path)?;
 
                 // Create blob from file contents
@@ -72,11 +116,29 @@ impl GitLog {
 
     // Get the OID of refs/notes/yaks if it exists
     fn get_local_ref(&self) -> Result<Option<git2::Oid>> {
-        match self.repo.refname_to_id("refs/notes/yaks") {
+        match self.repo.refname_to_id(&self.notes_ref) {
             Ok(oid) => Ok(Some(oid)),
             Err(_) => Ok(None),
         }
     }
+
+    // The blob of one of a yak's own files in a commit, in whichever layout
+    // the commit used
+    fn file_blob(&self, commit: &git2::Commit, paths: &[String]) -> Result<Option<git2::Oid>> {
+        let tree = commit.tree()?;
+        Ok(paths.iter().find_map(|path| {
+            let entry = tree.get_path(std::path::Path::new(path)).ok()?;
+            (entry.kind() == Some(git2::ObjectType::Blob)).then(|| entry.id())
+        }))
+    }
+
+    fn file_version(&self, commit: &git2::Commit, blob: git2::Oid) -> Result<FileVersion> {
+        let blob = self.repo.find_blob(blob)?;
+        Ok(FileVersion {
+            revision: revision_of(commit),
+            content: String::from_utf8_lossy(blob.content()).into_owned(),
+        })
+    }
 }
 
 impl LogPort for GitLog {
@@ -85,6 +147,7 @@ impl LogPort for GitLog {
         if !self.yaks_path.exists() {
             return Ok(());
         }
+        let _lock = YakLock::acquire(&self.yaks_path)?;
 
         let tree_oid = self.build_tree_from_yaks()?;
         let tree = self.repo.find_tree(tree_oid)?;
@@ -98,15 +161,366 @@ impl LogPort for GitLog {
 
         // Create commit
         let sig = self.repo.signature()?;
-        self.repo.commit(
-            Some("refs/notes/yaks"),
-            &sig,
-            &sig,
-            command,
-            &tree,
-            &parents,
-        )?;
+        self.repo
+            .commit(Some(&self.notes_ref), &sig, &sig, command, &tree, &parents)?;
 
         Ok(())
     }
+    fn entries(&self) -> Result<Vec<LogEntry>> {
+        let Some(head) = self.get_local_ref()? else {
+            return Ok(Vec::new());
+        };
+
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push(head)?;
+        revwalk.set_sorting(git2::Sort::TIME | git2::Sort::REVERSE)?;
+
+        let mut entries = Vec::new();
+        for oid in revwalk {
+            let commit = self.repo.find_commit(oid?)?;
+            // Merge commits from sync don't represent a command
+            if commit.parent_count() > 1 {
+                continue;
+            }
+            let Some(time) = DateTime::from_timestamp(commit.time().seconds(), 0) else {
+                continue;
+            };
+            entries.push(LogEntry {
+                command: commit.message().unwrap_or_default().trim().to_string(),
+                time,
+            });
+        }
+
+        Ok(entries)
+    }
+}
+
+fn revision_of(commit: &git2::Commit) -> Revision {
+    Revision {
+        id: commit.id().to_string(),
+        command: commit.message().unwrap_or_default().trim().to_string(),
+        author: commit.author().name().unwrap_or_default().to_string(),
+        time: DateTime::from_timestamp(commit.time().seconds(), 0).unwrap_or_default(),
+    }
+}
+
+impl HistoryPort for GitLog {
+    fn revisions(&self, name: Option<&str>) -> Result<Vec<Revision>> {
+        let Some(head) = self.get_local_ref()? else {
+            return Ok(Vec::new());
+        };
+
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push(head)?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+
+        // The yak's subtree id in a commit; unchanged ids mean an untouched yak
+        let subtree_id = |commit: &git2::Commit| -> Result<Option<git2::Oid>> {
+            let path = std::path::Path::new(name.unwrap_or_default());
+            Ok(commit.tree()?.get_path(path).ok().map(|entry| entry.id()))
+        };
+
+        let mut revisions = Vec::new();
+        for oid in revwalk {
+            let commit = self.repo.find_commit(oid?)?;
+            if name.is_some() {
+                let before = match commit.parent(0) {
+                    Ok(parent) => subtree_id(&parent)?,
+                    Err(_) => None,
+                };
+                if subtree_id(&commit)? == before {
+                    continue;
+                }
+            }
+            revisions.push(revision_of(&commit));
+        }
+
+        Ok(revisions)
+    }
+
+    fn changed_files(&self, revision: &str, name: &str) -> Result<Vec<FileChange>> {
+        let oid = git2::Oid::from_str(revision)?;
+        let commit = self
+            .repo
+            .find_commit(oid)
+            .with_context(|| format!("Unknown yak revision {revision}"))?;
+
+        // The yak's folder before and after; either side may be missing
+        let subtree = |commit: &git2::Commit| -> Result<Option<git2::Tree>> {
+            match commit.tree()?.get_path(std::path::Path::new(name)) {
+                Ok(entry) if entry.kind() == Some(git2::ObjectType::Tree) => {
+                    Ok(Some(self.repo.find_tree(entry.id())?))
+                }
+                _ => Ok(None),
+            }
+        };
+        let after = subtree(&commit)?;
+        let before = match commit.parent(0) {
+            Ok(parent) => subtree(&parent)?,
+            Err(_) => None,
+        };
+
+        let diff = self
+            .repo
+            .diff_tree_to_tree(before.as_ref(), after.as_ref(), None)?;
+        let changes = diff
+            .deltas()
+            .filter_map(|delta| {
+                let kind = match delta.status() {
+                    git2::Delta::Added => ChangeKind::Added,
+                    git2::Delta::Deleted => ChangeKind::Deleted,
+                    _ => ChangeKind::Modified,
+                };
+                let file = match kind {
+                    ChangeKind::Deleted => delta.old_file(),
+                    _ => delta.new_file(),
+                };
+                let path = yak_file_path(file.path()?.to_str()?);
+                Some(FileChange { path, kind })
+            })
+            .collect();
+
+        Ok(changes)
+    }
+
+    fn last_snapshot(&self, name: &str) -> Result<Option<(Revision, Vec<YakSnapshot>)>> {
+        let Some(head) = self.get_local_ref()? else {
+            return Ok(None);
+        };
+
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push(head)?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+
+        for oid in revwalk {
+            let commit = self.repo.find_commit(oid?)?;
+            let Ok(entry) = commit.tree()?.get_path(std::path::Path::new(name)) else {
+                continue;
+            };
+            if entry.kind() != Some(git2::ObjectType::Tree) {
+                continue;
+            }
+
+            // Every file under the yak's folder, grouped by the yak it belongs to
+            let subtree = self.repo.find_tree(entry.id())?;
+            let mut yaks: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+            let mut error = None;
+            subtree.walk(git2::TreeWalkMode::PreOrder, |root, entry| {
+                if entry.kind() != Some(git2::ObjectType::Blob) {
+                    return git2::TreeWalkResult::Ok;
+                }
+                let yak = match yak_file_path(root.trim_end_matches('/')).as_str() {
+                    "" => name.to_string(),
+                    sub => format!("{name}/{sub}"),
+                };
+                match self.repo.find_blob(entry.id()) {
+                    Ok(blob) => yaks.entry(yak).or_default().push((
+                        entry.name().unwrap_or_default().to_string(),
+                        String::from_utf8_lossy(blob.content()).into_owned(),
+                    )),
+                    Err(e) => {
+                        error = Some(e);
+                        return git2::TreeWalkResult::Abort;
+                    }
+                }
+                git2::TreeWalkResult::Ok
+            })?;
+            if let Some(e) = error {
+                return Err(e.into());
+            }
+
+            let snapshots = yaks
+                .into_iter()
+                .map(|(name, files)| YakSnapshot { name, files })
+                .collect();
+            return Ok(Some((revision_of(&commit), snapshots)));
+        }
+
+        Ok(None)
+    }
+
+    fn file_versions(&self, name: &str, file: &str) -> Result<Vec<FileVersion>> {
+        let Some(head) = self.get_local_ref()? else {
+            return Ok(Vec::new());
+        };
+
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push(head)?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+
+        let paths = yak_file_paths(name, file);
+        let mut versions = Vec::new();
+        for oid in revwalk {
+            let commit = self.repo.find_commit(oid?)?;
+            let Some(id) = self.file_blob(&commit, &paths)? else {
+                continue;
+            };
+            let before = match commit.parent(0) {
+                Ok(parent) => self.file_blob(&parent, &paths)?,
+                Err(_) => None,
+            };
+            if before == Some(id) {
+                continue;
+            }
+            versions.push(self.file_version(&commit, id)?);
+        }
+
+        Ok(versions)
+    }
+
+    fn remote_file_version(&self, name: &str, file: &str) -> Result<Option<FileVersion>> {
+        let Ok(oid) = self.repo.refname_to_id(&self.remote_ref) else {
+            return Ok(None);
+        };
+        let commit = self.repo.find_commit(oid)?;
+        match self.file_blob(&commit, &yak_file_paths(name, file))? {
+            Some(blob) => Ok(Some(self.file_version(&commit, blob)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_last_snapshot_finds_deleted_yak() {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        repo.config().unwrap().set_str("user.name", "ann").unwrap();
+        repo.config()
+            .unwrap()
+            .set_str("user.email", "ann@example.com")
+            .unwrap();
+        let yaks_path = dir.path().join(".yaks");
+        let log = GitLog::from_paths(repo, yaks_path.clone());
+
+        fs::create_dir_all(yaks_path.join("app/.meta")).unwrap();
+        fs::create_dir_all(yaks_path.join("app/api/.meta")).unwrap();
+        fs::write(yaks_path.join("app/.meta/context.md"), "the app").unwrap();
+        fs::write(yaks_path.join("app/api/.meta/state"), "done\n").unwrap();
+        log.log_command("add app").unwrap();
+        fs::remove_dir_all(yaks_path.join("app")).unwrap();
+        log.log_command("rm app").unwrap();
+
+        let (revision, snapshots) = log.last_snapshot("app").unwrap().unwrap();
+        assert_eq!(revision.command, "add app");
+        assert_eq!(revision.author, "ann");
+        assert_eq!(
+            snapshots,
+            vec![
+                YakSnapshot {
+                    name: "app".to_string(),
+                    files: vec![("context.md".to_string(), "the app".to_string())],
+                },
+                YakSnapshot {
+                    name: "app/api".to_string(),
+                    files: vec![("state".to_string(), "done\n".to_string())],
+                },
+            ]
+        );
+        assert!(log.last_snapshot("missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_revisions_filter_to_one_yak() {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        repo.config().unwrap().set_str("user.name", "ann").unwrap();
+        repo.config()
+            .unwrap()
+            .set_str("user.email", "ann@example.com")
+            .unwrap();
+        let yaks_path = dir.path().join(".yaks");
+        let log = GitLog::from_paths(repo, yaks_path.clone());
+
+        fs::create_dir_all(yaks_path.join("app/.meta")).unwrap();
+        fs::write(yaks_path.join("app/.meta/context.md"), "").unwrap();
+        log.log_command("add app").unwrap();
+        fs::create_dir_all(yaks_path.join("docs/.meta")).unwrap();
+        fs::write(yaks_path.join("docs/.meta/context.md"), "").unwrap();
+        log.log_command("add docs").unwrap();
+        fs::write(yaks_path.join("app/.meta/state"), "done\n").unwrap();
+        log.log_command("done app").unwrap();
+
+        let commands = |name| -> Vec<String> {
+            log.revisions(name)
+                .unwrap()
+                .into_iter()
+                .map(|revision| revision.command)
+                .collect()
+        };
+        assert_eq!(commands(None), vec!["done app", "add docs", "add app"]);
+        assert_eq!(commands(Some("app")), vec!["done app", "add app"]);
+        assert_eq!(commands(Some("docs")), vec!["add docs"]);
+
+        let latest = &log.revisions(Some("app")).unwrap()[0];
+        assert_eq!(
+            log.changed_files(&latest.id, "app").unwrap(),
+            vec![FileChange {
+                path: "state".to_string(),
+                kind: ChangeKind::Added,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_file_versions_follow_one_file_across_layouts() {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        repo.config().unwrap().set_str("user.name", "ann").unwrap();
+        repo.config()
+            .unwrap()
+            .set_str("user.email", "ann@example.com")
+            .unwrap();
+        let yaks_path = dir.path().join(".yaks");
+        let log = GitLog::from_paths(repo, yaks_path.clone());
+
+        // Before format v3 the yak's files sat beside its children
+        fs::create_dir_all(yaks_path.join("app")).unwrap();
+        fs::write(yaks_path.join("app/context.md"), "first").unwrap();
+        log.log_command("add app").unwrap();
+        fs::remove_file(yaks_path.join("app/context.md")).unwrap();
+        fs::create_dir_all(yaks_path.join("app/.meta")).unwrap();
+        fs::write(yaks_path.join("app/.meta/context.md"), "first").unwrap();
+        log.log_command("migrate meta").unwrap();
+        fs::write(yaks_path.join("app/.meta/state"), "done\n").unwrap();
+        log.log_command("done app").unwrap();
+        fs::write(yaks_path.join("app/.meta/context.md"), "second").unwrap();
+        log.log_command("context app").unwrap();
+
+        let versions: Vec<(String, String)> = log
+            .file_versions("app", "context.md")
+            .unwrap()
+            .into_iter()
+            .map(|version| (version.revision.command, version.content))
+            .collect();
+        assert_eq!(
+            versions,
+            vec![
+                ("context app".to_string(), "second".to_string()),
+                ("add app".to_string(), "first".to_string()),
+            ]
+        );
+        assert!(log.file_versions("docs", "context.md").unwrap().is_empty());
+
+        // Origin's copy, once a sync has fetched it
+        assert!(log
+            .remote_file_version("app", "context.md")
+            .unwrap()
+            .is_none());
+        let head = log.get_local_ref().unwrap().unwrap();
+        log.repo
+            .reference(&log.remote_ref, head, true, "fetch")
+            .unwrap();
+        let remote = log
+            .remote_file_version("app", "context.md")
+            .unwrap()
+            .unwrap();
+        assert_eq!(remote.revision.command, "context app");
+        assert_eq!(remote.content, "second");
+    }
 }
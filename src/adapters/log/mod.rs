@@ -1,3 +1,13 @@
+#[cfg(all(feature = "git-cli", not(feature = "libgit2")))]
+mod git_cli_log;
+#[cfg(feature = "libgit2")]
 mod git_log;
+#[cfg(not(any(feature = "libgit2", feature = "git-cli")))]
+mod no_log;
 
+#[cfg(all(feature = "git-cli", not(feature = "libgit2")))]
+pub use git_cli_log::GitCliLog;
+#[cfg(feature = "libgit2")]
 pub use git_log::GitLog;
+#[cfg(not(any(feature = "libgit2", feature = "git-cli")))]
+pub use no_log::NoLog;
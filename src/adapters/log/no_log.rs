@@ -0,0 +1,55 @@
+// Log stand-in for storage-only builds - operations aren't recorded, so there is no history
+
+use crate::ports::{
+    FileChange, FileVersion, HistoryPort, LogEntry, LogPort, Revision, YakSnapshot,
+};
+use anyhow::Result;
+use std::path::Path;
+
+const UNAVAILABLE: &str =
+    "yak history is unavailable: yx was built without the libgit2 or git-cli feature";
+
+pub struct NoLog;
+
+impl NoLog {
+    pub fn new(_yaks_path: &Path) -> Result<Self> {
+        Ok(Self)
+    }
+
+    /// Log for another repository's yaks (e.g., a configured workspace)
+    pub fn for_work_tree(_work_tree: &Path, _yaks_path: &Path) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+impl LogPort for NoLog {
+    fn log_command(&self, _command: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn entries(&self) -> Result<Vec<LogEntry>> {
+        Ok(Vec::new())
+    }
+}
+
+impl HistoryPort for NoLog {
+    fn revisions(&self, _name: Option<&str>) -> Result<Vec<Revision>> {
+        anyhow::bail!(UNAVAILABLE)
+    }
+
+    fn changed_files(&self, _revision: &str, _name: &str) -> Result<Vec<FileChange>> {
+        anyhow::bail!(UNAVAILABLE)
+    }
+
+    fn last_snapshot(&self, _name: &str) -> Result<Option<(Revision, Vec<YakSnapshot>)>> {
+        anyhow::bail!(UNAVAILABLE)
+    }
+
+    fn file_versions(&self, _name: &str, _file: &str) -> Result<Vec<FileVersion>> {
+        anyhow::bail!(UNAVAILABLE)
+    }
+
+    fn remote_file_version(&self, _name: &str, _file: &str) -> Result<Option<FileVersion>> {
+        anyhow::bail!(UNAVAILABLE)
+    }
+}
@@ -1,6 +1,18 @@
 // Adapters - implementations of port traits for specific technologies
 
+pub mod blame;
+pub mod browser;
+pub mod cipher;
 pub mod cli;
+pub mod config;
+#[cfg(not(feature = "libgit2"))]
+mod git_cli;
+pub mod local_state;
+pub mod lock;
 pub mod log;
+pub mod stash;
 pub mod storage;
 pub mod sync;
+pub mod timer;
+pub mod watcher;
+pub mod workspace;
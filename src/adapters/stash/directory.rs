@@ -0,0 +1,365 @@
+// Directory stash adapter - copies yak files into .git/yaks/stash/<n>/
+
+use crate::adapters::storage::DirectoryStorage;
+use crate::ports::{StashEntry, StashPort, StoragePort};
+use anyhow::{Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File inside each stash entry describing what it holds
+const META_FILE: &str = "meta";
+
+/// Folder inside each stash entry holding the copied yak files
+const YAKS_FOLDER: &str = "yaks";
+
+/// The file a yak's context is copied to, beside its fields
+const CONTEXT_FILE: &str = "context.md";
+
+/// Reads and restores yaks through the storage, so popping a stash takes
+/// the lock, updates the index and encrypts like any other write
+pub struct DirectoryStash<'a> {
+    storage: &'a dyn StoragePort,
+    stash_path: PathBuf,
+}
+
+impl<'a> DirectoryStash<'a> {
+    /// Stashes for the yaks in `storage`, kept with the repository's other
+    /// local state and shared by all its worktrees
+    pub fn new(storage: &'a DirectoryStorage) -> Result<Self> {
+        Ok(Self {
+            storage,
+            stash_path: storage.local_dir()?.join("stash"),
+        })
+    }
+
+    /// Creates a DirectoryStash with an explicit stash path.
+    /// This is intended for testing only.
+    #[cfg(test)]
+    fn from_path(storage: &'a dyn StoragePort, stash_path: PathBuf) -> Self {
+        Self {
+            storage,
+            stash_path,
+        }
+    }
+
+    /// Stash entry folders with their sequence numbers, newest first
+    fn entry_dirs(&self) -> Result<Vec<(u64, PathBuf)>> {
+        if !self.stash_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut dirs = Vec::new();
+        for entry in fs::read_dir(&self.stash_path).context("Failed to read stash")? {
+            let entry = entry?;
+            if let Ok(seq) = entry.file_name().to_string_lossy().parse::<u64>() {
+                dirs.push((seq, entry.path()));
+            }
+        }
+        dirs.sort_by_key(|(seq, _)| std::cmp::Reverse(*seq));
+        Ok(dirs)
+    }
+
+    fn read_entry(dir: &Path) -> Result<(StashEntry, Vec<(String, bool)>)> {
+        let meta = fs::read_to_string(dir.join(META_FILE))
+            .with_context(|| format!("Failed to read stash entry {}", dir.display()))?;
+
+        let mut reason = String::new();
+        let mut created = DateTime::<Utc>::default();
+        let mut yaks = Vec::new();
+        for line in meta.lines() {
+            if let Some(value) = line.strip_prefix("reason: ") {
+                reason = value.to_string();
+            } else if let Some(value) = line.strip_prefix("created: ") {
+                created = DateTime::parse_from_rfc3339(value)
+                    .map(|time| time.with_timezone(&Utc))
+                    .unwrap_or_default();
+            } else if let Some(name) = line.strip_prefix("yak: ") {
+                yaks.push((name.to_string(), true));
+            } else if let Some(name) = line.strip_prefix("absent: ") {
+                yaks.push((name.to_string(), false));
+            }
+        }
+
+        let entry = StashEntry {
+            reason,
+            created,
+            yaks: yaks.iter().map(|(name, _)| name.clone()).collect(),
+        };
+        Ok((entry, yaks))
+    }
+
+    /// Copy the yak's context, when it has one it can read, and fields (not
+    /// its children) into `to`
+    fn save(&self, name: &str, to: &Path) -> Result<()> {
+        fs::create_dir_all(to).with_context(|| format!("Failed to create {}", to.display()))?;
+        let context = self.storage.read_context(name).ok();
+        let mut files: Vec<(String, String)> = context
+            .map(|text| (CONTEXT_FILE.to_string(), text))
+            .into_iter()
+            .collect();
+        for field in self.storage.list_fields(name)? {
+            if let Some(text) = self.storage.read_field(name, &field)? {
+                files.push((field, text));
+            }
+        }
+        for (file, text) in files {
+            fs::write(to.join(&file), text)
+                .with_context(|| format!("Failed to stash {file} for '{name}'"))?;
+        }
+        Ok(())
+    }
+
+    /// Put back the context and fields `save` copied into `from`, clearing
+    /// fields set since
+    fn restore(&self, name: &str, from: &Path) -> Result<()> {
+        if self.storage.get_yak(name).is_err() {
+            self.storage.create_yak(name)?;
+        }
+
+        let mut context = None;
+        let mut fields = Vec::new();
+        if from.is_dir() {
+            for entry in fs::read_dir(from)? {
+                let entry = entry?;
+                if !entry.file_type()?.is_file() {
+                    continue;
+                }
+                let file = entry.file_name().to_string_lossy().to_string();
+                let text = fs::read_to_string(entry.path())
+                    .with_context(|| format!("Failed to read {}", entry.path().display()))?;
+                if file == CONTEXT_FILE {
+                    context = Some(text);
+                } else {
+                    fields.push((file, text));
+                }
+            }
+        }
+
+        for field in self.storage.list_fields(name)? {
+            if !fields.iter().any(|(file, _)| *file == field) {
+                self.storage.write_field(name, &field, None)?;
+            }
+        }
+        for (field, text) in &fields {
+            self.storage.write_field(name, field, Some(text))?;
+        }
+        match context {
+            Some(text) => self.storage.write_context(name, &text),
+            None => Ok(()),
+        }
+    }
+}
+
+impl StashPort for DirectoryStash<'_> {
+    fn push(&self, reason: &str, names: &[String]) -> Result<()> {
+        let seq = self
+            .entry_dirs()?
+            .first()
+            .map(|(seq, _)| seq + 1)
+            .unwrap_or(0);
+        let dir = self.stash_path.join(seq.to_string());
+        fs::create_dir_all(&dir).context("Failed to create stash entry")?;
+
+        let mut meta = format!(
+            "reason: {reason}\ncreated: {}\n",
+            Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+        );
+        for name in names {
+            if self.storage.get_yak(name).is_ok() {
+                self.save(name, &dir.join(YAKS_FOLDER).join(name))?;
+                meta.push_str(&format!("yak: {name}\n"));
+            } else {
+                meta.push_str(&format!("absent: {name}\n"));
+            }
+        }
+
+        fs::write(dir.join(META_FILE), meta).context("Failed to write stash entry")
+    }
+
+    fn list(&self) -> Result<Vec<StashEntry>> {
+        self.entry_dirs()?
+            .iter()
+            .map(|(_, dir)| Self::read_entry(dir).map(|(entry, _)| entry))
+            .collect()
+    }
+
+    fn pop(&self) -> Result<Option<StashEntry>> {
+        let Some((_, dir)) = self.entry_dirs()?.into_iter().next() else {
+            return Ok(None);
+        };
+        let (entry, yaks) = Self::read_entry(&dir)?;
+
+        self.storage.transaction(&mut || {
+            for (name, existed) in &yaks {
+                if *existed {
+                    self.restore(name, &dir.join(YAKS_FOLDER).join(name))?;
+                } else if self.storage.get_yak(name).is_ok() {
+                    self.storage.delete_yak(name)?;
+                }
+            }
+            Ok(())
+        })?;
+
+        fs::remove_dir_all(&dir).context("Failed to drop stash entry")?;
+        Ok(Some(entry))
+    }
+
+    fn discard(&self) -> Result<()> {
+        if let Some((_, dir)) = self.entry_dirs()?.into_iter().next() {
+            fs::remove_dir_all(&dir).context("Failed to drop stash entry")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::ports::CipherPort;
+    use std::rc::Rc;
+    use tempfile::TempDir;
+
+    fn setup(storage: &dyn StoragePort) -> (TempDir, DirectoryStash<'_>) {
+        let temp_dir = TempDir::new().unwrap();
+        let stash = DirectoryStash::from_path(storage, temp_dir.path().join("stash"));
+        (temp_dir, stash)
+    }
+
+    #[test]
+    fn test_push_and_pop_restores_yaks() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("existing").unwrap();
+        storage.write_context("existing", "before").unwrap();
+        let (_temp_dir, stash) = setup(&storage);
+
+        stash
+            .push(
+                "import --obsidian vault",
+                &["existing".to_string(), "new".to_string()],
+            )
+            .unwrap();
+
+        // Simulate the risky operation
+        storage.write_context("existing", "after").unwrap();
+        storage
+            .write_field("existing", "title", Some("Existing\n"))
+            .unwrap();
+        storage.create_yak("new").unwrap();
+
+        let entries = stash.list().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].reason, "import --obsidian vault");
+        assert_eq!(entries[0].yaks, vec!["existing", "new"]);
+
+        let popped = stash.pop().unwrap().unwrap();
+        assert_eq!(popped.reason, "import --obsidian vault");
+        assert_eq!(storage.read_context("existing").unwrap(), "before");
+        assert_eq!(storage.read_field("existing", "title").unwrap(), None);
+        assert!(storage.get_yak("new").is_err());
+        assert!(stash.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_pop_takes_newest_first() {
+        let storage = InMemoryStorage::new();
+        let (_temp_dir, stash) = setup(&storage);
+        stash.push("first", &[]).unwrap();
+        stash.push("second", &[]).unwrap();
+
+        let reasons: Vec<String> = stash
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|e| e.reason)
+            .collect();
+        assert_eq!(reasons, vec!["second", "first"]);
+
+        assert_eq!(stash.pop().unwrap().unwrap().reason, "second");
+        assert_eq!(stash.pop().unwrap().unwrap().reason, "first");
+        assert_eq!(stash.pop().unwrap(), None);
+    }
+
+    #[test]
+    fn test_discard_drops_newest_without_restoring() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("api").unwrap();
+        let (_temp_dir, stash) = setup(&storage);
+        stash.push("first", &[]).unwrap();
+        stash.push("second", &["api".to_string()]).unwrap();
+        storage.write_context("api", "after").unwrap();
+
+        stash.discard().unwrap();
+
+        assert_eq!(storage.read_context("api").unwrap(), "after");
+        let entries = stash.list().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].reason, "first");
+    }
+
+    #[test]
+    fn test_pop_keeps_nested_yaks() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("parent/child").unwrap();
+        storage.write_context("parent/child", "child").unwrap();
+        let (_temp_dir, stash) = setup(&storage);
+
+        stash.push("test", &["parent".to_string()]).unwrap();
+        stash.pop().unwrap();
+
+        assert_eq!(storage.read_context("parent/child").unwrap(), "child");
+    }
+
+    /// Covers every yak, "encrypting" by reversing the text after age's header
+    struct MockCipher;
+
+    const HEADER: &str = "-----BEGIN AGE ENCRYPTED FILE-----";
+
+    impl CipherPort for MockCipher {
+        fn covers(&self, _name: &str) -> bool {
+            true
+        }
+
+        fn encrypt(&self, text: &str) -> Result<String> {
+            Ok(format!(
+                "{HEADER}\n{}",
+                text.chars().rev().collect::<String>()
+            ))
+        }
+
+        fn decrypt(&self, ciphertext: &str) -> Result<Option<String>> {
+            let text = &ciphertext[HEADER.len() + 1..];
+            Ok(Some(text.chars().rev().collect()))
+        }
+    }
+
+    #[test]
+    fn test_pop_writes_through_the_storage() {
+        let yaks = TempDir::new().unwrap();
+        let storage =
+            DirectoryStorage::at(yaks.path().join(".yaks")).with_cipher(Rc::new(MockCipher));
+        storage.create_yak("pay").unwrap();
+        storage.write_context("pay", "bands\n").unwrap();
+        let (_temp_dir, stash) = setup(&storage);
+
+        stash
+            .push("bulk", &["pay".to_string(), "new".to_string()])
+            .unwrap();
+        storage.write_context("pay", "changed\n").unwrap();
+        storage.create_yak("new").unwrap();
+        stash.pop().unwrap();
+
+        // Sealed again on the way back, and listed from the updated index
+        let on_disk = fs::read_to_string(yaks.path().join(".yaks/pay/.meta/context.md")).unwrap();
+        assert!(on_disk.starts_with(HEADER));
+        assert_eq!(storage.read_context("pay").unwrap(), "bands\n");
+        let names: Vec<String> = storage
+            .list_yaks()
+            .unwrap()
+            .into_iter()
+            .map(|yak| yak.name)
+            .collect();
+        assert_eq!(names, vec!["pay"]);
+    }
+}
@@ -1,47 +1,254 @@
 // Directory-based storage adapter - implements .yaks/ directory structure
 
+use super::fields;
+use super::index::Index;
+use super::migrations::{self, STEPS};
+use super::pruned::{self, PRUNED_FILE};
+use super::{is_local_file, META_FOLDER};
+use crate::adapters::local_state::GitDirState;
+use crate::adapters::lock::{self, YakLock};
+use crate::adapters::workspace;
 use crate::domain::Yak;
-use crate::ports::StoragePort;
+use crate::ports::{
+    ArchivePort, CipherPort, ConfigPort, Migration, MigrationPort, PrunedPort, PrunedYak,
+    StoragePort, TrashEntry, TrashPort, YakIter,
+};
 use anyhow::{Context, Result};
-use std::fs;
-use std::path::PathBuf;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use std::cell::{Cell, RefCell};
+use std::fs::{self, OpenOptions};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::rc::Rc;
 use walkdir::WalkDir;
 
+/// Folder inside .yaks holding archived yaks, hidden from the active list
+pub(super) const ARCHIVE_FOLDER: &str = ".archive";
+
+/// Folder inside .yaks holding removed yaks, each in a folder named for
+/// when it was removed (e.g. `.trash/20261017T093000.123456Z/api`)
+pub(super) const TRASH_FOLDER: &str = ".trash";
+
+/// Folders at the top of .yaks that hold yaks kept out of the list
+pub const HIDDEN_FOLDERS: [&str; 2] = [ARCHIVE_FOLDER, TRASH_FOLDER];
+
+/// Whether `name` lies in one of the hidden folders rather than naming a yak
+fn is_hidden(name: &str) -> bool {
+    name.split('/')
+        .next()
+        .is_some_and(|first| HIDDEN_FOLDERS.contains(&first))
+}
+
+/// File in each trash folder naming the yak removed into it
+const TRASHED_NAME_FILE: &str = ".name";
+
+/// How trash folders are named, to the microsecond so removals don't share one
+const TRASH_STAMP: &str = "%Y%m%dT%H%M%S%.6fZ";
+
+/// Config key choosing where a repository's yaks live: `work-tree` (the
+/// default, in `.yaks`) or `data`, outside it in the user's data directory
+pub const LOCATION_KEY: &str = "yaks.location";
+
+/// Files an encrypted yak keeps readable: listing and sync read
+/// whether a yak is done straight from them
+const PLAIN_FILES: [&str; 2] = ["state", "done"];
+
+/// How an encrypted yak file starts (age's ASCII armor)
+pub(super) const ENCRYPTED_HEADER: &str = "-----BEGIN AGE ENCRYPTED FILE-----";
+
 pub struct DirectoryStorage {
     base_path: PathBuf,
+    /// Folder holding the lock file; the archive shares the active yaks' lock
+    lock_dir: PathBuf,
+    /// Partial names `find_yak` resolved, with the yak each matched
+    fuzzy_matches: RefCell<Vec<(String, String)>>,
+    /// Set while a transaction runs, so nested ones join it
+    in_transaction: Cell<bool>,
+    /// Encrypts the yaks it covers; without one, encrypted files can't be read
+    cipher: Option<Rc<dyn CipherPort>>,
 }
 
 impl DirectoryStorage {
-    pub fn new() -> Result<Self> {
+    /// Storage for this repository's yaks. Priority: YAK_PATH env var, then
+    /// the data directory when `yaks.location` is `data`, then
+    /// GIT_WORK_TREE/.yaks, then .yaks
+    pub fn new(config: &dyn ConfigPort) -> Result<Self> {
         // Check 1: Is git command available?
         Self::check_git_available()?;
 
         // Check 2: Are we in a git repository?
         Self::check_in_git_repo()?;
 
-        // Check 3: Is .yaks gitignored?
-        Self::check_yaks_gitignored()?;
+        // Check 3: Is .yaks gitignored? Not needed when yaks live outside the work tree
+        let work_tree = Self::work_tree();
+        let data_path = Self::data_path(config, &work_tree);
+        if data_path.is_none() {
+            Self::check_yaks_gitignored()?;
+        }
 
-        // Priority: YAK_PATH env var, then GIT_WORK_TREE/.yaks, then .yaks
-        // This matches bash version behavior: YAKS_PATH="$GIT_WORK_TREE/.yaks"
-        let base_path = if let Ok(yak_path) = std::env::var("YAK_PATH") {
-            yak_path.into()
-        } else if let Ok(git_work_tree) = std::env::var("GIT_WORK_TREE") {
-            PathBuf::from(git_work_tree).join(".yaks")
-        } else {
-            ".yaks".into()
+        let base_path = match std::env::var("YAK_PATH") {
+            Ok(yak_path) => yak_path.into(),
+            Err(_) => data_path.unwrap_or_else(|| work_tree.join(".yaks")),
         };
+        Ok(Self::at(base_path))
+    }
+
+    /// Where the yaks of the repository at `work_tree`, read with its
+    /// `config`, live, ignoring YAK_PATH
+    pub fn path_in(config: &dyn ConfigPort, work_tree: &Path) -> PathBuf {
+        Self::data_path(config, work_tree).unwrap_or_else(|| work_tree.join(".yaks"))
+    }
+
+    /// The repository's folder in the user's data directory
+    /// (`$XDG_DATA_HOME/yaks/<repo-id>`, by default under `~/.local/share`),
+    /// when its `yaks.location` is `data`
+    pub fn data_path(config: &dyn ConfigPort, work_tree: &Path) -> Option<PathBuf> {
+        if config.get(LOCATION_KEY).as_deref() != Some("data") {
+            return None;
+        }
+        let data_home = match std::env::var("XDG_DATA_HOME") {
+            Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => PathBuf::from(std::env::var("HOME").ok()?).join(".local/share"),
+        };
+        Some(data_home.join("yaks").join(Self::repo_id(work_tree)?))
+    }
+
+    /// A name for the repository that stays the same across its linked
+    /// worktrees and yx versions: its folder name and a hash of where its
+    /// git directory is, e.g. `yaks-3f9a0c1b2d4e5f60`
+    fn repo_id(work_tree: &Path) -> Option<String> {
+        let common_dir = GitDirState::common_dir(&Self::git_start_in(work_tree)).ok()?;
+        let common_dir = fs::canonicalize(common_dir).ok()?;
+        // The repository's own folder for `<repo>/.git`, the folder itself if bare
+        let folder = match common_dir.file_name() {
+            Some(name) if name == ".git" => common_dir.parent()?.file_name(),
+            name => name,
+        }?;
+        Some(format!(
+            "{}-{:016x}",
+            folder.to_string_lossy(),
+            fnv1a(common_dir.as_os_str().as_encoded_bytes())
+        ))
+    }
+
+    /// GIT_WORK_TREE, or the current directory ("" keeps paths relative)
+    fn work_tree() -> PathBuf {
+        std::env::var("GIT_WORK_TREE").unwrap_or_default().into()
+    }
+
+    /// Where git looks for the repository of `work_tree`: "" is the current
+    /// directory, which git opens as "."
+    fn git_start_in(work_tree: &Path) -> PathBuf {
+        if work_tree.as_os_str().is_empty() {
+            PathBuf::from(".")
+        } else {
+            work_tree.to_path_buf()
+        }
+    }
+
+    /// Set up the current repository for yaks by adding .yaks to its .gitignore
+    /// Returns false when .yaks was already ignored.
+    pub fn init() -> Result<bool> {
+        Self::check_git_available()?;
+        Self::check_in_git_repo()?;
+        if Self::check_yaks_gitignored().is_ok() {
+            return Ok(false);
+        }
+
+        let output = Command::new("git")
+            .args(["rev-parse", "--show-toplevel"])
+            .output()
+            .context("Failed to find the repository root")?;
+        let root = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+        let gitignore = root.join(".gitignore");
+
+        let mut text = fs::read_to_string(&gitignore).unwrap_or_default();
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push_str(".yaks\n");
+        fs::write(&gitignore, text).context("Failed to update .gitignore")?;
+
+        Ok(true)
+    }
+
+    /// Whether this is a git repository that hasn't been set up for yaks (see `init`)
+    pub fn needs_init(config: &dyn ConfigPort) -> bool {
+        Self::check_git_available().is_ok()
+            && Self::check_in_git_repo().is_ok()
+            && Self::data_path(config, &Self::work_tree()).is_none()
+            && Self::check_yaks_gitignored().is_err()
+    }
+
+    /// Storage for another repository's yaks (e.g., a configured workspace)
+    /// The checks in `new` only apply to the current directory, so they are skipped.
+    pub fn for_work_tree(config: &dyn ConfigPort, work_tree: &Path) -> Self {
+        Self::at(Self::path_in(config, work_tree))
+    }
+
+    /// Storage for the archived yaks, kept in `.yaks/.archive/`
+    pub fn archived(&self) -> Self {
+        Self {
+            lock_dir: self.lock_dir.clone(),
+            cipher: self.cipher.clone(),
+            ..Self::at(self.base_path.join(ARCHIVE_FOLDER))
+        }
+    }
+
+    /// Storage for the yaks in `base_path`, e.g. a `.yaks` folder found elsewhere
+    pub fn at(base_path: PathBuf) -> Self {
+        Self {
+            lock_dir: base_path.clone(),
+            base_path,
+            fuzzy_matches: RefCell::default(),
+            in_transaction: Cell::default(),
+            cipher: None,
+        }
+    }
+
+    /// Move yak files kept beside their children, as yx kept them before
+    /// format v3, into `.meta`; they can arrive at any time, synced in from an
+    /// older yx or made by hand, long after the migration ran
+    #[cfg_attr(not(feature = "libgit2"), allow(dead_code))]
+    pub fn adopt_legacy_files(&self) -> Result<()> {
+        migrations::files_to_meta(self)
+    }
 
-        Ok(Self { base_path })
+    /// Encrypt the files of the yaks `cipher` covers as they're written, and
+    /// decrypt any encrypted file as it's read
+    pub fn with_cipher(mut self, cipher: Rc<dyn CipherPort>) -> Self {
+        self.cipher = Some(cipher);
+        self
+    }
+
+    /// The folder these yaks are kept in
+    pub fn path(&self) -> &Path {
+        &self.base_path
+    }
+
+    /// The repository's folder for yak state that stays in this clone, such
+    /// as stashes: `.git/yaks`, or the selected workspace's folder in it
+    pub fn local_dir(&self) -> Result<PathBuf> {
+        let common_dir = GitDirState::common_dir(&Self::git_start_in(&Self::work_tree()))?;
+        Ok(workspace::local_dir(
+            &common_dir,
+            workspace::selected().as_deref(),
+        ))
+    }
+
+    /// Each partial name resolved to a yak so far, as (typed, matched), for `--verbose`
+    pub fn fuzzy_matches(&self) -> Vec<(String, String)> {
+        self.fuzzy_matches.borrow().clone()
     }
 
     /// Creates a DirectoryStorage with an explicit path, bypassing all checks.
     /// This is intended for testing only, where we want to use isolated temp
     /// directories without environment variable pollution.
     #[cfg(test)]
-    fn from_path_unchecked(base_path: PathBuf) -> Self {
-        Self { base_path }
+    pub(super) fn from_path_unchecked(base_path: PathBuf) -> Self {
+        Self::at(base_path)
     }
 
     fn check_git_available() -> Result<()> {
@@ -85,99 +292,293 @@ impl DirectoryStorage {
         Ok(())
     }
 
-    fn yak_dir(&self, name: &str) -> PathBuf {
+    /// Hold off other yx commands while this one changes the yaks
+    fn lock(&self) -> Result<YakLock> {
+        YakLock::acquire(&self.lock_dir)
+    }
+
+    pub(super) fn yak_dir(&self, name: &str) -> PathBuf {
         self.base_path.join(name)
     }
 
+    /// Folder holding the yak's own files; its other folders are children
+    fn meta_dir(&self, name: &str) -> PathBuf {
+        self.yak_dir(name).join(META_FOLDER)
+    }
+
+    /// Make the yak's .meta folder before writing into it, as yaks made only
+    /// to hold a child (e.g. `api` for `api/auth`) have none yet
+    fn make_meta_dir(&self, name: &str) -> Result<()> {
+        if self.yak_dir(name).is_dir() {
+            fs::create_dir_all(self.meta_dir(name))
+                .with_context(|| format!("Failed to create {META_FOLDER} for yak: {name}"))?;
+        }
+        Ok(())
+    }
+
     fn done_marker_path(&self, name: &str) -> PathBuf {
-        self.yak_dir(name).join("done")
+        self.meta_dir(name).join("done")
     }
 
     fn context_path(&self, name: &str) -> PathBuf {
-        self.yak_dir(name).join("context.md")
+        self.meta_dir(name).join("context.md")
+    }
+
+    /// When a file last changed; older done markers are empty, so this
+    /// stands in for the completion time they don't record
+    fn file_time(path: &Path) -> Option<DateTime<Utc>> {
+        fs::metadata(path)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .map(DateTime::<Utc>::from)
+    }
+
+    fn field_path(&self, name: &str, field: &str) -> PathBuf {
+        self.meta_dir(name).join(field)
+    }
+
+    /// `text` as the yak's `file` stores it: encrypted if the cipher covers the yak
+    fn seal(&self, name: &str, file: &str, text: &str) -> Result<String> {
+        match &self.cipher {
+            Some(cipher) if cipher.covers(name) && !PLAIN_FILES.contains(&file) => cipher
+                .encrypt(text)
+                .with_context(|| format!("Failed to encrypt {file} for '{name}'")),
+            _ => Ok(text.to_string()),
+        }
+    }
+
+    /// A stored file's text, decrypted if it was encrypted; None when no key opens it
+    fn unseal(&self, text: String) -> Result<Option<String>> {
+        if !text.starts_with(ENCRYPTED_HEADER) {
+            return Ok(Some(text));
+        }
+        match &self.cipher {
+            Some(cipher) => cipher.decrypt(&text),
+            None => Ok(None),
+        }
+    }
+
+    /// `text` from the yak's `file`, decrypted if it was encrypted; an error
+    /// when no key opens it
+    pub(super) fn unseal_file(&self, name: &str, file: &str, text: String) -> Result<String> {
+        let what = file.strip_suffix(".md").unwrap_or(file);
+        match self.unseal(text)? {
+            Some(text) => Ok(text),
+            None => anyhow::bail!(
+                "the {what} of '{name}' is encrypted: set yaks.age.identity to an age key that opens it"
+            ),
+        }
+    }
+
+    /// Encrypt the plain files of `name` and the yaks under it that the cipher
+    /// covers now, e.g. after they're moved under an encrypted namespace
+    fn seal_tree(&self, name: &str) -> Result<()> {
+        let Some(cipher) = &self.cipher else {
+            return Ok(());
+        };
+        let files: Vec<PathBuf> = WalkDir::new(self.yak_dir(name))
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .collect();
+        for path in files {
+            // Only a yak's own files, in <yak>/.meta/<file>
+            let Some(meta) = path.parent() else {
+                continue;
+            };
+            let (Some(yak), Some(file)) = (
+                meta.parent()
+                    .filter(|_| meta.file_name() == Some(META_FOLDER.as_ref()))
+                    .and_then(|dir| dir.strip_prefix(&self.base_path).ok())
+                    .and_then(Path::to_str),
+                path.file_name().and_then(|file| file.to_str()),
+            ) else {
+                continue;
+            };
+            if !cipher.covers(yak) || PLAIN_FILES.contains(&file) {
+                continue;
+            }
+            let text = fs::read_to_string(&path)?;
+            if !text.starts_with(ENCRYPTED_HEADER) {
+                write_atomic(&path, &self.seal(yak, file, &text)?)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Record now as the yak's creation time in its `created` field
+    fn write_created(&self, name: &str) -> Result<()> {
+        self.write_field(name, "created", Some(&fields::timestamp(Utc::now())))
+    }
+
+    /// Read a yak, and its context when `context` is set
+    fn read_yak(&self, name: &str, context: bool) -> Result<Yak> {
+        let dir = self.yak_dir(name);
+        if !dir.exists() {
+            anyhow::bail!("yak '{name}' not found");
+        }
+
+        let mut yak = fields::read_yak(self, name, context)?;
+        if yak.done {
+            yak.done_at = yak
+                .done_at
+                .or_else(|| Self::file_time(&self.done_marker_path(name)))
+                .or_else(|| Self::file_time(&self.field_path(name, "state")));
+        }
+        // Yaks added before it was recorded fall back to the directory's creation time
+        yak.created_at = yak.created_at.or_else(|| {
+            fs::metadata(&dir)
+                .and_then(|meta| meta.created())
+                .ok()
+                .map(DateTime::<Utc>::from)
+        });
+        yak.modified_at = self.read_modified_at(name);
+        Ok(yak)
+    }
+
+    /// Yak names from the index, rebuilding it first when it's missing or
+    /// no longer matches the folders
+    fn indexed_names(&self) -> Result<Vec<String>> {
+        if let Some(index) = Index::load(&self.base_path) {
+            if index.is_fresh(&self.base_path) {
+                return Ok(index.names());
+            }
+        }
+        let index = Index::build(&self.base_path)?;
+        // Listing works without the index (e.g. in a read-only .yaks), just slower
+        let _ = index.save(&self.base_path, write_atomic);
+        Ok(index.names())
+    }
+
+    /// Update the index, if there is one, after `names` and the yaks under
+    /// them changed; one that can't be updated is removed to be rebuilt
+    fn reindex(&self, names: &[&str]) {
+        let Some(mut index) = Index::load(&self.base_path) else {
+            return;
+        };
+        let updated = index
+            .refresh(&self.base_path, names)
+            .and_then(|()| index.save(&self.base_path, write_atomic));
+        if updated.is_err() {
+            let _ = fs::remove_file(self.base_path.join(super::index::INDEX_FILE));
+        }
+    }
+
+    fn trash_dir(&self) -> PathBuf {
+        self.base_path.join(TRASH_FOLDER)
+    }
+
+    /// Every yak in the trash with the folder it's in, most recently removed first
+    fn trash_entries(&self) -> Result<Vec<(PathBuf, TrashEntry)>> {
+        let trash_dir = self.trash_dir();
+        if !trash_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&trash_dir).context("Failed to read the trash")? {
+            let dir = entry?.path();
+            let Ok(name) = fs::read_to_string(dir.join(TRASHED_NAME_FILE)) else {
+                continue;
+            };
+            // "<stamp>" or, for removals in the same microsecond, "<stamp>-<n>"
+            let folder = dir.file_name().unwrap_or_default().to_string_lossy();
+            let stamp = folder.split('-').next().unwrap_or_default();
+            let Ok(removed) = NaiveDateTime::parse_from_str(stamp, TRASH_STAMP) else {
+                continue;
+            };
+            let entry = TrashEntry {
+                name: name.trim().to_string(),
+                removed: removed.and_utc(),
+            };
+            entries.push((dir, entry));
+        }
+        entries.sort_by(|(a, _), (b, _)| b.file_name().cmp(&a.file_name()));
+        Ok(entries)
+    }
+
+    /// Latest modification time of the yak's own files (not its children's)
+    fn read_modified_at(&self, name: &str) -> Option<DateTime<Utc>> {
+        fs::read_dir(self.meta_dir(name))
+            .ok()?
+            .filter_map(|entry| entry.ok()?.metadata().ok())
+            .filter(|meta| meta.is_file())
+            .filter_map(|meta| meta.modified().ok())
+            .max()
+            .map(DateTime::<Utc>::from)
     }
 }
 
 impl StoragePort for DirectoryStorage {
     fn create_yak(&self, name: &str) -> Result<()> {
-        let dir = self.yak_dir(name);
-        fs::create_dir_all(&dir)
+        let _lock = self.lock()?;
+        // A new .yaks starts out in the current format
+        let version_file = self.base_path.join(migrations::VERSION_FILE);
+        if !version_file.exists() && self.format_version()? == migrations::current_version() {
+            write_atomic(
+                &version_file,
+                &format!("{}\n", migrations::current_version()),
+            )
+            .context("Failed to record the storage format version")?;
+        }
+
+        // Sealed first, so a yak that can't be encrypted isn't half made
+        let context = self.seal(name, "context.md", "")?;
+        fs::create_dir_all(self.meta_dir(name))
             .with_context(|| format!("Failed to create yak directory: {name}"))?;
 
         // Create empty context.md file by default
         let context_file = self.context_path(name);
-        fs::write(&context_file, "")
+        write_atomic(&context_file, &context)
             .with_context(|| format!("Failed to create context.md for yak: {name}"))?;
 
+        self.write_created(name)?;
+        self.reindex(&[name]);
         Ok(())
     }
 
     fn get_yak(&self, name: &str) -> Result<Yak> {
-        let dir = self.yak_dir(name);
-        if !dir.exists() {
-            anyhow::bail!("yak '{name}' not found");
-        }
-
-        let done = self.done_marker_path(name).exists();
-        let context = self.read_context(name).ok();
-
-        Ok(Yak {
-            name: name.to_string(),
-            done,
-            context,
-        })
+        self.read_yak(name, true)
     }
 
     fn list_yaks(&self) -> Result<Vec<Yak>> {
-        let mut yaks = Vec::new();
+        self.iter_yaks(false)?.collect()
+    }
 
+    fn iter_yaks(&self, context: bool) -> Result<YakIter<'_>> {
         if !self.base_path.exists() {
-            return Ok(yaks);
+            return Ok(Box::new(std::iter::empty()));
         }
 
-        // Use WalkDir to recursively find all directories (yaks)
-        for entry in WalkDir::new(&self.base_path)
-            .min_depth(1)
+        // Names come from the index; each yak is read as the iterator reaches it
+        let yaks = self
+            .indexed_names()?
             .into_iter()
-            .filter_entry(|e| e.file_type().is_dir())
-        {
-            let entry = entry?;
-            // Get relative path from base_path
-            if let Ok(rel_path) = entry.path().strip_prefix(&self.base_path) {
-                if let Some(name) = rel_path.to_str() {
-                    // Only add if we can successfully read it as a yak
-                    if let Ok(yak) = self.get_yak(name) {
-                        yaks.push(yak);
-                    }
-                }
-            }
-        }
-
-        Ok(yaks)
+            // Only yield folders that can be read as a yak
+            .filter_map(move |name| self.read_yak(&name, context).ok().map(Ok));
+        Ok(Box::new(yaks))
     }
 
     fn mark_done(&self, name: &str, done: bool) -> Result<()> {
-        let marker = self.done_marker_path(name);
-
-        if done {
-            fs::write(&marker, "").with_context(|| format!("Failed to mark '{name}' as done"))?;
-        } else if marker.exists() {
-            fs::remove_file(&marker)
-                .with_context(|| format!("Failed to mark '{name}' as undone"))?;
-        }
-
+        let _lock = self.lock()?;
+        let word = if done { "done" } else { "undone" };
+        fields::mark_done(self, name, done)
+            .with_context(|| format!("Failed to mark '{name}' as {word}"))?;
         Ok(())
     }
 
     fn delete_yak(&self, name: &str) -> Result<()> {
+        let _lock = self.lock()?;
         let dir = self.yak_dir(name);
         if dir.exists() {
             fs::remove_dir_all(&dir).with_context(|| format!("Failed to remove yak '{name}'"))?;
+            self.reindex(&[name]);
         }
         Ok(())
     }
 
     fn rename_yak(&self, from: &str, to: &str) -> Result<()> {
+        let _lock = self.lock()?;
         let from_dir = self.yak_dir(from);
         let to_dir = self.yak_dir(to);
 
@@ -199,23 +600,194 @@ impl StoragePort for DirectoryStorage {
 
         fs::rename(&from_dir, &to_dir)
             .with_context(|| format!("Failed to rename '{from}' to '{to}'"))?;
+        self.reindex(&[from, to]);
+        self.seal_tree(to)?;
 
         Ok(())
     }
 
+    fn copy_yak(&self, from: &str, to: &str) -> Result<()> {
+        let _lock = self.lock()?;
+        let from_dir = self.yak_dir(from);
+        let to_dir = self.yak_dir(to);
+
+        if !from_dir.exists() {
+            anyhow::bail!("yak '{from}' not found");
+        }
+
+        if to_dir.exists() {
+            anyhow::bail!("Yak '{to}' already exists");
+        }
+
+        let to_meta = self.meta_dir(to);
+        fs::create_dir_all(&to_meta)
+            .with_context(|| format!("Failed to create yak directory: {to}"))?;
+
+        // .meta holds the yak's own data; its other folders are its children
+        let from_meta = self.meta_dir(from);
+        if from_meta.is_dir() {
+            for entry in fs::read_dir(&from_meta)? {
+                let entry = entry?;
+                if entry.file_type()?.is_file() {
+                    fs::copy(entry.path(), to_meta.join(entry.file_name()))
+                        .with_context(|| format!("Failed to copy '{from}' to '{to}'"))?;
+                }
+            }
+        }
+
+        // The copy is a new yak, so it gets its own creation time
+        self.write_created(to)?;
+        self.seal_tree(to)
+    }
+
     fn read_context(&self, name: &str) -> Result<String> {
         let path = self.context_path(name);
-        fs::read_to_string(&path).with_context(|| format!("Failed to read context for '{name}'"))
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read context for '{name}'"))?;
+        self.unseal_file(name, "context.md", text)
     }
 
     fn write_context(&self, name: &str, text: &str) -> Result<()> {
+        let _lock = self.lock()?;
+        self.make_meta_dir(name)?;
         let path = self.context_path(name);
-        fs::write(&path, text).with_context(|| format!("Failed to write context for '{name}'"))
+        write_atomic(&path, &self.seal(name, "context.md", text)?)
+            .with_context(|| format!("Failed to write context for '{name}'"))?;
+        self.reindex(&[name]);
+        Ok(())
+    }
+
+    fn context_file(&self, name: &str) -> Option<PathBuf> {
+        // Editing ciphertext in place would only garble it
+        let encrypted = fs::read_to_string(self.context_path(name))
+            .is_ok_and(|text| text.starts_with(ENCRYPTED_HEADER));
+        let covered = self
+            .cipher
+            .as_ref()
+            .is_some_and(|cipher| cipher.covers(name));
+        (!encrypted && !covered).then(|| self.context_path(name))
+    }
+
+    fn revision(&self) -> Result<Option<u64>> {
+        // Every path with its size and modification time; any add, edit,
+        // rename or delete changes the hash
+        let mut hasher = DefaultHasher::new();
+        if self.base_path.exists() {
+            // The top folder's own time changes with the lock and index, so
+            // only what's inside it counts
+            let walk = WalkDir::new(&self.base_path)
+                .min_depth(1)
+                .sort_by_file_name();
+            for entry in walk {
+                let entry = entry?;
+                let relative = entry.path().strip_prefix(&self.base_path)?;
+                if is_local_file(relative) {
+                    continue;
+                }
+                let meta = entry.metadata()?;
+                entry.path().hash(&mut hasher);
+                meta.len().hash(&mut hasher);
+                meta.modified().ok().hash(&mut hasher);
+            }
+        }
+        Ok(Some(hasher.finish()))
+    }
+
+    fn transaction(&self, steps: &mut dyn FnMut() -> Result<()>) -> Result<()> {
+        if self.in_transaction.get() {
+            return steps();
+        }
+
+        // A copy of every yak file, put back in place of .yaks if a step fails,
+        // and of the trash, which steps that remove or restore yaks change too
+        let backup = tempfile::tempdir().context("Failed to back up the yaks")?;
+        let trash_backup = tempfile::tempdir().context("Failed to back up the trash")?;
+        let existed = self.base_path.exists();
+        let trash_existed = self.trash_dir().exists();
+        let _lock = self.lock()?;
+        if existed {
+            copy_tree(&self.base_path, backup.path()).context("Failed to back up the yaks")?;
+        }
+        if trash_existed {
+            copy_tree(&self.trash_dir(), trash_backup.path())
+                .context("Failed to back up the trash")?;
+        }
+
+        self.in_transaction.set(true);
+        let result = steps();
+        self.in_transaction.set(false);
+
+        if result.is_err() {
+            lock::clear_yaks_dir(&self.base_path).context("Failed to roll back the yaks")?;
+            if existed {
+                copy_tree(backup.path(), &self.base_path)
+                    .context("Failed to roll back the yaks")?;
+            }
+            if self.trash_dir().exists() {
+                fs::remove_dir_all(self.trash_dir()).context("Failed to roll back the trash")?;
+            }
+            if trash_existed {
+                copy_tree(trash_backup.path(), &self.trash_dir())
+                    .context("Failed to roll back the trash")?;
+            }
+        }
+        result
+    }
+
+    fn read_field(&self, name: &str, field: &str) -> Result<Option<String>> {
+        let path = self.field_path(name, field);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {field} for '{name}'"))?;
+        // Without a key, an encrypted yak's metadata reads as unset
+        self.unseal(text)
+    }
+
+    fn write_field(&self, name: &str, field: &str, value: Option<&str>) -> Result<()> {
+        let _lock = self.lock()?;
+        let path = self.field_path(name, field);
+        match value {
+            Some(text) => {
+                self.make_meta_dir(name)?;
+                write_atomic(&path, &self.seal(name, field, text)?)
+                    .with_context(|| format!("Failed to write {field} for '{name}'"))?
+            }
+            None => {
+                if !path.is_file() {
+                    return Ok(());
+                }
+                fs::remove_file(&path)
+                    .with_context(|| format!("Failed to clear {field} for '{name}'"))?;
+            }
+        }
+        self.reindex(&[name]);
+        Ok(())
+    }
+
+    fn list_fields(&self, name: &str) -> Result<Vec<String>> {
+        let meta_dir = self.meta_dir(name);
+        if !meta_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut fields = Vec::new();
+        for entry in fs::read_dir(&meta_dir)
+            .with_context(|| format!("Failed to read the fields of '{name}'"))?
+        {
+            let entry = entry?;
+            let file = entry.file_name().to_string_lossy().to_string();
+            if entry.file_type()?.is_file() && file != "context.md" {
+                fields.push(file);
+            }
+        }
+        fields.sort();
+        Ok(fields)
     }
 
     fn find_yak(&self, name: &str) -> Result<String> {
-        // First, try exact match
-        if self.yak_dir(name).exists() {
+        // First, try exact match, never landing in the archive or trash folders
+        if !is_hidden(name) && self.yak_dir(name).exists() {
             return Ok(name.to_string());
         }
 
@@ -225,15 +797,280 @@ impl StoragePort for DirectoryStorage {
 
         match matches.len() {
             0 => anyhow::bail!("yak '{name}' not found"),
-            1 => Ok(matches[0].name.clone()),
+            1 => {
+                let matched = matches[0].name.clone();
+                self.fuzzy_matches
+                    .borrow_mut()
+                    .push((name.to_string(), matched.clone()));
+                Ok(matched)
+            }
             _ => anyhow::bail!("yak name '{name}' is ambiguous"),
         }
     }
 }
 
+impl ArchivePort for DirectoryStorage {
+    fn archive(&self, name: &str) -> Result<()> {
+        let _lock = self.lock()?;
+        if !self.yak_dir(name).exists() {
+            anyhow::bail!("yak '{name}' not found");
+        }
+        let archived = self.archived();
+        if archived.yak_dir(name).exists() {
+            anyhow::bail!("yak '{name}' is already archived");
+        }
+        move_dir(&self.yak_dir(name), &archived.yak_dir(name))
+            .with_context(|| format!("Failed to archive '{name}'"))?;
+        self.reindex(&[name]);
+        archived.reindex(&[name]);
+        Ok(())
+    }
+
+    fn unarchive(&self, name: &str) -> Result<()> {
+        let _lock = self.lock()?;
+        let archived = self.archived();
+        let from = archived.yak_dir(name);
+        if !from.exists() {
+            anyhow::bail!("archived yak '{name}' not found");
+        }
+        if self.yak_dir(name).exists() {
+            anyhow::bail!("Yak '{name}' already exists");
+        }
+        move_dir(&from, &self.yak_dir(name))
+            .with_context(|| format!("Failed to unarchive '{name}'"))?;
+
+        // Parents only created to hold the yak in the archive go with it
+        let mut parent = from.parent();
+        while let Some(dir) = parent.filter(|dir| *dir != archived.base_path) {
+            if fs::remove_dir(dir).is_err() {
+                break;
+            }
+            parent = dir.parent();
+        }
+        self.reindex(&[name]);
+        archived.reindex(&[name]);
+
+        Ok(())
+    }
+}
+
+impl TrashPort for DirectoryStorage {
+    fn trash(&self, name: &str) -> Result<()> {
+        let _lock = self.lock()?;
+        let dir = self.yak_dir(name);
+        if !dir.exists() {
+            anyhow::bail!("yak '{name}' not found");
+        }
+
+        fs::create_dir_all(self.trash_dir()).context("Failed to create the trash")?;
+        let stamp = Utc::now().format(TRASH_STAMP).to_string();
+        let mut entry = self.trash_dir().join(&stamp);
+        let mut n = 1;
+        while let Err(e) = fs::create_dir(&entry) {
+            if e.kind() != std::io::ErrorKind::AlreadyExists {
+                return Err(e).context("Failed to create the trash");
+            }
+            entry = self.trash_dir().join(format!("{stamp}-{n}"));
+            n += 1;
+        }
+        write_atomic(&entry.join(TRASHED_NAME_FILE), &format!("{name}\n"))
+            .with_context(|| format!("Failed to move '{name}' to the trash"))?;
+        move_dir(&dir, &entry.join(name))
+            .with_context(|| format!("Failed to move '{name}' to the trash"))?;
+        self.reindex(&[name]);
+        Ok(())
+    }
+
+    fn trashed(&self) -> Result<Vec<TrashEntry>> {
+        Ok(self
+            .trash_entries()?
+            .into_iter()
+            .map(|(_, entry)| entry)
+            .collect())
+    }
+
+    fn restore_trashed(&self, name: &str) -> Result<()> {
+        let _lock = self.lock()?;
+        let Some((dir, _)) = self
+            .trash_entries()?
+            .into_iter()
+            .find(|(_, entry)| entry.name == name)
+        else {
+            anyhow::bail!("yak '{name}' not found in the trash");
+        };
+        if self.yak_dir(name).exists() {
+            anyhow::bail!("Yak '{name}' already exists");
+        }
+        move_dir(&dir.join(name), &self.yak_dir(name))
+            .with_context(|| format!("Failed to restore '{name}' from the trash"))?;
+        fs::remove_dir_all(&dir)
+            .with_context(|| format!("Failed to restore '{name}' from the trash"))?;
+        self.reindex(&[name]);
+        Ok(())
+    }
+}
+
+impl PrunedPort for DirectoryStorage {
+    fn record_pruned(&self, yaks: &[PrunedYak]) -> Result<()> {
+        let _lock = self.lock()?;
+        // Encrypted yaks keep their secrets sealed in the record too
+        let mut lines = String::new();
+        for yak in yaks {
+            let sealed = PrunedYak {
+                title: yak
+                    .title
+                    .as_deref()
+                    .map(|title| self.seal(&yak.name, "title", title))
+                    .transpose()?,
+                context: self.seal(&yak.name, "context.md", &yak.context)?,
+                ..yak.clone()
+            };
+            lines.push_str(&format!("{}\n", pruned::encode(&sealed)));
+        }
+        // Appended in one write, so a crash can't leave half the yaks recorded
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.base_path.join(PRUNED_FILE))
+            .and_then(|mut file| file.write_all(lines.as_bytes()))
+            .context("Failed to record the pruned yaks")
+    }
+
+    fn pruned(&self) -> Result<Vec<PrunedYak>> {
+        let path = self.base_path.join(PRUNED_FILE);
+        if !path.is_file() {
+            return Ok(Vec::new());
+        }
+        let text = fs::read_to_string(&path).context("Failed to read the pruned yaks")?;
+        // Without the key, a sealed yak is listed with no title or context
+        text.lines()
+            .filter_map(pruned::decode)
+            .map(|yak| {
+                Ok(PrunedYak {
+                    title: match yak.title.clone() {
+                        Some(title) => self.unseal(title)?,
+                        None => None,
+                    },
+                    context: self.unseal(yak.context.clone())?.unwrap_or_default(),
+                    ..yak
+                })
+            })
+            .collect()
+    }
+}
+
+impl MigrationPort for DirectoryStorage {
+    fn format_version(&self) -> Result<u32> {
+        if let Some(version) = migrations::read_version(&self.base_path)? {
+            return Ok(version);
+        }
+        // Yaks from before versions were recorded are at 0; with no yaks
+        // yet, there's nothing kept in an older format
+        let has_yaks = self.base_path.is_dir()
+            && fs::read_dir(&self.base_path)?
+                .filter_map(|entry| entry.ok())
+                .any(|entry| entry.path().is_dir());
+        Ok(if has_yaks {
+            0
+        } else {
+            migrations::current_version()
+        })
+    }
+
+    fn migrations(&self) -> Vec<Migration> {
+        STEPS.iter().map(|step| step.migration).collect()
+    }
+
+    fn pending_migrations(&self) -> Result<Vec<Migration>> {
+        let version = self.format_version()?;
+        let current = migrations::current_version();
+        if version > current {
+            anyhow::bail!(
+                "These yaks are kept in storage format v{version}, but this yx only knows up to v{current}; upgrade yx to use them"
+            );
+        }
+        Ok(STEPS
+            .iter()
+            .map(|step| step.migration)
+            .filter(|migration| migration.version > version)
+            .collect())
+    }
+
+    fn apply_migration(&self, migration: &Migration) -> Result<()> {
+        let _lock = self.lock()?;
+        let step = STEPS
+            .iter()
+            .find(|step| step.migration == *migration)
+            .with_context(|| format!("Unknown migration '{}'", migration.name))?;
+        (step.apply)(self)
+            .with_context(|| format!("Failed to migrate yaks: {}", migration.description))?;
+        write_atomic(
+            &self.base_path.join(migrations::VERSION_FILE),
+            &format!("{}\n", migration.version),
+        )
+        .context("Failed to record the storage format version")
+    }
+}
+
+/// Move a yak folder, creating parent folders at the destination
+fn move_dir(from: &Path, to: &Path) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(from, to)?;
+    Ok(())
+}
+
+/// Write `contents` to `path` in one step: into a temporary file beside it,
+/// then renamed over it, so a crash leaves the old file or the new one but
+/// never part of either
+fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let mut builder = tempfile::Builder::new();
+    builder.prefix(".write-");
+    // Temporary files are private by default; yak files are ordinary ones
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        builder.permissions(fs::Permissions::from_mode(0o644));
+    }
+    let mut file = builder.tempfile_in(dir)?;
+    file.write_all(contents.as_bytes())?;
+    file.as_file().sync_all()?;
+    file.persist(path)?;
+    Ok(())
+}
+
+/// Copy the folder `from` and everything in it but the lock file to `to`
+fn copy_tree(from: &Path, to: &Path) -> Result<()> {
+    for entry in WalkDir::new(from) {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(from)?;
+        if is_local_file(relative) {
+            continue;
+        }
+        let target = to.join(relative);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// 64-bit FNV-1a: unlike std's hasher, the same on every build, so a
+/// repository keeps its data folder across yx upgrades
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::SecondsFormat;
     use tempfile::TempDir;
 
     fn setup_test_storage() -> (DirectoryStorage, TempDir) {
@@ -263,8 +1100,86 @@ mod tests {
         let (storage, _temp) = setup_test_storage();
         storage.create_yak("yak1").unwrap();
         storage.create_yak("yak2").unwrap();
+        storage.write_context("yak2", "notes").unwrap();
         let yaks = storage.list_yaks().unwrap();
         assert_eq!(yaks.len(), 2);
+        assert!(yaks.iter().all(|yak| yak.context.is_none()));
+
+        let yaks = storage.list_yaks_with_context().unwrap();
+        assert_eq!(yaks[1].context.as_deref(), Some("notes"));
+    }
+
+    #[test]
+    fn test_list_yaks_keeps_the_index_current() {
+        let (storage, temp) = setup_test_storage();
+        storage.create_yak("api").unwrap();
+        assert_eq!(storage.list_yaks().unwrap().len(), 1);
+        let index = temp.path().join(super::super::index::INDEX_FILE);
+        assert!(index.is_file());
+
+        // Changes through storage update it; folders made by hand rebuild it
+        storage.rename_yak("api", "web").unwrap();
+        fs::create_dir_all(temp.path().join("web/auth")).unwrap();
+        let names: Vec<String> = storage
+            .list_yaks()
+            .unwrap()
+            .into_iter()
+            .map(|yak| yak.name)
+            .collect();
+        assert_eq!(names, vec!["web", "web/auth"]);
+        assert!(fs::read_to_string(&index)
+            .unwrap()
+            .ends_with("\tweb/auth\n"));
+
+        // Rebuilding the index isn't a change to the yaks
+        let revision = storage.revision().unwrap();
+        fs::remove_file(&index).unwrap();
+        storage.list_yaks().unwrap();
+        assert_eq!(storage.revision().unwrap(), revision);
+    }
+
+    #[test]
+    fn test_iter_yaks_can_skip_contexts() {
+        let (storage, _temp) = setup_test_storage();
+        storage.create_yak("api/auth").unwrap();
+        storage.write_context("api/auth", "tokens").unwrap();
+
+        let mut yaks = storage.iter_yaks(false).unwrap();
+        let first = yaks.next().unwrap().unwrap();
+        assert_eq!(first.name, "api");
+        let auth = yaks.next().unwrap().unwrap();
+        assert_eq!((auth.name.as_str(), auth.context), ("api/auth", None));
+        assert!(yaks.next().is_none());
+
+        let with_context: Vec<Yak> = storage.iter_yaks(true).unwrap().flatten().collect();
+        assert_eq!(with_context[1].context.as_deref(), Some("tokens"));
+    }
+
+    #[test]
+    fn test_find_yak_records_fuzzy_matches() {
+        let (storage, _temp) = setup_test_storage();
+        storage.create_yak("app/api").unwrap();
+        assert_eq!(storage.find_yak("app/api").unwrap(), "app/api");
+        assert_eq!(storage.find_yak("api").unwrap(), "app/api");
+        assert_eq!(
+            storage.fuzzy_matches(),
+            vec![("api".to_string(), "app/api".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_find_yak_skips_the_archive_and_trash() {
+        let (storage, _temp) = setup_test_storage();
+        storage.create_yak("two").unwrap();
+        storage.create_yak("gone").unwrap();
+        storage.archive("two").unwrap();
+        storage.trash("gone").unwrap();
+
+        assert!(storage.find_yak(".archive").is_err());
+        assert!(storage.find_yak(".archive/two").is_err());
+        assert!(storage.find_yak(".trash").is_err());
+        assert!(storage.find_yak("two").is_err());
+        assert!(storage.find_yak("gone").is_err());
     }
 
     #[test]
@@ -274,6 +1189,17 @@ mod tests {
         storage.mark_done("test-yak", true).unwrap();
         let yak = storage.get_yak("test-yak").unwrap();
         assert!(yak.done);
+        assert!(yak.done_at.is_some());
+    }
+
+    #[test]
+    fn test_done_at_falls_back_for_empty_marker() {
+        let (storage, _temp) = setup_test_storage();
+        storage.create_yak("test-yak").unwrap();
+        fs::write(storage.done_marker_path("test-yak"), "").unwrap();
+        let yak = storage.get_yak("test-yak").unwrap();
+        assert!(yak.done);
+        assert!(yak.done_at.is_some());
     }
 
     #[test]
@@ -293,6 +1219,193 @@ mod tests {
         assert_eq!(context, "Test context");
     }
 
+    #[test]
+    fn test_fields() {
+        let (storage, _temp) = setup_test_storage();
+        storage.create_yak("test-yak").unwrap();
+        assert_eq!(storage.read_field("test-yak", "links").unwrap(), None);
+
+        storage
+            .write_field("test-yak", "links", Some("https://example.com\n"))
+            .unwrap();
+        let yak = storage.get_yak("test-yak").unwrap();
+        assert_eq!(yak.links, vec!["https://example.com".to_string()]);
+
+        storage.write_field("test-yak", "links", None).unwrap();
+        assert_eq!(storage.read_field("test-yak", "links").unwrap(), None);
+    }
+
+    /// Covers `private/*`, "encrypting" by reversing the text after the header
+    struct MockCipher {
+        has_key: bool,
+    }
+
+    impl CipherPort for MockCipher {
+        fn covers(&self, name: &str) -> bool {
+            name.starts_with("private/")
+        }
+
+        fn encrypt(&self, text: &str) -> Result<String> {
+            Ok(format!(
+                "{ENCRYPTED_HEADER}\n{}",
+                text.chars().rev().collect::<String>()
+            ))
+        }
+
+        fn decrypt(&self, ciphertext: &str) -> Result<Option<String>> {
+            let text = &ciphertext[ENCRYPTED_HEADER.len() + 1..];
+            Ok(self.has_key.then(|| text.chars().rev().collect()))
+        }
+    }
+
+    /// Config with `yaks.location` set to the value given, if any
+    struct MockConfig(Option<&'static str>);
+
+    impl ConfigPort for MockConfig {
+        fn get(&self, key: &str) -> Option<String> {
+            self.0.filter(|_| key == LOCATION_KEY).map(String::from)
+        }
+
+        fn get_all(&self, key: &str) -> Vec<String> {
+            self.get(key).into_iter().collect()
+        }
+    }
+
+    #[test]
+    fn test_repo_id_names_the_repository_from_anywhere_in_it() {
+        let temp = TempDir::new().unwrap();
+        let work_tree = temp.path().join("shop");
+        fs::create_dir_all(work_tree.join("src")).unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(&work_tree)
+                .args(args)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success());
+        };
+        git(&["init", "-q"]);
+
+        let id = DirectoryStorage::repo_id(&work_tree).unwrap();
+        assert!(id.starts_with("shop-"));
+        assert_eq!(id.len(), "shop-".len() + 16);
+        assert_eq!(DirectoryStorage::repo_id(&work_tree.join("src")), Some(id));
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+
+        // Yaks stay in the work tree until the repository opts in
+        let config = MockConfig(None);
+        assert_eq!(DirectoryStorage::data_path(&config, &work_tree), None);
+        assert_eq!(
+            DirectoryStorage::path_in(&config, &work_tree),
+            work_tree.join(".yaks")
+        );
+        let config = MockConfig(Some("data"));
+        assert!(
+            DirectoryStorage::path_in(&config, &work_tree).ends_with(format!(
+                "yaks/{}",
+                DirectoryStorage::repo_id(&work_tree).unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_covered_yaks_are_stored_encrypted() {
+        let temp = TempDir::new().unwrap();
+        let storage = DirectoryStorage::from_path_unchecked(temp.path().join(".yaks"))
+            .with_cipher(Rc::new(MockCipher { has_key: true }));
+        let on_disk =
+            |path: &str| fs::read_to_string(temp.path().join(".yaks").join(path)).unwrap();
+
+        storage.create_yak("private/pay").unwrap();
+        storage.write_context("private/pay", "bands").unwrap();
+        storage
+            .write_field("private/pay", "title", Some("Pay review"))
+            .unwrap();
+        storage.mark_done("private/pay", true).unwrap();
+        assert!(on_disk("private/pay/.meta/context.md").starts_with(ENCRYPTED_HEADER));
+        assert!(!on_disk("private/pay/.meta/title").contains("Pay"));
+        assert_eq!(on_disk("private/pay/.meta/state"), "done\n");
+        assert_eq!(storage.read_context("private/pay").unwrap(), "bands");
+        assert_eq!(
+            storage.get_yak("private/pay").unwrap().title.as_deref(),
+            Some("Pay review")
+        );
+        assert_eq!(storage.context_file("private/pay"), None);
+
+        // Moving a yak under the namespace encrypts what it already had
+        storage.create_yak("docs").unwrap();
+        storage.write_context("docs", "notes").unwrap();
+        storage.rename_yak("docs", "private/docs").unwrap();
+        assert!(on_disk("private/docs/.meta/context.md").starts_with(ENCRYPTED_HEADER));
+        assert_eq!(storage.read_context("private/docs").unwrap(), "notes");
+
+        // Without the key, the yaks still list but their secrets stay unread
+        let keyless = DirectoryStorage::from_path_unchecked(temp.path().join(".yaks"))
+            .with_cipher(Rc::new(MockCipher { has_key: false }));
+        let pay = keyless.get_yak("private/pay").unwrap();
+        assert!(pay.done);
+        assert_eq!((pay.title, pay.context), (None, None));
+        assert_eq!(keyless.list_yaks().unwrap().len(), 3);
+        assert!(keyless.read_context("private/pay").is_err());
+    }
+
+    #[test]
+    fn test_pruned_record_keeps_encrypted_yaks_sealed() {
+        let temp = TempDir::new().unwrap();
+        let storage = DirectoryStorage::from_path_unchecked(temp.path().join(".yaks"))
+            .with_cipher(Rc::new(MockCipher { has_key: true }));
+        let pay = PrunedYak {
+            name: "private/pay".to_string(),
+            title: Some("Pay review".to_string()),
+            completed: None,
+            pruned: "2026-10-17T09:30:00Z".parse().unwrap(),
+            context: "bands".to_string(),
+        };
+        let docs = PrunedYak {
+            name: "docs".to_string(),
+            ..pay.clone()
+        };
+
+        storage.record_pruned(&[pay.clone(), docs.clone()]).unwrap();
+
+        let record = fs::read_to_string(temp.path().join(".yaks").join(PRUNED_FILE)).unwrap();
+        assert_eq!(record.matches("bands").count(), 1);
+        assert_eq!(storage.pruned().unwrap(), vec![pay, docs]);
+        let keyless = DirectoryStorage::from_path_unchecked(temp.path().join(".yaks"))
+            .with_cipher(Rc::new(MockCipher { has_key: false }));
+        let pruned = keyless.pruned().unwrap();
+        assert_eq!(
+            (pruned[0].title.as_deref(), pruned[0].context.as_str()),
+            (None, "")
+        );
+    }
+
+    #[test]
+    fn test_writes_replace_files_without_leaving_temporaries() {
+        let (storage, temp) = setup_test_storage();
+        storage.create_yak("api").unwrap();
+        storage.write_context("api", "first").unwrap();
+        storage.write_context("api", "second").unwrap();
+        storage
+            .write_field("api", "due", Some("2026-10-20\n"))
+            .unwrap();
+        storage.mark_done("api", true).unwrap();
+
+        assert_eq!(storage.read_context("api").unwrap(), "second");
+        let mut files: Vec<String> = fs::read_dir(temp.path().join("api").join(META_FOLDER))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        assert_eq!(
+            files,
+            vec!["completed", "context.md", "created", "due", "state"]
+        );
+        assert!(storage.write_context("missing", "text").is_err());
+    }
+
     #[test]
     fn test_rename_yak() {
         let (storage, _temp) = setup_test_storage();
@@ -328,4 +1441,242 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("already exists"));
     }
+
+    #[test]
+    fn test_copy_yak_leaves_children_behind() {
+        let (storage, _temp) = setup_test_storage();
+        storage.create_yak("release").unwrap();
+        storage.create_yak("release/tag").unwrap();
+        storage.write_context("release", "Checklist").unwrap();
+        storage
+            .write_field("release", "links", Some("https://example.com\n"))
+            .unwrap();
+
+        storage.copy_yak("release", "release v2").unwrap();
+
+        let copy = storage.get_yak("release v2").unwrap();
+        assert_eq!(copy.context.unwrap(), "Checklist");
+        assert_eq!(copy.links, vec!["https://example.com"]);
+        assert!(storage.get_yak("release v2/tag").is_err());
+        assert!(storage.copy_yak("release", "release v2").is_err());
+    }
+
+    #[test]
+    fn test_created_and_modified_times() {
+        let (storage, _temp) = setup_test_storage();
+        storage.create_yak("release").unwrap();
+        storage
+            .write_field("release", "created", Some("2026-01-02T03:04:05Z\n"))
+            .unwrap();
+
+        let yak = storage.get_yak("release").unwrap();
+        assert_eq!(
+            yak.created_at
+                .unwrap()
+                .to_rfc3339_opts(SecondsFormat::Secs, true),
+            "2026-01-02T03:04:05Z"
+        );
+        assert!(yak.modified_at.is_some());
+
+        storage.copy_yak("release", "release v2").unwrap();
+        let copy = storage.get_yak("release v2").unwrap();
+        assert!(copy.created_at > yak.created_at);
+    }
+
+    #[test]
+    fn test_revision_changes_with_the_yaks() {
+        let (storage, _temp) = setup_test_storage();
+        let empty = storage.revision().unwrap();
+        storage.create_yak("release").unwrap();
+        let created = storage.revision().unwrap();
+
+        assert_ne!(empty, created);
+        assert_eq!(storage.revision().unwrap(), created);
+        storage.write_context("release", "Checklist").unwrap();
+        assert_ne!(storage.revision().unwrap(), created);
+    }
+
+    #[test]
+    fn test_failed_transaction_puts_yaks_back() {
+        let (storage, _temp) = setup_test_storage();
+        storage.create_yak("release").unwrap();
+        storage.write_context("release", "notes").unwrap();
+
+        let result = storage.transaction(&mut || {
+            storage.write_context("release", "changed")?;
+            storage.create_yak("release/tag")?;
+            storage.transaction(&mut || storage.mark_done("release", true))?;
+            anyhow::bail!("step failed")
+        });
+
+        assert!(result.is_err());
+        assert_eq!(storage.read_context("release").unwrap(), "notes");
+        assert!(!storage.get_yak("release").unwrap().done);
+        assert!(storage.get_yak("release/tag").is_err());
+
+        storage
+            .transaction(&mut || storage.create_yak("docs"))
+            .unwrap();
+        assert!(storage.get_yak("docs").is_ok());
+    }
+
+    #[test]
+    fn test_failed_transaction_puts_the_trash_back() {
+        let (storage, _temp) = setup_test_storage();
+        storage.create_yak("old").unwrap();
+        storage.create_yak("keep").unwrap();
+        storage.trash("old").unwrap();
+
+        let result = storage.transaction(&mut || {
+            storage.trash("keep")?;
+            storage.restore_trashed("old")?;
+            anyhow::bail!("step failed")
+        });
+
+        assert!(result.is_err());
+        assert!(storage.get_yak("keep").is_ok());
+        assert!(storage.get_yak("old").is_err());
+        let trashed: Vec<String> = storage
+            .trashed()
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.name)
+            .collect();
+        assert_eq!(trashed, vec!["old"]);
+    }
+
+    #[test]
+    fn test_migrations_bring_old_yaks_to_the_current_format() {
+        let (storage, temp) = setup_test_storage();
+        assert!(storage.pending_migrations().unwrap().is_empty());
+
+        // New yaks start out in the current format
+        storage.create_yak("api").unwrap();
+        assert!(storage.pending_migrations().unwrap().is_empty());
+
+        // Yaks from before the format was recorded: files beside their
+        // children rather than in .meta, and done by marker
+        fs::remove_dir_all(temp.path().join("api")).unwrap();
+        fs::create_dir_all(temp.path().join("api/auth")).unwrap();
+        fs::write(temp.path().join("api/context.md"), "the api").unwrap();
+        fs::write(temp.path().join("api/done"), "2026-03-02T09:30:00Z\n").unwrap();
+        fs::write(temp.path().join("api/auth/context.md"), "tokens").unwrap();
+        fs::remove_file(temp.path().join(migrations::VERSION_FILE)).unwrap();
+        assert_eq!(storage.format_version().unwrap(), 0);
+        for migration in storage.pending_migrations().unwrap() {
+            storage.apply_migration(&migration).unwrap();
+        }
+        assert_eq!(
+            storage.format_version().unwrap(),
+            migrations::current_version()
+        );
+        assert!(storage.pending_migrations().unwrap().is_empty());
+        let api = storage.get_yak("api").unwrap();
+        assert!(api.done);
+        assert_eq!(
+            api.done_at
+                .unwrap()
+                .to_rfc3339_opts(SecondsFormat::Secs, true),
+            "2026-03-02T09:30:00Z"
+        );
+        assert_eq!(api.context.as_deref(), Some("the api"));
+        assert!(!storage.done_marker_path("api").exists());
+        assert_eq!(
+            storage.read_field("api", "state").unwrap().as_deref(),
+            Some("done\n")
+        );
+        let names: Vec<String> = storage
+            .list_yaks()
+            .unwrap()
+            .into_iter()
+            .map(|yak| yak.name)
+            .collect();
+        assert_eq!(names, vec!["api", "api/auth"]);
+        assert_eq!(storage.read_context("api/auth").unwrap(), "tokens");
+
+        fs::write(temp.path().join(migrations::VERSION_FILE), "99\n").unwrap();
+        let error = storage.pending_migrations().unwrap_err();
+        assert!(error.to_string().contains("upgrade yx"));
+    }
+
+    #[test]
+    fn test_old_layout_yaks_arriving_after_the_migration_are_adopted() {
+        let (storage, temp) = setup_test_storage();
+        storage.create_yak("api").unwrap();
+        storage.write_context("api", "the api").unwrap();
+
+        // As a sync from an older yx leaves them, with the version still current
+        fs::create_dir_all(temp.path().join("docs")).unwrap();
+        fs::write(temp.path().join("docs/context.md"), "readme").unwrap();
+        fs::write(temp.path().join("api/context.md"), "the api, edited").unwrap();
+        assert!(storage.pending_migrations().unwrap().is_empty());
+
+        storage.adopt_legacy_files().unwrap();
+
+        assert!(!temp.path().join("docs/context.md").exists());
+        assert_eq!(storage.read_context("docs").unwrap(), "readme");
+        assert_eq!(storage.read_context("api").unwrap(), "the api, edited");
+    }
+
+    #[test]
+    fn test_trash_and_restore_yak() {
+        let (storage, _temp) = setup_test_storage();
+        storage.create_yak("api/auth").unwrap();
+        storage.write_context("api/auth", "tokens").unwrap();
+        storage.create_yak("docs").unwrap();
+
+        storage.trash("api").unwrap();
+        storage.create_yak("api").unwrap();
+        storage.trash("api").unwrap();
+        let names = |storage: &DirectoryStorage| -> Vec<String> {
+            storage
+                .list_yaks()
+                .unwrap()
+                .into_iter()
+                .map(|yak| yak.name)
+                .collect()
+        };
+        assert_eq!(names(&storage), vec!["docs"]);
+        let trashed: Vec<String> = storage
+            .trashed()
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.name)
+            .collect();
+        assert_eq!(trashed, vec!["api", "api"]);
+
+        // The older copy comes back once the newer one is out of the way
+        storage.restore_trashed("api").unwrap();
+        assert!(storage.restore_trashed("api").is_err());
+        storage.delete_yak("api").unwrap();
+        storage.restore_trashed("api").unwrap();
+        assert_eq!(names(&storage), vec!["api", "api/auth", "docs"]);
+        assert_eq!(storage.read_context("api/auth").unwrap(), "tokens");
+        assert!(storage.trashed().unwrap().is_empty());
+        assert!(storage.restore_trashed("api").is_err());
+    }
+
+    #[test]
+    fn test_archive_and_unarchive_yak() {
+        let (storage, _temp) = setup_test_storage();
+        storage.create_yak("app").unwrap();
+        storage.create_yak("app/api").unwrap();
+        storage.create_yak("docs").unwrap();
+
+        storage.archive("app/api").unwrap();
+        let names: Vec<String> = storage
+            .list_yaks()
+            .unwrap()
+            .into_iter()
+            .map(|y| y.name)
+            .collect();
+        assert_eq!(names.len(), 2);
+        assert!(!names.contains(&"app/api".to_string()));
+        assert!(storage.archived().get_yak("app/api").is_ok());
+        assert!(storage.archive("app/api").is_err());
+
+        storage.unarchive("app/api").unwrap();
+        assert!(storage.get_yak("app/api").is_ok());
+        assert!(storage.archived().list_yaks().unwrap().is_empty());
+    }
 }
@@ -0,0 +1,97 @@
+// Yak fields - the per-yak files every storage keeps, and how they become a Yak
+
+use crate::domain::{parse_due_date, Priority, Yak, YakState};
+use crate::ports::StoragePort;
+use anyhow::Result;
+use chrono::{DateTime, SecondsFormat, Utc};
+
+/// Read a yak from its fields, and its context when `context` is set; a done
+/// yak's `state` is "done" with its completion time in `completed` (or, from
+/// before storage format v2, a `done` marker holding that time), and `created`
+/// holds the creation time, so storages only fill in what they know beyond
+/// that (such as file times)
+pub(super) fn read_yak(storage: &dyn StoragePort, name: &str, context: bool) -> Result<Yak> {
+    let done_marker = storage.read_field(name, "done")?;
+    let links = storage
+        .read_field(name, "links")?
+        .map(|text| text.lines().map(|line| line.to_string()).collect())
+        .unwrap_or_default();
+    let text = |field: &str| -> Result<Option<String>> {
+        Ok(storage
+            .read_field(name, field)?
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty()))
+    };
+
+    let state = text("state")?.and_then(|text| YakState::parse(&text).ok());
+    let done = done_marker.is_some() || state == Some(YakState::Done);
+
+    Ok(Yak {
+        name: name.to_string(),
+        done,
+        context: context.then(|| storage.read_context(name).ok()).flatten(),
+        links,
+        done_at: match done_marker.as_deref().and_then(time) {
+            Some(done_at) => Some(done_at),
+            None if done => text("completed")?.as_deref().and_then(time),
+            None => None,
+        },
+        title: text("title")?,
+        assignee: text("assignee")?,
+        priority: text("priority")?.and_then(|text| Priority::parse(&text).ok()),
+        due: text("due")?.and_then(|text| parse_due_date(&text).ok()),
+        state: state.filter(|state| *state != YakState::Done),
+        started_at: text("started")?.as_deref().and_then(time),
+        created_at: text("created")?.as_deref().and_then(time),
+        modified_at: None,
+    })
+}
+
+/// Mark a yak done, with now as its completion time, or reopen it; returns
+/// whether that changed anything. Reopening also drops a `done` marker left
+/// from before format v2
+pub(super) fn mark_done(storage: &dyn StoragePort, name: &str, done: bool) -> Result<bool> {
+    let marker = storage.read_field(name, "done")?;
+    let state = storage.read_field(name, "state")?;
+    let was_done =
+        marker.is_some() || state.is_some_and(|text| YakState::parse(&text) == Ok(YakState::Done));
+
+    if done && !was_done {
+        storage.write_field(name, "state", Some("done\n"))?;
+        storage.write_field(name, "completed", Some(&timestamp(Utc::now())))?;
+    } else if !done && was_done {
+        storage.write_field(name, "done", None)?;
+        storage.write_field(name, "state", None)?;
+        storage.write_field(name, "completed", None)?;
+    }
+    Ok(done != was_done)
+}
+
+/// Rewrite a `done` marker from before format v2 as the `state` and
+/// `completed` fields, keeping `done_at` (the marker's time, or a storage's
+/// stand-in for it) as the completion time
+pub(super) fn migrate_done_marker(
+    storage: &dyn StoragePort,
+    name: &str,
+    done_at: Option<DateTime<Utc>>,
+) -> Result<()> {
+    if storage.read_field(name, "done")?.is_none() {
+        return Ok(());
+    }
+    storage.write_field(name, "state", Some("done\n"))?;
+    if let Some(done_at) = done_at {
+        storage.write_field(name, "completed", Some(&timestamp(done_at)))?;
+    }
+    storage.write_field(name, "done", None)
+}
+
+/// A time as kept in a field, e.g. "2026-10-17T09:30:00Z"
+pub(super) fn timestamp(time: DateTime<Utc>) -> String {
+    format!("{}\n", time.to_rfc3339_opts(SecondsFormat::Secs, true))
+}
+
+fn time(text: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(text.trim())
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
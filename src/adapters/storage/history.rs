@@ -0,0 +1,198 @@
+// Unsealed history - the yak history with encrypted files as their plain text
+
+use super::DirectoryStorage;
+use crate::ports::{FileChange, FileVersion, HistoryPort, Revision, YakSnapshot};
+use anyhow::Result;
+
+/// Reads the history through `history` and decrypts the versions of yaks the
+/// storage keeps encrypted, so they can be shown, diffed and restored
+pub struct UnsealedHistory<'a> {
+    history: &'a dyn HistoryPort,
+    storage: &'a DirectoryStorage,
+}
+
+impl<'a> UnsealedHistory<'a> {
+    pub fn new(history: &'a dyn HistoryPort, storage: &'a DirectoryStorage) -> Self {
+        Self { history, storage }
+    }
+
+    fn unseal(&self, name: &str, file: &str, text: String) -> Result<String> {
+        self.storage.unseal_file(name, file, text)
+    }
+}
+
+impl HistoryPort for UnsealedHistory<'_> {
+    fn revisions(&self, name: Option<&str>) -> Result<Vec<Revision>> {
+        self.history.revisions(name)
+    }
+
+    fn changed_files(&self, revision: &str, name: &str) -> Result<Vec<FileChange>> {
+        self.history.changed_files(revision, name)
+    }
+
+    fn last_snapshot(&self, name: &str) -> Result<Option<(Revision, Vec<YakSnapshot>)>> {
+        let Some((revision, snapshots)) = self.history.last_snapshot(name)? else {
+            return Ok(None);
+        };
+        let snapshots = snapshots
+            .into_iter()
+            .map(|snapshot| {
+                let files = snapshot
+                    .files
+                    .into_iter()
+                    .map(|(file, text)| {
+                        let text = self.unseal(&snapshot.name, &file, text)?;
+                        Ok((file, text))
+                    })
+                    .collect::<Result<_>>()?;
+                Ok(YakSnapshot {
+                    name: snapshot.name,
+                    files,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Some((revision, snapshots)))
+    }
+
+    fn file_versions(&self, name: &str, file: &str) -> Result<Vec<FileVersion>> {
+        self.history
+            .file_versions(name, file)?
+            .into_iter()
+            .map(|version| {
+                Ok(FileVersion {
+                    content: self.unseal(name, file, version.content)?,
+                    revision: version.revision,
+                })
+            })
+            .collect()
+    }
+
+    fn remote_file_version(&self, name: &str, file: &str) -> Result<Option<FileVersion>> {
+        self.history
+            .remote_file_version(name, file)?
+            .map(|version| {
+                Ok(FileVersion {
+                    content: self.unseal(name, file, version.content)?,
+                    revision: version.revision,
+                })
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::directory::ENCRYPTED_HEADER;
+    use crate::application::ContextHistory;
+    use crate::ports::{CipherPort, LogEntry, LogPort, OutputPort, StoragePort};
+    use chrono::Utc;
+    use std::fs;
+    use std::rc::Rc;
+    use tempfile::TempDir;
+
+    /// Covers every yak, "encrypting" by reversing the text after the header
+    struct MockCipher;
+
+    impl CipherPort for MockCipher {
+        fn covers(&self, _name: &str) -> bool {
+            true
+        }
+
+        fn encrypt(&self, text: &str) -> Result<String> {
+            Ok(format!(
+                "{ENCRYPTED_HEADER}\n{}",
+                text.chars().rev().collect::<String>()
+            ))
+        }
+
+        fn decrypt(&self, ciphertext: &str) -> Result<Option<String>> {
+            let text = &ciphertext[ENCRYPTED_HEADER.len() + 1..];
+            Ok(Some(text.chars().rev().collect()))
+        }
+    }
+
+    /// One logged version of the yak's context, stored as the cipher sealed it
+    struct MockHistory {
+        content: String,
+    }
+
+    impl HistoryPort for MockHistory {
+        fn revisions(&self, _name: Option<&str>) -> Result<Vec<Revision>> {
+            unimplemented!()
+        }
+
+        fn changed_files(&self, _revision: &str, _name: &str) -> Result<Vec<FileChange>> {
+            unimplemented!()
+        }
+
+        fn last_snapshot(&self, _name: &str) -> Result<Option<(Revision, Vec<YakSnapshot>)>> {
+            unimplemented!()
+        }
+
+        fn file_versions(&self, _name: &str, _file: &str) -> Result<Vec<FileVersion>> {
+            Ok(vec![FileVersion {
+                revision: Revision {
+                    id: "a1b2c3d4e5".to_string(),
+                    command: "context pay".to_string(),
+                    author: "Alice".to_string(),
+                    time: Utc::now(),
+                },
+                content: self.content.clone(),
+            }])
+        }
+
+        fn remote_file_version(&self, _name: &str, _file: &str) -> Result<Option<FileVersion>> {
+            Ok(None)
+        }
+    }
+
+    struct MockLog;
+
+    impl LogPort for MockLog {
+        fn log_command(&self, _command: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    struct MockOutput;
+
+    impl OutputPort for MockOutput {
+        fn success(&self, _message: &str) {}
+
+        fn error(&self, _message: &str) {}
+
+        fn info(&self, _message: &str) {}
+
+        fn warn(&self, _message: &str) {}
+    }
+
+    #[test]
+    fn test_restores_an_encrypted_context_as_its_text() {
+        let temp = TempDir::new().unwrap();
+        let storage = DirectoryStorage::from_path_unchecked(temp.path().join(".yaks"))
+            .with_cipher(Rc::new(MockCipher));
+        storage.create_yak("pay").unwrap();
+        let history = MockHistory {
+            content: MockCipher.encrypt("bands\n").unwrap(),
+        };
+        let versions = UnsealedHistory::new(&history, &storage);
+
+        assert_eq!(
+            versions.file_versions("pay", "context.md").unwrap()[0].content,
+            "bands\n"
+        );
+        ContextHistory::new(&storage, &MockOutput, &MockLog, &versions)
+            .restore("pay", "a1b2")
+            .unwrap();
+
+        // Sealed once, not a second time over the logged ciphertext
+        let on_disk = fs::read_to_string(temp.path().join(".yaks/pay/.meta/context.md")).unwrap();
+        assert_eq!(on_disk, history.content);
+        assert_eq!(storage.read_context("pay").unwrap(), "bands\n");
+    }
+}
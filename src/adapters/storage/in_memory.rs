@@ -0,0 +1,437 @@
+// In-memory storage adapter - yaks held in maps, for tests and tools embedding yx
+
+use super::fields;
+use crate::domain::{Yak, YakState};
+use crate::ports::{ArchivePort, StoragePort, YakIter};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::cell::{Cell, OnceCell, RefCell};
+use std::collections::BTreeMap;
+
+/// What a yak folder holds in DirectoryStorage: its context and field files
+#[derive(Debug, Clone, Default)]
+struct Entry {
+    /// None for parents that only exist to hold other yaks
+    context: Option<String>,
+    fields: BTreeMap<String, String>,
+    modified_at: Option<DateTime<Utc>>,
+}
+
+impl Entry {
+    fn is_empty(&self) -> bool {
+        self.context.is_none() && self.fields.is_empty()
+    }
+}
+
+/// Storage that behaves like DirectoryStorage without touching the filesystem
+/// or the environment; nothing outlives the value
+#[derive(Default)]
+pub struct InMemoryStorage {
+    yaks: RefCell<BTreeMap<String, Entry>>,
+    /// Storage for the archived yaks, made when first needed
+    archive: OnceCell<Box<InMemoryStorage>>,
+    revision: Cell<u64>,
+    /// Set while a transaction runs, so nested ones join it
+    in_transaction: Cell<bool>,
+}
+
+impl InMemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Storage with these yaks already in it
+    pub fn with_yaks(yaks: impl IntoIterator<Item = Yak>) -> Self {
+        let storage = Self::new();
+        for yak in yaks {
+            storage.add_yak(yak);
+        }
+        storage
+    }
+
+    /// Store `yak` as it is, with its times, state and fields, replacing any
+    /// yak of the same name
+    pub fn add_yak(&self, yak: Yak) {
+        let mut fields = BTreeMap::new();
+        let mut set = |field: &str, value: Option<String>| {
+            if let Some(text) = value {
+                fields.insert(field.to_string(), text);
+            }
+        };
+        set(
+            "completed",
+            yak.done_at.filter(|_| yak.done).map(fields::timestamp),
+        );
+        set(
+            "links",
+            (!yak.links.is_empty()).then(|| format!("{}\n", yak.links.join("\n"))),
+        );
+        set("title", yak.title.map(|text| format!("{text}\n")));
+        set("assignee", yak.assignee.map(|text| format!("{text}\n")));
+        set("priority", yak.priority.map(|p| format!("{p}\n")));
+        set("due", yak.due.map(|due| format!("{due}\n")));
+        let state = if yak.done {
+            Some(YakState::Done)
+        } else {
+            yak.state
+        };
+        set("state", state.map(|state| format!("{state}\n")));
+        set("started", yak.started_at.map(fields::timestamp));
+        set("created", yak.created_at.map(fields::timestamp));
+
+        self.add_parents(&yak.name);
+        self.yaks.borrow_mut().insert(
+            yak.name,
+            Entry {
+                context: Some(yak.context.unwrap_or_default()),
+                fields,
+                modified_at: yak.modified_at,
+            },
+        );
+        self.bump();
+    }
+
+    /// Storage for the archived yaks, like `DirectoryStorage::archived`
+    pub fn archived(&self) -> &InMemoryStorage {
+        self.archive.get_or_init(Box::default)
+    }
+
+    fn read_yak(&self, name: &str, context: bool) -> Result<Yak> {
+        let modified_at = match self.yaks.borrow().get(name) {
+            Some(entry) => entry.modified_at,
+            None => anyhow::bail!("yak '{name}' not found"),
+        };
+        let mut yak = fields::read_yak(self, name, context)?;
+        yak.modified_at = modified_at;
+        Ok(yak)
+    }
+
+    fn exists(&self, name: &str) -> bool {
+        self.yaks.borrow().contains_key(name)
+    }
+
+    /// Record a change to `name`'s own data
+    fn touch(&self, name: &str) {
+        if let Some(entry) = self.yaks.borrow_mut().get_mut(name) {
+            entry.modified_at = Some(Utc::now());
+        }
+        self.bump();
+    }
+
+    fn bump(&self) {
+        self.revision.set(self.revision.get() + 1);
+    }
+
+    /// Make the parents a new yak at `name` sits under, as creating its
+    /// folder would
+    fn add_parents(&self, name: &str) {
+        let mut yaks = self.yaks.borrow_mut();
+        for (at, _) in name.match_indices('/') {
+            yaks.entry(name[..at].to_string()).or_default();
+        }
+    }
+
+    /// Remove `name` and the yaks under it, keyed by their path below `name`
+    fn take_subtree(&self, name: &str) -> Vec<(String, Entry)> {
+        let mut yaks = self.yaks.borrow_mut();
+        let paths: Vec<String> = yaks
+            .keys()
+            .filter(|path| is_within(path, name))
+            .cloned()
+            .collect();
+        paths
+            .into_iter()
+            .filter_map(|path| {
+                let entry = yaks.remove(&path)?;
+                Some((path[name.len()..].to_string(), entry))
+            })
+            .collect()
+    }
+
+    /// Put a subtree from `take_subtree` back under `name`
+    fn put_subtree(&self, name: &str, subtree: Vec<(String, Entry)>) {
+        self.add_parents(name);
+        let mut yaks = self.yaks.borrow_mut();
+        for (suffix, entry) in subtree {
+            yaks.insert(format!("{name}{suffix}"), entry);
+        }
+        drop(yaks);
+        self.bump();
+    }
+}
+
+/// Whether `path` is the yak `name` or one under it
+fn is_within(path: &str, name: &str) -> bool {
+    path.strip_prefix(name)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+impl StoragePort for InMemoryStorage {
+    fn create_yak(&self, name: &str) -> Result<()> {
+        self.add_parents(name);
+        let mut entry = self.yaks.borrow_mut().remove(name).unwrap_or_default();
+        entry.context = Some(String::new());
+        entry
+            .fields
+            .insert("created".to_string(), fields::timestamp(Utc::now()));
+        self.yaks.borrow_mut().insert(name.to_string(), entry);
+        self.touch(name);
+        Ok(())
+    }
+
+    fn get_yak(&self, name: &str) -> Result<Yak> {
+        self.read_yak(name, true)
+    }
+
+    fn list_yaks(&self) -> Result<Vec<Yak>> {
+        self.iter_yaks(false)?.collect()
+    }
+
+    fn iter_yaks(&self, context: bool) -> Result<YakIter<'_>> {
+        let names: Vec<String> = self.yaks.borrow().keys().cloned().collect();
+        Ok(Box::new(
+            names
+                .into_iter()
+                .map(move |name| self.read_yak(&name, context)),
+        ))
+    }
+
+    fn mark_done(&self, name: &str, done: bool) -> Result<()> {
+        if !self.exists(name) {
+            anyhow::bail!("yak '{name}' not found");
+        }
+        // Marking a done yak done again keeps when it was first done
+        fields::mark_done(self, name, done)?;
+        Ok(())
+    }
+
+    fn delete_yak(&self, name: &str) -> Result<()> {
+        if !self.take_subtree(name).is_empty() {
+            self.bump();
+        }
+        Ok(())
+    }
+
+    fn rename_yak(&self, from: &str, to: &str) -> Result<()> {
+        if !self.exists(from) {
+            anyhow::bail!("yak '{from}' not found");
+        }
+        if self.exists(to) {
+            anyhow::bail!("Yak '{to}' already exists");
+        }
+        let subtree = self.take_subtree(from);
+        self.put_subtree(to, subtree);
+        Ok(())
+    }
+
+    fn copy_yak(&self, from: &str, to: &str) -> Result<()> {
+        let Some(mut entry) = self.yaks.borrow().get(from).cloned() else {
+            anyhow::bail!("yak '{from}' not found");
+        };
+        if self.exists(to) {
+            anyhow::bail!("Yak '{to}' already exists");
+        }
+        // The copy is a new yak, so it gets its own creation time
+        entry
+            .fields
+            .insert("created".to_string(), fields::timestamp(Utc::now()));
+        self.add_parents(to);
+        self.yaks.borrow_mut().insert(to.to_string(), entry);
+        self.touch(to);
+        Ok(())
+    }
+
+    fn read_context(&self, name: &str) -> Result<String> {
+        self.yaks
+            .borrow()
+            .get(name)
+            .and_then(|entry| entry.context.clone())
+            .ok_or_else(|| anyhow::anyhow!("Failed to read context for '{name}'"))
+    }
+
+    fn write_context(&self, name: &str, text: &str) -> Result<()> {
+        match self.yaks.borrow_mut().get_mut(name) {
+            Some(entry) => entry.context = Some(text.to_string()),
+            None => anyhow::bail!("Failed to write context for '{name}'"),
+        }
+        self.touch(name);
+        Ok(())
+    }
+
+    fn revision(&self) -> Result<Option<u64>> {
+        Ok(Some(self.revision.get()))
+    }
+
+    fn transaction(&self, steps: &mut dyn FnMut() -> Result<()>) -> Result<()> {
+        if self.in_transaction.get() {
+            return steps();
+        }
+
+        let yaks = self.yaks.borrow().clone();
+        let archived = self.archived().yaks.borrow().clone();
+        self.in_transaction.set(true);
+        let result = steps();
+        self.in_transaction.set(false);
+
+        if result.is_err() {
+            *self.yaks.borrow_mut() = yaks;
+            *self.archived().yaks.borrow_mut() = archived;
+            self.bump();
+        }
+        result
+    }
+
+    fn read_field(&self, name: &str, field: &str) -> Result<Option<String>> {
+        Ok(self
+            .yaks
+            .borrow()
+            .get(name)
+            .and_then(|entry| entry.fields.get(field).cloned()))
+    }
+
+    fn write_field(&self, name: &str, field: &str, value: Option<&str>) -> Result<()> {
+        match (self.yaks.borrow_mut().get_mut(name), value) {
+            (Some(entry), Some(text)) => {
+                entry.fields.insert(field.to_string(), text.to_string());
+            }
+            (Some(entry), None) => {
+                entry.fields.remove(field);
+            }
+            (None, Some(_)) => anyhow::bail!("Failed to write {field} for '{name}'"),
+            (None, None) => return Ok(()),
+        }
+        self.touch(name);
+        Ok(())
+    }
+
+    fn list_fields(&self, name: &str) -> Result<Vec<String>> {
+        Ok(self
+            .yaks
+            .borrow()
+            .get(name)
+            .map(|entry| entry.fields.keys().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    fn find_yak(&self, name: &str) -> Result<String> {
+        if self.exists(name) {
+            return Ok(name.to_string());
+        }
+
+        let yaks = self.yaks.borrow();
+        let matches: Vec<&String> = yaks.keys().filter(|path| path.contains(name)).collect();
+        match matches.as_slice() {
+            [] => anyhow::bail!("yak '{name}' not found"),
+            [matched] => Ok(matched.to_string()),
+            _ => anyhow::bail!("yak name '{name}' is ambiguous"),
+        }
+    }
+}
+
+impl ArchivePort for InMemoryStorage {
+    fn archive(&self, name: &str) -> Result<()> {
+        if !self.exists(name) {
+            anyhow::bail!("yak '{name}' not found");
+        }
+        let archived = self.archived();
+        if archived.exists(name) {
+            anyhow::bail!("yak '{name}' is already archived");
+        }
+        archived.put_subtree(name, self.take_subtree(name));
+        self.bump();
+        Ok(())
+    }
+
+    fn unarchive(&self, name: &str) -> Result<()> {
+        let archived = self.archived();
+        if !archived.exists(name) {
+            anyhow::bail!("archived yak '{name}' not found");
+        }
+        if self.exists(name) {
+            anyhow::bail!("Yak '{name}' already exists");
+        }
+        self.put_subtree(name, archived.take_subtree(name));
+
+        // Parents only created to hold the yak in the archive go with it
+        let mut archived_yaks = archived.yaks.borrow_mut();
+        for (at, _) in name.rmatch_indices('/') {
+            let parent = &name[..at];
+            let has_children = archived_yaks
+                .keys()
+                .any(|path| path != parent && is_within(path, parent));
+            if has_children || !archived_yaks.get(parent).is_some_and(Entry::is_empty) {
+                break;
+            }
+            archived_yaks.remove(parent);
+        }
+        archived.bump();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Priority, YakState};
+    use chrono::{NaiveDate, TimeZone};
+
+    #[test]
+    fn test_added_yaks_read_back_unchanged() {
+        let time = Utc.with_ymd_and_hms(2026, 10, 17, 9, 30, 0).unwrap();
+        let yak = Yak {
+            done_at: Some(time),
+            links: vec!["https://example.com/1".to_string()],
+            title: Some("Ship it".to_string()),
+            assignee: Some("me@example.com".to_string()),
+            priority: Some(Priority::HIGHEST),
+            due: NaiveDate::from_ymd_opt(2026, 10, 20),
+            created_at: Some(time),
+            modified_at: Some(time),
+            ..Yak::new("release/ship".to_string())
+                .with_context("notes".to_string())
+                .mark_done()
+        };
+        let storage = InMemoryStorage::with_yaks([
+            yak.clone(),
+            Yak::new("docs".to_string()).with_state(YakState::Blocked),
+        ]);
+
+        assert_eq!(storage.get_yak("release/ship").unwrap(), yak);
+        assert_eq!(
+            storage.get_yak("docs").unwrap().state,
+            Some(YakState::Blocked)
+        );
+        let names: Vec<String> = storage
+            .list_yaks()
+            .unwrap()
+            .into_iter()
+            .map(|y| y.name)
+            .collect();
+        assert_eq!(names, vec!["docs", "release", "release/ship"]);
+        assert_eq!(storage.find_yak("ship").unwrap(), "release/ship");
+        assert!(storage.find_yak("s").is_err());
+    }
+
+    #[test]
+    fn test_rename_delete_and_archive_move_subtrees() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("api/auth").unwrap();
+        storage.write_context("api/auth", "tokens").unwrap();
+        let before = storage.revision().unwrap();
+
+        storage.rename_yak("api", "backend/api").unwrap();
+        assert_ne!(storage.revision().unwrap(), before);
+        assert_eq!(storage.read_context("backend/api/auth").unwrap(), "tokens");
+        assert!(storage.rename_yak("backend", "backend/api").is_err());
+
+        storage.archive("backend/api").unwrap();
+        assert!(!storage.exists("backend/api/auth"));
+        assert!(storage.archived().exists("backend/api/auth"));
+        storage.unarchive("backend/api").unwrap();
+        assert!(storage.exists("backend/api/auth"));
+        assert!(storage.archived().list_yaks().unwrap().is_empty());
+
+        storage.delete_yak("backend").unwrap();
+        assert!(storage.list_yaks().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,191 @@
+// Manifest index - the yak folders in a .yaks folder with their modification
+// times, so listing can tell from the folders alone whether its names still hold
+
+use super::directory::HIDDEN_FOLDERS;
+use super::META_FOLDER;
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+use walkdir::WalkDir;
+
+/// The index's file name at the top of .yaks (and of the archive, which has its own)
+pub const INDEX_FILE: &str = ".index";
+
+/// First line of the index; an index without it (e.g. one that also held
+/// states) is rebuilt
+const HEADER: &str = "yx-index 2";
+
+/// One line per yak, "<modified>\t<name>", where modified is the yak
+/// folder's modification time in nanoseconds since the epoch (adding or
+/// removing a child changes it); keyed by name components, so entries sort
+/// parents first and siblings by name, as the folders walk
+#[derive(Default)]
+pub(super) struct Index {
+    entries: BTreeMap<Vec<String>, u128>,
+}
+
+impl Index {
+    /// The index saved in `yaks_dir`, if there is a readable one
+    pub fn load(yaks_dir: &Path) -> Option<Self> {
+        let text = fs::read_to_string(yaks_dir.join(INDEX_FILE)).ok()?;
+        let mut lines = text.lines();
+        if lines.next()? != HEADER {
+            return None;
+        }
+        let mut entries = BTreeMap::new();
+        for line in lines {
+            let (modified, name) = line.split_once('\t')?;
+            entries.insert(key(name), modified.parse().ok()?);
+        }
+        Some(Self { entries })
+    }
+
+    /// Index every yak folder in `yaks_dir`
+    pub fn build(yaks_dir: &Path) -> Result<Self> {
+        let mut index = Self::default();
+        index.add_subtree(yaks_dir, yaks_dir)?;
+        Ok(index)
+    }
+
+    /// Whether this still matches the folders in `yaks_dir`: the same yaks
+    /// at the top, and every indexed folder unchanged since it was indexed
+    pub fn is_fresh(&self, yaks_dir: &Path) -> bool {
+        let Ok(top) = fs::read_dir(yaks_dir) else {
+            return self.entries.is_empty();
+        };
+        let top: Vec<Vec<String>> = top
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry.path().is_dir()
+                    && !HIDDEN_FOLDERS
+                        .iter()
+                        .any(|folder| entry.file_name() == *folder)
+            })
+            .map(|entry| vec![entry.file_name().to_string_lossy().into_owned()])
+            .collect();
+        let indexed_top = self.entries.keys().filter(|name| name.len() == 1).count();
+        if top.len() != indexed_top || !top.iter().all(|name| self.entries.contains_key(name)) {
+            return false;
+        }
+        self.entries.iter().all(|(name, indexed)| {
+            modified(&yaks_dir.join(name.join("/"))).is_some_and(|time| time == *indexed)
+        })
+    }
+
+    /// Yak names in folder walk order
+    pub fn names(&self) -> Vec<String> {
+        self.entries.keys().map(|name| name.join("/")).collect()
+    }
+
+    /// Re-index `names` and the yaks under them after they changed, along
+    /// with the folders above them (which a change to a child touches)
+    pub fn refresh(&mut self, yaks_dir: &Path, names: &[&str]) -> Result<()> {
+        for name in names {
+            let name_key = key(name);
+            self.entries
+                .retain(|other, _| !other.starts_with(&name_key));
+            let dir = yaks_dir.join(name);
+            if dir.is_dir() {
+                self.add_subtree(yaks_dir, &dir)?;
+            }
+            for depth in 1..name_key.len() {
+                let parent = name_key[..depth].join("/");
+                match modified(&yaks_dir.join(&parent)) {
+                    Some(time) => {
+                        self.entries.insert(name_key[..depth].to_vec(), time);
+                    }
+                    // Parents that only held the yak may go with it
+                    None => {
+                        self.entries.remove(&name_key[..depth]);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the index into `yaks_dir` with `write`, e.g. an atomic writer
+    pub fn save(&self, yaks_dir: &Path, write: fn(&Path, &str) -> Result<()>) -> Result<()> {
+        let mut text = format!("{HEADER}\n");
+        for (name, modified) in &self.entries {
+            text.push_str(&format!("{modified}\t{}\n", name.join("/")));
+        }
+        write(&yaks_dir.join(INDEX_FILE), &text).context("Failed to save the yak index")
+    }
+
+    fn add_subtree(&mut self, yaks_dir: &Path, dir: &Path) -> Result<()> {
+        let walk = WalkDir::new(dir)
+            .min_depth(if dir == yaks_dir { 1 } else { 0 })
+            .into_iter()
+            .filter_entry(|e| {
+                e.file_type().is_dir()
+                    && e.file_name() != META_FOLDER
+                    && !(e.path().parent() == Some(yaks_dir)
+                        && HIDDEN_FOLDERS.iter().any(|folder| e.file_name() == *folder))
+            });
+        for entry in walk {
+            let entry = entry?;
+            let Some(name) = entry.path().strip_prefix(yaks_dir)?.to_str() else {
+                continue;
+            };
+            let Some(time) = modified(entry.path()) else {
+                continue;
+            };
+            self.entries.insert(key(name), time);
+        }
+        Ok(())
+    }
+}
+
+fn key(name: &str) -> Vec<String> {
+    name.split('/').map(str::to_string).collect()
+}
+
+fn modified(path: &Path) -> Option<u128> {
+    let time = fs::metadata(path).ok()?.modified().ok()?;
+    Some(time.duration_since(UNIX_EPOCH).ok()?.as_nanos())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, text: &str) -> Result<()> {
+        Ok(fs::write(path, text)?)
+    }
+
+    #[test]
+    fn test_index_lists_yaks_until_folders_change() {
+        let dir = TempDir::new().unwrap();
+        for name in ["api/auth", "api-docs", ".archive/old"] {
+            fs::create_dir_all(dir.path().join(name)).unwrap();
+        }
+
+        let index = Index::build(dir.path()).unwrap();
+        index.save(dir.path(), write).unwrap();
+        let index = Index::load(dir.path()).unwrap();
+        assert_eq!(index.names(), vec!["api", "api/auth", "api-docs"]);
+        assert!(index.is_fresh(dir.path()));
+
+        // An index in an older format is rebuilt rather than misread
+        fs::write(dir.path().join(INDEX_FILE), "1\ttodo\tapi\n").unwrap();
+        assert!(Index::load(dir.path()).is_none());
+
+        // A yak added by hand, below the top or at it
+        fs::create_dir(dir.path().join("api/auth/tokens")).unwrap();
+        assert!(!index.is_fresh(dir.path()));
+        let mut index = Index::build(dir.path()).unwrap();
+        fs::create_dir(dir.path().join("web")).unwrap();
+        assert!(!index.is_fresh(dir.path()));
+
+        index.refresh(dir.path(), &["web"]).unwrap();
+        assert!(index.is_fresh(dir.path()));
+        fs::remove_dir_all(dir.path().join("api/auth")).unwrap();
+        index.refresh(dir.path(), &["api/auth"]).unwrap();
+        assert_eq!(index.names(), vec!["api", "api-docs", "web"]);
+        assert!(index.is_fresh(dir.path()));
+    }
+}
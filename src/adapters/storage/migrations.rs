@@ -0,0 +1,115 @@
+// Storage format migrations - each change to the .yaks layout, and how yaks
+// stored before it are brought up to date
+
+use super::{fields, DirectoryStorage, META_FOLDER};
+use crate::ports::{Migration, StoragePort};
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+/// File at the top of .yaks holding the format version its yaks are kept in
+pub const VERSION_FILE: &str = ".version";
+
+/// A migration and the step that applies it to a .yaks folder; steps may
+/// find some yaks already migrated (e.g. ones synced in from a newer yx),
+/// so each leaves those as they are
+pub(super) struct Step {
+    pub migration: Migration,
+    pub apply: fn(&DirectoryStorage) -> Result<()>,
+}
+
+/// Every migration, oldest first
+pub(super) const STEPS: &[Step] = &[
+    Step {
+        migration: Migration {
+            version: 1,
+            name: "version",
+            description: "record the storage format in .yaks/.version",
+        },
+        apply: record_version,
+    },
+    Step {
+        migration: Migration {
+            version: 2,
+            name: "state",
+            description: "keep done in the state field, rewriting done markers",
+        },
+        apply: done_to_state,
+    },
+    Step {
+        migration: Migration {
+            version: 3,
+            name: "meta",
+            description: "move each yak's own files into its .meta folder",
+        },
+        apply: files_to_meta,
+    },
+];
+
+/// The format version this build keeps yaks in
+pub(super) fn current_version() -> u32 {
+    STEPS.last().map_or(0, |step| step.migration.version)
+}
+
+/// The format version recorded in `yaks_dir`, if any
+pub(super) fn read_version(yaks_dir: &Path) -> Result<Option<u32>> {
+    let path = yaks_dir.join(VERSION_FILE);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let text =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let version = text
+        .trim()
+        .parse()
+        .with_context(|| format!("Invalid storage format version in {}", path.display()))?;
+    Ok(Some(version))
+}
+
+/// Nothing moves: the version file itself is what this migration adds
+fn record_version(_storage: &DirectoryStorage) -> Result<()> {
+    Ok(())
+}
+
+/// Rewrite every `done` marker, archived yaks' too, as `state` "done" with
+/// the marker's time in `completed`. Yak files are read where this yx keeps
+/// them, so they're moved into .meta (v3) first
+fn done_to_state(storage: &DirectoryStorage) -> Result<()> {
+    files_to_meta(storage)?;
+    for storage in [storage, &storage.archived()] {
+        let yaks: Vec<_> = storage.iter_yaks(false)?.collect::<Result<_>>()?;
+        for yak in yaks {
+            if yak.done {
+                fields::migrate_done_marker(storage, &yak.name, yak.done_at)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Move the files directly in each yak's folder, archived yaks' too, into
+/// its .meta folder; folders stay where they are, as they're child yaks. A
+/// file already in .meta is replaced, as the one beside it is the newer edit
+pub(super) fn files_to_meta(storage: &DirectoryStorage) -> Result<()> {
+    for storage in [storage, &storage.archived()] {
+        let yaks: Vec<_> = storage.iter_yaks(false)?.collect::<Result<_>>()?;
+        for yak in yaks {
+            let dir = storage.yak_dir(&yak.name);
+            let meta = dir.join(META_FOLDER);
+            for entry in fs::read_dir(&dir)? {
+                let entry = entry?;
+                if !entry.file_type()?.is_file() {
+                    continue;
+                }
+                fs::create_dir_all(&meta)?;
+                fs::rename(entry.path(), meta.join(entry.file_name())).with_context(|| {
+                    format!(
+                        "Failed to move {} into {META_FOLDER}",
+                        entry.path().display()
+                    )
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
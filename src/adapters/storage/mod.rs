@@ -1,5 +1,53 @@
 // Storage adapters - implementations for different storage backends
 
 pub mod directory;
+mod fields;
+mod history;
+mod index;
+// For library users and tests; the yx binary always stores yaks on disk
+#[allow(dead_code)]
+pub mod in_memory;
+mod migrations;
+mod pruned;
 
 pub use directory::DirectoryStorage;
+pub use history::UnsealedHistory;
+#[allow(unused_imports)]
+pub use in_memory::InMemoryStorage;
+
+use crate::adapters::lock::LOCK_FILE;
+use std::path::Path;
+
+/// Whether `relative`, a path inside .yaks, is one of the files kept for this
+/// checkout alone (the lock, the index, the trash and the pruned record)
+/// rather than part of the yaks, so it is never logged, synced or backed up
+pub fn is_local_file(relative: &Path) -> bool {
+    relative == Path::new(LOCK_FILE)
+        || relative == Path::new(pruned::PRUNED_FILE)
+        || relative.starts_with(directory::TRASH_FOLDER)
+        || relative.file_name() == Some(std::ffi::OsStr::new(index::INDEX_FILE))
+}
+
+/// Folder inside each yak holding the yak's own files (context.md, state,
+/// ...), so they can't collide with child yaks, which are its other folders
+pub const META_FOLDER: &str = ".meta";
+
+/// A path inside .yaks as a file of the yak it belongs to, with the `.meta`
+/// folders dropped, e.g. "api/.meta/state" -> "api/state"
+#[cfg_attr(not(any(feature = "libgit2", feature = "git-cli")), allow(dead_code))]
+pub fn yak_file_path(path: &str) -> String {
+    path.split('/')
+        .filter(|part| *part != META_FOLDER)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Where one of a yak's own files has been kept inside .yaks, newest layout
+/// first: "api/.meta/context.md", then "api/context.md" from before format v3
+#[cfg_attr(not(any(feature = "libgit2", feature = "git-cli")), allow(dead_code))]
+pub fn yak_file_paths(name: &str, file: &str) -> [String; 2] {
+    [
+        format!("{name}/{META_FOLDER}/{file}"),
+        format!("{name}/{file}"),
+    ]
+}
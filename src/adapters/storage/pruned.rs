@@ -0,0 +1,156 @@
+// Pruned record - done yaks `yx prune` removed, one JSON object per line in
+// .yaks/.pruned.jsonl, e.g.
+// {"name": "api", "title": null, "completed": "2026-10-17T09:30:00Z", ...}
+
+use crate::ports::PrunedYak;
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::collections::BTreeMap;
+
+/// The record's file name at the top of .yaks
+pub const PRUNED_FILE: &str = ".pruned.jsonl";
+
+/// One line of the record, without its newline
+pub(super) fn encode(yak: &PrunedYak) -> String {
+    let time = |time: &DateTime<Utc>| quote(&time.to_rfc3339_opts(SecondsFormat::Secs, true));
+    let fields = [
+        ("name", quote(&yak.name)),
+        ("title", yak.title.as_deref().map_or("null".into(), quote)),
+        (
+            "completed",
+            yak.completed.as_ref().map_or("null".into(), time),
+        ),
+        ("pruned", time(&yak.pruned)),
+        ("context", quote(&yak.context)),
+    ];
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(key, value)| format!("{}: {value}", quote(key)))
+        .collect();
+    format!("{{{}}}", fields.join(", "))
+}
+
+/// Read a line written by `encode`; None for lines it can't make sense of
+pub(super) fn decode(line: &str) -> Option<PrunedYak> {
+    let fields = parse_object(line)?;
+    let time = |key: &str| -> Option<DateTime<Utc>> {
+        let text = fields.get(key)?.as_deref()?;
+        DateTime::parse_from_rfc3339(text)
+            .ok()
+            .map(|time| time.with_timezone(&Utc))
+    };
+    Some(PrunedYak {
+        name: fields.get("name")?.clone()?,
+        title: fields.get("title").cloned().flatten(),
+        completed: time("completed"),
+        pruned: time("pruned")?,
+        context: fields.get("context").cloned().flatten().unwrap_or_default(),
+    })
+}
+
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// A flat object of string and null values, as `encode` writes
+fn parse_object(text: &str) -> Option<BTreeMap<String, Option<String>>> {
+    let mut chars = text.trim().chars().peekable();
+    let mut fields = BTreeMap::new();
+    let skip_space = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+    };
+
+    if chars.next()? != '{' {
+        return None;
+    }
+    loop {
+        skip_space(&mut chars);
+        if chars.next_if_eq(&'}').is_some() {
+            break;
+        }
+        let key = parse_string(&mut chars)?;
+        skip_space(&mut chars);
+        chars.next().filter(|c| *c == ':')?;
+        skip_space(&mut chars);
+        let value = if chars.peek() == Some(&'"') {
+            Some(parse_string(&mut chars)?)
+        } else {
+            let word: String = chars.by_ref().take(4).collect();
+            (word == "null").then_some(())?;
+            None
+        };
+        fields.insert(key, value);
+        skip_space(&mut chars);
+        match chars.next()? {
+            ',' => continue,
+            '}' => break,
+            _ => return None,
+        }
+    }
+    Some(fields)
+}
+
+fn parse_string(chars: &mut impl Iterator<Item = char>) -> Option<String> {
+    if chars.next()? != '"' {
+        return None;
+    }
+    let mut value = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(value),
+            '\\' => match chars.next()? {
+                'n' => value.push('\n'),
+                'r' => value.push('\r'),
+                't' => value.push('\t'),
+                'u' => {
+                    let hex: String = chars.take(4).collect();
+                    value.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                }
+                c => value.push(c),
+            },
+            c => value.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_pruned_yaks_read_back_as_written() {
+        let yak = PrunedYak {
+            name: "api/auth".to_string(),
+            title: Some("Auth \"v2\"".to_string()),
+            completed: Some(Utc.with_ymd_and_hms(2026, 10, 16, 9, 30, 0).unwrap()),
+            pruned: Utc.with_ymd_and_hms(2026, 10, 17, 8, 0, 0).unwrap(),
+            context: "tokens\n\trefresh \\ rotate\u{1}".to_string(),
+        };
+        let line = encode(&yak);
+        assert!(!line.contains('\n'));
+        assert_eq!(decode(&line), Some(yak.clone()));
+
+        let untitled = PrunedYak {
+            title: None,
+            completed: None,
+            ..yak
+        };
+        assert_eq!(decode(&encode(&untitled)), Some(untitled));
+        assert_eq!(decode("{\"name\": \"api\""), None);
+        assert_eq!(decode("not json"), None);
+    }
+}
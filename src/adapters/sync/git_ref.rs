@@ -1,37 +1,93 @@
 // Git ref sync adapter - synchronizes yaks via git refs/notes/yaks
 
-use crate::ports::SyncPort;
+use crate::adapters::lock::{self, YakLock};
+use crate::adapters::storage::directory::HIDDEN_FOLDERS;
+use crate::adapters::storage::{is_local_file, yak_file_path, DirectoryStorage, META_FOLDER};
+use crate::adapters::workspace;
+use crate::ports::{IncomingChange, SyncPhase, SyncPort, SyncProgress, SyncStatus};
 use anyhow::{Context, Result};
-use git2::{Oid, Repository};
-use std::path::PathBuf;
+use git2::{FetchOptions, Oid, PushOptions, RemoteCallbacks, Repository};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// A yak's done state and direct files (name, blob id) in some tree
+#[derive(Default, PartialEq, Eq)]
+struct YakFiles {
+    done: bool,
+    files: Vec<(String, Oid)>,
+}
 
 pub struct GitRefSync {
     repo: Repository,
     yaks_path: PathBuf,
+    /// refs/notes/yaks, or the selected workspace's own ref
+    notes_ref: String,
+    /// Where origin's copy of `notes_ref` is fetched to while syncing
+    remote_ref: String,
 }
 
 impl GitRefSync {
-    pub fn new() -> Result<Self> {
+    /// Sync for the yaks in `yaks_path`, as resolved by DirectoryStorage
+    pub fn new(yaks_path: &Path) -> Result<Self> {
         let git_work_tree = std::env::var("GIT_WORK_TREE")
             .or_else(|_| std::env::current_dir().map(|p| p.display().to_string()))?;
 
         let repo = Repository::open(&git_work_tree)
             .with_context(|| format!("Failed to open git repository at {git_work_tree}"))?;
 
-        let yaks_path = std::env::var("YAK_PATH")
-            .unwrap_or_else(|_| ".yaks".to_string())
-            .into();
+        let workspace = workspace::selected();
+        Ok(Self {
+            repo,
+            yaks_path: yaks_path.to_path_buf(),
+            notes_ref: workspace::notes_ref(workspace.as_deref()),
+            remote_ref: workspace::remote_ref(workspace.as_deref()),
+        })
+    }
 
-        Ok(Self { repo, yaks_path })
+    /// Sync for another repository's yaks (e.g., a configured workspace)
+    pub fn for_work_tree(work_tree: &Path, yaks_path: &Path) -> Result<Self> {
+        let repo = Repository::open(work_tree)
+            .with_context(|| format!("Failed to open git repository at {}", work_tree.display()))?;
+
+        Ok(Self {
+            repo,
+            yaks_path: yaks_path.to_path_buf(),
+            notes_ref: workspace::notes_ref(None),
+            remote_ref: workspace::remote_ref(None),
+        })
     }
 
     // Fetch refs/notes/yaks from origin into refs/remotes/origin/yaks
-    fn fetch_remote(&self) -> Result<()> {
+    // The ref is kept between commands, like a remote-tracking branch, so
+    // `yx context --diff=remote` can read origin's copy after a sync
+    fn fetch_remote(&self, progress: &dyn Fn(SyncProgress)) -> Result<()> {
         // Try to fetch, but don't fail if remote doesn't exist or has no yaks ref yet
-        let refspec = "refs/notes/yaks:refs/remotes/origin/yaks";
+        let refspec = format!("{}:{}", self.notes_ref, self.remote_ref);
 
         if let Ok(mut remote) = self.repo.find_remote("origin") {
-            let _ = remote.fetch(&[refspec], None, None);
+            // Cleared first so a remote without a yaks ref leaves none behind,
+            // and put back if the fetch fails so the last known copy stays
+            let previous = self.get_remote_ref()?;
+            if let Ok(mut ref_) = self.repo.find_reference(&self.remote_ref) {
+                ref_.delete()?;
+            }
+
+            let mut callbacks = RemoteCallbacks::new();
+            callbacks.transfer_progress(|stats| {
+                progress(SyncProgress {
+                    phase: SyncPhase::Fetch,
+                    objects: Some((stats.received_objects(), stats.total_objects())),
+                });
+                true
+            });
+            let mut options = FetchOptions::new();
+            options.remote_callbacks(callbacks);
+            if remote.fetch(&[&refspec], Some(&mut options), None).is_err() {
+                if let Some(oid) = previous {
+                    self.repo
+                        .reference(&self.remote_ref, oid, true, "fetch: keep last known")?;
+                }
+            }
         }
 
         Ok(())
@@ -39,7 +95,7 @@ impl GitRefSync {
 
     // Get the OID of refs/remotes/origin/yaks if it exists
     fn get_remote_ref(&self) -> Result<Option<Oid>> {
-        match self.repo.refname_to_id("refs/remotes/origin/yaks") {
+        match self.repo.refname_to_id(&self.remote_ref) {
             Ok(oid) => Ok(Some(oid)),
             Err(_) => Ok(None),
         }
@@ -47,7 +103,7 @@ impl GitRefSync {
 
     // Get the OID of refs/notes/yaks if it exists
     fn get_local_ref(&self) -> Result<Option<Oid>> {
-        match self.repo.refname_to_id("refs/notes/yaks") {
+        match self.repo.refname_to_id(&self.notes_ref) {
             Ok(oid) => Ok(Some(oid)),
             Err(_) => Ok(None),
         }
@@ -65,6 +121,9 @@ impl GitRefSync {
             {
                 let path = entry.path();
                 let relative = path.strip_prefix(&self.yaks_path)?;
+                if is_local_file(relative) {
+                    continue;
+                }
                 let contents = std::fs::read(path)?;
 
                 // Create blob from file contents
@@ -97,7 +156,12 @@ impl GitRefSync {
     fn has_uncommitted_changes(&self, local_ref: Option<Oid>) -> Result<bool> {
         // If no local ref exists, any content in .yaks is uncommitted
         let Some(local_oid) = local_ref else {
-            return Ok(self.yaks_path.exists() && self.yaks_path.read_dir()?.next().is_some());
+            return Ok(self.yaks_path.exists()
+                && self
+                    .yaks_path
+                    .read_dir()?
+                    .filter_map(|e| e.ok())
+                    .any(|e| !is_local_file(Path::new(&e.file_name()))));
         };
 
         // Compare .yaks directory with the tree at local_ref
@@ -124,25 +188,17 @@ impl GitRefSync {
 
         // Create commit
         let sig = self.repo.signature()?;
-        let oid = self.repo.commit(
-            Some("refs/notes/yaks"),
-            &sig,
-            &sig,
-            message,
-            &tree,
-            &parents,
-        )?;
+        let oid = self
+            .repo
+            .commit(Some(&self.notes_ref), &sig, &sig, message, &tree, &parents)?;
 
         Ok(oid)
     }
 
     // Extract .yaks directory from refs/notes/yaks
     fn extract_to_working_dir(&self) -> Result<()> {
-        // Remove existing .yaks
-        if self.yaks_path.exists() {
-            std::fs::remove_dir_all(&self.yaks_path)?;
-        }
-        std::fs::create_dir_all(&self.yaks_path)?;
+        // Remove existing .yaks, keeping the lock this sync holds
+        lock::clear_yaks_dir(&self.yaks_path)?;
 
         // Extract from refs/notes/yaks if it exists
         if let Some(oid) = self.get_local_ref()? {
@@ -173,7 +229,7 @@ impl GitRefSync {
             })?;
         }
 
-        Ok(())
+        DirectoryStorage::at(self.yaks_path.clone()).adopt_legacy_files()
     }
 
     // Merge remote ref into local ref at git level
@@ -187,7 +243,7 @@ impl GitRefSync {
         if self.repo.graph_descendant_of(remote_ref, local_ref)? {
             // Remote is ahead, fast-forward to it
             self.repo
-                .reference("refs/notes/yaks", remote_ref, true, "sync: fast-forward")?;
+                .reference(&self.notes_ref, remote_ref, true, "sync: fast-forward")?;
             return Ok(remote_ref);
         }
 
@@ -230,7 +286,7 @@ impl GitRefSync {
         // Create merge commit
         let sig = self.repo.signature()?;
         let merge_oid = self.repo.commit(
-            Some("refs/notes/yaks"),
+            Some(&self.notes_ref),
             &sig,
             &sig,
             "Merge yaks",
@@ -242,18 +298,132 @@ impl GitRefSync {
     }
 
     // Push refs/notes/yaks to origin
-    fn push_to_remote(&self) -> Result<()> {
-        if self.get_local_ref()?.is_none() {
+    // Push to origin; a failed push doesn't stop a sync, so it comes back as a warning
+    fn push_to_remote(&self, progress: &dyn Fn(SyncProgress)) -> Result<Option<String>> {
+        let Some(local_oid) = self.get_local_ref()? else {
             // Nothing to push
-            return Ok(());
-        }
+            return Ok(None);
+        };
 
         if let Ok(mut remote) = self.repo.find_remote("origin") {
-            let refspec = "refs/notes/yaks:refs/notes/yaks";
-            let _ = remote.push(&[refspec], None);
+            let refspec = format!("{0}:{0}", self.notes_ref);
+            let mut callbacks = RemoteCallbacks::new();
+            callbacks.push_transfer_progress(|current, total, _bytes| {
+                progress(SyncProgress {
+                    phase: SyncPhase::Push,
+                    objects: Some((current, total)),
+                });
+            });
+            let mut options = PushOptions::new();
+            options.remote_callbacks(callbacks);
+            if let Err(e) = remote.push(&[refspec], Some(&mut options)) {
+                return Ok(Some(format!(
+                    "push to origin skipped: {}; your yaks are only synced locally",
+                    e.message()
+                )));
+            }
+            // Origin now has what was pushed
+            self.repo
+                .reference(&self.remote_ref, local_oid, true, "sync: pushed")?;
         }
 
-        Ok(())
+        Ok(None)
+    }
+
+    // Count commits reachable from `oid`
+    fn count_commits(&self, oid: Oid) -> Result<usize> {
+        let mut walk = self.repo.revwalk()?;
+        walk.push(oid)?;
+        Ok(walk.count())
+    }
+
+    // Tree a sync would leave in .yaks, following the same steps as `sync`
+    // but only writing objects, never refs or files
+    fn simulate_sync(&self, local_ref: Option<Oid>, remote_ref: Oid, current: Oid) -> Result<Oid> {
+        let remote_tree = self.repo.find_commit(remote_ref)?.tree()?;
+        let has_changes = self.has_uncommitted_changes(local_ref)?;
+
+        // Local yaks replace their remote versions wholesale before committing
+        let ours = if has_changes {
+            let mut builder = self.repo.treebuilder(Some(&remote_tree))?;
+            for entry in self.repo.find_tree(current)?.iter() {
+                if entry.kind() == Some(git2::ObjectType::Tree) {
+                    builder.insert(entry.name_bytes(), entry.id(), entry.filemode())?;
+                }
+            }
+            builder.write()?
+        } else {
+            match local_ref {
+                Some(local_oid) => self.repo.find_commit(local_oid)?.tree_id(),
+                // No local ref, just use remote
+                None => return Ok(remote_tree.id()),
+            }
+        };
+
+        let base = match local_ref {
+            Some(local_oid) => {
+                if local_oid == remote_ref
+                    || self.repo.graph_descendant_of(local_oid, remote_ref)?
+                {
+                    return Ok(ours);
+                }
+                if !has_changes && self.repo.graph_descendant_of(remote_ref, local_oid)? {
+                    return Ok(remote_tree.id());
+                }
+                match self.repo.merge_base(local_oid, remote_ref) {
+                    Ok(base) => self.repo.find_commit(base)?.tree()?,
+                    Err(_) => self.repo.find_tree(self.repo.treebuilder(None)?.write()?)?,
+                }
+            }
+            None => self.repo.find_tree(self.repo.treebuilder(None)?.write()?)?,
+        };
+
+        let mut index =
+            self.repo
+                .merge_trees(&base, &self.repo.find_tree(ours)?, &remote_tree, None)?;
+        if index.has_conflicts() {
+            anyhow::bail!("Merge conflicts detected - this should not happen with yaks");
+        }
+        Ok(index.write_tree_to(&self.repo)?)
+    }
+
+    // Every yak folder in a tree, keyed by yak name
+    fn yaks_in_tree(&self, tree_oid: Oid) -> Result<BTreeMap<String, YakFiles>> {
+        let mut yaks: BTreeMap<String, YakFiles> = BTreeMap::new();
+        self.repo
+            .find_tree(tree_oid)?
+            .walk(git2::TreeWalkMode::PreOrder, |dir, entry| {
+                let name = entry.name().unwrap_or_default();
+                // Archived and trashed yaks, and files kept for one checkout,
+                // aren't in the list, as for DirectoryStorage::list_yaks
+                if dir.is_empty() && HIDDEN_FOLDERS.contains(&name)
+                    || is_local_file(Path::new(&format!("{dir}{name}")))
+                {
+                    return git2::TreeWalkResult::Skip;
+                }
+                match entry.kind() {
+                    // A yak's .meta folder holds its files rather than a child
+                    Some(git2::ObjectType::Tree) if name != META_FOLDER => {
+                        yaks.entry(format!("{dir}{name}")).or_default();
+                    }
+                    Some(git2::ObjectType::Blob) if !dir.is_empty() => {
+                        let yak = yaks
+                            .entry(yak_file_path(dir.trim_end_matches('/')))
+                            .or_default();
+                        // A done marker, or (from storage format v2) state "done"
+                        yak.done |= name == "done"
+                            || name == "state"
+                                && self
+                                    .repo
+                                    .find_blob(entry.id())
+                                    .is_ok_and(|blob| blob.content().trim_ascii() == b"done");
+                        yak.files.push((name.to_string(), entry.id()));
+                    }
+                    _ => {}
+                }
+                git2::TreeWalkResult::Ok
+            })?;
+        Ok(yaks)
     }
 
     // Merge remote files into local .yaks directory (last-write-wins at yak level)
@@ -298,6 +468,7 @@ impl GitRefSync {
                     e.path()
                         .strip_prefix(&self.yaks_path)
                         .ok()
+                        .filter(|p| !is_local_file(p))
                         .and_then(|p| p.to_str().map(|s| s.to_string()))
                 })
                 .collect()
@@ -335,10 +506,7 @@ impl GitRefSync {
         }
 
         // Replace .yaks with merged content
-        if self.yaks_path.exists() {
-            std::fs::remove_dir_all(&self.yaks_path)?;
-        }
-        std::fs::create_dir_all(&self.yaks_path)?;
+        lock::clear_yaks_dir(&self.yaks_path)?;
 
         for entry in walkdir::WalkDir::new(temp_dir.path())
             .into_iter()
@@ -354,30 +522,49 @@ impl GitRefSync {
             std::fs::copy(path, dest)?;
         }
 
-        Ok(())
+        // Yaks origin got from an older yx are committed in the current layout
+        DirectoryStorage::at(self.yaks_path.clone()).adopt_legacy_files()
     }
 }
 
 impl SyncPort for GitRefSync {
     fn push(&self) -> Result<()> {
-        self.push_to_remote()
+        match self.push_to_remote(&|_| {})? {
+            Some(warning) => anyhow::bail!(warning),
+            None => Ok(()),
+        }
     }
 
     fn pull(&self) -> Result<()> {
-        self.fetch_remote()?;
+        let _lock = YakLock::acquire(&self.yaks_path)?;
+        self.fetch_remote(&|_| {})?;
         self.extract_to_working_dir()
     }
 
-    fn sync(&self) -> Result<()> {
+    fn sync(&self, progress: &dyn Fn(SyncProgress)) -> Result<Vec<String>> {
+        let phase = |phase| {
+            progress(SyncProgress {
+                phase,
+                objects: None,
+            })
+        };
+
+        // Other yx commands wait until .yaks is extracted again
+        let _lock = YakLock::acquire(&self.yaks_path)?;
+
         // Step 1: Fetch remote
-        self.fetch_remote()?;
+        phase(SyncPhase::Fetch);
+        self.fetch_remote(progress)?;
 
         let remote_ref = self.get_remote_ref()?;
         let local_ref = self.get_local_ref()?;
 
         // Step 2: If we have local uncommitted changes AND a remote, merge files first
-        if self.has_uncommitted_changes(local_ref)? && remote_ref.is_some() {
-            self.merge_remote_into_local_yaks(remote_ref.unwrap())?;
+        phase(SyncPhase::Merge);
+        if let Some(remote_oid) = remote_ref {
+            if self.has_uncommitted_changes(local_ref)? {
+                self.merge_remote_into_local_yaks(remote_oid)?;
+            }
         }
 
         // Step 3: Commit any uncommitted changes in .yaks
@@ -395,20 +582,72 @@ impl SyncPort for GitRefSync {
         } else if let Some(remote_oid) = remote_ref {
             // No local ref, just use remote
             self.repo
-                .reference("refs/notes/yaks", remote_oid, true, "sync: use remote")?;
+                .reference(&self.notes_ref, remote_oid, true, "sync: use remote")?;
         }
 
         // Step 5: Push to remote
-        self.push_to_remote()?;
+        phase(SyncPhase::Push);
+        let warnings: Vec<String> = self.push_to_remote(progress)?.into_iter().collect();
 
         // Step 6: Extract final result to .yaks
         self.extract_to_working_dir()?;
 
-        // Cleanup: remove refs/remotes/origin/yaks
-        if let Ok(mut ref_) = self.repo.find_reference("refs/remotes/origin/yaks") {
-            let _ = ref_.delete();
-        }
+        Ok(warnings)
+    }
 
-        Ok(())
+    fn status(&self) -> Result<SyncStatus> {
+        self.fetch_remote(&|_| {})?;
+
+        let local_ref = self.get_local_ref()?;
+        let remote_ref = self.get_remote_ref()?;
+        let has_local_changes = self.has_uncommitted_changes(local_ref)?;
+
+        let remote = match (local_ref, remote_ref) {
+            (Some(local_oid), Some(remote_oid)) => {
+                Some(self.repo.graph_ahead_behind(local_oid, remote_oid)?)
+            }
+            (None, Some(remote_oid)) => Some((0, self.count_commits(remote_oid)?)),
+            (_, None) => None,
+        };
+
+        Ok(SyncStatus {
+            has_local_changes,
+            remote,
+        })
+    }
+
+    fn preview(&self) -> Result<Vec<(String, IncomingChange)>> {
+        self.fetch_remote(&|_| {})?;
+
+        let local_ref = self.get_local_ref()?;
+        let remote_ref = self.get_remote_ref()?;
+        let current = self.build_tree_from_yaks()?;
+        let result = match remote_ref {
+            Some(remote_oid) => self.simulate_sync(local_ref, remote_oid, current),
+            None => Ok(current),
+        };
+
+        let mut before = self.yaks_in_tree(current)?;
+        let after = self.yaks_in_tree(result?)?;
+
+        let mut changes = Vec::new();
+        for (name, yak) in &after {
+            let change = match before.remove(name) {
+                None => IncomingChange::Added,
+                Some(old) if !old.done && yak.done => IncomingChange::Completed,
+                Some(old) if old.done && !yak.done => IncomingChange::Reopened,
+                Some(old) if old != *yak => IncomingChange::Updated,
+                Some(_) => continue,
+            };
+            changes.push((name.clone(), change));
+        }
+        changes.extend(
+            before
+                .into_keys()
+                .map(|name| (name, IncomingChange::Removed)),
+        );
+        changes.sort_by(|a, b| a.0.cmp(&b.0));
+
+        Ok(changes)
     }
 }
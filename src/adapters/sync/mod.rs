@@ -1,5 +1,11 @@
 // Sync adapters - implementations for git ref synchronization
 
+#[cfg(feature = "libgit2")]
 pub mod git_ref;
+#[cfg(not(feature = "libgit2"))]
+pub mod no_sync;
 
+#[cfg(feature = "libgit2")]
 pub use git_ref::GitRefSync;
+#[cfg(not(feature = "libgit2"))]
+pub use no_sync::NoSync;
@@ -0,0 +1,42 @@
+// Sync stand-in for builds without libgit2 - every operation reports that sync is unavailable
+
+use crate::ports::{IncomingChange, SyncPort, SyncProgress, SyncStatus};
+use anyhow::Result;
+use std::path::Path;
+
+const UNAVAILABLE: &str = "sync is unavailable: yx was built without the libgit2 feature";
+
+pub struct NoSync;
+
+impl NoSync {
+    pub fn new(_yaks_path: &Path) -> Result<Self> {
+        Ok(Self)
+    }
+
+    /// Sync for another repository's yaks (e.g., a configured workspace)
+    pub fn for_work_tree(_work_tree: &Path, _yaks_path: &Path) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+impl SyncPort for NoSync {
+    fn push(&self) -> Result<()> {
+        anyhow::bail!(UNAVAILABLE)
+    }
+
+    fn pull(&self) -> Result<()> {
+        anyhow::bail!(UNAVAILABLE)
+    }
+
+    fn sync(&self, _progress: &dyn Fn(SyncProgress)) -> Result<Vec<String>> {
+        anyhow::bail!(UNAVAILABLE)
+    }
+
+    fn status(&self) -> Result<SyncStatus> {
+        anyhow::bail!(UNAVAILABLE)
+    }
+
+    fn preview(&self) -> Result<Vec<(String, IncomingChange)>> {
+        anyhow::bail!(UNAVAILABLE)
+    }
+}
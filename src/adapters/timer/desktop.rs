@@ -0,0 +1,37 @@
+// Desktop timer adapter - sleeps in-process and notifies via the OS notification tool
+
+use crate::ports::TimerPort;
+use anyhow::Result;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+pub struct DesktopTimer;
+
+impl TimerPort for DesktopTimer {
+    fn wait(&self, duration: Duration) -> Result<()> {
+        std::thread::sleep(duration);
+        Ok(())
+    }
+
+    fn notify(&self, title: &str, message: &str) {
+        // Terminal bell in case no notification tool is available
+        eprint!("\x07");
+
+        let mut command = if cfg!(target_os = "macos") {
+            let script = format!(
+                "display notification \"{}\" with title \"{}\"",
+                message.replace('"', "'"),
+                title.replace('"', "'")
+            );
+            let mut command = Command::new("osascript");
+            command.arg("-e").arg(script);
+            command
+        } else {
+            let mut command = Command::new("notify-send");
+            command.arg(title).arg(message);
+            command
+        };
+
+        let _ = command.stdout(Stdio::null()).stderr(Stdio::null()).status();
+    }
+}
@@ -0,0 +1,3 @@
+mod desktop;
+
+pub use desktop::DesktopTimer;
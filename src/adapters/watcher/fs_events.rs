@@ -0,0 +1,71 @@
+// Filesystem event watcher adapter - wakes on inotify, FSEvents or
+// ReadDirectoryChanges events for the yaks folder via the notify crate
+
+use crate::ports::WatcherPort;
+use anyhow::{Context, Result};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+/// How long events must stop for before a change counts as finished, so one
+/// command's burst of writes wakes the watch once
+const SETTLE: Duration = Duration::from_millis(100);
+
+pub struct FsEventWatcher {
+    // Dropping the watcher stops the events
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<notify::Event>>,
+}
+
+impl FsEventWatcher {
+    /// Watch everything under `path`
+    pub fn new(path: &Path) -> Result<Self> {
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        watcher
+            .watch(path, RecursiveMode::Recursive)
+            .with_context(|| format!("Failed to watch {}", path.display()))?;
+        Ok(Self {
+            _watcher: watcher,
+            events,
+        })
+    }
+}
+
+impl WatcherPort for FsEventWatcher {
+    fn wait_for_change(&self, timeout: Duration) -> Result<()> {
+        match self.events.recv_timeout(timeout) {
+            Ok(event) => {
+                event?;
+            }
+            Err(RecvTimeoutError::Timeout) => return Ok(()),
+            Err(RecvTimeoutError::Disconnected) => anyhow::bail!("stopped watching the yaks"),
+        }
+        while self.events.recv_timeout(SETTLE).is_ok() {}
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_wakes_when_a_file_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let watcher = FsEventWatcher::new(dir.path()).unwrap();
+        let path = dir.path().join("context.md");
+        let writer = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            std::fs::write(path, "changed").unwrap();
+        });
+
+        let started = Instant::now();
+        watcher.wait_for_change(Duration::from_secs(10)).unwrap();
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        writer.join().unwrap();
+    }
+}
@@ -0,0 +1,3 @@
+mod fs_events;
+
+pub use fs_events::FsEventWatcher;
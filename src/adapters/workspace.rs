@@ -0,0 +1,135 @@
+// Named workspaces - separate yak lists in one repository, each with its own
+// root and notes ref, picked with `yx --workspace <name>` or YX_WORKSPACE
+
+use crate::ports::ConfigPort;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming the workspace to work in
+pub const WORKSPACE_ENV: &str = "YX_WORKSPACE";
+
+/// Config section giving each workspace's root,
+/// e.g. `git config yaks.roots.personal ~/yaks/personal`
+pub const ROOTS_SECTION: &str = "yaks.roots";
+
+/// The workspace YX_WORKSPACE names, if any; `root` has already rejected
+/// names that aren't valid, so those are ignored here
+pub fn selected() -> Option<String> {
+    std::env::var(WORKSPACE_ENV)
+        .ok()
+        .filter(|name| validate(name).is_ok())
+}
+
+/// Workspace names become a config key, a ref component and a folder name,
+/// so they follow git's rule for config keys: letters, digits and `-`,
+/// starting with a letter
+pub fn validate(name: &str) -> Result<()> {
+    let safe = name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    if !safe {
+        anyhow::bail!(
+            "'{name}' isn't a valid workspace name: use letters, digits and '-', starting with a letter"
+        );
+    }
+    Ok(())
+}
+
+/// The ref a workspace's yaks are logged to: refs/notes/yaks, or
+/// refs/notes/yaks-<name> for a named one
+#[cfg_attr(not(any(feature = "libgit2", feature = "git-cli")), allow(dead_code))]
+pub fn notes_ref(workspace: Option<&str>) -> String {
+    format!("refs/notes/{}", ref_name(workspace))
+}
+
+/// Where `yx sync` fetches origin's copy of a workspace's yaks to
+#[cfg_attr(not(any(feature = "libgit2", feature = "git-cli")), allow(dead_code))]
+pub fn remote_ref(workspace: Option<&str>) -> String {
+    format!("refs/remotes/origin/{}", ref_name(workspace))
+}
+
+/// The folder in `.git` a workspace keeps its local state in: `yaks`, or
+/// `yaks/workspaces/<name>` so focus, read marks and stashes stay apart
+pub fn local_dir(common_dir: &Path, workspace: Option<&str>) -> PathBuf {
+    match workspace {
+        Some(name) => common_dir.join("yaks").join("workspaces").join(name),
+        None => common_dir.join("yaks"),
+    }
+}
+
+/// The root the workspace's yaks live in, from `yaks.roots.<name>`, with
+/// `~/` expanded and relative roots taken from `work_tree`
+pub fn root(config: &dyn ConfigPort, name: &str, work_tree: &Path) -> Result<PathBuf> {
+    validate(name)?;
+    let Some(value) = config
+        .get(&format!("{ROOTS_SECTION}.{name}"))
+        .filter(|value| !value.trim().is_empty())
+    else {
+        anyhow::bail!(
+            "no workspace named '{name}': define its root with `git config {ROOTS_SECTION}.{name} <path>`"
+        );
+    };
+    let value = value.trim();
+    Ok(match (value.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => PathBuf::from(home).join(rest),
+        _ => work_tree.join(value),
+    })
+}
+
+#[cfg_attr(not(any(feature = "libgit2", feature = "git-cli")), allow(dead_code))]
+fn ref_name(workspace: Option<&str>) -> String {
+    match workspace {
+        Some(name) => format!("yaks-{name}"),
+        None => "yaks".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct MockConfig(HashMap<String, String>);
+
+    impl ConfigPort for MockConfig {
+        fn get(&self, key: &str) -> Option<String> {
+            self.0.get(key).cloned()
+        }
+
+        fn get_all(&self, key: &str) -> Vec<String> {
+            self.get(key).into_iter().collect()
+        }
+    }
+
+    #[test]
+    fn test_named_workspaces_get_their_own_root_refs_and_state() {
+        let config = MockConfig(HashMap::from([(
+            "yaks.roots.personal".to_string(),
+            ".yaks-personal".to_string(),
+        )]));
+        let work_tree = Path::new("/src/shop");
+
+        assert_eq!(
+            root(&config, "personal", work_tree).unwrap(),
+            Path::new("/src/shop/.yaks-personal")
+        );
+        let error = root(&config, "team", work_tree).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("git config yaks.roots.team <path>"));
+        for name in ["../x", ".hidden", "a b", "a.b", "2fa", ""] {
+            let error = root(&config, name, work_tree).unwrap_err();
+            assert!(error.to_string().contains("isn't a valid workspace name"));
+        }
+
+        assert_eq!(notes_ref(None), "refs/notes/yaks");
+        assert_eq!(notes_ref(Some("personal")), "refs/notes/yaks-personal");
+        assert_eq!(
+            remote_ref(Some("personal")),
+            "refs/remotes/origin/yaks-personal"
+        );
+        assert_eq!(
+            local_dir(Path::new(".git"), Some("personal")),
+            Path::new(".git/yaks/workspaces/personal")
+        );
+    }
+}
@@ -1,93 +1,154 @@
 // AddYak use case - creates a new yak
 
+use super::read_tracking::ReadTracker;
+use super::{hints, naming};
+use crate::domain::checklist::parse_outline;
 use crate::domain::validate_yak_name;
-use crate::ports::{LogPort, OutputPort, StoragePort};
+use crate::ports::{ConfigPort, LocalStatePort, LogPort, OutputPort, StoragePort};
 use anyhow::Result;
 
 pub struct AddYak<'a> {
     storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
     log: &'a dyn LogPort,
+    config: Option<&'a dyn ConfigPort>,
+    parent: Option<String>,
+    context: Option<String>,
+    read_tracker: Option<ReadTracker<'a>>,
 }
 
 impl<'a> AddYak<'a> {
     pub fn new(
         storage: &'a dyn StoragePort,
-        _output: &'a dyn OutputPort,
+        output: &'a dyn OutputPort,
         log: &'a dyn LogPort,
     ) -> Self {
-        Self { storage, log }
+        Self {
+            storage,
+            output,
+            log,
+            config: None,
+            parent: None,
+            context: None,
+            read_tracker: None,
+        }
     }
 
-    pub fn execute(&self, name: &str) -> Result<()> {
-        // Validate yak name
-        validate_yak_name(name).map_err(|e| anyhow::anyhow!(e))?;
-
-        self.storage.create_yak(name)?;
-        self.log.log_command(&format!("add {name}"))?;
-        Ok(())
+    /// Apply the repo's name normalization rules and hint setting, if configured
+    pub fn with_config(mut self, config: &'a dyn ConfigPort) -> Self {
+        self.config = Some(config);
+        self
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::domain::Yak;
-    use std::cell::RefCell;
-
-    struct MockStorage {
-        created: RefCell<Vec<String>>,
+    /// Add the yak under this existing one (exact or fuzzy match)
+    pub fn with_parent(mut self, parent: String) -> Self {
+        self.parent = Some(parent);
+        self
     }
 
-    impl MockStorage {
-        fn new() -> Self {
-            Self {
-                created: RefCell::new(Vec::new()),
-            }
-        }
+    /// Write this context for the new yak
+    pub fn with_context(mut self, context: String) -> Self {
+        self.context = Some(context);
+        self
+    }
 
-        fn was_created(&self, name: &str) -> bool {
-            self.created.borrow().contains(&name.to_string())
-        }
+    /// Treat the written context as read, so it isn't badged as updated
+    pub fn with_read_tracking(mut self, state: &'a dyn LocalStatePort) -> Self {
+        self.read_tracker = Some(ReadTracker::new(state));
+        self
     }
 
-    impl StoragePort for MockStorage {
-        fn create_yak(&self, name: &str) -> Result<()> {
-            self.created.borrow_mut().push(name.to_string());
-            Ok(())
+    pub fn execute(&self, name: &str) -> Result<()> {
+        let rules = naming::configured_rules(self.config)?;
+        let (name, title) = naming::normalize(name, &rules);
+        let name = match self.resolve_parent()? {
+            Some(parent) => format!("{parent}/{name}"),
+            None => name,
+        };
+        // Creating it again would wipe its context
+        if self.storage.get_yak(&name).is_ok() {
+            self.output.warn(&format!("'{name}' already exists"));
+        } else {
+            self.create(&name, title.as_deref())?;
         }
 
-        fn get_yak(&self, _name: &str) -> Result<Yak> {
-            unimplemented!()
+        if let Some(context) = self.context.as_deref().filter(|text| !text.is_empty()) {
+            self.storage.write_context(&name, context)?;
+            self.log.log_command(&format!("context {name}"))?;
+            if let Some(tracker) = &self.read_tracker {
+                tracker.mark_seen(&name, context)?;
+            }
         }
 
-        fn list_yaks(&self) -> Result<Vec<Yak>> {
-            unimplemented!()
+        if hints::hints_enabled(self.config) {
+            self.output.hint(&format!(
+                "set context with `yx context {name}`; mark done with `yx done {name}`"
+            ));
         }
+        Ok(())
+    }
 
-        fn mark_done(&self, _name: &str, _done: bool) -> Result<()> {
-            unimplemented!()
-        }
+    /// Add one yak per non-blank line of `text`, nesting indented lines under
+    /// the line above. Yaks that already exist are left alone, and lines that
+    /// can't be added are reported and skipped.
+    pub fn execute_lines(&self, text: &str) -> Result<()> {
+        let rules = naming::configured_rules(self.config)?;
+        let parent = self.resolve_parent()?;
 
-        fn delete_yak(&self, _name: &str) -> Result<()> {
-            unimplemented!()
-        }
+        let (mut added, mut existing, mut failed) = (0, 0, 0);
+        for line in parse_outline(text) {
+            let (name, title) = naming::normalize(&line, &rules);
+            let name = match &parent {
+                Some(parent) => format!("{parent}/{name}"),
+                None => name,
+            };
 
-        fn rename_yak(&self, _from: &str, _to: &str) -> Result<()> {
-            unimplemented!()
+            if self.storage.get_yak(&name).is_ok() {
+                existing += 1;
+                continue;
+            }
+            match self.create(&name, title.as_deref()) {
+                Ok(()) => added += 1,
+                Err(e) => {
+                    self.output.warn(&format!("Skipped '{name}': {e}"));
+                    failed += 1;
+                }
+            }
         }
 
-        fn read_context(&self, _name: &str) -> Result<String> {
-            unimplemented!()
-        }
+        self.output.info(&format!(
+            "Added {added} yak(s) ({existing} already existed, {failed} skipped)"
+        ));
+        Ok(())
+    }
 
-        fn write_context(&self, _name: &str, _text: &str) -> Result<()> {
-            unimplemented!()
-        }
+    fn resolve_parent(&self) -> Result<Option<String>> {
+        self.parent
+            .as_deref()
+            .map(|parent| self.storage.find_yak(parent))
+            .transpose()
+    }
 
-        fn find_yak(&self, _name: &str) -> Result<String> {
-            unimplemented!()
+    fn create(&self, name: &str, title: Option<&str>) -> Result<()> {
+        // Validate yak name
+        validate_yak_name(name).map_err(|e| anyhow::anyhow!(e))?;
+
+        self.storage.create_yak(name)?;
+        if let Some(title) = title {
+            self.storage.write_field(name, "title", Some(title))?;
+            self.output
+                .success(&format!("Added '{name}' (titled '{title}')"));
         }
+        self.log.log_command(&format!("add {name}"))
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::ports::LogEntry;
+    use std::cell::RefCell;
 
     struct MockOutput {
         messages: RefCell<Vec<String>>,
@@ -103,6 +164,10 @@ mod tests {
         fn last_message(&self) -> Option<String> {
             self.messages.borrow().last().cloned()
         }
+
+        fn get_messages(&self) -> Vec<String> {
+            self.messages.borrow().clone()
+        }
     }
 
     impl OutputPort for MockOutput {
@@ -121,6 +186,18 @@ mod tests {
                 .borrow_mut()
                 .push(format!("INFO: {}", message));
         }
+
+        fn warn(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("WARN: {}", message));
+        }
+
+        fn hint(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("HINT: {}", message));
+        }
     }
 
     struct MockLog;
@@ -129,16 +206,169 @@ mod tests {
         fn log_command(&self, _command: &str) -> Result<()> {
             Ok(())
         }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    fn names(storage: &InMemoryStorage) -> Vec<String> {
+        storage
+            .list_yaks()
+            .unwrap()
+            .into_iter()
+            .map(|yak| yak.name)
+            .collect()
     }
 
     #[test]
     fn test_add_yak_creates_yak() {
-        let storage = MockStorage::new();
+        let storage = InMemoryStorage::new();
         let output = MockOutput::new();
         let use_case = AddYak::new(&storage, &output, &MockLog);
 
         use_case.execute("test-yak").unwrap();
 
-        assert!(storage.was_created("test-yak"));
+        assert!(storage.get_yak("test-yak").is_ok());
+    }
+
+    #[test]
+    fn test_add_existing_yak_warns_and_leaves_it() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        let use_case = AddYak::new(&storage, &output, &MockLog);
+
+        use_case.execute("docs").unwrap();
+        use_case.execute("docs").unwrap();
+
+        assert_eq!(names(&storage), vec!["docs"]);
+        assert!(output
+            .messages
+            .borrow()
+            .contains(&"WARN: 'docs' already exists".to_string()));
+    }
+
+    struct MockConfig(&'static str);
+
+    impl ConfigPort for MockConfig {
+        fn get(&self, key: &str) -> Option<String> {
+            match key {
+                "yaks.names.normalize" => Some(self.0.to_string()),
+                "yaks.hints" => Some("false".to_string()),
+                _ => None,
+            }
+        }
+
+        fn get_all(&self, _key: &str) -> Vec<String> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_add_yak_normalizes_name_and_keeps_title() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        let config = MockConfig("all");
+        let use_case = AddYak::new(&storage, &output, &MockLog).with_config(&config);
+
+        use_case.execute("Fix the Login?").unwrap();
+
+        assert!(storage.get_yak("fix-the-login").is_ok());
+        assert_eq!(
+            storage.read_field("fix-the-login", "title").unwrap(),
+            Some("Fix the Login?".to_string())
+        );
+        assert_eq!(
+            output.last_message(),
+            Some("Added 'fix-the-login' (titled 'Fix the Login?')".to_string())
+        );
+    }
+
+    #[test]
+    fn test_add_yak_without_rules_keeps_name() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        let config = MockConfig("");
+        let use_case = AddYak::new(&storage, &output, &MockLog).with_config(&config);
+
+        use_case.execute("Fix Login").unwrap();
+
+        assert!(storage.get_yak("Fix Login").is_ok());
+        assert_eq!(storage.read_field("Fix Login", "title").unwrap(), None);
+    }
+
+    #[test]
+    fn test_add_yak_hints_at_next_steps() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        let use_case = AddYak::new(&storage, &output, &MockLog);
+
+        use_case.execute("docs").unwrap();
+
+        assert_eq!(
+            output.last_message(),
+            Some(
+                "HINT: set context with `yx context docs`; mark done with `yx done docs`"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_add_yak_under_fuzzy_matched_parent() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("backend/api").unwrap();
+        let output = MockOutput::new();
+        let use_case = AddYak::new(&storage, &output, &MockLog).with_parent("api".to_string());
+
+        use_case.execute("write tests").unwrap();
+
+        assert!(storage.get_yak("backend/api/write tests").is_ok());
+        assert!(use_case
+            .with_parent("web".to_string())
+            .execute("x")
+            .is_err());
+    }
+
+    #[test]
+    fn test_add_yak_writes_context() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        let use_case = AddYak::new(&storage, &output, &MockLog)
+            .with_context("Steps to reproduce\n".to_string());
+
+        use_case.execute("bug").unwrap();
+
+        assert_eq!(storage.read_context("bug").unwrap(), "Steps to reproduce\n");
+
+        // Nothing piped in means no context to write
+        let storage = InMemoryStorage::new();
+        let use_case = AddYak::new(&storage, &output, &MockLog).with_context(String::new());
+        use_case.execute("bug").unwrap();
+        assert_eq!(storage.read_context("bug").unwrap(), "");
+    }
+
+    #[test]
+    fn test_add_yak_lines_builds_hierarchy_and_summarizes() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("release").unwrap();
+        let output = MockOutput::new();
+        let use_case = AddYak::new(&storage, &output, &MockLog);
+
+        use_case
+            .execute_lines("release\n  - write notes\n\nfix: login\ndocs\n")
+            .unwrap();
+
+        assert_eq!(
+            names(&storage),
+            vec!["docs", "release", "release/write notes"]
+        );
+        assert_eq!(
+            output.get_messages(),
+            vec![
+                "WARN: Skipped 'fix: login': Invalid yak name: contains forbidden characters (\\ : * ? | < > \")",
+                "INFO: Added 2 yak(s) (1 already existed, 1 skipped)",
+            ]
+        );
     }
 }
@@ -0,0 +1,513 @@
+// ApplyRetention use case - prunes, archives and expires yaks according to configured retention policies
+
+use crate::domain::{graph, Yak};
+use crate::ports::{ArchivePort, ConfigPort, LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+
+/// Config key holding how many days done yaks are kept before being pruned
+pub const DONE_RETENTION_KEY: &str = "yaks.retention.doneDays";
+
+/// Config key holding how many days done yaks stay in the list before being archived
+pub const ARCHIVE_RETENTION_KEY: &str = "yaks.retention.archiveDays";
+
+/// Config key holding how many days archived yaks are kept before being deleted
+pub const ARCHIVED_RETENTION_KEY: &str = "yaks.retention.archivedDays";
+
+pub struct ApplyRetention<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+    config: &'a dyn ConfigPort,
+    archive: &'a dyn ArchivePort,
+    archived: &'a dyn StoragePort,
+}
+
+impl<'a> ApplyRetention<'a> {
+    /// `archive` moves yaks out of `storage`, and `archived` is the storage
+    /// holding the yaks it moved
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+        config: &'a dyn ConfigPort,
+        archive: &'a dyn ArchivePort,
+        archived: &'a dyn StoragePort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            log,
+            config,
+            archive,
+            archived,
+        }
+    }
+
+    /// Apply every configured policy, printing a summary line for each one that changed something
+    pub fn execute(&self) -> Result<()> {
+        if let Some(days) = self.policy_days(DONE_RETENTION_KEY)? {
+            let pruned = self.prune_done(days)?;
+            if pruned > 0 {
+                self.output.info(&format!(
+                    "Retention: pruned {pruned} done {} older than {days} days",
+                    noun(pruned)
+                ));
+            }
+        }
+
+        if let Some(days) = self.policy_days(ARCHIVE_RETENTION_KEY)? {
+            let archived = self.archive_done(days)?;
+            if archived > 0 {
+                self.output.info(&format!(
+                    "Retention: archived {archived} done {} older than {days} days",
+                    noun(archived)
+                ));
+            }
+        }
+
+        if let Some(days) = self.policy_days(ARCHIVED_RETENTION_KEY)? {
+            let deleted = self.expire_archived(days)?;
+            if deleted > 0 {
+                self.output.info(&format!(
+                    "Retention: deleted {deleted} archived {} untouched for {days} days",
+                    noun(deleted)
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Days configured under `key`, or None when that policy isn't set
+    fn policy_days(&self, key: &str) -> Result<Option<i64>> {
+        let Some(value) = self.config.get(key) else {
+            return Ok(None);
+        };
+        let days = value
+            .trim()
+            .parse()
+            .map_err(|_| anyhow::anyhow!("{key} must be a whole number of days, got '{value}'"))?;
+        Ok(Some(days))
+    }
+
+    /// Delete done yaks completed more than `days` ago
+    fn prune_done(&self, days: i64) -> Result<usize> {
+        let mut pruned = 0;
+        for name in done_before(&self.storage.list_yaks()?, days) {
+            // A parent pruned earlier in the loop takes its children with it
+            if self.storage.get_yak(&name).is_err() {
+                continue;
+            }
+            self.storage.delete_yak(&name)?;
+            self.log.log_command(&format!("rm {name}"))?;
+            pruned += 1;
+        }
+        Ok(pruned)
+    }
+
+    /// Move done yaks completed more than `days` ago into the archive
+    fn archive_done(&self, days: i64) -> Result<usize> {
+        let mut archived = 0;
+        for name in done_before(&self.storage.list_yaks()?, days) {
+            // A parent archived earlier in the loop takes its children with it
+            if self.storage.get_yak(&name).is_err() {
+                continue;
+            }
+            self.archive.archive(&name)?;
+            self.log.log_command(&format!("archive {name}"))?;
+            archived += 1;
+        }
+        Ok(archived)
+    }
+
+    /// Delete archived yaks whose whole subtree hasn't changed in `days` days
+    ///
+    /// Archiving keeps a yak's files as they were, so its last change is the
+    /// closest record of how long it has sat in the archive.
+    fn expire_archived(&self, days: i64) -> Result<usize> {
+        let cutoff = Utc::now() - Duration::days(days);
+        let yaks = self.archived.list_yaks()?;
+        let expired: Vec<String> = yaks
+            .iter()
+            .filter(|yak| last_change(yak).is_some_and(|at| at < cutoff))
+            .filter(|yak| {
+                graph::descendants_of(&yaks, &yak.name)
+                    .iter()
+                    .all(|descendant| last_change(descendant).is_some_and(|at| at < cutoff))
+            })
+            .map(|yak| yak.name.clone())
+            .collect();
+
+        let mut deleted = 0;
+        for name in &expired {
+            // A parent deleted earlier in the loop takes its children with it
+            if self.archived.get_yak(name).is_err() {
+                continue;
+            }
+            self.archived.delete_yak(name)?;
+            self.log.log_command(&format!("rm --archived {name}"))?;
+            deleted += 1;
+        }
+        Ok(deleted)
+    }
+}
+
+/// Names of done yaks completed more than `days` ago, leaving out any with open
+/// descendants so open work is never taken along
+fn done_before(yaks: &[Yak], days: i64) -> Vec<String> {
+    let cutoff = Utc::now() - Duration::days(days);
+    yaks.iter()
+        .filter(|yak| yak.done && yak.done_at.is_some_and(|done_at| done_at < cutoff))
+        .filter(|yak| {
+            graph::descendants_of(yaks, &yak.name)
+                .iter()
+                .all(|descendant| descendant.done)
+        })
+        .map(|yak| yak.name.clone())
+        .collect()
+}
+
+/// When the yak last changed, falling back to when it was completed or created
+fn last_change(yak: &Yak) -> Option<DateTime<Utc>> {
+    yak.modified_at.or(yak.done_at).or(yak.created_at)
+}
+
+fn noun(count: usize) -> &'static str {
+    if count == 1 {
+        "yak"
+    } else {
+        "yaks"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use crate::ports::LogEntry;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    fn add_yak(storage: &InMemoryStorage, name: &str, done_days_ago: Option<i64>) {
+        storage.add_yak(Yak {
+            name: name.to_string(),
+            done: done_days_ago.is_some(),
+            done_at: done_days_ago.map(|days| Utc::now() - Duration::days(days)),
+            ..Default::default()
+        });
+    }
+
+    fn add_archived(storage: &InMemoryStorage, name: &str, changed_days_ago: i64) {
+        storage.archived().add_yak(Yak {
+            name: name.to_string(),
+            modified_at: Some(Utc::now() - Duration::days(changed_days_ago)),
+            ..Default::default()
+        });
+    }
+
+    fn yak_exists(storage: &InMemoryStorage, name: &str) -> bool {
+        storage.get_yak(name).is_ok()
+    }
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl MockOutput {
+        fn new() -> Self {
+            Self {
+                messages: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn get_messages(&self) -> Vec<String> {
+            self.messages.borrow().clone()
+        }
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockLog;
+
+    impl LogPort for MockLog {
+        fn log_command(&self, _command: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    struct MockConfig {
+        values: HashMap<String, String>,
+    }
+
+    impl MockConfig {
+        fn with(key: &str, value: &str) -> Self {
+            Self {
+                values: HashMap::from([(key.to_string(), value.to_string())]),
+            }
+        }
+
+        fn and(mut self, key: &str, value: &str) -> Self {
+            self.values.insert(key.to_string(), value.to_string());
+            self
+        }
+    }
+
+    impl ConfigPort for MockConfig {
+        fn get(&self, key: &str) -> Option<String> {
+            self.values.get(key).cloned()
+        }
+
+        fn get_all(&self, _key: &str) -> Vec<String> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_retention_prunes_old_done_yaks() {
+        let storage = InMemoryStorage::new();
+        add_yak(&storage, "old", Some(30));
+        add_yak(&storage, "recent", Some(1));
+        add_yak(&storage, "open", None);
+        let output = MockOutput::new();
+        let config = MockConfig::with(DONE_RETENTION_KEY, "14");
+        let use_case = ApplyRetention::new(
+            &storage,
+            &output,
+            &MockLog,
+            &config,
+            &storage,
+            storage.archived(),
+        );
+
+        use_case.execute().unwrap();
+
+        assert!(!yak_exists(&storage, "old"));
+        assert!(yak_exists(&storage, "recent"));
+        assert!(yak_exists(&storage, "open"));
+        assert_eq!(
+            output.get_messages(),
+            vec!["Retention: pruned 1 done yak older than 14 days"]
+        );
+    }
+
+    #[test]
+    fn test_retention_keeps_yaks_with_open_descendants() {
+        let storage = InMemoryStorage::new();
+        add_yak(&storage, "parent", Some(30));
+        add_yak(&storage, "parent/child", None);
+        let output = MockOutput::new();
+        let config = MockConfig::with(DONE_RETENTION_KEY, "14");
+        let use_case = ApplyRetention::new(
+            &storage,
+            &output,
+            &MockLog,
+            &config,
+            &storage,
+            storage.archived(),
+        );
+
+        use_case.execute().unwrap();
+
+        assert!(yak_exists(&storage, "parent"));
+        assert!(output.get_messages().is_empty());
+    }
+
+    #[test]
+    fn test_retention_does_nothing_without_policy() {
+        let storage = InMemoryStorage::new();
+        add_yak(&storage, "old", Some(300));
+        let output = MockOutput::new();
+        let config = MockConfig {
+            values: HashMap::new(),
+        };
+        let use_case = ApplyRetention::new(
+            &storage,
+            &output,
+            &MockLog,
+            &config,
+            &storage,
+            storage.archived(),
+        );
+
+        use_case.execute().unwrap();
+
+        assert!(yak_exists(&storage, "old"));
+    }
+
+    #[test]
+    fn test_retention_rejects_invalid_policy() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        let config = MockConfig::with(DONE_RETENTION_KEY, "two weeks");
+        let use_case = ApplyRetention::new(
+            &storage,
+            &output,
+            &MockLog,
+            &config,
+            &storage,
+            storage.archived(),
+        );
+
+        assert!(use_case.execute().is_err());
+    }
+
+    #[test]
+    fn test_retention_archives_old_done_yaks() {
+        let storage = InMemoryStorage::new();
+        add_yak(&storage, "old", Some(30));
+        add_yak(&storage, "old/step", Some(31));
+        add_yak(&storage, "recent", Some(1));
+        add_yak(&storage, "open", None);
+        let output = MockOutput::new();
+        let config = MockConfig::with(ARCHIVE_RETENTION_KEY, "14");
+        let use_case = ApplyRetention::new(
+            &storage,
+            &output,
+            &MockLog,
+            &config,
+            &storage,
+            storage.archived(),
+        );
+
+        use_case.execute().unwrap();
+
+        assert!(!yak_exists(&storage, "old"));
+        assert!(yak_exists(storage.archived(), "old"));
+        assert!(yak_exists(storage.archived(), "old/step"));
+        assert!(yak_exists(&storage, "recent"));
+        assert!(yak_exists(&storage, "open"));
+        assert_eq!(
+            output.get_messages(),
+            vec!["Retention: archived 1 done yak older than 14 days"]
+        );
+    }
+
+    #[test]
+    fn test_retention_does_not_archive_yaks_with_open_descendants() {
+        let storage = InMemoryStorage::new();
+        add_yak(&storage, "parent", Some(30));
+        add_yak(&storage, "parent/child", None);
+        let output = MockOutput::new();
+        let config = MockConfig::with(ARCHIVE_RETENTION_KEY, "14");
+        let use_case = ApplyRetention::new(
+            &storage,
+            &output,
+            &MockLog,
+            &config,
+            &storage,
+            storage.archived(),
+        );
+
+        use_case.execute().unwrap();
+
+        assert!(yak_exists(&storage, "parent"));
+        assert!(!yak_exists(storage.archived(), "parent"));
+        assert!(output.get_messages().is_empty());
+    }
+
+    #[test]
+    fn test_retention_deletes_expired_archived_yaks() {
+        let storage = InMemoryStorage::new();
+        add_archived(&storage, "ancient", 120);
+        add_archived(&storage, "fresh", 10);
+        add_archived(&storage, "mixed", 120);
+        add_archived(&storage, "mixed/touched", 5);
+        let output = MockOutput::new();
+        let config = MockConfig::with(ARCHIVED_RETENTION_KEY, "90");
+        let use_case = ApplyRetention::new(
+            &storage,
+            &output,
+            &MockLog,
+            &config,
+            &storage,
+            storage.archived(),
+        );
+
+        use_case.execute().unwrap();
+
+        assert!(!yak_exists(storage.archived(), "ancient"));
+        assert!(yak_exists(storage.archived(), "fresh"));
+        assert!(yak_exists(storage.archived(), "mixed"));
+        assert!(yak_exists(storage.archived(), "mixed/touched"));
+        assert_eq!(
+            output.get_messages(),
+            vec!["Retention: deleted 1 archived yak untouched for 90 days"]
+        );
+    }
+
+    #[test]
+    fn test_retention_applies_every_configured_policy() {
+        let storage = InMemoryStorage::new();
+        add_yak(&storage, "ancient", Some(400));
+        add_yak(&storage, "old", Some(30));
+        add_archived(&storage, "expired", 120);
+        let output = MockOutput::new();
+        let config = MockConfig::with(DONE_RETENTION_KEY, "365")
+            .and(ARCHIVE_RETENTION_KEY, "14")
+            .and(ARCHIVED_RETENTION_KEY, "90");
+        let use_case = ApplyRetention::new(
+            &storage,
+            &output,
+            &MockLog,
+            &config,
+            &storage,
+            storage.archived(),
+        );
+
+        use_case.execute().unwrap();
+
+        assert!(!yak_exists(&storage, "ancient"));
+        assert!(!yak_exists(storage.archived(), "ancient"));
+        assert!(yak_exists(storage.archived(), "old"));
+        assert!(!yak_exists(storage.archived(), "expired"));
+        assert_eq!(
+            output.get_messages(),
+            vec![
+                "Retention: pruned 1 done yak older than 365 days",
+                "Retention: archived 1 done yak older than 14 days",
+                "Retention: deleted 1 archived yak untouched for 90 days",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_retention_rejects_invalid_archive_policy() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        let config = MockConfig::with(ARCHIVED_RETENTION_KEY, "forever");
+        let use_case = ApplyRetention::new(
+            &storage,
+            &output,
+            &MockLog,
+            &config,
+            &storage,
+            storage.archived(),
+        );
+
+        assert!(use_case.execute().is_err());
+    }
+}
@@ -0,0 +1,96 @@
+// ArchiveYak use case - moves a yak out of the active list without deleting it
+
+use crate::ports::{ArchivePort, LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+
+pub struct ArchiveYak<'a> {
+    storage: &'a dyn StoragePort,
+    log: &'a dyn LogPort,
+    archive: &'a dyn ArchivePort,
+}
+
+impl<'a> ArchiveYak<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        _output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+        archive: &'a dyn ArchivePort,
+    ) -> Self {
+        Self {
+            storage,
+            log,
+            archive,
+        }
+    }
+
+    pub fn execute(&self, name: &str) -> Result<()> {
+        // Resolve yak name (exact or fuzzy match)
+        let resolved_name = self.storage.find_yak(name)?;
+
+        self.archive.archive(&resolved_name)?;
+        self.log.log_command(&format!("archive {resolved_name}"))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use crate::ports::LogEntry;
+    use std::cell::RefCell;
+
+    struct MockOutput;
+
+    impl OutputPort for MockOutput {
+        fn success(&self, _message: &str) {}
+        fn error(&self, _message: &str) {}
+        fn info(&self, _message: &str) {}
+
+        fn warn(&self, _message: &str) {}
+    }
+
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_archive_yak_resolves_name_and_logs() {
+        let storage = InMemoryStorage::with_yaks([Yak::new("fix login bug".to_string())]);
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = ArchiveYak::new(&storage, &MockOutput, &log, &storage);
+
+        use_case.execute("login").unwrap();
+
+        assert!(storage.find_yak("fix login bug").is_err());
+        assert!(storage.archived().get_yak("fix login bug").is_ok());
+        assert_eq!(*log.commands.borrow(), vec!["archive fix login bug"]);
+    }
+
+    #[test]
+    fn test_archive_unknown_yak_fails() {
+        let storage = InMemoryStorage::new();
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = ArchiveYak::new(&storage, &MockOutput, &log, &storage);
+
+        assert!(use_case.execute("nope").is_err());
+        assert!(log.commands.borrow().is_empty());
+    }
+}
@@ -0,0 +1,162 @@
+// BlameYak use case - who last changed a yak, when, and which of its files
+
+use super::TimeStyle;
+use crate::ports::{ChangeKind, HistoryPort, OutputPort, StoragePort};
+use anyhow::Result;
+
+pub struct BlameYak<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    history: &'a dyn HistoryPort,
+    time_style: TimeStyle,
+}
+
+impl<'a> BlameYak<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        history: &'a dyn HistoryPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            history,
+            time_style: TimeStyle::default(),
+        }
+    }
+
+    /// Show times relative to now ("2h ago") or as local date and time
+    pub fn with_time_style(mut self, time_style: TimeStyle) -> Self {
+        self.time_style = time_style;
+        self
+    }
+
+    pub fn execute(&self, name: &str) -> Result<()> {
+        // Removed yaks still have history, so fall back to the name as given
+        let name = self
+            .storage
+            .find_yak(name)
+            .unwrap_or_else(|_| name.to_string());
+
+        let Some(revision) = self.history.revisions(Some(&name))?.into_iter().next() else {
+            anyhow::bail!("no history for yak '{name}'");
+        };
+
+        self.output.info(&format!(
+            "Last changed by {} {} in {} ({})",
+            revision.author,
+            match self.time_style {
+                TimeStyle::Relative => self.time_style.render(revision.time),
+                TimeStyle::Absolute => format!("on {}", self.time_style.render(revision.time)),
+            },
+            &revision.id[..revision.id.len().min(7)],
+            revision.command
+        ));
+
+        for change in self.history.changed_files(&revision.id, &name)? {
+            let kind = match change.kind {
+                ChangeKind::Added => "added",
+                ChangeKind::Modified => "modified",
+                ChangeKind::Deleted => "deleted",
+            };
+            self.output.info(&format!("  {kind:<8} {}", change.path));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use crate::ports::{FileChange, FileVersion, Revision, YakSnapshot};
+    use chrono::{TimeZone, Utc};
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, _message: &str) {}
+        fn error(&self, _message: &str) {}
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockHistory;
+
+    impl HistoryPort for MockHistory {
+        fn revisions(&self, name: Option<&str>) -> Result<Vec<Revision>> {
+            if name != Some("app") {
+                return Ok(Vec::new());
+            }
+            Ok(vec![Revision {
+                id: "0123456789abcdef".to_string(),
+                command: "done app".to_string(),
+                author: "ann".to_string(),
+                time: Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap(),
+            }])
+        }
+
+        fn changed_files(&self, revision: &str, name: &str) -> Result<Vec<FileChange>> {
+            assert_eq!((revision, name), ("0123456789abcdef", "app"));
+            Ok(vec![
+                FileChange {
+                    path: "done".to_string(),
+                    kind: ChangeKind::Added,
+                },
+                FileChange {
+                    path: "context.md".to_string(),
+                    kind: ChangeKind::Modified,
+                },
+            ])
+        }
+
+        fn last_snapshot(&self, _name: &str) -> Result<Option<(Revision, Vec<YakSnapshot>)>> {
+            unimplemented!()
+        }
+
+        fn file_versions(&self, _name: &str, _file: &str) -> Result<Vec<FileVersion>> {
+            unimplemented!()
+        }
+
+        fn remote_file_version(&self, _name: &str, _file: &str) -> Result<Option<FileVersion>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_blame_shows_last_revision_and_files() {
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let storage = InMemoryStorage::with_yaks([Yak::new("app".to_string())]);
+        let use_case = BlameYak::new(&storage, &output, &MockHistory);
+
+        use_case.execute("ap").unwrap();
+
+        let messages = output.messages.borrow();
+        assert!(messages[0].starts_with("Last changed by ann on 2026-10-1"));
+        assert!(messages[0].ends_with(" in 0123456 (done app)"));
+        assert_eq!(messages[1..], ["  added    done", "  modified context.md"]);
+    }
+
+    #[test]
+    fn test_blame_without_history_is_an_error() {
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let storage = InMemoryStorage::with_yaks([Yak::new("app".to_string())]);
+        let use_case = BlameYak::new(&storage, &output, &MockHistory);
+
+        assert!(use_case.execute("nope").is_err());
+    }
+}
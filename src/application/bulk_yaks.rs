@@ -0,0 +1,482 @@
+// BulkYaks use case - applies a script of yx commands as one all-or-nothing operation
+
+use super::transaction::{self, DeferredLog};
+use super::{AddYak, DoneYak, MoveYak, PrioritizeYak, RemoveYak, ScheduleYak, SetState};
+use crate::ports::{ConfigPort, LogPort, OutputPort, StashPort, StoragePort, TrashPort};
+use anyhow::Result;
+
+/// One line of a bulk script
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BulkCommand {
+    Add(String),
+    Done {
+        names: Vec<String>,
+        undo: bool,
+    },
+    Remove {
+        name: String,
+        recursive: bool,
+    },
+    Move {
+        from: String,
+        to: String,
+    },
+    State {
+        name: String,
+        state: String,
+    },
+    Priority {
+        name: String,
+        priority: Option<String>,
+    },
+    Due {
+        name: String,
+        date: Option<String>,
+    },
+}
+
+pub struct BulkYaks<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+    stash: &'a dyn StashPort,
+    config: Option<&'a dyn ConfigPort>,
+    trash: Option<(&'a dyn TrashPort, &'a dyn ConfigPort)>,
+}
+
+impl<'a> BulkYaks<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+        stash: &'a dyn StashPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            log,
+            stash,
+            config: None,
+            trash: None,
+        }
+    }
+
+    /// Apply the repo's name normalization rules and hint setting, if configured
+    pub fn with_config(mut self, config: &'a dyn ConfigPort) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Move yaks that `rm` lines remove to the trash, as `yx rm` does
+    pub fn with_trash(mut self, trash: &'a dyn TrashPort, config: &'a dyn ConfigPort) -> Self {
+        self.trash = Some((trash, config));
+        self
+    }
+
+    /// Run each command in `script`, read from `source` (a file path, or "-" for stdin)
+    /// The whole script is parsed before anything changes; if a command fails,
+    /// every yak is put back as it was and nothing is logged.
+    pub fn execute(&self, script: &str, source: &str) -> Result<()> {
+        let mut commands = Vec::new();
+        for (index, line) in script.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let command = split_words(line)
+                .and_then(|words| parse_command(&words))
+                .map_err(|e| anyhow::anyhow!("line {}: {e}", index + 1))?;
+            commands.push((index + 1, line, command));
+        }
+        if commands.is_empty() {
+            anyhow::bail!("no commands in {source}");
+        }
+
+        let reason = format!("bulk {source}");
+        let mut names: Vec<String> = self
+            .storage
+            .list_yaks()?
+            .into_iter()
+            .map(|yak| yak.name)
+            .collect();
+        names.sort();
+        self.stash.push(&reason, &names)?;
+
+        // Logged once as the whole batch, and put back if any command fails
+        let deferred = DeferredLog::default();
+        let applied = transaction::apply(self.storage, self.log, &reason, || {
+            for (number, line, command) in &commands {
+                self.run(command, &deferred)
+                    .map_err(|e| anyhow::anyhow!("line {number}: `{line}` failed: {e}"))?;
+            }
+            Ok(())
+        });
+        if let Err(e) = applied {
+            // Nothing changed, so there is nothing for the snapshot to undo
+            self.stash.discard()?;
+            anyhow::bail!("{e} - no yaks were changed");
+        }
+
+        self.output.success(&format!(
+            "Applied {} command(s) from {source}; `yx stash pop` undoes them",
+            commands.len()
+        ));
+        Ok(())
+    }
+
+    fn run(&self, command: &BulkCommand, log: &DeferredLog) -> Result<()> {
+        let (storage, output) = (self.storage, self.output);
+        match command {
+            BulkCommand::Add(name) => {
+                let mut use_case = AddYak::new(storage, output, log);
+                if let Some(config) = self.config {
+                    use_case = use_case.with_config(config);
+                }
+                use_case.execute(name)
+            }
+            BulkCommand::Done { names, undo } => {
+                let names: Vec<&str> = names.iter().map(String::as_str).collect();
+                DoneYak::new(storage, output, log).execute(&names, *undo, false)
+            }
+            BulkCommand::Remove { name, recursive } => {
+                let mut use_case = RemoveYak::new(storage, output, log).with_force(true);
+                if let Some((trash, config)) = self.trash {
+                    use_case = use_case.with_trash(trash, config);
+                }
+                use_case.execute(name, *recursive)
+            }
+            BulkCommand::Move { from, to } => {
+                let mut use_case = MoveYak::new(storage, output, log);
+                if let Some(config) = self.config {
+                    use_case = use_case.with_config(config);
+                }
+                use_case.execute(from, to)
+            }
+            BulkCommand::State { name, state } => {
+                SetState::new(storage, output, log).execute(name, state)
+            }
+            BulkCommand::Priority { name, priority } => {
+                PrioritizeYak::new(storage, output, log).execute(name, priority.as_deref())
+            }
+            BulkCommand::Due { name, date } => {
+                ScheduleYak::new(storage, output, log).execute(name, date.as_deref())
+            }
+        }
+    }
+}
+
+/// Split a line into words like a shell would: whitespace separates words,
+/// and single or double quotes keep spaces (and `#`) inside one word
+fn split_words(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') => {
+                let escaped = chars.next().ok_or("trailing backslash")?;
+                word.get_or_insert_with(String::new).push(escaped);
+            }
+            (Some(_), c) => word.get_or_insert_with(String::new).push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                word.get_or_insert_with(String::new);
+            }
+            (None, '\\') => {
+                let escaped = chars.next().ok_or("trailing backslash")?;
+                word.get_or_insert_with(String::new).push(escaped);
+            }
+            (None, c) if c.is_whitespace() => words.extend(word.take()),
+            (None, c) => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if let Some(q) = quote {
+        return Err(format!("unclosed {q}"));
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+/// Parse the words of one line, accepting the commands (and aliases) that
+/// change yaks the way `yx` itself does
+fn parse_command(words: &[String]) -> Result<BulkCommand, String> {
+    let Some((verb, args)) = words.split_first() else {
+        return Err("empty command".to_string());
+    };
+    let (flags, rest): (Vec<&String>, Vec<&String>) = args
+        .iter()
+        .partition(|arg| arg.starts_with("--") || *arg == "-r");
+    let has_flag = |names: &[&str]| flags.iter().any(|flag| names.contains(&flag.as_str()));
+    let allowed: &[&str] = match verb.as_str() {
+        "done" | "finish" => &["--undo"],
+        "rm" | "remove" => &["--recursive", "-r", "--force"],
+        _ => &[],
+    };
+    if let Some(flag) = flags.iter().find(|flag| !allowed.contains(&flag.as_str())) {
+        return Err(format!("`{verb}` doesn't take {flag} in a bulk script"));
+    }
+
+    let joined = || {
+        let name = rest
+            .iter()
+            .map(|word| word.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if name.is_empty() {
+            Err(format!("`{verb}` needs a yak name"))
+        } else {
+            Ok(name)
+        }
+    };
+    let name_and = |what: &str| match rest.as_slice() {
+        [name] => Ok((name.to_string(), None)),
+        [name, value] => Ok((name.to_string(), Some(value.to_string()))),
+        _ => Err(format!(
+            "usage: {verb} <name> [{what}] (quote names with spaces)"
+        )),
+    };
+
+    Ok(match verb.as_str() {
+        "add" => BulkCommand::Add(joined()?),
+        "done" | "finish" => BulkCommand::Done {
+            names: rest.iter().map(|word| word.to_string()).collect(),
+            undo: has_flag(&["--undo"]),
+        },
+        "rm" | "remove" => BulkCommand::Remove {
+            name: joined()?,
+            recursive: has_flag(&["--recursive", "-r"]),
+        },
+        "mv" | "move" => match rest.as_slice() {
+            [from, to] => BulkCommand::Move {
+                from: from.to_string(),
+                to: to.to_string(),
+            },
+            _ => return Err(format!("usage: {verb} <from> <to> (quote names with spaces)")),
+        },
+        "state" => match name_and("state")? {
+            (name, Some(state)) => BulkCommand::State { name, state },
+            _ => return Err("usage: state <name> <state>".to_string()),
+        },
+        "priority" => {
+            let (name, priority) = name_and("priority")?;
+            BulkCommand::Priority { name, priority }
+        }
+        "due" => {
+            let (name, date) = name_and("date")?;
+            BulkCommand::Due { name, date }
+        }
+        other => {
+            return Err(format!(
+                "`{other}` can't be used in a bulk script: use add, done, rm, mv, state, priority or due"
+            ))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use crate::ports::{LogEntry, StashEntry, TrashEntry};
+    use chrono::Utc;
+    use std::cell::RefCell;
+
+    struct MockOutput;
+
+    impl OutputPort for MockOutput {
+        fn success(&self, _message: &str) {}
+
+        fn error(&self, _message: &str) {}
+
+        fn info(&self, _message: &str) {}
+
+        fn warn(&self, _message: &str) {}
+    }
+
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Snapshots the storage's yaks wholesale
+    struct MockStash<'s> {
+        storage: &'s InMemoryStorage,
+        saved: RefCell<Option<Vec<Yak>>>,
+    }
+
+    impl StashPort for MockStash<'_> {
+        fn push(&self, _reason: &str, _names: &[String]) -> Result<()> {
+            *self.saved.borrow_mut() = Some(self.storage.list_yaks_with_context()?);
+            Ok(())
+        }
+
+        fn list(&self) -> Result<Vec<StashEntry>> {
+            Ok(self
+                .saved
+                .borrow()
+                .iter()
+                .map(|_| StashEntry {
+                    reason: "bulk".to_string(),
+                    created: Utc::now(),
+                    yaks: Vec::new(),
+                })
+                .collect())
+        }
+
+        fn pop(&self) -> Result<Option<StashEntry>> {
+            if let Some(yaks) = self.saved.borrow_mut().take() {
+                for yak in self.storage.list_yaks()? {
+                    if !yak.name.contains('/') {
+                        self.storage.delete_yak(&yak.name)?;
+                    }
+                }
+                for yak in yaks {
+                    self.storage.add_yak(yak);
+                }
+            }
+            Ok(None)
+        }
+
+        fn discard(&self) -> Result<()> {
+            self.saved.borrow_mut().take();
+            Ok(())
+        }
+    }
+
+    /// Takes trashed yaks out of the storage, remembering their names
+    struct MockTrash<'s> {
+        storage: &'s InMemoryStorage,
+        trashed: RefCell<Vec<String>>,
+    }
+
+    impl TrashPort for MockTrash<'_> {
+        fn trash(&self, name: &str) -> Result<()> {
+            self.storage.delete_yak(name)?;
+            self.trashed.borrow_mut().push(name.to_string());
+            Ok(())
+        }
+
+        fn trashed(&self) -> Result<Vec<TrashEntry>> {
+            unimplemented!()
+        }
+
+        fn restore_trashed(&self, _name: &str) -> Result<()> {
+            unimplemented!()
+        }
+    }
+
+    struct MockConfig;
+
+    impl ConfigPort for MockConfig {
+        fn get(&self, _key: &str) -> Option<String> {
+            None
+        }
+
+        fn get_all(&self, _key: &str) -> Vec<String> {
+            Vec::new()
+        }
+    }
+
+    fn storage() -> InMemoryStorage {
+        InMemoryStorage::with_yaks([Yak::new("release".to_string()), Yak::new("old".to_string())])
+    }
+
+    fn names(storage: &InMemoryStorage) -> Vec<String> {
+        storage
+            .list_yaks()
+            .unwrap()
+            .into_iter()
+            .map(|yak| yak.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_bulk_applies_every_command_and_logs_once() {
+        let storage = storage();
+        let stash = MockStash {
+            storage: &storage,
+            saved: RefCell::new(None),
+        };
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let script = "# tidy up\nadd 'release/write notes'\n\nmv release ship\ndone \"ship/write notes\"\nrm old\n";
+
+        BulkYaks::new(&storage, &MockOutput, &log, &stash)
+            .execute(script, "tidy.yx")
+            .unwrap();
+
+        assert_eq!(names(&storage), vec!["ship", "ship/write notes"]);
+        assert!(storage.get_yak("ship/write notes").unwrap().done);
+        assert_eq!(*log.commands.borrow(), vec!["bulk tidy.yx"]);
+        assert_eq!(stash.list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_bulk_rolls_back_when_a_command_fails() {
+        let storage = storage();
+        let stash = MockStash {
+            storage: &storage,
+            saved: RefCell::new(None),
+        };
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = BulkYaks::new(&storage, &MockOutput, &log, &stash);
+
+        let error = use_case
+            .execute("add new\nrm old\ndone missing\n", "-")
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("line 3: `done missing` failed"));
+
+        // Lines that can't be parsed stop the script before anything runs
+        let error = use_case.execute("add one\nlist\n", "-").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("line 2: `list` can't be used"));
+        assert!(use_case.execute("mv 'a b\n", "-").is_err());
+
+        assert_eq!(names(&storage), vec!["old", "release"]);
+        assert!(log.commands.borrow().is_empty());
+        assert!(stash.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_bulk_removes_into_the_trash() {
+        let storage = storage();
+        let stash = MockStash {
+            storage: &storage,
+            saved: RefCell::new(None),
+        };
+        let trash = MockTrash {
+            storage: &storage,
+            trashed: RefCell::new(Vec::new()),
+        };
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+
+        BulkYaks::new(&storage, &MockOutput, &log, &stash)
+            .with_trash(&trash, &MockConfig)
+            .execute("rm old\n", "-")
+            .unwrap();
+
+        assert_eq!(names(&storage), vec!["release"]);
+        assert_eq!(*trash.trashed.borrow(), vec!["old"]);
+    }
+}
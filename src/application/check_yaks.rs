@@ -0,0 +1,195 @@
+// CheckYaks use case - validates invariants across all yaks, for CI and pre-push hooks
+
+use crate::domain::{graph, parse_due_date, validate_yak_name, Priority, YakState};
+use crate::ports::{OutputPort, StoragePort};
+use anyhow::Result;
+
+/// A broken invariant, printed as `check<TAB>yak<TAB>message`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Finding {
+    check: &'static str,
+    name: String,
+    message: String,
+}
+
+pub struct CheckYaks<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+}
+
+impl<'a> CheckYaks<'a> {
+    pub fn new(storage: &'a dyn StoragePort, output: &'a dyn OutputPort) -> Self {
+        Self { storage, output }
+    }
+
+    /// Print one line per broken invariant; any finding makes the command fail
+    pub fn execute(&self) -> Result<()> {
+        let findings = self.findings()?;
+        if findings.is_empty() {
+            self.output.success("No problems found");
+            return Ok(());
+        }
+
+        for finding in &findings {
+            self.output.info(&format!(
+                "{}\t{}\t{}",
+                finding.check, finding.name, finding.message
+            ));
+        }
+        anyhow::bail!("{} problem(s) found", findings.len());
+    }
+
+    fn findings(&self) -> Result<Vec<Finding>> {
+        let mut yaks = self.storage.list_yaks()?;
+        yaks.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut findings = Vec::new();
+        let mut add = |check, name: &str, message: String| {
+            findings.push(Finding {
+                check,
+                name: name.to_string(),
+                message,
+            })
+        };
+
+        for yak in &yaks {
+            let leaf = yak.name.rsplit('/').next().unwrap_or(&yak.name);
+            if let Err(e) = validate_yak_name(leaf) {
+                add("invalid-name", &yak.name, e);
+            }
+
+            if yak.done {
+                let open: Vec<&str> = graph::descendants_of(&yaks, &yak.name)
+                    .iter()
+                    .filter(|child| !child.done)
+                    .map(|child| child.name.as_str())
+                    .collect();
+                if !open.is_empty() {
+                    add(
+                        "done-with-open-children",
+                        &yak.name,
+                        format!("done, but still open: {}", open.join(", ")),
+                    );
+                }
+            }
+
+            for (field, problem) in self.field_problems(&yak.name)? {
+                add("invalid-field", &yak.name, format!("{field}: {problem}"));
+            }
+        }
+
+        Ok(findings)
+    }
+
+    /// Fields holding values the commands that set them would have rejected
+    fn field_problems(&self, name: &str) -> Result<Vec<(&'static str, String)>> {
+        let mut problems = Vec::new();
+        if let Some(text) = self.storage.read_field(name, "priority")? {
+            if let Err(e) = Priority::parse(&text) {
+                problems.push(("priority", e));
+            }
+        }
+        if let Some(text) = self.storage.read_field(name, "due")? {
+            if let Err(e) = parse_due_date(&text) {
+                problems.push(("due", e));
+            }
+        }
+        if let Some(text) = self.storage.read_field(name, "state")? {
+            if let Err(e) = YakState::parse(&text) {
+                problems.push(("state", e));
+            }
+        }
+        Ok(problems)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, _message: &str) {}
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    fn check(yaks: Vec<Yak>, fields: &[(&str, &str, &str)]) -> (bool, Vec<String>) {
+        let storage = InMemoryStorage::with_yaks(yaks);
+        for (name, field, value) in fields {
+            storage.write_field(name, field, Some(value)).unwrap();
+        }
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let result = CheckYaks::new(&storage, &output).execute();
+        (result.is_ok(), output.messages.take())
+    }
+
+    #[test]
+    fn test_check_passes_for_consistent_yaks() {
+        let yaks = vec![
+            Yak::new("release".to_string()).mark_done(),
+            Yak::new("release/tag".to_string()).mark_done(),
+            Yak::new("docs".to_string()),
+        ];
+
+        assert_eq!(
+            check(
+                yaks,
+                &[("docs", "priority", "P1\n"), ("docs", "state", "doing\n")]
+            ),
+            (true, vec!["No problems found".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_check_reports_each_broken_invariant() {
+        let yaks = vec![
+            Yak::new("release".to_string()).mark_done(),
+            Yak::new("release/notes".to_string()).mark_done(),
+            Yak::new("release/notes/draft".to_string()),
+            Yak::new("release/tag".to_string()),
+            Yak::new("docs".to_string()),
+            Yak::new("bad:name".to_string()),
+        ];
+
+        let (ok, messages) = check(
+            yaks,
+            &[("docs", "due", "soon\n"), ("docs", "state", "paused\n")],
+        );
+
+        assert!(!ok);
+        assert_eq!(messages.len(), 5);
+        assert!(messages[0].starts_with("invalid-name\tbad:name\t"));
+        assert!(messages[1].starts_with("invalid-field\tdocs\tdue: "));
+        assert_eq!(
+            messages[2],
+            "invalid-field\tdocs\tstate: Invalid state 'paused': use todo, doing, blocked or done"
+        );
+        assert_eq!(
+            messages[3],
+            "done-with-open-children\trelease\tdone, but still open: release/notes/draft, release/tag"
+        );
+        assert_eq!(
+            messages[4],
+            "done-with-open-children\trelease/notes\tdone, but still open: release/notes/draft"
+        );
+    }
+}
@@ -0,0 +1,123 @@
+// CompleteNames use case - lists yak names for shell tab completion
+
+use crate::domain::{graph, YakFilter};
+use crate::ports::{OutputPort, StoragePort};
+use anyhow::Result;
+use chrono::Local;
+
+pub struct CompleteNames<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+}
+
+impl<'a> CompleteNames<'a> {
+    pub fn new(storage: &'a dyn StoragePort, output: &'a dyn OutputPort) -> Self {
+        Self { storage, output }
+    }
+
+    /// Print every yak name starting with `prefix`, one per line
+    pub fn execute(&self, prefix: &str, only: Option<&str>) -> Result<()> {
+        let filter = only
+            .map(YakFilter::parse)
+            .transpose()
+            .map_err(anyhow::Error::msg)?;
+        let yaks = self.storage.list_yaks()?;
+        let today = Local::now().date_naive();
+        let mut names: Vec<String> = yaks
+            .iter()
+            .filter(|yak| {
+                filter.as_ref().is_none_or(|filter| {
+                    filter.matches(yak, graph::children_done(&yaks, &yak.name), today)
+                })
+            })
+            .map(|yak| yak.name.clone())
+            .filter(|name| name.starts_with(prefix))
+            .collect();
+        names.sort();
+
+        for name in names {
+            self.output.info(&name);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use std::cell::RefCell;
+
+    fn storage(yaks: &[(&str, bool)]) -> InMemoryStorage {
+        InMemoryStorage::with_yaks(yaks.iter().map(|(name, done)| Yak {
+            name: name.to_string(),
+            done: *done,
+            ..Default::default()
+        }))
+    }
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl MockOutput {
+        fn new() -> Self {
+            Self {
+                messages: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn get_messages(&self) -> Vec<String> {
+            self.messages.borrow().clone()
+        }
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    #[test]
+    fn test_complete_lists_matching_names_sorted() {
+        let storage = storage(&[
+            ("feature/login", false),
+            ("docs", false),
+            ("feature", false),
+            ("fix bug", true),
+        ]);
+        let output = MockOutput::new();
+        let use_case = CompleteNames::new(&storage, &output);
+
+        use_case.execute("fea", None).unwrap();
+
+        assert_eq!(output.get_messages(), vec!["feature", "feature/login"]);
+    }
+
+    #[test]
+    fn test_complete_filters_by_state() {
+        let storage = storage(&[("fix bug", true), ("fix docs", false)]);
+        let output = MockOutput::new();
+        let use_case = CompleteNames::new(&storage, &output);
+
+        use_case.execute("fix", Some("done")).unwrap();
+
+        assert_eq!(output.get_messages(), vec!["fix bug"]);
+    }
+}
@@ -0,0 +1,367 @@
+// ContextHistory use case - earlier versions of a yak's context, from the refs/notes/yaks history
+
+use super::read_tracking::ReadTracker;
+use super::TimeStyle;
+use crate::domain::diff::{unified_diff, DiffLine};
+use crate::ports::{
+    FileVersion, HistoryPort, LocalStatePort, LogPort, OutputPort, StoragePort, Style,
+};
+use anyhow::Result;
+
+/// The file each version is read from
+const CONTEXT_FILE: &str = "context.md";
+
+/// The revision `diff` takes for the version origin has
+pub const REMOTE: &str = "remote";
+
+pub struct ContextHistory<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+    history: &'a dyn HistoryPort,
+    read_tracker: Option<ReadTracker<'a>>,
+    time_style: TimeStyle,
+}
+
+impl<'a> ContextHistory<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+        history: &'a dyn HistoryPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            log,
+            history,
+            read_tracker: None,
+            time_style: TimeStyle::default(),
+        }
+    }
+
+    /// Treat a restored context as read, so our own restore isn't badged as updated
+    pub fn with_read_tracking(mut self, state: &'a dyn LocalStatePort) -> Self {
+        self.read_tracker = Some(ReadTracker::new(state));
+        self
+    }
+
+    /// Show times relative to now ("2h ago") or as local date and time
+    pub fn with_time_style(mut self, time_style: TimeStyle) -> Self {
+        self.time_style = time_style;
+        self
+    }
+
+    /// List the versions of the yak's context, newest first
+    pub fn execute(&self, name: &str) -> Result<()> {
+        let (name, versions) = self.versions(name)?;
+        let current = self.storage.read_context(&name).unwrap_or_default();
+
+        for (i, version) in versions.iter().enumerate() {
+            let revision = &version.revision;
+            let lines = version.content.lines().count();
+            let marker = if i == 0 && version.content == current {
+                " (current)"
+            } else {
+                ""
+            };
+            self.output.info(&format!(
+                "{} {:<16} {:<12} {} - {lines} line{}{marker}",
+                short_id(&revision.id),
+                self.time_style.render(revision.time),
+                revision.author,
+                revision.command,
+                if lines == 1 { "" } else { "s" }
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Print the context as it was in `revision` (a revision id or its start)
+    pub fn show(&self, name: &str, revision: &str) -> Result<()> {
+        let (_, version) = self.version(name, revision)?;
+        self.output.info(version.content.trim_end_matches('\n'));
+        Ok(())
+    }
+
+    /// Make the context as it was in `revision` the current one
+    pub fn restore(&self, name: &str, revision: &str) -> Result<()> {
+        let (name, version) = self.version(name, revision)?;
+        let short_id = short_id(&version.revision.id);
+
+        self.storage.write_context(&name, &version.content)?;
+        self.log
+            .log_command(&format!("context --restore {short_id} {name}"))?;
+        if let Some(tracker) = &self.read_tracker {
+            tracker.mark_seen(&name, &version.content)?;
+        }
+
+        self.output.success(&format!(
+            "Restored the context of '{name}' from {short_id} ({})",
+            version.revision.command
+        ));
+        Ok(())
+    }
+
+    /// Show how the current context differs from the version in `revision`,
+    /// from the one origin has for "remote", or by default from the last
+    /// logged version that isn't the current one
+    pub fn diff(&self, name: &str, revision: Option<&str>) -> Result<()> {
+        let (name, version) = match revision {
+            Some(REMOTE) => {
+                let name = self.storage.find_yak(name)?;
+                let Some(version) = self.history.remote_file_version(&name, CONTEXT_FILE)? else {
+                    anyhow::bail!(
+                        "origin has no context for '{name}' that yx knows of: run `yx sync` first"
+                    );
+                };
+                (name, version)
+            }
+            Some(revision) => self.version(name, revision)?,
+            None => {
+                let (name, versions) = self.versions(name)?;
+                let current = self.storage.read_context(&name).unwrap_or_default();
+                let Some(version) = versions
+                    .into_iter()
+                    .find(|version| version.content != current)
+                else {
+                    anyhow::bail!("the context of '{name}' has no earlier version");
+                };
+                (name, version)
+            }
+        };
+        let current = self.storage.read_context(&name).unwrap_or_default();
+        let short_id = short_id(&version.revision.id);
+
+        let lines = unified_diff(&version.content, &current);
+        if lines.is_empty() {
+            self.output.note(&format!(
+                "The context of '{name}' is the same as in {short_id}"
+            ));
+            return Ok(());
+        }
+
+        self.output.info(&format!(
+            "--- {name} @ {short_id} ({})",
+            version.revision.command
+        ));
+        self.output.info(&format!("+++ {name} (current)"));
+        for line in lines {
+            self.output.info(&match line {
+                DiffLine::Hunk(text) => self.output.paint(&text, Style::Hunk),
+                DiffLine::Context(text) => format!(" {text}"),
+                DiffLine::Removed(text) => self.output.paint(&format!("-{text}"), Style::Removed),
+                DiffLine::Added(text) => self.output.paint(&format!("+{text}"), Style::Added),
+            });
+        }
+        Ok(())
+    }
+
+    fn versions(&self, name: &str) -> Result<(String, Vec<FileVersion>)> {
+        let name = self.storage.find_yak(name)?;
+        let versions = self.history.file_versions(&name, CONTEXT_FILE)?;
+        if versions.is_empty() {
+            anyhow::bail!("no context history for yak '{name}'");
+        }
+        Ok((name, versions))
+    }
+
+    fn version(&self, name: &str, revision: &str) -> Result<(String, FileVersion)> {
+        let (name, versions) = self.versions(name)?;
+        let mut matches = versions
+            .into_iter()
+            .filter(|version| !revision.is_empty() && version.revision.id.starts_with(revision));
+        match (matches.next(), matches.next()) {
+            (Some(version), None) => Ok((name, version)),
+            (Some(_), Some(_)) => {
+                anyhow::bail!("'{revision}' matches several versions of '{name}': give more of it")
+            }
+            (None, _) => anyhow::bail!(
+                "no version {revision} of the context of '{name}': see `yx context --history {name}`"
+            ),
+        }
+    }
+}
+
+fn short_id(id: &str) -> &str {
+    &id[..id.len().min(7)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::ports::{FileChange, LogEntry, Revision, YakSnapshot};
+    use chrono::{TimeZone, Utc};
+    use std::cell::RefCell;
+
+    struct MockHistory {
+        versions: Vec<FileVersion>,
+    }
+
+    impl MockHistory {
+        /// Versions given as (id, command, content), newest first
+        fn new(versions: &[(&str, &str, &str)]) -> Self {
+            Self {
+                versions: versions
+                    .iter()
+                    .map(|(id, command, content)| FileVersion {
+                        revision: Revision {
+                            id: id.to_string(),
+                            command: command.to_string(),
+                            author: "ann".to_string(),
+                            time: Utc.with_ymd_and_hms(2026, 10, 1, 9, 0, 0).unwrap(),
+                        },
+                        content: content.to_string(),
+                    })
+                    .collect(),
+            }
+        }
+    }
+
+    impl HistoryPort for MockHistory {
+        fn revisions(&self, _name: Option<&str>) -> Result<Vec<Revision>> {
+            unimplemented!()
+        }
+
+        fn changed_files(&self, _revision: &str, _name: &str) -> Result<Vec<FileChange>> {
+            unimplemented!()
+        }
+
+        fn last_snapshot(&self, _name: &str) -> Result<Option<(Revision, Vec<YakSnapshot>)>> {
+            unimplemented!()
+        }
+
+        fn file_versions(&self, name: &str, file: &str) -> Result<Vec<FileVersion>> {
+            assert_eq!(file, "context.md");
+            Ok(if name == "auth" {
+                self.versions.clone()
+            } else {
+                Vec::new()
+            })
+        }
+
+        fn remote_file_version(&self, _name: &str, _file: &str) -> Result<Option<FileVersion>> {
+            Ok(None)
+        }
+    }
+
+    #[derive(Default)]
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, _message: &str) {}
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, _message: &str) {}
+    }
+
+    #[derive(Default)]
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_context_history_lists_and_shows_versions() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("auth").unwrap();
+        storage.write_context("auth", "tokens\nexpiry\n").unwrap();
+        storage.create_yak("docs").unwrap();
+        let history = MockHistory::new(&[
+            ("b2c3d4e5f6", "context auth", "tokens\nexpiry\n"),
+            ("a1b2c3d4e5", "add auth", "tokens\n"),
+        ]);
+        let output = MockOutput::default();
+        let log = MockLog::default();
+        let use_case = ContextHistory::new(&storage, &output, &log, &history)
+            .with_time_style(TimeStyle::Absolute);
+
+        use_case.execute("auth").unwrap();
+        use_case.show("auth", "a1b").unwrap();
+
+        let messages = output.messages.borrow();
+        assert!(messages[0].starts_with("b2c3d4e "));
+        assert!(messages[0].ends_with("ann          context auth - 2 lines (current)"));
+        assert!(messages[1].ends_with("add auth - 1 line"));
+        assert_eq!(messages[2], "tokens");
+        assert!(use_case.show("auth", "ff").is_err());
+        assert!(use_case.execute("docs").is_err());
+    }
+
+    #[test]
+    fn test_context_diff_against_the_previous_version() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("auth").unwrap();
+        storage.write_context("auth", "tokens\nexpiry\n").unwrap();
+        let history = MockHistory::new(&[
+            ("b2c3d4e5f6", "context auth", "tokens\nexpiry\n"),
+            ("a1b2c3d4e5", "add auth", "tokens\n"),
+        ]);
+        let output = MockOutput::default();
+        let log = MockLog::default();
+        let use_case = ContextHistory::new(&storage, &output, &log, &history);
+
+        use_case.diff("auth", None).unwrap();
+
+        assert_eq!(
+            *output.messages.borrow(),
+            vec![
+                "--- auth @ a1b2c3d (add auth)",
+                "+++ auth (current)",
+                "@@ -1 +1,2 @@",
+                " tokens",
+                "+expiry",
+            ]
+        );
+        let error = use_case.diff("auth", Some(REMOTE)).unwrap_err();
+        assert!(error.to_string().contains("run `yx sync` first"));
+    }
+
+    #[test]
+    fn test_context_history_restores_a_version() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("auth").unwrap();
+        storage.write_context("auth", "oops").unwrap();
+        let history = MockHistory::new(&[
+            ("b2c3d4e5f6", "context auth", "oops"),
+            ("a1b2c3d4e5", "add auth", "tokens\n"),
+        ]);
+        let output = MockOutput::default();
+        let log = MockLog::default();
+
+        ContextHistory::new(&storage, &output, &log, &history)
+            .restore("auth", "a1b2")
+            .unwrap();
+
+        assert_eq!(storage.read_context("auth").unwrap(), "tokens\n");
+        assert_eq!(
+            *log.commands.borrow(),
+            vec!["context --restore a1b2c3d auth"]
+        );
+        assert_eq!(
+            *output.messages.borrow(),
+            vec!["Restored the context of 'auth' from a1b2c3d (add auth)"]
+        );
+    }
+}
@@ -0,0 +1,203 @@
+// CopyYak use case - duplicates a yak (and optionally its children) under a new name
+
+use super::naming;
+use super::read_tracking::ReadTracker;
+use crate::domain::{graph, validate_yak_name};
+use crate::ports::{ConfigPort, LocalStatePort, LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+
+pub struct CopyYak<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+    config: Option<&'a dyn ConfigPort>,
+    read_tracker: Option<ReadTracker<'a>>,
+}
+
+impl<'a> CopyYak<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            log,
+            config: None,
+            read_tracker: None,
+        }
+    }
+
+    /// Apply the repo's name normalization rules, if any are configured
+    pub fn with_config(mut self, config: &'a dyn ConfigPort) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Treat copied context as read, so copies aren't badged as updated
+    pub fn with_read_tracking(mut self, state: &'a dyn LocalStatePort) -> Self {
+        self.read_tracker = Some(ReadTracker::new(state));
+        self
+    }
+
+    /// Copy `from` to `to`, with its whole subtree when `recursive`
+    /// Copies start out open, so a finished yak can serve as a template.
+    pub fn execute(&self, from: &str, to: &str, recursive: bool) -> Result<()> {
+        let rules = naming::configured_rules(self.config)?;
+        let (to, title) = naming::normalize(to, &rules);
+
+        // Validate new name
+        validate_yak_name(&to).map_err(|e| anyhow::anyhow!(e))?;
+
+        // Resolve source yak name (exact or fuzzy match)
+        let resolved_from = self.storage.find_yak(from)?;
+        if graph::is_descendant_of(&to, &resolved_from) {
+            anyhow::bail!("cannot copy '{resolved_from}' into itself");
+        }
+
+        let mut names = vec![resolved_from.clone()];
+        if recursive {
+            let all_yaks = self.storage.list_yaks()?;
+            let mut descendants: Vec<String> = graph::descendants_of(&all_yaks, &resolved_from)
+                .iter()
+                .map(|yak| yak.name.clone())
+                .collect();
+            // Parents before children
+            descendants.sort();
+            names.extend(descendants);
+        }
+
+        for name in &names {
+            let copy = format!("{to}{}", &name[resolved_from.len()..]);
+            self.storage.copy_yak(name, &copy)?;
+            self.storage.mark_done(&copy, false)?;
+            if let Some(tracker) = &self.read_tracker {
+                tracker.mark_seen(&copy, &self.storage.read_context(&copy)?)?;
+            }
+        }
+        if let Some(title) = &title {
+            self.storage.write_field(&to, "title", Some(title))?;
+        }
+
+        if recursive {
+            self.log
+                .log_command(&format!("cp --recursive {resolved_from} {to}"))?;
+        } else {
+            self.log.log_command(&format!("cp {resolved_from} {to}"))?;
+        }
+
+        match names.len() - 1 {
+            0 => self
+                .output
+                .success(&format!("Copied '{resolved_from}' to '{to}'")),
+            count => self.output.success(&format!(
+                "Copied '{resolved_from}' and {count} yak(s) under it to '{to}'"
+            )),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use crate::ports::LogEntry;
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, _message: &str) {}
+
+        fn info(&self, _message: &str) {}
+
+        fn warn(&self, _message: &str) {}
+    }
+
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    fn yak(name: &str) -> Yak {
+        Yak::new(name.to_string()).with_context(format!("{name} notes"))
+    }
+
+    fn setup() -> (InMemoryStorage, MockOutput, MockLog) {
+        let storage = InMemoryStorage::with_yaks([
+            yak("release").mark_done(),
+            yak("release/tag").mark_done(),
+            yak("release/tag/push"),
+        ]);
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        (storage, output, log)
+    }
+
+    #[test]
+    fn test_copy_yak_without_children() {
+        let (storage, output, log) = setup();
+        let use_case = CopyYak::new(&storage, &output, &log);
+
+        use_case.execute("release", "release v2", false).unwrap();
+
+        let copy = storage.get_yak("release v2").unwrap();
+        assert_eq!(copy.context.as_deref(), Some("release notes"));
+        assert!(!copy.done);
+        assert!(storage.get_yak("release v2/tag").is_err());
+        assert_eq!(*log.commands.borrow(), vec!["cp release release v2"]);
+        assert_eq!(
+            *output.messages.borrow(),
+            vec!["Copied 'release' to 'release v2'"]
+        );
+    }
+
+    #[test]
+    fn test_copy_yak_recursive_copies_subtree() {
+        let (storage, output, log) = setup();
+        let use_case = CopyYak::new(&storage, &output, &log);
+
+        use_case.execute("release", "next", true).unwrap();
+
+        assert!(storage.get_yak("next/tag").is_ok_and(|y| !y.done));
+        assert!(storage.get_yak("next/tag/push").is_ok());
+        assert_eq!(*log.commands.borrow(), vec!["cp --recursive release next"]);
+        assert_eq!(
+            *output.messages.borrow(),
+            vec!["Copied 'release' and 2 yak(s) under it to 'next'"]
+        );
+    }
+
+    #[test]
+    fn test_copy_yak_rejects_copy_into_itself() {
+        let (storage, output, log) = setup();
+        let use_case = CopyYak::new(&storage, &output, &log);
+
+        assert!(use_case.execute("release", "release/again", true).is_err());
+        assert!(log.commands.borrow().is_empty());
+    }
+}
@@ -0,0 +1,174 @@
+// CountYaks use case - counts yaks matching filters, for scripts and CI gates
+
+use crate::domain::{graph, Yak, YakFilter, YakState};
+use crate::ports::{OutputPort, StoragePort};
+use anyhow::Result;
+use chrono::Local;
+
+pub struct CountYaks<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    scope: Option<String>,
+    state: Option<String>,
+}
+
+impl<'a> CountYaks<'a> {
+    pub fn new(storage: &'a dyn StoragePort, output: &'a dyn OutputPort) -> Self {
+        Self {
+            storage,
+            output,
+            scope: None,
+            state: None,
+        }
+    }
+
+    /// Only count the yaks under this yak (fuzzy matched), not the yak itself
+    pub fn with_scope(mut self, name: String) -> Self {
+        self.scope = Some(name);
+        self
+    }
+
+    /// Only count yaks in this state (todo, doing, blocked or done)
+    pub fn with_state(mut self, state: String) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// Print how many yaks match, filtered like `yx list --only`
+    /// With `fail_if_any` or `fail_if_none`, a count of more than zero or of zero
+    /// is an error after printing, so the exit code can gate a CI job.
+    pub fn execute(&self, only: Option<&str>, fail_if_any: bool, fail_if_none: bool) -> Result<()> {
+        let state = match &self.state {
+            Some(text) => Some(YakState::parse(text).map_err(|e| anyhow::anyhow!(e))?),
+            None => None,
+        };
+        let filter = only
+            .map(YakFilter::parse)
+            .transpose()
+            .map_err(anyhow::Error::msg)?;
+        // Resolve yak name (exact or fuzzy match)
+        let scope = match &self.scope {
+            Some(name) => Some(self.storage.find_yak(name)?),
+            None => None,
+        };
+
+        let today = Local::now().date_naive();
+        let wanted = |yak: &Yak| {
+            scope
+                .as_ref()
+                .is_none_or(|scope| graph::is_descendant_of(&yak.name, scope))
+                && state.is_none_or(|state| yak.current_state() == state)
+        };
+        // Contexts are never read; only a filter, which can depend on a yak's
+        // children, needs every yak held at once
+        let yaks = self.storage.iter_yaks(false)?;
+        let count = match &filter {
+            Some(filter) => {
+                let yaks = yaks.collect::<Result<Vec<Yak>>>()?;
+                yaks.iter()
+                    .filter(|yak| wanted(yak))
+                    .filter(|yak| {
+                        filter.matches(yak, graph::children_done(&yaks, &yak.name), today)
+                    })
+                    .count()
+            }
+            None => {
+                let mut count = 0;
+                for yak in yaks {
+                    if wanted(&yak?) {
+                        count += 1;
+                    }
+                }
+                count
+            }
+        };
+
+        self.output.info(&count.to_string());
+
+        if fail_if_any && count > 0 {
+            anyhow::bail!("{count} yak(s) match");
+        }
+        if fail_if_none && count == 0 {
+            anyhow::bail!("no yaks match");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, _message: &str) {}
+
+        fn error(&self, _message: &str) {}
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    fn storage() -> InMemoryStorage {
+        InMemoryStorage::with_yaks([
+            Yak::new("release".to_string()),
+            Yak::new("release/notes".to_string()).mark_done(),
+            Yak::new("release/tag".to_string()).with_state(YakState::Doing),
+            Yak::new("docs".to_string()).with_state(YakState::Doing),
+        ])
+    }
+
+    #[test]
+    fn test_count_filters_by_scope_and_state() {
+        let storage = storage();
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+
+        CountYaks::new(&storage, &output)
+            .execute(None, false, false)
+            .unwrap();
+        CountYaks::new(&storage, &output)
+            .with_scope("release".to_string())
+            .execute(Some("not-done"), false, false)
+            .unwrap();
+        CountYaks::new(&storage, &output)
+            .with_state("doing".to_string())
+            .execute(None, false, false)
+            .unwrap();
+
+        assert_eq!(*output.messages.borrow(), vec!["4", "1", "2"]);
+        assert!(CountYaks::new(&storage, &output)
+            .with_state("later".to_string())
+            .execute(None, false, false)
+            .is_err());
+    }
+
+    #[test]
+    fn test_count_fails_if_any_or_none_match() {
+        let storage = storage();
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let use_case = CountYaks::new(&storage, &output).with_scope("release".to_string());
+
+        assert!(use_case.execute(Some("not-done"), true, false).is_err());
+        assert!(use_case.execute(Some("not-done"), false, true).is_ok());
+        assert!(use_case.execute(Some("done"), true, false).is_err());
+
+        let use_case = use_case.with_state("blocked".to_string());
+        assert!(use_case.execute(None, true, false).is_ok());
+        assert!(use_case.execute(None, false, true).is_err());
+        assert_eq!(*output.messages.borrow(), vec!["1", "1", "1", "0", "0"]);
+    }
+}
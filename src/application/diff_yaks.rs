@@ -0,0 +1,143 @@
+// DiffYaks use case - preview what a sync would change in the local yaks
+
+use crate::ports::{IncomingChange, OutputPort, SyncPort};
+use anyhow::Result;
+
+pub struct DiffYaks<'a> {
+    sync: &'a dyn SyncPort,
+    output: &'a dyn OutputPort,
+}
+
+impl<'a> DiffYaks<'a> {
+    pub fn new(sync: &'a dyn SyncPort, output: &'a dyn OutputPort) -> Self {
+        Self { sync, output }
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        let changes = self.sync.preview()?;
+        if changes.is_empty() {
+            self.output.note("Sync would not change any yaks");
+            return Ok(());
+        }
+
+        for (name, change) in &changes {
+            self.output.info(&change_line(name, *change));
+        }
+
+        let count = changes.len();
+        let noun = if count == 1 { "yak" } else { "yaks" };
+        self.output.note(&format!(
+            "Sync would change {count} {noun}; run `yx sync` to apply"
+        ));
+
+        Ok(())
+    }
+}
+
+/// One previewed change, e.g. "x completed  docs"
+pub(super) fn change_line(name: &str, change: IncomingChange) -> String {
+    let label = match change {
+        IncomingChange::Added => "+ added",
+        IncomingChange::Completed => "x completed",
+        IncomingChange::Reopened => "o reopened",
+        IncomingChange::Updated => "~ updated",
+        IncomingChange::Removed => "- removed",
+    };
+    format!("{label:<12} {name}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::{SyncProgress, SyncStatus};
+    use std::cell::RefCell;
+
+    struct MockSync {
+        changes: Vec<(String, IncomingChange)>,
+    }
+
+    impl SyncPort for MockSync {
+        fn push(&self) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn pull(&self) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn sync(&self, _progress: &dyn Fn(SyncProgress)) -> Result<Vec<String>> {
+            unimplemented!()
+        }
+
+        fn status(&self) -> Result<SyncStatus> {
+            unimplemented!()
+        }
+
+        fn preview(&self) -> Result<Vec<(String, IncomingChange)>> {
+            Ok(self.changes.clone())
+        }
+    }
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, _message: &str) {}
+        fn error(&self, _message: &str) {}
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn note(&self, message: &str) {
+            self.messages.borrow_mut().push(format!("NOTE: {message}"));
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    #[test]
+    fn test_diff_lists_each_incoming_change() {
+        let sync = MockSync {
+            changes: vec![
+                ("api".to_string(), IncomingChange::Added),
+                ("docs".to_string(), IncomingChange::Completed),
+                ("old".to_string(), IncomingChange::Removed),
+            ],
+        };
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+
+        DiffYaks::new(&sync, &output).execute().unwrap();
+
+        assert_eq!(
+            *output.messages.borrow(),
+            vec![
+                "+ added      api",
+                "x completed  docs",
+                "- removed    old",
+                "NOTE: Sync would change 3 yaks; run `yx sync` to apply",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_when_up_to_date() {
+        let sync = MockSync {
+            changes: Vec::new(),
+        };
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+
+        DiffYaks::new(&sync, &output).execute().unwrap();
+
+        assert_eq!(
+            *output.messages.borrow(),
+            vec!["NOTE: Sync would not change any yaks"]
+        );
+    }
+}
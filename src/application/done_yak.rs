@@ -1,65 +1,140 @@
-// DoneYak use case - marks a yak as done or undone
+// DoneYak use case - marks yaks as done or undone
 
+use super::transaction;
+use crate::domain::{graph, Yak};
 use crate::ports::{LogPort, OutputPort, StoragePort};
 use anyhow::Result;
+use std::collections::HashSet;
 
 pub struct DoneYak<'a> {
     storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
     log: &'a dyn LogPort,
 }
 
 impl<'a> DoneYak<'a> {
     pub fn new(
         storage: &'a dyn StoragePort,
-        _output: &'a dyn OutputPort,
+        output: &'a dyn OutputPort,
         log: &'a dyn LogPort,
     ) -> Self {
-        Self { storage, log }
+        Self {
+            storage,
+            output,
+            log,
+        }
     }
 
-    pub fn execute(&self, name: &str, undo: bool, recursive: bool) -> Result<()> {
-        // Resolve yak name (exact or fuzzy match)
-        let resolved_name = self.storage.find_yak(name)?;
+    /// Mark one or more yaks. Every name is checked first and nothing
+    /// changes unless all of them can be marked.
+    pub fn execute(&self, names: &[&str], undo: bool, recursive: bool) -> Result<()> {
+        if names.is_empty() {
+            anyhow::bail!("no yak name given");
+        }
+
+        // `yx done Fix the bug` has always meant one yak; keep that working
+        let joined = names.join(" ");
+        if names.len() > 1 && self.storage.get_yak(&joined).is_ok() {
+            return self.execute(&[joined.as_str()], undo, recursive);
+        }
+
+        // Resolve yak names (exact or fuzzy match)
+        let resolved: Vec<Result<String>> = names
+            .iter()
+            .map(|name| self.storage.find_yak(name))
+            .collect();
+        let batch: HashSet<&str> = resolved
+            .iter()
+            .filter_map(|result| result.as_ref().ok())
+            .map(String::as_str)
+            .collect();
+
+        let all_yaks = self.storage.list_yaks()?;
+        let mut targets: Vec<String> = Vec::new();
+        let mut failures = Vec::new();
+        for result in &resolved {
+            let checked = match result {
+                Ok(name) => self
+                    .check(&all_yaks, name, &batch, undo, recursive)
+                    .map(|()| name),
+                Err(e) => Err(anyhow::anyhow!("{e}")),
+            };
+            match checked {
+                Ok(name) if !targets.contains(name) => targets.push(name.clone()),
+                Ok(_) => {}
+                Err(e) => failures.push(e),
+            }
+        }
+
+        if names.len() == 1 {
+            if let Some(e) = failures.pop() {
+                return Err(e);
+            }
+        } else if !failures.is_empty() {
+            for e in &failures {
+                self.output.error(&e.to_string());
+            }
+            anyhow::bail!(
+                "nothing marked - {} of {} yaks failed",
+                failures.len(),
+                names.len()
+            );
+        }
 
-        // If marking as done (not undo) and not recursive, check for incomplete children
+        // All the yaks are marked, and logged as one command, or none are
+        let command = match (undo, recursive) {
+            (true, _) => format!("done --undo {}", targets.join(" ")),
+            (false, true) => format!("done --recursive {}", targets.join(" ")),
+            (false, false) => format!("done {}", targets.join(" ")),
+        };
+        transaction::apply(self.storage, self.log, &command, || {
+            targets
+                .iter()
+                .try_for_each(|name| self.mark(&all_yaks, name, undo, recursive))
+        })?;
+
+        if names.len() > 1 {
+            let state = if undo { "not done" } else { "done" };
+            for name in &targets {
+                self.output.success(&format!("Marked '{name}' {state}"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// A yak can't be marked done while children outside the batch are incomplete
+    fn check(
+        &self,
+        all_yaks: &[Yak],
+        name: &str,
+        batch: &HashSet<&str>,
+        undo: bool,
+        recursive: bool,
+    ) -> Result<()> {
         if !undo && !recursive {
-            let all_yaks = self.storage.list_yaks()?;
-            let has_incomplete_children = all_yaks
+            let has_incomplete_children = graph::descendants_of(all_yaks, name)
                 .iter()
-                .any(|yak| yak.name.starts_with(&format!("{resolved_name}/")) && !yak.done);
+                .any(|yak| !yak.done && !batch.contains(yak.name.as_str()));
 
             if has_incomplete_children {
-                anyhow::bail!("cannot mark '{resolved_name}' as done - it has incomplete children");
+                anyhow::bail!("cannot mark '{name}' as done - it has incomplete children");
             }
         }
+        Ok(())
+    }
 
+    fn mark(&self, all_yaks: &[Yak], name: &str, undo: bool, recursive: bool) -> Result<()> {
         // If recursive, mark all children as done too
         if recursive && !undo {
-            let all_yaks = self.storage.list_yaks()?;
-            let children: Vec<String> = all_yaks
-                .iter()
-                .filter(|yak| {
-                    yak.name == resolved_name || yak.name.starts_with(&format!("{resolved_name}/"))
-                })
-                .map(|yak| yak.name.clone())
-                .collect();
-
-            for child_name in children {
-                self.storage.mark_done(&child_name, true)?;
+            self.storage.mark_done(name, true)?;
+            for descendant in graph::descendants_of(all_yaks, name) {
+                self.storage.mark_done(&descendant.name, true)?;
             }
-            self.log
-                .log_command(&format!("done --recursive {resolved_name}"))?;
         } else {
             // Mark as done (or undone if undo flag is set)
-            self.storage.mark_done(&resolved_name, !undo)?;
-            if undo {
-                self.log
-                    .log_command(&format!("done --undo {resolved_name}"))?;
-            } else {
-                self.log.log_command(&format!("done {resolved_name}"))?;
-            }
+            self.storage.mark_done(name, !undo)?;
         }
-
         Ok(())
     }
 }
@@ -67,88 +142,11 @@ impl<'a> DoneYak<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::adapters::storage::InMemoryStorage;
     use crate::domain::Yak;
+    use crate::ports::LogEntry;
     use std::cell::RefCell;
 
-    struct MockStorage {
-        yaks: RefCell<Vec<Yak>>,
-    }
-
-    impl MockStorage {
-        fn new() -> Self {
-            Self {
-                yaks: RefCell::new(Vec::new()),
-            }
-        }
-
-        fn add_yak(&self, name: &str, done: bool) {
-            self.yaks.borrow_mut().push(Yak {
-                name: name.to_string(),
-                done,
-                context: None,
-            });
-        }
-
-        fn get_yak_status(&self, name: &str) -> Option<bool> {
-            self.yaks
-                .borrow()
-                .iter()
-                .find(|y| y.name == name)
-                .map(|y| y.done)
-        }
-    }
-
-    impl StoragePort for MockStorage {
-        fn create_yak(&self, _name: &str) -> Result<()> {
-            unimplemented!()
-        }
-
-        fn get_yak(&self, name: &str) -> Result<Yak> {
-            self.yaks
-                .borrow()
-                .iter()
-                .find(|y| y.name == name)
-                .cloned()
-                .ok_or_else(|| anyhow::anyhow!("yak '{}' not found", name))
-        }
-
-        fn list_yaks(&self) -> Result<Vec<Yak>> {
-            Ok(self.yaks.borrow().clone())
-        }
-
-        fn mark_done(&self, name: &str, done: bool) -> Result<()> {
-            let mut yaks = self.yaks.borrow_mut();
-            if let Some(yak) = yaks.iter_mut().find(|y| y.name == name) {
-                yak.done = done;
-                Ok(())
-            } else {
-                anyhow::bail!("yak '{}' not found", name)
-            }
-        }
-
-        fn delete_yak(&self, _name: &str) -> Result<()> {
-            unimplemented!()
-        }
-
-        fn rename_yak(&self, _from: &str, _to: &str) -> Result<()> {
-            unimplemented!()
-        }
-
-        fn read_context(&self, _name: &str) -> Result<String> {
-            unimplemented!()
-        }
-
-        fn write_context(&self, _name: &str, _text: &str) -> Result<()> {
-            unimplemented!()
-        }
-
-        fn find_yak(&self, name: &str) -> Result<String> {
-            // For tests, just return the name if it exists
-            self.get_yak(name)?;
-            Ok(name.to_string())
-        }
-    }
-
     struct MockOutput {
         messages: RefCell<Vec<String>>,
     }
@@ -160,8 +158,8 @@ mod tests {
             }
         }
 
-        fn last_message(&self) -> Option<String> {
-            self.messages.borrow().last().cloned()
+        fn get_messages(&self) -> Vec<String> {
+            self.messages.borrow().clone()
         }
     }
 
@@ -181,6 +179,12 @@ mod tests {
                 .borrow_mut()
                 .push(format!("INFO: {}", message));
         }
+
+        fn warn(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("WARN: {}", message));
+        }
     }
 
     struct MockLog;
@@ -189,40 +193,109 @@ mod tests {
         fn log_command(&self, _command: &str) -> Result<()> {
             Ok(())
         }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
     }
 
     #[test]
     fn test_done_yak_marks_as_done() {
-        let storage = MockStorage::new();
-        storage.add_yak("test-yak", false);
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("test-yak".to_string()));
         let output = MockOutput::new();
         let use_case = DoneYak::new(&storage, &output, &MockLog);
 
-        use_case.execute("test-yak", false, false).unwrap();
+        use_case.execute(&["test-yak"], false, false).unwrap();
 
-        assert_eq!(storage.get_yak_status("test-yak"), Some(true));
+        assert!(storage.get_yak("test-yak").unwrap().done);
     }
 
     #[test]
     fn test_done_yak_with_undo_marks_as_not_done() {
-        let storage = MockStorage::new();
-        storage.add_yak("test-yak", true);
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("test-yak".to_string()).mark_done());
         let output = MockOutput::new();
         let use_case = DoneYak::new(&storage, &output, &MockLog);
 
-        use_case.execute("test-yak", true, false).unwrap();
+        use_case.execute(&["test-yak"], true, false).unwrap();
 
-        assert_eq!(storage.get_yak_status("test-yak"), Some(false));
+        assert!(!storage.get_yak("test-yak").unwrap().done);
     }
 
     #[test]
     fn test_done_yak_fails_for_nonexistent_yak() {
-        let storage = MockStorage::new();
+        let storage = InMemoryStorage::new();
         let output = MockOutput::new();
         let use_case = DoneYak::new(&storage, &output, &MockLog);
 
-        let result = use_case.execute("nonexistent", false, false);
+        let result = use_case.execute(&["nonexistent"], false, false);
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_done_yak_marks_several_and_reports_each() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("parent".to_string()));
+        storage.add_yak(Yak::new("parent/child".to_string()));
+        storage.add_yak(Yak::new("docs".to_string()));
+        let output = MockOutput::new();
+        let use_case = DoneYak::new(&storage, &output, &MockLog);
+
+        use_case
+            .execute(&["parent", "parent/child", "docs"], false, false)
+            .unwrap();
+
+        assert!(storage.get_yak("parent").unwrap().done);
+        assert!(storage.get_yak("parent/child").unwrap().done);
+        assert_eq!(
+            output.get_messages(),
+            vec![
+                "Marked 'parent' done",
+                "Marked 'parent/child' done",
+                "Marked 'docs' done"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_done_yak_marks_nothing_when_any_name_fails() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("docs".to_string()));
+        storage.add_yak(Yak::new("parent".to_string()));
+        storage.add_yak(Yak::new("parent/child".to_string()));
+        let output = MockOutput::new();
+        let use_case = DoneYak::new(&storage, &output, &MockLog);
+
+        let result = use_case.execute(&["docs", "missing", "parent"], false, false);
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "nothing marked - 2 of 3 yaks failed"
+        );
+        assert!(!storage.get_yak("docs").unwrap().done);
+        assert_eq!(
+            output.get_messages(),
+            vec![
+                "ERROR: yak 'missing' not found",
+                "ERROR: cannot mark 'parent' as done - it has incomplete children"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_done_yak_keeps_unquoted_multi_word_names() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("Fix the bug".to_string()));
+        let output = MockOutput::new();
+        let use_case = DoneYak::new(&storage, &output, &MockLog);
+
+        use_case
+            .execute(&["Fix", "the", "bug"], false, false)
+            .unwrap();
+
+        assert!(storage.get_yak("Fix the bug").unwrap().done);
+        assert!(output.get_messages().is_empty());
+    }
 }
@@ -0,0 +1,652 @@
+// EditAll use case - edit a whole (sub)tree of yaks as one text buffer, like `git rebase -i`
+
+use super::transaction::{self, DeferredLog};
+use super::{editor, RemoveYak};
+use crate::domain::{graph, validate_yak_name, Yak};
+use crate::ports::{ConfigPort, LogPort, OutputPort, StashPort, StoragePort, TrashPort};
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+
+/// Spaces per nesting level in the buffer
+const INDENT: &str = "  ";
+
+/// Columns a tab counts for when reading indentation back
+const TAB_WIDTH: usize = 4;
+
+const HELP: &str = "\
+# Each line is a yak: `- [ ] name #id` (open) or `- [x] name #id` (done).
+# Indent by two spaces to nest under the line above.
+# Edit a name or indentation to rename/move, toggle [ ]/[x] to change state,
+# delete a line to remove the yak (and anything only nested under it),
+# add a line without an #id to create a yak. Lines starting with # are ignored.
+";
+
+/// A yak as listed in the buffer
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    id: Option<usize>,
+    name: String,
+    done: bool,
+}
+
+/// One change the edited buffer asks for
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Change {
+    Rename { from: String, to: String },
+    Remove(String),
+    Add { name: String, done: bool },
+    SetDone { name: String, done: bool },
+}
+
+impl Change {
+    fn describe(&self) -> String {
+        match self {
+            Change::Rename { from, to } => format!("move '{from}' -> '{to}'"),
+            Change::Remove(name) => format!("remove '{name}'"),
+            Change::Add { name, done: false } => format!("add '{name}'"),
+            Change::Add { name, done: true } => format!("add '{name}' (done)"),
+            Change::SetDone { name, done: true } => format!("mark '{name}' done"),
+            Change::SetDone { name, done: false } => format!("reopen '{name}'"),
+        }
+    }
+}
+
+pub struct EditAll<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+    stash: Option<&'a dyn StashPort>,
+    trash: Option<(&'a dyn TrashPort, &'a dyn ConfigPort)>,
+}
+
+impl<'a> EditAll<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            log,
+            stash: None,
+            trash: None,
+        }
+    }
+
+    /// Snapshot the affected yaks before applying so `yx stash pop` can undo it
+    pub fn with_stash(mut self, stash: &'a dyn StashPort) -> Self {
+        self.stash = Some(stash);
+        self
+    }
+
+    /// Move removed yaks to the trash rather than deleting them
+    pub fn with_trash(mut self, trash: &'a dyn TrashPort, config: &'a dyn ConfigPort) -> Self {
+        self.trash = Some((trash, config));
+        self
+    }
+
+    /// Edit every yak, or those under `prefix`; `yes` applies without asking
+    /// From a terminal the buffer opens in $EDITOR; otherwise the edited buffer is read from stdin.
+    pub fn execute(&self, prefix: Option<&str>, yes: bool) -> Result<()> {
+        let prefix = prefix.map(|p| self.storage.find_yak(p)).transpose()?;
+        let yaks = self.storage.list_yaks()?;
+        let original: Vec<&Yak> = match &prefix {
+            Some(prefix) => graph::descendants_of(&yaks, prefix),
+            None => yaks.iter().collect(),
+        };
+
+        let buffer = render_buffer(&original, prefix.as_deref());
+        let edited = if atty::is(atty::Stream::Stdin) {
+            editor::edit_in_editor(&buffer)?
+        } else {
+            editor::read_stdin()?
+        };
+
+        // Like `git rebase -i`, an emptied buffer aborts rather than deleting everything
+        let is_empty = edited
+            .lines()
+            .map(str::trim)
+            .all(|line| line.is_empty() || line.starts_with('#'));
+        if is_empty && !original.is_empty() {
+            self.output.note("Empty buffer - nothing changed");
+            return Ok(());
+        }
+
+        let changes = plan_changes(&original, &edited, prefix.as_deref())?;
+        if changes.is_empty() {
+            self.output.note("No changes");
+            return Ok(());
+        }
+
+        self.output.info("Changes:");
+        for change in &changes {
+            self.output.info(&format!("  {}", change.describe()));
+        }
+
+        let confirmed = if yes {
+            true
+        } else {
+            let question = format!("Apply {} changes?", changes.len());
+            match self.output.confirm(&question) {
+                Some(answer) => answer,
+                None => {
+                    self.output.note("Re-run with --yes to apply these changes");
+                    return Ok(());
+                }
+            }
+        };
+        if !confirmed {
+            self.output.note("Nothing changed");
+            return Ok(());
+        }
+
+        if let Some(stash) = self.stash {
+            let mut names: Vec<String> = original.iter().map(|yak| yak.name.clone()).collect();
+            for change in &changes {
+                if let Change::Add { name, .. } | Change::Rename { to: name, .. } = change {
+                    names.push(name.clone());
+                }
+            }
+            stash.push("edit-all", &names)?;
+        }
+
+        if let Err(e) = self.apply(&changes) {
+            // Nothing changed, so there is nothing for the snapshot to undo
+            if let Some(stash) = self.stash {
+                stash.discard()?;
+            }
+            anyhow::bail!("{e} - no yaks were changed");
+        }
+        self.output
+            .success(&format!("Applied {} changes", changes.len()));
+
+        Ok(())
+    }
+
+    /// Apply changes in an order that keeps names valid along the way:
+    /// renames (parents first), removals (children first), then additions and state changes in buffer order
+    /// Logged once as "edit-all", and put back if any change fails
+    fn apply(&self, changes: &[Change]) -> Result<()> {
+        transaction::apply(self.storage, self.log, "edit-all", || {
+            self.apply_steps(changes)
+        })
+    }
+
+    fn apply_steps(&self, changes: &[Change]) -> Result<()> {
+        let deferred = DeferredLog::default();
+        let mut renames: Vec<(String, String)> = changes
+            .iter()
+            .filter_map(|change| match change {
+                Change::Rename { from, to } => Some((from.clone(), to.clone())),
+                _ => None,
+            })
+            .collect();
+        renames.sort_by_key(|(from, _)| from.matches('/').count());
+
+        // Moving a parent moves its children, so later names are followed through each rename
+        let mut applied: Vec<(String, String)> = Vec::new();
+        let current = |name: &str, applied: &[(String, String)]| {
+            applied.iter().fold(name.to_string(), |name, (from, to)| {
+                if name == *from {
+                    to.clone()
+                } else if graph::is_descendant_of(&name, from) {
+                    format!("{to}{}", &name[from.len()..])
+                } else {
+                    name
+                }
+            })
+        };
+
+        for (from, to) in &renames {
+            let from = current(from, &applied);
+            if from == *to {
+                continue;
+            }
+            self.storage.rename_yak(&from, to)?;
+            deferred.log_command(&format!("move {from} {to}"))?;
+            applied.push((from, to.clone()));
+        }
+
+        let mut removals: Vec<String> = changes
+            .iter()
+            .filter_map(|change| match change {
+                Change::Remove(name) => Some(current(name, &applied)),
+                _ => None,
+            })
+            .collect();
+        removals.sort_by_key(|name| std::cmp::Reverse(name.matches('/').count()));
+        for name in &removals {
+            let mut use_case =
+                RemoveYak::new(self.storage, self.output, &deferred).with_force(true);
+            if let Some((trash, config)) = self.trash {
+                use_case = use_case.with_trash(trash, config);
+            }
+            use_case.execute(name, false)?;
+        }
+
+        for change in changes {
+            match change {
+                Change::Add { name, done } => {
+                    // A renamed child may already have brought the folder into being
+                    if self.storage.get_yak(name).is_err() {
+                        self.storage.create_yak(name)?;
+                    }
+                    deferred.log_command(&format!("add {name}"))?;
+                    if *done {
+                        self.storage.mark_done(name, true)?;
+                        deferred.log_command(&format!("done {name}"))?;
+                    }
+                }
+                Change::SetDone { name, done } => {
+                    self.storage.mark_done(name, *done)?;
+                    let command = if *done { "done" } else { "done --undo" };
+                    deferred.log_command(&format!("{command} {name}"))?;
+                }
+                Change::Rename { .. } | Change::Remove(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Lay yaks out as an indented checklist, numbering each so edits can be traced back
+fn render_buffer(yaks: &[&Yak], prefix: Option<&str>) -> String {
+    let mut sorted: Vec<&Yak> = yaks.to_vec();
+    sorted.sort_by(|a, b| a.name.cmp(&b.name));
+    let names: HashSet<&str> = sorted.iter().map(|yak| yak.name.as_str()).collect();
+
+    let mut text = match prefix {
+        Some(prefix) => format!("# Yaks under '{prefix}'\n"),
+        None => "# All yaks\n".to_string(),
+    };
+    text.push_str(HELP);
+    text.push('\n');
+
+    let mut depths: HashMap<&str, usize> = HashMap::new();
+    for (index, yak) in sorted.iter().enumerate() {
+        // Nest under the closest listed ancestor; unlisted ones stay part of the label
+        let mut parent = graph::parent_of(&yak.name);
+        while let Some(name) = parent {
+            if names.contains(name) {
+                break;
+            }
+            parent = graph::parent_of(name);
+        }
+
+        let (depth, label) = match parent {
+            Some(parent) => (depths[parent] + 1, &yak.name[parent.len() + 1..]),
+            None => (0, relative(&yak.name, prefix)),
+        };
+        depths.insert(&yak.name, depth);
+
+        let checkbox = if yak.done { "[x]" } else { "[ ]" };
+        text.push_str(&format!(
+            "{}- {checkbox} {label} #{}\n",
+            INDENT.repeat(depth),
+            index + 1
+        ));
+    }
+
+    text
+}
+
+/// Name relative to the prefix being edited
+fn relative<'n>(name: &'n str, prefix: Option<&str>) -> &'n str {
+    prefix
+        .and_then(|prefix| name.strip_prefix(prefix))
+        .and_then(|rest| rest.strip_prefix('/'))
+        .unwrap_or(name)
+}
+
+/// Read the edited buffer back into entries with full names
+fn parse_buffer(text: &str, prefix: Option<&str>) -> Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    // (indent, full name) of the lines enclosing the current one
+    let mut parents: Vec<(usize, String)> = Vec::new();
+
+    for (number, line) in text.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let invalid =
+            || anyhow::anyhow!("line {}: expected '- [ ] name', got '{line}'", number + 1);
+
+        let indent: usize = line[..line.len() - trimmed.len()]
+            .chars()
+            .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+            .sum();
+        let rest = trimmed.strip_prefix("- ").ok_or_else(invalid)?;
+        let (done, label) = if let Some(label) = rest.strip_prefix("[ ]") {
+            (false, label)
+        } else if let Some(label) = rest
+            .strip_prefix("[x]")
+            .or_else(|| rest.strip_prefix("[X]"))
+        {
+            (true, label)
+        } else {
+            return Err(invalid());
+        };
+
+        // A trailing `#<number>` ties the line to the yak it was rendered from
+        let label = label.trim();
+        let (label, id) = match label.rsplit_once(" #") {
+            Some((name, id)) if !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()) => {
+                (name.trim_end(), Some(id.parse::<usize>()?))
+            }
+            _ => (label, None),
+        };
+        if label.is_empty() {
+            return Err(invalid());
+        }
+
+        while parents.last().is_some_and(|(depth, _)| *depth >= indent) {
+            parents.pop();
+        }
+        let name = match (parents.last(), prefix) {
+            (Some((_, parent)), _) => format!("{parent}/{label}"),
+            (None, Some(prefix)) => format!("{prefix}/{label}"),
+            (None, None) => label.to_string(),
+        };
+
+        parents.push((indent, name.clone()));
+        entries.push(Entry { id, name, done });
+    }
+
+    Ok(entries)
+}
+
+/// Compare the edited buffer with the yaks it was rendered from
+fn plan_changes(original: &[&Yak], edited: &str, prefix: Option<&str>) -> Result<Vec<Change>> {
+    let mut sorted: Vec<&Yak> = original.to_vec();
+    sorted.sort_by(|a, b| a.name.cmp(&b.name));
+    let entries = parse_buffer(edited, prefix)?;
+
+    let mut seen_ids = HashSet::new();
+    let mut seen_names = HashSet::new();
+    for entry in &entries {
+        validate_yak_name(&entry.name).map_err(|e| anyhow::anyhow!(e))?;
+        if !seen_names.insert(entry.name.as_str()) {
+            anyhow::bail!("'{}' appears more than once", entry.name);
+        }
+        if let Some(id) = entry.id {
+            if id == 0 || id > sorted.len() {
+                anyhow::bail!("unknown yak #{id} - only add lines without an #id");
+            }
+            if !seen_ids.insert(id) {
+                anyhow::bail!("yak #{id} appears more than once");
+            }
+        }
+    }
+
+    let mut changes = Vec::new();
+    for entry in &entries {
+        match entry.id {
+            Some(id) => {
+                let yak = sorted[id - 1];
+                if yak.name != entry.name {
+                    changes.push(Change::Rename {
+                        from: yak.name.clone(),
+                        to: entry.name.clone(),
+                    });
+                }
+                if yak.done != entry.done {
+                    changes.push(Change::SetDone {
+                        name: entry.name.clone(),
+                        done: entry.done,
+                    });
+                }
+            }
+            None => changes.push(Change::Add {
+                name: entry.name.clone(),
+                done: entry.done,
+            }),
+        }
+    }
+    for (index, yak) in sorted.iter().enumerate() {
+        if !seen_ids.contains(&(index + 1)) {
+            changes.push(Change::Remove(yak.name.clone()));
+        }
+    }
+
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::ports::{LogEntry, TrashEntry};
+    use std::cell::RefCell;
+
+    struct MockOutput;
+
+    impl OutputPort for MockOutput {
+        fn success(&self, _message: &str) {}
+        fn error(&self, _message: &str) {}
+        fn info(&self, _message: &str) {}
+
+        fn warn(&self, _message: &str) {}
+    }
+
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    struct MockTrash<'s> {
+        storage: &'s InMemoryStorage,
+        trashed: RefCell<Vec<String>>,
+    }
+
+    impl TrashPort for MockTrash<'_> {
+        fn trash(&self, name: &str) -> Result<()> {
+            self.storage.delete_yak(name)?;
+            self.trashed.borrow_mut().push(name.to_string());
+            Ok(())
+        }
+
+        fn trashed(&self) -> Result<Vec<TrashEntry>> {
+            unimplemented!()
+        }
+
+        fn restore_trashed(&self, _name: &str) -> Result<()> {
+            unimplemented!()
+        }
+    }
+
+    struct MockConfig;
+
+    impl ConfigPort for MockConfig {
+        fn get(&self, _key: &str) -> Option<String> {
+            None
+        }
+
+        fn get_all(&self, _key: &str) -> Vec<String> {
+            Vec::new()
+        }
+    }
+
+    fn storage_with(names: &[(&str, bool)]) -> InMemoryStorage {
+        InMemoryStorage::with_yaks(names.iter().map(|(name, done)| Yak {
+            name: name.to_string(),
+            done: *done,
+            ..Default::default()
+        }))
+    }
+
+    fn names(storage: &InMemoryStorage) -> Vec<(String, bool)> {
+        storage
+            .list_yaks()
+            .unwrap()
+            .into_iter()
+            .map(|yak| (yak.name, yak.done))
+            .collect()
+    }
+
+    fn owned(names: &[(&str, bool)]) -> Vec<(String, bool)> {
+        names
+            .iter()
+            .map(|(name, done)| (name.to_string(), *done))
+            .collect()
+    }
+
+    #[test]
+    fn test_render_buffer_numbers_and_nests_yaks() {
+        let storage = storage_with(&[("app/api", true), ("app", false), ("docs", false)]);
+        let yaks = storage.list_yaks().unwrap();
+        let refs: Vec<&Yak> = yaks.iter().collect();
+
+        let buffer = render_buffer(&refs, None);
+
+        let lines: Vec<&str> = buffer.lines().filter(|l| !l.starts_with('#')).collect();
+        assert_eq!(
+            lines,
+            vec!["", "- [ ] app #1", "  - [x] api #2", "- [ ] docs #3"]
+        );
+    }
+
+    #[test]
+    fn test_plan_detects_every_kind_of_change() {
+        let storage = storage_with(&[("app", false), ("app/api", false), ("old", false)]);
+        let yaks = storage.list_yaks().unwrap();
+        let refs: Vec<&Yak> = yaks.iter().collect();
+        let edited = "- [ ] web #1\n  - [x] api #2\n  - [ ] docs\n";
+
+        let changes = plan_changes(&refs, edited, None).unwrap();
+
+        assert_eq!(
+            changes.iter().map(Change::describe).collect::<Vec<_>>(),
+            vec![
+                "move 'app' -> 'web'",
+                "move 'app/api' -> 'web/api'",
+                "mark 'web/api' done",
+                "add 'web/docs'",
+                "remove 'old'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_follows_renamed_parents() {
+        let storage = storage_with(&[("app", false), ("app/api", false), ("old", false)]);
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = EditAll::new(&storage, &MockOutput, &log);
+        let yaks = storage.list_yaks().unwrap();
+        let refs: Vec<&Yak> = yaks.iter().collect();
+        let changes = plan_changes(
+            &refs,
+            "- [ ] web #1\n  - [x] api #2\n  - [ ] docs\n- [ ] old/kept\n",
+            None,
+        );
+        // "old" was deleted, so adding "old/kept" creates it afresh as a parent
+        let changes = changes.unwrap();
+
+        use_case.apply(&changes).unwrap();
+
+        assert_eq!(
+            names(&storage),
+            owned(&[
+                ("old", false),
+                ("old/kept", false),
+                ("web", false),
+                ("web/api", true),
+                ("web/docs", false)
+            ])
+        );
+        assert_eq!(*log.commands.borrow(), vec!["edit-all"]);
+    }
+
+    #[test]
+    fn test_apply_puts_everything_back_when_a_change_fails() {
+        let storage = storage_with(&[("app", false), ("old", false)]);
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = EditAll::new(&storage, &MockOutput, &log);
+        let changes = vec![
+            Change::Rename {
+                from: "app".to_string(),
+                to: "web".to_string(),
+            },
+            Change::Remove("old".to_string()),
+            Change::SetDone {
+                name: "missing".to_string(),
+                done: true,
+            },
+        ];
+
+        assert!(use_case.apply(&changes).is_err());
+
+        assert_eq!(names(&storage), owned(&[("app", false), ("old", false)]));
+        assert!(log.commands.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_apply_removes_into_the_trash_children_first() {
+        let storage = storage_with(&[("app", false), ("old", false), ("old/child", false)]);
+        let trash = MockTrash {
+            storage: &storage,
+            trashed: RefCell::new(Vec::new()),
+        };
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = EditAll::new(&storage, &MockOutput, &log).with_trash(&trash, &MockConfig);
+        let changes = vec![
+            Change::Remove("old".to_string()),
+            Change::Remove("old/child".to_string()),
+        ];
+
+        use_case.apply(&changes).unwrap();
+
+        assert_eq!(names(&storage), owned(&[("app", false)]));
+        assert_eq!(*trash.trashed.borrow(), vec!["old/child", "old"]);
+    }
+
+    #[test]
+    fn test_plan_with_prefix_uses_relative_names() {
+        let storage = storage_with(&[("app", false), ("app/api", false)]);
+        let yaks = storage.list_yaks().unwrap();
+        let refs = graph::descendants_of(&yaks, "app");
+
+        let buffer = render_buffer(&refs, Some("app"));
+        assert!(buffer.contains("\n- [ ] api #1\n"));
+
+        let changes = plan_changes(&refs, "- [ ] rest #1\n", Some("app")).unwrap();
+        assert_eq!(
+            changes,
+            vec![Change::Rename {
+                from: "app/api".to_string(),
+                to: "app/rest".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_plan_rejects_bad_buffers() {
+        let storage = storage_with(&[("a", false)]);
+        let yaks = storage.list_yaks().unwrap();
+        let refs: Vec<&Yak> = yaks.iter().collect();
+
+        assert!(plan_changes(&refs, "a #1\n", None).is_err());
+        assert!(plan_changes(&refs, "- [ ] a #7\n", None).is_err());
+        assert!(plan_changes(&refs, "- [ ] a #1\n- [ ] b #1\n", None).is_err());
+        assert!(plan_changes(&refs, "- [ ] a #1\n- [ ] a\n", None).is_err());
+        assert!(plan_changes(&refs, "- [ ] what? #1\n", None).is_err());
+    }
+}
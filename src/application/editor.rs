@@ -0,0 +1,52 @@
+// Editor helpers - text the user edits in $EDITOR, or pipes in on stdin
+
+use anyhow::{Context, Result};
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process::Command;
+
+/// Open `initial_content` in $EDITOR (vi by default) and return the saved text
+pub(super) fn edit_in_editor(initial_content: &str) -> Result<String> {
+    // Create a temporary file with the current content
+    let temp_file = tempfile::NamedTempFile::new().context("Failed to create temporary file")?;
+    let temp_path = temp_file.path();
+
+    // Write current content to temp file
+    fs::write(temp_path, initial_content)
+        .context("Failed to write initial content to temp file")?;
+
+    open_in_editor(temp_path)?;
+
+    // Read edited content
+    let content = fs::read_to_string(temp_path).context("Failed to read edited content")?;
+
+    Ok(content)
+}
+
+/// Open a file in $EDITOR (vi by default) and wait for it to close
+pub(super) fn open_in_editor(path: &Path) -> Result<()> {
+    // Get editor from environment or default to vi
+    let editor = env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+
+    // Launch editor
+    let status = Command::new(&editor)
+        .arg(path)
+        .status()
+        .context(format!("Failed to launch editor: {editor}"))?;
+
+    if !status.success() {
+        anyhow::bail!("Editor exited with non-zero status");
+    }
+    Ok(())
+}
+
+/// Read everything piped in on stdin
+pub(super) fn read_stdin() -> Result<String> {
+    let mut buffer = String::new();
+    io::stdin()
+        .read_to_string(&mut buffer)
+        .context("Failed to read from stdin")?;
+    Ok(buffer)
+}
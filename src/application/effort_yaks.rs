@@ -0,0 +1,208 @@
+// EffortYaks use case - groups open yaks into S/M/L buckets by estimated effort
+
+use crate::domain::effort::{EffortBucket, Estimate};
+use crate::domain::{graph, Yak};
+use crate::ports::{OutputPort, StoragePort};
+use anyhow::Result;
+use std::collections::BTreeMap;
+
+/// Context longer than this suggests more than a quick fix
+const LONG_CONTEXT_CHARS: usize = 280;
+
+pub struct EffortYaks<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+}
+
+impl<'a> EffortYaks<'a> {
+    pub fn new(storage: &'a dyn StoragePort, output: &'a dyn OutputPort) -> Self {
+        Self { storage, output }
+    }
+
+    /// Show open yaks grouped by size; with `max`, only yaks estimated at most that long
+    pub fn execute(&self, max: Option<&str>) -> Result<()> {
+        let max = max
+            .map(Estimate::parse)
+            .transpose()
+            .map_err(|e| anyhow::anyhow!(e))?;
+
+        let yaks = self.storage.list_yaks_with_context()?;
+        let open: Vec<&Yak> = yaks.iter().filter(|yak| !yak.done).collect();
+        if open.is_empty() {
+            self.output.note("You have no yaks. Are you done?");
+            return Ok(());
+        }
+
+        // Bucket -> (estimate, name), guesses sort after real estimates
+        let mut buckets: BTreeMap<EffortBucket, Vec<(Option<Estimate>, &str)>> = BTreeMap::new();
+        for yak in open {
+            let estimate = self
+                .storage
+                .read_field(&yak.name, "estimate")?
+                .and_then(|text| Estimate::parse(&text).ok());
+
+            let bucket = match (estimate, max) {
+                (Some(estimate), Some(max)) if estimate > max => continue,
+                (Some(estimate), _) => estimate.bucket(),
+                (None, Some(_)) => continue,
+                (None, None) => guess_bucket(yak, &yaks),
+            };
+            buckets
+                .entry(bucket)
+                .or_default()
+                .push((estimate, yak.name.as_str()));
+        }
+
+        if buckets.is_empty() {
+            if let Some(max) = max {
+                self.output
+                    .note(&format!("No open yaks estimated at {max} or less"));
+            }
+            return Ok(());
+        }
+
+        for (bucket, mut entries) in buckets {
+            entries.sort_by(|a, b| (a.0.is_none(), a.0, a.1).cmp(&(b.0.is_none(), b.0, b.1)));
+            self.output.info(bucket.label());
+            for (estimate, name) in entries {
+                let size = estimate.map_or("guess".to_string(), |e| e.to_string());
+                self.output.info(&format!("  - {name} [{size}]"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Guess the size of an unestimated yak from its open sub-tasks and context
+fn guess_bucket(yak: &Yak, all: &[Yak]) -> EffortBucket {
+    let open_descendants = graph::descendants_of(all, &yak.name)
+        .iter()
+        .filter(|descendant| !descendant.done)
+        .count();
+    let context_len = yak.context.as_deref().map_or(0, |c| c.trim().len());
+
+    match open_descendants {
+        0 if context_len <= LONG_CONTEXT_CHARS => EffortBucket::Small,
+        0..=2 => EffortBucket::Medium,
+        _ => EffortBucket::Large,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use std::cell::RefCell;
+
+    fn add_yak(storage: &InMemoryStorage, yak: Yak, estimate: Option<&str>) {
+        let name = yak.name.clone();
+        storage.add_yak(yak);
+        storage.write_field(&name, "estimate", estimate).unwrap();
+    }
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl MockOutput {
+        fn new() -> Self {
+            Self {
+                messages: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn get_messages(&self) -> Vec<String> {
+            self.messages.borrow().clone()
+        }
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    fn sample_storage() -> InMemoryStorage {
+        let storage = InMemoryStorage::new();
+        add_yak(&storage, Yak::new("typo".to_string()), Some("15m\n"));
+        add_yak(&storage, Yak::new("docs".to_string()), Some("30m\n"));
+        add_yak(&storage, Yak::new("api".to_string()), Some("3h\n"));
+        add_yak(&storage, Yak::new("rewrite".to_string()), Some("2d\n"));
+        add_yak(&storage, Yak::new("unsized".to_string()), None);
+        add_yak(
+            &storage,
+            Yak::new("shipped".to_string()).mark_done(),
+            Some("10m\n"),
+        );
+        storage
+    }
+
+    #[test]
+    fn test_effort_groups_open_yaks_by_bucket() {
+        let storage = sample_storage();
+        let output = MockOutput::new();
+        let use_case = EffortYaks::new(&storage, &output);
+
+        use_case.execute(None).unwrap();
+
+        assert_eq!(
+            output.get_messages(),
+            vec![
+                "S (up to 1h)",
+                "  - typo [15m]",
+                "  - docs [30m]",
+                "  - unsized [guess]",
+                "M (1h to 4h)",
+                "  - api [3h]",
+                "L (over 4h)",
+                "  - rewrite [16h]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_effort_max_shows_only_small_enough_yaks() {
+        let storage = sample_storage();
+        let output = MockOutput::new();
+        let use_case = EffortYaks::new(&storage, &output);
+
+        use_case.execute(Some("30m")).unwrap();
+
+        assert_eq!(
+            output.get_messages(),
+            vec!["S (up to 1h)", "  - typo [15m]", "  - docs [30m]"]
+        );
+    }
+
+    #[test]
+    fn test_effort_guesses_from_open_children() {
+        let storage = InMemoryStorage::new();
+        add_yak(&storage, Yak::new("big".to_string()), None);
+        for child in ["a", "b", "c"] {
+            add_yak(&storage, Yak::new(format!("big/{child}")), Some("10m"));
+        }
+        let output = MockOutput::new();
+        let use_case = EffortYaks::new(&storage, &output);
+
+        use_case.execute(None).unwrap();
+
+        let messages = output.get_messages();
+        assert_eq!(messages[messages.len() - 2], "L (over 4h)");
+        assert_eq!(messages[messages.len() - 1], "  - big [guess]");
+    }
+}
@@ -0,0 +1,379 @@
+// EscalateYaks use case - raises the priority of yaks left open too long or past their due date
+
+use super::history::History;
+use crate::domain::{Priority, Yak};
+use crate::ports::{ConfigPort, LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+use chrono::{DateTime, Duration, Local, Utc};
+
+/// Config key: open yaks go up one priority level every this many days
+pub const ESCALATE_OPEN_DAYS_KEY: &str = "yaks.escalate.openDays";
+/// Config key: priority that overdue yaks are raised to (e.g. P0)
+pub const ESCALATE_OVERDUE_KEY: &str = "yaks.escalate.overdue";
+
+/// Field recording when a yak was last escalated, so it isn't bumped again right away
+const ESCALATED_FIELD: &str = "escalated";
+
+/// Escalation rules read from config
+struct Rules {
+    open_days: Option<i64>,
+    overdue: Option<Priority>,
+}
+
+pub struct EscalateYaks<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+    config: &'a dyn ConfigPort,
+}
+
+impl<'a> EscalateYaks<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+        config: &'a dyn ConfigPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            log,
+            config,
+        }
+    }
+
+    /// Apply the escalation rules, failing if none are configured
+    pub fn execute(&self) -> Result<()> {
+        let Some(rules) = self.rules()? else {
+            anyhow::bail!(
+                "no escalation rules configured - set {ESCALATE_OPEN_DAYS_KEY} or {ESCALATE_OVERDUE_KEY}"
+            );
+        };
+        if self.escalate(&rules)? == 0 {
+            self.output.note("No yaks needed escalating");
+        }
+        Ok(())
+    }
+
+    /// Apply the escalation rules if any are configured, quietly otherwise (used by sync)
+    pub fn execute_if_configured(&self) -> Result<()> {
+        if let Some(rules) = self.rules()? {
+            self.escalate(&rules)?;
+        }
+        Ok(())
+    }
+
+    fn rules(&self) -> Result<Option<Rules>> {
+        let open_days = self
+            .config
+            .get(ESCALATE_OPEN_DAYS_KEY)
+            .map(|value| {
+                value
+                    .trim()
+                    .parse::<i64>()
+                    .ok()
+                    .filter(|days| *days > 0)
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "{ESCALATE_OPEN_DAYS_KEY} must be a whole number of days, got '{value}'"
+                        )
+                    })
+            })
+            .transpose()?;
+        let overdue = self
+            .config
+            .get(ESCALATE_OVERDUE_KEY)
+            .map(|value| {
+                Priority::parse(&value).map_err(|e| anyhow::anyhow!("{ESCALATE_OVERDUE_KEY}: {e}"))
+            })
+            .transpose()?;
+
+        if open_days.is_none() && overdue.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(Rules { open_days, overdue }))
+    }
+
+    /// Raise priorities per the rules, returning how many yaks changed
+    fn escalate(&self, rules: &Rules) -> Result<usize> {
+        let yaks = self.storage.list_yaks()?;
+        let history = match rules.open_days {
+            Some(_) => History::replay(&self.log.entries()?, &yaks),
+            None => History::default(),
+        };
+        let now = Utc::now();
+        let today = now.with_timezone(&Local).date_naive();
+
+        let mut escalated = 0;
+        for yak in yaks.iter().filter(|yak| !yak.done) {
+            let current = yak.priority.unwrap_or(Priority::LOWEST);
+
+            let overdue = rules
+                .overdue
+                .filter(|target| yak.due.is_some_and(|due| due < today) && *target < current)
+                .map(|target| (target, "overdue".to_string()));
+            let stale = || {
+                let days = rules.open_days?;
+                let since = self.open_since(yak, &history)?;
+                let age = now - since;
+                (age >= Duration::days(days) && current > Priority::HIGHEST)
+                    .then(|| (current.raise(), format!("open {} days", age.num_days())))
+            };
+
+            if let Some((priority, reason)) = overdue.or_else(stale) {
+                self.storage
+                    .write_field(&yak.name, "priority", Some(&format!("{priority}\n")))?;
+                self.storage.write_field(
+                    &yak.name,
+                    ESCALATED_FIELD,
+                    Some(&format!("{}\n", now.to_rfc3339())),
+                )?;
+                self.log
+                    .log_command(&format!("escalate {} {priority}", yak.name))?;
+                self.output.info(&format!(
+                    "Escalated '{}' to {priority} ({reason})",
+                    yak.name
+                ));
+                escalated += 1;
+            }
+        }
+
+        Ok(escalated)
+    }
+
+    /// When the yak's current open stretch started: its last escalation, else its creation
+    fn open_since(&self, yak: &Yak, history: &History) -> Option<DateTime<Utc>> {
+        let escalated = self
+            .storage
+            .read_field(&yak.name, ESCALATED_FIELD)
+            .ok()
+            .flatten()
+            .and_then(|text| DateTime::parse_from_rfc3339(text.trim()).ok())
+            .map(|time| time.with_timezone(&Utc));
+        escalated.or_else(|| history.created.get(&yak.name).copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::ports::LogEntry;
+    use chrono::NaiveDate;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl MockOutput {
+        fn new() -> Self {
+            Self {
+                messages: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn get_messages(&self) -> Vec<String> {
+            self.messages.borrow().clone()
+        }
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockLog {
+        entries: Vec<LogEntry>,
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl MockLog {
+        /// A log in which each yak was added `days` ago
+        fn with_added(added: &[(&str, i64)]) -> Self {
+            Self {
+                entries: added
+                    .iter()
+                    .map(|(name, days)| LogEntry {
+                        command: format!("add {name}"),
+                        time: Utc::now() - Duration::days(*days),
+                    })
+                    .collect(),
+                commands: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            Ok(self.entries.clone())
+        }
+    }
+
+    struct MockConfig {
+        values: HashMap<&'static str, &'static str>,
+    }
+
+    impl MockConfig {
+        fn with(values: &[(&'static str, &'static str)]) -> Self {
+            Self {
+                values: values.iter().copied().collect(),
+            }
+        }
+    }
+
+    impl ConfigPort for MockConfig {
+        fn get(&self, key: &str) -> Option<String> {
+            self.values.get(key).map(|value| value.to_string())
+        }
+
+        fn get_all(&self, _key: &str) -> Vec<String> {
+            unimplemented!()
+        }
+    }
+
+    fn yak(name: &str, priority: Option<&str>) -> Yak {
+        Yak {
+            name: name.to_string(),
+            priority: priority.map(|p| Priority::parse(p).unwrap()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_escalate_bumps_yaks_open_too_long() {
+        let storage = InMemoryStorage::with_yaks(vec![
+            yak("old", Some("P2")),
+            yak("unset", None),
+            yak("fresh", Some("P2")),
+            yak("urgent", Some("P0")),
+        ]);
+        let output = MockOutput::new();
+        let log = MockLog::with_added(&[("old", 45), ("unset", 31), ("fresh", 3), ("urgent", 90)]);
+        let config = MockConfig::with(&[(ESCALATE_OPEN_DAYS_KEY, "30")]);
+        let use_case = EscalateYaks::new(&storage, &output, &log, &config);
+
+        use_case.execute().unwrap();
+
+        assert_eq!(
+            storage.read_field("old", "priority").unwrap(),
+            Some("P1\n".to_string())
+        );
+        assert_eq!(
+            storage.read_field("unset", "priority").unwrap(),
+            Some("P2\n".to_string())
+        );
+        assert_eq!(
+            storage.read_field("fresh", "priority").unwrap(),
+            Some("P2\n".to_string())
+        );
+        assert_eq!(
+            storage.read_field("urgent", "priority").unwrap(),
+            Some("P0\n".to_string())
+        );
+        assert!(storage
+            .read_field("old", ESCALATED_FIELD)
+            .unwrap()
+            .is_some());
+        assert_eq!(
+            *log.commands.borrow(),
+            vec!["escalate old P1", "escalate unset P2"]
+        );
+        assert_eq!(
+            output.get_messages(),
+            vec![
+                "Escalated 'old' to P1 (open 45 days)",
+                "Escalated 'unset' to P2 (open 31 days)",
+            ]
+        );
+
+        // Escalating again right away changes nothing
+        use_case.execute().unwrap();
+        assert_eq!(
+            storage.read_field("old", "priority").unwrap(),
+            Some("P1\n".to_string())
+        );
+        assert_eq!(
+            output.get_messages().last().unwrap(),
+            "No yaks needed escalating"
+        );
+    }
+
+    #[test]
+    fn test_escalate_raises_overdue_yaks() {
+        let overdue = Yak {
+            due: NaiveDate::from_ymd_opt(2020, 1, 1),
+            ..yak("late", Some("P3"))
+        };
+        let upcoming = Yak {
+            due: NaiveDate::from_ymd_opt(2999, 1, 1),
+            ..yak("later", Some("P3"))
+        };
+        let storage = InMemoryStorage::with_yaks(vec![overdue, upcoming]);
+        let output = MockOutput::new();
+        let log = MockLog::with_added(&[]);
+        let config = MockConfig::with(&[(ESCALATE_OVERDUE_KEY, "P0")]);
+        let use_case = EscalateYaks::new(&storage, &output, &log, &config);
+
+        use_case.execute().unwrap();
+
+        assert_eq!(
+            storage.read_field("late", "priority").unwrap(),
+            Some("P0\n".to_string())
+        );
+        assert_eq!(
+            storage.read_field("later", "priority").unwrap(),
+            Some("P3\n".to_string())
+        );
+        assert_eq!(
+            output.get_messages(),
+            vec!["Escalated 'late' to P0 (overdue)"]
+        );
+    }
+
+    #[test]
+    fn test_escalate_requires_rules_unless_automatic() {
+        let storage = InMemoryStorage::with_yaks(vec![yak("old", None)]);
+        let output = MockOutput::new();
+        let log = MockLog::with_added(&[("old", 400)]);
+        let config = MockConfig::with(&[]);
+        let use_case = EscalateYaks::new(&storage, &output, &log, &config);
+
+        assert!(use_case.execute().is_err());
+        use_case.execute_if_configured().unwrap();
+        assert!(output.get_messages().is_empty());
+        assert!(log.commands.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_escalate_rejects_invalid_rules() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        let log = MockLog::with_added(&[]);
+        let config = MockConfig::with(&[(ESCALATE_OVERDUE_KEY, "urgent")]);
+        let use_case = EscalateYaks::new(&storage, &output, &log, &config);
+
+        assert!(use_case.execute_if_configured().is_err());
+    }
+}
@@ -0,0 +1,119 @@
+// EstimateYak use case - records how long a yak is expected to take
+
+use crate::domain::effort::Estimate;
+use crate::ports::{LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+
+pub struct EstimateYak<'a> {
+    storage: &'a dyn StoragePort,
+    log: &'a dyn LogPort,
+}
+
+impl<'a> EstimateYak<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        _output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+    ) -> Self {
+        Self { storage, log }
+    }
+
+    /// Set the estimate (e.g. "30m", "2h"), or clear it when `estimate` is None
+    pub fn execute(&self, name: &str, estimate: Option<&str>) -> Result<()> {
+        let estimate = estimate
+            .map(Estimate::parse)
+            .transpose()
+            .map_err(|e| anyhow::anyhow!(e))?;
+
+        // Resolve yak name (exact or fuzzy match)
+        let resolved_name = self.storage.find_yak(name)?;
+
+        match estimate {
+            Some(estimate) => {
+                self.storage.write_field(
+                    &resolved_name,
+                    "estimate",
+                    Some(&format!("{estimate}\n")),
+                )?;
+                self.log
+                    .log_command(&format!("estimate {resolved_name} {estimate}"))?;
+            }
+            None => {
+                self.storage.write_field(&resolved_name, "estimate", None)?;
+                self.log
+                    .log_command(&format!("estimate --clear {resolved_name}"))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::ports::LogEntry;
+    use std::cell::RefCell;
+
+    struct MockOutput;
+
+    impl OutputPort for MockOutput {
+        fn success(&self, _message: &str) {}
+        fn error(&self, _message: &str) {}
+        fn info(&self, _message: &str) {}
+
+        fn warn(&self, _message: &str) {}
+    }
+
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_estimate_yak_sets_and_clears_estimate() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("docs").unwrap();
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = EstimateYak::new(&storage, &MockOutput, &log);
+
+        use_case.execute("docs", Some("90m")).unwrap();
+        assert_eq!(
+            storage.read_field("docs", "estimate").unwrap(),
+            Some("1h30m\n".to_string())
+        );
+
+        use_case.execute("docs", None).unwrap();
+        assert_eq!(storage.read_field("docs", "estimate").unwrap(), None);
+        assert_eq!(
+            *log.commands.borrow(),
+            vec!["estimate docs 1h30m", "estimate --clear docs"]
+        );
+    }
+
+    #[test]
+    fn test_estimate_yak_rejects_invalid_estimate() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("docs").unwrap();
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = EstimateYak::new(&storage, &MockOutput, &log);
+
+        assert!(use_case.execute("docs", Some("soon")).is_err());
+        assert!(log.commands.borrow().is_empty());
+    }
+}
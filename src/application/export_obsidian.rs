@@ -0,0 +1,281 @@
+// ExportObsidian use case - writes one markdown note per yak into an Obsidian vault
+
+use crate::domain::graph;
+use crate::domain::Yak;
+use crate::ports::{OutputPort, StoragePort};
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Folder inside the vault that holds the exported notes
+const VAULT_FOLDER: &str = "yaks";
+
+pub struct ExportObsidian<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+}
+
+impl<'a> ExportObsidian<'a> {
+    pub fn new(storage: &'a dyn StoragePort, output: &'a dyn OutputPort) -> Self {
+        Self { storage, output }
+    }
+
+    /// Export all yaks into `<vault>/yaks/`
+    /// Only notes whose content changed are rewritten, and notes exported for
+    /// yaks that no longer exist are removed, so repeated exports stay incremental.
+    pub fn execute(&self, vault: &Path) -> Result<()> {
+        if !vault.is_dir() {
+            anyhow::bail!("vault directory '{}' not found", vault.display());
+        }
+
+        let root = vault.join(VAULT_FOLDER);
+        let yaks = self.storage.list_yaks_with_context()?;
+
+        let mut written = HashSet::new();
+        let mut updated = 0;
+        for yak in &yaks {
+            let path = note_path(&root, &yak.name);
+            let note = render_note(yak, &yaks);
+
+            if fs::read_to_string(&path).ok().as_deref() != Some(note.as_str()) {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent).with_context(|| {
+                        format!("Failed to create folder for note '{}'", yak.name)
+                    })?;
+                }
+                fs::write(&path, &note)
+                    .with_context(|| format!("Failed to write note for '{}'", yak.name))?;
+                updated += 1;
+            }
+            written.insert(path);
+        }
+
+        let removed = remove_stale_notes(&root, &written)?;
+
+        self.output.info(&format!(
+            "Exported {} yaks to {} ({updated} updated, {removed} removed)",
+            yaks.len(),
+            root.display()
+        ));
+
+        Ok(())
+    }
+}
+
+/// Path of the note for a yak, mirroring the hierarchy as folders
+fn note_path(root: &Path, name: &str) -> PathBuf {
+    root.join(format!("{name}.md"))
+}
+
+/// Wiki-link to another yak's note, labelled with its leaf name
+fn wiki_link(name: &str) -> String {
+    let leaf = name.rsplit('/').next().unwrap_or(name);
+    format!("[[{VAULT_FOLDER}/{name}|{leaf}]]")
+}
+
+/// Quote a value for YAML front-matter
+fn yaml_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Render a yak as a markdown note with front-matter and hierarchy links
+fn render_note(yak: &Yak, all: &[Yak]) -> String {
+    let mut note = String::from("---\n");
+    note.push_str(&format!("yak: {}\n", yaml_string(&yak.name)));
+    note.push_str(&format!("state: {}\n", yak.current_state()));
+    if let Some(done_at) = yak.done_at.filter(|_| yak.done) {
+        note.push_str(&format!("completed: {}\n", done_at.format("%Y-%m-%d")));
+    }
+    if !yak.links.is_empty() {
+        note.push_str("links:\n");
+        for link in &yak.links {
+            note.push_str(&format!("  - {}\n", yaml_string(link)));
+        }
+    }
+    note.push_str("---\n\n");
+
+    let leaf = yak.name.rsplit('/').next().unwrap_or(&yak.name);
+    note.push_str(&format!("# {leaf}\n\n"));
+
+    if let Some(parent) = graph::parent_of(&yak.name) {
+        note.push_str(&format!("Parent: {}\n\n", wiki_link(parent)));
+    }
+
+    let context = yak.context.as_deref().unwrap_or_default().trim_end();
+    if !context.is_empty() {
+        note.push_str(context);
+        note.push_str("\n\n");
+    }
+
+    let mut children: Vec<&Yak> = graph::children_of(all, &yak.name);
+    children.sort_by(|a, b| a.name.cmp(&b.name));
+    if !children.is_empty() {
+        note.push_str("## Children\n\n");
+        for child in children {
+            let checkbox = if child.done { "[x]" } else { "[ ]" };
+            note.push_str(&format!("- {checkbox} {}\n", wiki_link(&child.name)));
+        }
+        note.push('\n');
+    }
+
+    format!("{}\n", note.trim_end())
+}
+
+/// Whether the note at `path` is one `render_note` wrote: its front-matter
+/// opens with the `yak:` key, which hand-written notes don't have
+fn is_exported(path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|text| text.starts_with("---\nyak: "))
+}
+
+/// Remove exported notes for yaks that no longer exist, returning how many
+/// were removed; other notes in the folder are the user's and stay
+fn remove_stale_notes(root: &Path, keep: &HashSet<PathBuf>) -> Result<usize> {
+    if !root.exists() {
+        return Ok(0);
+    }
+
+    let stale: Vec<PathBuf> = WalkDir::new(root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.path().to_path_buf())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+        .filter(|path| !keep.contains(path) && is_exported(path))
+        .collect();
+
+    for path in &stale {
+        fs::remove_file(path)
+            .with_context(|| format!("Failed to remove stale note {}", path.display()))?;
+    }
+
+    Ok(stale.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use std::cell::RefCell;
+    use tempfile::TempDir;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl MockOutput {
+        fn new() -> Self {
+            Self {
+                messages: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn last_message(&self) -> Option<String> {
+            self.messages.borrow().last().cloned()
+        }
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    #[test]
+    fn test_render_note_includes_front_matter_and_links() {
+        let parent = Yak::new("app".to_string());
+        let child = Yak::new("app/api".to_string())
+            .with_context("Build the API".to_string())
+            .with_links(vec!["https://example.com/pr/1".to_string()]);
+        let all = vec![parent.clone(), child.clone()];
+
+        assert_eq!(
+            render_note(&child, &all),
+            "---\nyak: \"app/api\"\nstate: todo\nlinks:\n  - \"https://example.com/pr/1\"\n---\n\n\
+             # api\n\nParent: [[yaks/app|app]]\n\nBuild the API\n"
+        );
+        assert_eq!(
+            render_note(&parent, &all),
+            "---\nyak: \"app\"\nstate: todo\n---\n\n# app\n\n## Children\n\n- [ ] [[yaks/app/api|api]]\n"
+        );
+    }
+
+    #[test]
+    fn test_export_writes_notes_incrementally() {
+        let vault = TempDir::new().unwrap();
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("app".to_string()));
+        storage.add_yak(Yak::new("app/api".to_string()));
+        let output = MockOutput::new();
+        let use_case = ExportObsidian::new(&storage, &output);
+
+        use_case.execute(vault.path()).unwrap();
+        assert!(vault.path().join("yaks/app.md").exists());
+        assert!(vault.path().join("yaks/app/api.md").exists());
+
+        use_case.execute(vault.path()).unwrap();
+        assert!(output
+            .last_message()
+            .unwrap()
+            .ends_with("(0 updated, 0 removed)"));
+
+        storage.delete_yak("app/api").unwrap();
+        use_case.execute(vault.path()).unwrap();
+        assert!(!vault.path().join("yaks/app/api.md").exists());
+        assert!(output
+            .last_message()
+            .unwrap()
+            .ends_with("(1 updated, 1 removed)"));
+    }
+
+    #[test]
+    fn test_export_keeps_hand_written_notes() {
+        let vault = TempDir::new().unwrap();
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("app".to_string()));
+        let output = MockOutput::new();
+        let own_note = vault.path().join("yaks/ideas.md");
+        fs::create_dir_all(own_note.parent().unwrap()).unwrap();
+        fs::write(
+            &own_note,
+            "---\ntags: [later]\n---\n\nA yak to add someday\n",
+        )
+        .unwrap();
+
+        ExportObsidian::new(&storage, &output)
+            .execute(vault.path())
+            .unwrap();
+
+        assert!(own_note.exists());
+        assert!(output
+            .last_message()
+            .unwrap()
+            .ends_with("(1 updated, 0 removed)"));
+    }
+
+    #[test]
+    fn test_export_fails_for_missing_vault() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        let use_case = ExportObsidian::new(&storage, &output);
+
+        let result = use_case.execute(Path::new("/nonexistent/vault"));
+
+        assert!(result.is_err());
+    }
+}
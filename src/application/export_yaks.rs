@@ -0,0 +1,540 @@
+// ExportYaks use case - serializes every yak to JSON, JSON lines, CSV or Markdown for reporting or backup,
+// or yaks with due dates to an iCalendar feed
+
+use super::output_format::{
+    csv_field, json_array, json_line, json_object, json_optional, json_string,
+};
+use super::{ics, OutputFormat, StateLabels};
+use crate::domain::{graph, Yak, YakFilter};
+use crate::ports::{OutputPort, StoragePort};
+use anyhow::{Context, Result};
+use chrono::{Days, Local, Utc};
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Metadata fields exported alongside the yak itself
+const EXPORTED_FIELDS: [&str; 2] = ["estimate", "source"];
+
+/// A yak with its stored metadata, ready to render
+struct Record {
+    yak: Yak,
+    estimate: Option<String>,
+    source: Option<String>,
+}
+
+impl Record {
+    fn state(&self) -> String {
+        self.yak.current_state().to_string()
+    }
+
+    fn completed(&self) -> Option<String> {
+        self.yak.done_at.map(|time| time.to_rfc3339())
+    }
+
+    fn context(&self) -> &str {
+        self.yak.context.as_deref().unwrap_or("")
+    }
+}
+
+pub struct ExportYaks<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    labels: StateLabels,
+}
+
+impl<'a> ExportYaks<'a> {
+    pub fn new(storage: &'a dyn StoragePort, output: &'a dyn OutputPort) -> Self {
+        Self {
+            storage,
+            output,
+            labels: StateLabels::default(),
+        }
+    }
+
+    /// Show states in Markdown with these labels; JSON and CSV keep plain state names for tools
+    pub fn with_state_labels(mut self, labels: StateLabels) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Export yaks as JSON, CSV, Markdown or ICS to `path`, or print them
+    pub fn execute(
+        &self,
+        format: OutputFormat,
+        only: Option<&str>,
+        path: Option<&Path>,
+    ) -> Result<()> {
+        format.ensure(
+            "export",
+            &[
+                OutputFormat::Json,
+                OutputFormat::Jsonl,
+                OutputFormat::Csv,
+                OutputFormat::Markdown,
+                OutputFormat::Ics,
+            ],
+        )?;
+        let render: fn(&[Record], &StateLabels) -> String = match format {
+            OutputFormat::Json => render_json,
+            OutputFormat::Csv => render_csv,
+            OutputFormat::Ics => render_ics,
+            _ => render_markdown,
+        };
+
+        let filter = only
+            .map(YakFilter::parse)
+            .transpose()
+            .map_err(anyhow::Error::msg)?;
+        if format == OutputFormat::Jsonl {
+            return self.export_lines(filter.as_ref(), path);
+        }
+        let all = self.storage.list_yaks_with_context()?;
+        let today = Local::now().date_naive();
+        let mut yaks: Vec<Yak> = all
+            .iter()
+            .filter(|yak| {
+                filter.as_ref().is_none_or(|filter| {
+                    filter.matches(yak, graph::children_done(&all, &yak.name), today)
+                })
+            })
+            .cloned()
+            .collect();
+        // A calendar only has somewhere to put yaks that are due
+        if format == OutputFormat::Ics {
+            yaks.retain(|yak| yak.due.is_some());
+        }
+        yaks.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let records = yaks
+            .into_iter()
+            .map(|yak| self.record(yak))
+            .collect::<Result<Vec<_>>>()?;
+
+        let text = render(&records, &self.labels);
+        match path {
+            Some(path) => {
+                fs::write(path, &text)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+                self.output.success(&format!(
+                    "Exported {} yaks to {}",
+                    records.len(),
+                    path.display()
+                ));
+            }
+            // Only the final newline: an ICS line keeps its CR, so each ends in CRLF
+            None => self.output.info(text.strip_suffix('\n').unwrap_or(&text)),
+        }
+
+        Ok(())
+    }
+
+    /// Export one JSON object per line, in the storage's order, reading and
+    /// writing each yak in turn rather than holding them all
+    fn export_lines(&self, filter: Option<&YakFilter>, path: Option<&Path>) -> Result<()> {
+        // A filter can depend on a yak's children, so it needs every yak's
+        // fields at hand; their contexts are still left unread
+        let all = match filter {
+            Some(_) => self.storage.iter_yaks(false)?.collect::<Result<Vec<_>>>()?,
+            None => Vec::new(),
+        };
+        let today = Local::now().date_naive();
+        let failed = |path: &Path| format!("Failed to write {}", path.display());
+        let mut file = match path {
+            Some(path) => Some(BufWriter::new(
+                fs::File::create(path).with_context(|| failed(path))?,
+            )),
+            None => None,
+        };
+
+        let mut count = 0;
+        for yak in self.storage.iter_yaks(true)? {
+            let yak = yak?;
+            if filter.is_some_and(|filter| {
+                !filter.matches(&yak, graph::children_done(&all, &yak.name), today)
+            }) {
+                continue;
+            }
+            let line = json_line(&json_fields(&self.record(yak)?));
+            match (&mut file, path) {
+                (Some(file), Some(path)) => {
+                    writeln!(file, "{line}").with_context(|| failed(path))?;
+                }
+                _ => self.output.info(&line),
+            }
+            count += 1;
+        }
+
+        if let (Some(mut file), Some(path)) = (file, path) {
+            file.flush().with_context(|| failed(path))?;
+            self.output
+                .success(&format!("Exported {count} yaks to {}", path.display()));
+        }
+        Ok(())
+    }
+
+    /// `yak` with the metadata fields exported alongside it
+    fn record(&self, yak: Yak) -> Result<Record> {
+        let [estimate, source] =
+            EXPORTED_FIELDS.map(|field| self.storage.read_field(&yak.name, field));
+        Ok(Record {
+            estimate: estimate?,
+            source: source?,
+            yak,
+        })
+    }
+}
+
+/// A record's fields as JSON values, in export order
+fn json_fields(record: &Record) -> [(&'static str, String); 9] {
+    let links: Vec<String> = record.yak.links.iter().map(|l| json_string(l)).collect();
+    [
+        ("name", json_string(&record.yak.name)),
+        ("state", json_string(&record.state())),
+        ("completed", json_optional(record.completed().as_deref())),
+        ("title", json_optional(record.yak.title.as_deref())),
+        ("assignee", json_optional(record.yak.assignee.as_deref())),
+        ("estimate", json_optional(record.estimate.as_deref())),
+        ("source", json_optional(record.source.as_deref())),
+        ("links", format!("[{}]", links.join(", "))),
+        ("context", json_string(record.context())),
+    ]
+}
+
+fn render_json(records: &[Record], _labels: &StateLabels) -> String {
+    let objects: Vec<String> = records
+        .iter()
+        .map(|record| json_object(&json_fields(record), 1))
+        .collect();
+
+    format!("{}\n", json_array(&objects, 0))
+}
+
+fn render_csv(records: &[Record], _labels: &StateLabels) -> String {
+    let mut text =
+        String::from("name,state,completed,title,assignee,estimate,source,links,context\n");
+    for record in records {
+        let fields = [
+            record.yak.name.clone(),
+            record.state().to_string(),
+            record.completed().unwrap_or_default(),
+            record.yak.title.clone().unwrap_or_default(),
+            record.yak.assignee.clone().unwrap_or_default(),
+            record.estimate.clone().unwrap_or_default(),
+            record.source.clone().unwrap_or_default(),
+            record.yak.links.join(" "),
+            record.context().to_string(),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        text.push_str(&row.join(","));
+        text.push('\n');
+    }
+    text
+}
+
+/// A calendar with a to-do for task apps and an all-day event for calendar apps
+/// on each yak's due date
+fn render_ics(records: &[Record], _labels: &StateLabels) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//yaks//yx//EN".to_string(),
+        "X-WR-CALNAME:Yaks".to_string(),
+    ];
+    for record in records {
+        let yak = &record.yak;
+        let Some(due) = yak.due else {
+            continue;
+        };
+        // Calendars need a DTSTAMP; the yak's last change keeps re-exports stable
+        let stamp = yak.modified_at.or(yak.created_at).unwrap_or_else(Utc::now);
+        let summary = ics::text(yak.title.as_deref().unwrap_or(&yak.name));
+        let mut common = vec![
+            format!("DTSTAMP:{}", ics::date_time(stamp)),
+            format!("SUMMARY:{summary}"),
+        ];
+        let context = record.context().trim();
+        if !context.is_empty() {
+            common.push(format!("DESCRIPTION:{}", ics::text(context)));
+        }
+        if let Some(link) = yak.links.first() {
+            common.push(format!("URL:{link}"));
+        }
+
+        lines.push("BEGIN:VTODO".to_string());
+        lines.push(format!("UID:{}", ics::text(&format!("{}@yaks", yak.name))));
+        lines.extend(common.iter().cloned());
+        lines.push(format!("DUE;VALUE=DATE:{}", ics::date(due)));
+        if yak.done {
+            lines.push("STATUS:COMPLETED".to_string());
+            if let Some(done_at) = yak.done_at {
+                lines.push(format!("COMPLETED:{}", ics::date_time(done_at)));
+            }
+        } else {
+            lines.push("STATUS:NEEDS-ACTION".to_string());
+        }
+        lines.push("END:VTODO".to_string());
+
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!(
+            "UID:{}",
+            ics::text(&format!("{}-due@yaks", yak.name))
+        ));
+        lines.extend(common);
+        lines.push(format!("DTSTART;VALUE=DATE:{}", ics::date(due)));
+        let next_day = due.checked_add_days(Days::new(1)).unwrap_or(due);
+        lines.push(format!("DTEND;VALUE=DATE:{}", ics::date(next_day)));
+        lines.push("TRANSP:TRANSPARENT".to_string());
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+    ics::content(&lines)
+}
+
+fn render_markdown(records: &[Record], labels: &StateLabels) -> String {
+    let mut text = String::from("# Yaks\n");
+    for record in records {
+        let checkbox = labels.checkbox(record.yak.done);
+        text.push_str(&format!("\n## {checkbox} {}\n\n", record.yak.name));
+
+        let mut details = vec![format!(
+            "- State: {}",
+            labels.word(record.yak.current_state())
+        )];
+        if let Some(completed) = record.completed() {
+            details.push(format!("- Completed: {completed}"));
+        }
+        let optional = [
+            ("Title", record.yak.title.as_deref()),
+            ("Assignee", record.yak.assignee.as_deref()),
+            ("Estimate", record.estimate.as_deref()),
+            ("Source", record.source.as_deref()),
+        ];
+        for (label, value) in optional {
+            if let Some(value) = value {
+                details.push(format!("- {label}: {value}"));
+            }
+        }
+        for link in &record.yak.links {
+            details.push(format!("- Link: <{link}>"));
+        }
+        text.push_str(&details.join("\n"));
+        text.push('\n');
+
+        let context = record.context().trim();
+        if !context.is_empty() {
+            text.push_str(&format!("\n{context}\n"));
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use chrono::{NaiveDate, TimeZone};
+    use std::cell::RefCell;
+    use tempfile::TempDir;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl MockOutput {
+        fn new() -> Self {
+            Self {
+                messages: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn last_message(&self) -> Option<String> {
+            self.messages.borrow().last().cloned()
+        }
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    fn storage() -> InMemoryStorage {
+        InMemoryStorage::with_yaks(vec![
+            Yak {
+                name: "docs".to_string(),
+                done: true,
+                done_at: Some(Utc.with_ymd_and_hms(2026, 3, 2, 9, 30, 0).unwrap()),
+                ..Default::default()
+            },
+            Yak {
+                name: "api".to_string(),
+                context: Some("Needs \"auth\", then tests\n".to_string()),
+                links: vec!["https://example.com/pr/1".to_string()],
+                due: NaiveDate::from_ymd_opt(2026, 3, 5),
+                modified_at: Some(Utc.with_ymd_and_hms(2026, 3, 1, 8, 0, 0).unwrap()),
+                ..Default::default()
+            },
+        ])
+    }
+
+    #[test]
+    fn test_export_json() {
+        let storage = storage();
+        storage.write_field("api", "estimate", Some("2h")).unwrap();
+        let output = MockOutput::new();
+        let use_case = ExportYaks::new(&storage, &output);
+
+        use_case
+            .execute(OutputFormat::Json, Some("not-done"), None)
+            .unwrap();
+
+        assert_eq!(
+            output.last_message().unwrap(),
+            r#"[
+  {
+    "name": "api",
+    "state": "todo",
+    "completed": null,
+    "title": null,
+    "assignee": null,
+    "estimate": "2h",
+    "source": null,
+    "links": ["https://example.com/pr/1"],
+    "context": "Needs \"auth\", then tests\n"
+  }
+]"#
+        );
+    }
+
+    #[test]
+    fn test_export_jsonl_writes_a_line_per_yak() {
+        let storage = storage();
+        let output = MockOutput::new();
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("yaks.jsonl");
+
+        ExportYaks::new(&storage, &output)
+            .execute(OutputFormat::Jsonl, None, Some(&path))
+            .unwrap();
+
+        let text = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with(r#""context": "Needs \"auth\", then tests\n"}"#));
+        assert!(lines[1].starts_with(
+            r#"{"name": "docs", "state": "done", "completed": "2026-03-02T09:30:00+00:00""#
+        ));
+        assert_eq!(
+            output.last_message().unwrap(),
+            format!("Exported 2 yaks to {}", path.display())
+        );
+    }
+
+    #[test]
+    fn test_export_csv_quotes_fields() {
+        let storage = storage();
+        storage.write_field("api", "estimate", Some("2h")).unwrap();
+        let output = MockOutput::new();
+        let use_case = ExportYaks::new(&storage, &output);
+
+        use_case.execute(OutputFormat::Csv, None, None).unwrap();
+
+        assert_eq!(
+            output.last_message().unwrap(),
+            "name,state,completed,title,assignee,estimate,source,links,context\n\
+             api,todo,,,,2h,,https://example.com/pr/1,\"Needs \"\"auth\"\", then tests\n\"\n\
+             docs,done,2026-03-02T09:30:00+00:00,,,,,,"
+        );
+    }
+
+    #[test]
+    fn test_export_markdown_to_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("yaks.md");
+        let storage = storage();
+        let output = MockOutput::new();
+        let use_case = ExportYaks::new(&storage, &output);
+
+        use_case
+            .execute(OutputFormat::Markdown, Some("done"), Some(&path))
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# Yaks\n\n## [x] docs\n\n- State: done\n- Completed: 2026-03-02T09:30:00+00:00\n"
+        );
+        assert_eq!(
+            output.last_message(),
+            Some(format!("Exported 1 yaks to {}", path.display()))
+        );
+    }
+
+    #[test]
+    fn test_export_ics_has_yaks_with_due_dates() {
+        let storage = storage();
+        let output = MockOutput::new();
+        let use_case = ExportYaks::new(&storage, &output);
+
+        use_case.execute(OutputFormat::Ics, None, None).unwrap();
+
+        let lines: Vec<String> = output
+            .last_message()
+            .unwrap()
+            .split('\n')
+            .map(|line| line.trim_end_matches('\r').to_string())
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                "BEGIN:VCALENDAR",
+                "VERSION:2.0",
+                "PRODID:-//yaks//yx//EN",
+                "X-WR-CALNAME:Yaks",
+                "BEGIN:VTODO",
+                "UID:api@yaks",
+                "DTSTAMP:20260301T080000Z",
+                "SUMMARY:api",
+                "DESCRIPTION:Needs \"auth\"\\, then tests",
+                "URL:https://example.com/pr/1",
+                "DUE;VALUE=DATE:20260305",
+                "STATUS:NEEDS-ACTION",
+                "END:VTODO",
+                "BEGIN:VEVENT",
+                "UID:api-due@yaks",
+                "DTSTAMP:20260301T080000Z",
+                "SUMMARY:api",
+                "DESCRIPTION:Needs \"auth\"\\, then tests",
+                "URL:https://example.com/pr/1",
+                "DTSTART;VALUE=DATE:20260305",
+                "DTEND;VALUE=DATE:20260306",
+                "TRANSP:TRANSPARENT",
+                "END:VEVENT",
+                "END:VCALENDAR",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_export_rejects_unknown_format() {
+        let storage = storage();
+        let output = MockOutput::new();
+        let use_case = ExportYaks::new(&storage, &output);
+
+        assert!(use_case.execute(OutputFormat::Table, None, None).is_err());
+    }
+}
@@ -0,0 +1,179 @@
+// FocusYak use case - remembers the yak being worked on, as the default for other commands
+
+use super::list_yaks::numbered_yak;
+use crate::ports::{LocalStatePort, OutputPort, StoragePort};
+use anyhow::Result;
+
+/// Local state key holding the focused yak's name
+pub(super) const FOCUS_KEY: &str = "focus";
+
+pub struct FocusYak<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    state: &'a dyn LocalStatePort,
+}
+
+impl<'a> FocusYak<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        state: &'a dyn LocalStatePort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            state,
+        }
+    }
+
+    /// Focus on `name`, or show the focused yak when no name is given
+    pub fn execute(&self, name: Option<&str>) -> Result<()> {
+        let Some(name) = name else {
+            match focused(self.state)? {
+                Some(focus) => self.output.info(&focus),
+                None => self.output.note("No focused yak"),
+            }
+            return Ok(());
+        };
+
+        // Resolve yak name (exact or fuzzy match)
+        let resolved_name = self.storage.find_yak(name)?;
+        self.state
+            .write(FOCUS_KEY, Some(&format!("{resolved_name}\n")))?;
+        self.output
+            .success(&format!("Focused on '{resolved_name}'"));
+        Ok(())
+    }
+
+    pub fn clear(&self) -> Result<()> {
+        self.state.write(FOCUS_KEY, None)?;
+        self.output.success("Cleared focus");
+        Ok(())
+    }
+}
+
+/// The focused yak, if one is set
+pub(super) fn focused(state: &dyn LocalStatePort) -> Result<Option<String>> {
+    Ok(state
+        .read(FOCUS_KEY)?
+        .map(|text| text.trim().to_string())
+        .filter(|name| !name.is_empty()))
+}
+
+/// The yak a command should act on: `name` when given (a number picks from the
+/// last `yx list --numbered`, unless a yak has that exact name), otherwise the
+/// focused yak
+pub fn name_or_focus(
+    name: &str,
+    storage: &dyn StoragePort,
+    state: &dyn LocalStatePort,
+) -> Result<String> {
+    if let Some(listed) = numbered_yak(name, state)? {
+        if storage.get_yak(name.trim()).is_err() {
+            return Ok(listed);
+        }
+    }
+    if !name.is_empty() {
+        return Ok(name.to_string());
+    }
+    focused(state)?.ok_or_else(|| {
+        anyhow::anyhow!("no yak name given and no focused yak - set one with `yx focus <name>`")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::application::list_yaks::LAST_LIST_KEY;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, _message: &str) {}
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockState {
+        values: RefCell<HashMap<String, String>>,
+    }
+
+    impl LocalStatePort for MockState {
+        fn read(&self, key: &str) -> Result<Option<String>> {
+            Ok(self.values.borrow().get(key).cloned())
+        }
+
+        fn write(&self, key: &str, value: Option<&str>) -> Result<()> {
+            match value {
+                Some(text) => self
+                    .values
+                    .borrow_mut()
+                    .insert(key.to_string(), text.to_string()),
+                None => self.values.borrow_mut().remove(key),
+            };
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_focus_sets_shows_and_clears() {
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let state = MockState {
+            values: RefCell::new(HashMap::new()),
+        };
+        let storage = InMemoryStorage::new();
+        storage.create_yak("api/auth").unwrap();
+        let use_case = FocusYak::new(&storage, &output, &state);
+
+        use_case.execute(Some("auth")).unwrap();
+        use_case.execute(None).unwrap();
+        assert_eq!(name_or_focus("", &storage, &state).unwrap(), "api/auth");
+        assert_eq!(name_or_focus("docs", &storage, &state).unwrap(), "docs");
+
+        use_case.clear().unwrap();
+        use_case.execute(None).unwrap();
+        assert!(name_or_focus("", &storage, &state).is_err());
+        assert_eq!(
+            *output.messages.borrow(),
+            vec![
+                "Focused on 'api/auth'",
+                "api/auth",
+                "Cleared focus",
+                "No focused yak"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_a_yak_named_like_a_number_wins_over_the_list() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("2").unwrap();
+        storage.create_yak("docs").unwrap();
+        let state = MockState {
+            values: RefCell::new(HashMap::from([(
+                LAST_LIST_KEY.to_string(),
+                "docs\nrelease\n".to_string(),
+            )])),
+        };
+
+        assert_eq!(name_or_focus("1", &storage, &state).unwrap(), "docs");
+        assert_eq!(name_or_focus("2", &storage, &state).unwrap(), "2");
+    }
+}
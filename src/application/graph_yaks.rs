@@ -0,0 +1,150 @@
+// GraphYaks use case - renders the yak hierarchy as a graph description (Graphviz DOT)
+
+use super::OutputFormat;
+use crate::domain::graph;
+use crate::domain::Yak;
+use crate::ports::{OutputPort, StoragePort};
+use anyhow::Result;
+
+pub struct GraphYaks<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+}
+
+impl<'a> GraphYaks<'a> {
+    pub fn new(storage: &'a dyn StoragePort, output: &'a dyn OutputPort) -> Self {
+        Self { storage, output }
+    }
+
+    pub fn execute(&self, format: OutputFormat) -> Result<()> {
+        format.ensure("graph", &[OutputFormat::Dot])?;
+
+        let mut yaks = self.storage.list_yaks()?;
+        yaks.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for line in render_dot(&yaks) {
+            self.output.info(&line);
+        }
+
+        Ok(())
+    }
+}
+
+/// Quote an identifier or label for DOT
+fn dot_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// One node per yak labelled with its leaf name, and an edge from each parent to its children
+fn render_dot(yaks: &[Yak]) -> Vec<String> {
+    let mut lines = vec![
+        "digraph yaks {".to_string(),
+        "  rankdir=LR;".to_string(),
+        "  node [shape=box, style=rounded];".to_string(),
+    ];
+
+    for yak in yaks {
+        let leaf = yak.name.rsplit('/').next().unwrap_or(&yak.name);
+        let style = if yak.done {
+            ", style=\"rounded,filled\", color=gray60, fillcolor=gray90, fontcolor=gray50"
+        } else {
+            ""
+        };
+        lines.push(format!(
+            "  {} [label={}{style}];",
+            dot_string(&yak.name),
+            dot_string(leaf)
+        ));
+    }
+
+    for yak in yaks {
+        if let Some(parent) = graph::parent_of(&yak.name) {
+            lines.push(format!(
+                "  {} -> {};",
+                dot_string(parent),
+                dot_string(&yak.name)
+            ));
+        }
+    }
+
+    lines.push("}".to_string());
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl MockOutput {
+        fn new() -> Self {
+            Self {
+                messages: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn get_messages(&self) -> Vec<String> {
+            self.messages.borrow().clone()
+        }
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    #[test]
+    fn test_graph_renders_dot() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("app/api".to_string()).mark_done());
+        storage.add_yak(Yak::new("app".to_string()));
+        storage.add_yak(Yak::new("say \"hi\"".to_string()));
+        let output = MockOutput::new();
+        let use_case = GraphYaks::new(&storage, &output);
+
+        use_case.execute(OutputFormat::Dot).unwrap();
+
+        assert_eq!(
+            output.get_messages(),
+            vec![
+                "digraph yaks {",
+                "  rankdir=LR;",
+                "  node [shape=box, style=rounded];",
+                "  \"app\" [label=\"app\"];",
+                "  \"app/api\" [label=\"api\", style=\"rounded,filled\", color=gray60, fillcolor=gray90, fontcolor=gray50];",
+                "  \"say \\\"hi\\\"\" [label=\"say \\\"hi\\\"\"];",
+                "  \"app\" -> \"app/api\";",
+                "}",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_graph_rejects_unknown_format() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        let use_case = GraphYaks::new(&storage, &output);
+
+        assert!(use_case.execute(OutputFormat::Svg).is_err());
+    }
+}
@@ -0,0 +1,191 @@
+// GrepYaks use case - line-oriented search of yak contexts, grep style
+
+use crate::domain::Pattern;
+use crate::ports::{OutputPort, StoragePort, Style};
+use anyhow::Result;
+
+pub struct GrepYaks<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    regex: bool,
+    ignore_case: bool,
+}
+
+impl<'a> GrepYaks<'a> {
+    pub fn new(storage: &'a dyn StoragePort, output: &'a dyn OutputPort) -> Self {
+        Self {
+            storage,
+            output,
+            regex: false,
+            ignore_case: false,
+        }
+    }
+
+    /// Treat the pattern as a regular expression instead of plain text
+    pub fn with_regex(mut self, regex: bool) -> Self {
+        self.regex = regex;
+        self
+    }
+
+    /// Match regardless of case
+    pub fn with_ignore_case(mut self, ignore_case: bool) -> Self {
+        self.ignore_case = ignore_case;
+        self
+    }
+
+    /// Print `name:line: text` for every context line matching `pattern`
+    /// Finding nothing is an error, so scripts can tell from the exit code.
+    pub fn execute(&self, pattern: &str) -> Result<()> {
+        if pattern.is_empty() {
+            anyhow::bail!("pattern cannot be empty");
+        }
+        let compiled = if self.regex {
+            Pattern::regex(pattern, self.ignore_case).map_err(|e| anyhow::anyhow!(e))?
+        } else {
+            Pattern::literal(pattern, self.ignore_case)
+        };
+
+        let mut yaks = self.storage.list_yaks_with_context()?;
+        yaks.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut found = 0;
+        for yak in &yaks {
+            let Some(context) = &yak.context else {
+                continue;
+            };
+            for (index, line) in context.lines().enumerate() {
+                let matches = compiled.find_all(line);
+                if matches.is_empty() {
+                    continue;
+                }
+                found += 1;
+                self.output
+                    .info(&self.format_line(&yak.name, index + 1, line, &matches));
+            }
+        }
+
+        if found == 0 {
+            anyhow::bail!("no context lines match '{pattern}'");
+        }
+        Ok(())
+    }
+
+    fn format_line(
+        &self,
+        name: &str,
+        number: usize,
+        line: &str,
+        matches: &[(usize, usize)],
+    ) -> String {
+        let mut highlighted = String::new();
+        let mut last = 0;
+        for &(start, end) in matches.iter().filter(|(start, end)| end > start) {
+            highlighted.push_str(&line[last..start]);
+            highlighted.push_str(&self.output.paint(&line[start..end], Style::Match));
+            last = end;
+        }
+        highlighted.push_str(&line[last..]);
+        format!(
+            "{}:{}: {highlighted}",
+            self.output.paint(name, Style::Name),
+            self.output.paint(&number.to_string(), Style::LineNumber)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+        color: bool,
+    }
+
+    impl OutputPort for MockOutput {
+        fn paint(&self, text: &str, style: Style) -> String {
+            if self.color {
+                format!("<{style:?}>{text}</>")
+            } else {
+                text.to_string()
+            }
+        }
+
+        fn success(&self, _message: &str) {}
+
+        fn error(&self, _message: &str) {}
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    fn storage() -> InMemoryStorage {
+        InMemoryStorage::with_yaks([
+            Yak::new("release".to_string())
+                .with_context("Bump to v1.2\nTag it\nAnnounce v1.2 on the blog".to_string()),
+            Yak::new("api/auth".to_string())
+                .with_context("Token expiry is TODO\n\ntodo: refresh".to_string()),
+            Yak::new("docs".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_grep_prints_matching_lines() {
+        let storage = storage();
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+            color: false,
+        };
+        GrepYaks::new(&storage, &output)
+            .with_ignore_case(true)
+            .execute("todo")
+            .unwrap();
+        assert_eq!(
+            *output.messages.borrow(),
+            vec![
+                "api/auth:1: Token expiry is TODO",
+                "api/auth:3: todo: refresh"
+            ]
+        );
+
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+            color: true,
+        };
+        GrepYaks::new(&storage, &output)
+            .with_regex(true)
+            .execute(r"v\d\.\d")
+            .unwrap();
+        assert_eq!(
+            *output.messages.borrow(),
+            vec![
+                "<Name>release</>:<LineNumber>1</>: Bump to <Match>v1.2</>",
+                "<Name>release</>:<LineNumber>3</>: Announce <Match>v1.2</> on the blog",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_grep_fails_without_matches() {
+        let storage = storage();
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+            color: false,
+        };
+
+        assert!(GrepYaks::new(&storage, &output).execute("todo!").is_err());
+        assert!(GrepYaks::new(&storage, &output)
+            .with_regex(true)
+            .execute("v(1")
+            .is_err());
+        assert!(output.messages.borrow().is_empty());
+    }
+}
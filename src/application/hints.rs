@@ -0,0 +1,39 @@
+// Hints - one-line suggestions of what to do next, shown after some commands
+
+use crate::ports::ConfigPort;
+
+/// Config key turning hints off ("false") or on (the default)
+pub const HINTS_KEY: &str = "yaks.hints";
+
+/// Whether hints are on; they are unless `yaks.hints` is false
+pub fn hints_enabled(config: Option<&dyn ConfigPort>) -> bool {
+    config
+        .and_then(|config| config.get(HINTS_KEY))
+        .is_none_or(|value| !matches!(value.trim(), "false" | "no" | "off" | "0"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockConfig(Option<&'static str>);
+
+    impl ConfigPort for MockConfig {
+        fn get(&self, _key: &str) -> Option<String> {
+            self.0.map(|value| value.to_string())
+        }
+
+        fn get_all(&self, _key: &str) -> Vec<String> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_hints_are_on_unless_turned_off() {
+        assert!(hints_enabled(None));
+        assert!(hints_enabled(Some(&MockConfig(None))));
+        assert!(hints_enabled(Some(&MockConfig(Some("true")))));
+        assert!(!hints_enabled(Some(&MockConfig(Some("false")))));
+        assert!(!hints_enabled(Some(&MockConfig(Some("off")))));
+    }
+}
@@ -0,0 +1,127 @@
+// History - when yaks were created and completed, replayed from the command log
+
+use crate::domain::{graph, Yak};
+use crate::ports::LogEntry;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// When each yak was created and last completed, according to the command log
+#[derive(Default)]
+pub(super) struct History {
+    pub(super) created: HashMap<String, DateTime<Utc>>,
+    pub(super) completed: HashMap<String, DateTime<Utc>>,
+}
+
+impl History {
+    /// Replay logged commands, following renames and removals
+    pub(super) fn replay(entries: &[LogEntry], yaks: &[Yak]) -> Self {
+        let mut history = Self::default();
+
+        for entry in entries {
+            let (verb, rest) = entry
+                .command
+                .split_once(' ')
+                .unwrap_or((entry.command.as_str(), ""));
+
+            match verb {
+                "add" => {
+                    history.created.insert(rest.to_string(), entry.time);
+                }
+                "done" => {
+                    if let Some(name) = rest.strip_prefix("--undo ") {
+                        history.completed.remove(name);
+                    } else if let Some(name) = rest.strip_prefix("--recursive ") {
+                        history.completed.insert(name.to_string(), entry.time);
+                        for descendant in graph::descendants_of(yaks, name) {
+                            history
+                                .completed
+                                .insert(descendant.name.clone(), entry.time);
+                        }
+                    } else {
+                        history.completed.insert(rest.to_string(), entry.time);
+                    }
+                }
+                "rm" => {
+                    let name = rest.strip_prefix("--recursive ").unwrap_or(rest);
+                    remove_subtree(&mut history.created, name);
+                    remove_subtree(&mut history.completed, name);
+                }
+                "move" => {
+                    if let Some((from, to)) = history.split_known(rest) {
+                        rename_subtree(&mut history.created, from, to);
+                        rename_subtree(&mut history.completed, from, to);
+                    }
+                }
+                "merge" => {
+                    if let Some((source, target)) = history.split_known(rest) {
+                        let children = format!("{source}/");
+                        rename_subtree(&mut history.created, &children, &format!("{target}/"));
+                        rename_subtree(&mut history.completed, &children, &format!("{target}/"));
+                        history.created.remove(source);
+                        history.completed.remove(source);
+                    }
+                }
+                "cp" => {
+                    let (recursive, rest) = match rest.strip_prefix("--recursive ") {
+                        Some(rest) => (true, rest),
+                        None => (false, rest),
+                    };
+                    if let Some((from, to)) = history.split_known(rest) {
+                        let copied: Vec<String> = history
+                            .created
+                            .keys()
+                            .filter(|name| *name == from || (recursive && in_subtree(name, from)))
+                            .map(|name| format!("{to}{}", &name[from.len()..]))
+                            .collect();
+                        for name in copied {
+                            history.created.insert(name, entry.time);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        history
+    }
+
+    /// When a done yak was completed: its latest logged `done`, else its done marker's time
+    pub(super) fn completed_at(&self, yak: &Yak) -> Option<DateTime<Utc>> {
+        if !yak.done {
+            return None;
+        }
+        self.completed.get(&yak.name).copied().or(yak.done_at)
+    }
+
+    /// Split "<from> <to>" where names may contain spaces, using the source we know about
+    fn split_known<'s>(&self, rest: &'s str) -> Option<(&'s str, &'s str)> {
+        rest.match_indices(' ')
+            .map(|(i, _)| i)
+            .find(|&i| {
+                self.created.contains_key(&rest[..i]) || self.completed.contains_key(&rest[..i])
+            })
+            .map(|i| (&rest[..i], &rest[i + 1..]))
+    }
+}
+
+/// Whether `name` is `root` or nested below it
+fn in_subtree(name: &str, root: &str) -> bool {
+    name == root || graph::is_descendant_of(name, root)
+}
+
+fn remove_subtree(times: &mut HashMap<String, DateTime<Utc>>, root: &str) {
+    times.retain(|name, _| !in_subtree(name, root));
+}
+
+fn rename_subtree(times: &mut HashMap<String, DateTime<Utc>>, from: &str, to: &str) {
+    let moved: Vec<String> = times
+        .keys()
+        .filter(|name| in_subtree(name, from))
+        .cloned()
+        .collect();
+    for name in moved {
+        if let Some(time) = times.remove(&name) {
+            times.insert(format!("{to}{}", &name[from.len()..]), time);
+        }
+    }
+}
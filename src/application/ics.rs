@@ -0,0 +1,73 @@
+// iCalendar - escaping and line folding for the RFC 5545 text that calendar apps read
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Longest content line in octets before it's folded onto the next
+const MAX_LINE: usize = 75;
+
+/// A property value with the characters iCalendar gives meaning escaped
+pub(super) fn text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// A whole-day DATE value, e.g. 20260302
+pub(super) fn date(day: NaiveDate) -> String {
+    day.format("%Y%m%d").to_string()
+}
+
+/// A UTC DATE-TIME value, e.g. 20260302T093000Z
+pub(super) fn date_time(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// `lines` as an iCalendar stream: each ends in CRLF, and long ones continue
+/// on lines starting with a space, never splitting a character
+pub(super) fn content(lines: &[String]) -> String {
+    let mut stream = String::new();
+    for line in lines {
+        let mut width = 0;
+        for c in line.chars() {
+            if width + c.len_utf8() > MAX_LINE {
+                stream.push_str("\r\n ");
+                // The leading space counts towards the continuation's length
+                width = 1;
+            }
+            stream.push(c);
+            width += c.len_utf8();
+        }
+        stream.push_str("\r\n");
+    }
+    stream
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_escapes_and_content_folds() {
+        assert_eq!(text("a;b,c\\d\r\ne"), "a\\;b\\,c\\\\d\\ne");
+
+        let long = format!("DESCRIPTION:{}", "é".repeat(40));
+        let stream = content(&[long, "END:VTODO".to_string()]);
+        let lines: Vec<&str> = stream.split("\r\n").collect();
+        assert!(lines.iter().all(|line| line.len() <= MAX_LINE));
+        assert!(lines[1].starts_with(' '));
+        assert_eq!(lines[2], "END:VTODO");
+        assert_eq!(
+            stream.replace("\r\n ", ""),
+            format!("DESCRIPTION:{}\r\nEND:VTODO\r\n", "é".repeat(40))
+        );
+    }
+}
@@ -0,0 +1,261 @@
+// ImportChecklist use case - creates a yak hierarchy from a TODO.md-style markdown checklist
+
+use crate::domain::checklist::parse_checklist;
+use crate::domain::validate_yak_name;
+use crate::ports::{LogPort, OutputPort, StashPort, StoragePort};
+use anyhow::Result;
+
+pub struct ImportChecklist<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+    stash: Option<&'a dyn StashPort>,
+}
+
+impl<'a> ImportChecklist<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            log,
+            stash: None,
+        }
+    }
+
+    /// Snapshot the affected yaks before importing so `yx stash pop` can undo it
+    pub fn with_stash(mut self, stash: &'a dyn StashPort) -> Self {
+        self.stash = Some(stash);
+        self
+    }
+
+    /// Import the checklist in `text`, read from `source` (a file path, or "-" for stdin)
+    /// With `dry_run`, only report what would change.
+    pub fn execute(&self, text: &str, source: &str, dry_run: bool) -> Result<()> {
+        let items = parse_checklist(text);
+        let command = format!("import --markdown {source}");
+
+        let mut valid = Vec::new();
+        for item in &items {
+            match validate_yak_name(&item.name) {
+                Ok(()) => valid.push(item),
+                Err(e) => self.output.note(&format!("Skipped '{}': {e}", item.name)),
+            }
+        }
+
+        if !dry_run {
+            if let Some(stash) = self.stash {
+                let names: Vec<String> = valid.iter().map(|item| item.name.clone()).collect();
+                if !names.is_empty() {
+                    stash.push(&command, &names)?;
+                }
+            }
+        }
+
+        let mut created = 0;
+        let mut updated = 0;
+        // Every item is imported or, if one fails, none are
+        self.storage.transaction(&mut || {
+            for item in &valid {
+                let state = if item.done { "done" } else { "open" };
+                match self.storage.get_yak(&item.name) {
+                    Ok(existing) if existing.done == item.done => continue,
+                    Ok(_) => {
+                        if dry_run {
+                            self.output
+                                .info(&format!("Would mark '{}' {state}", item.name));
+                        } else {
+                            self.storage.mark_done(&item.name, item.done)?;
+                        }
+                        updated += 1;
+                    }
+                    Err(_) => {
+                        if dry_run {
+                            self.output
+                                .info(&format!("Would create '{}' ({state})", item.name));
+                        } else {
+                            self.storage.create_yak(&item.name)?;
+                            if item.done {
+                                self.storage.mark_done(&item.name, true)?;
+                            }
+                        }
+                        created += 1;
+                    }
+                }
+            }
+            Ok(())
+        })?;
+
+        if !dry_run && created + updated > 0 {
+            self.log.log_command(&command)?;
+        }
+
+        let verb = if dry_run { "Would import" } else { "Imported" };
+        self.output.info(&format!(
+            "{verb} {} items ({created} created, {updated} updated)",
+            items.len()
+        ));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::ports::{LogEntry, StashEntry};
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl MockOutput {
+        fn new() -> Self {
+            Self {
+                messages: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn last_message(&self) -> Option<String> {
+            self.messages.borrow().last().cloned()
+        }
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockLog;
+
+    impl LogPort for MockLog {
+        fn log_command(&self, _command: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    struct MockStash {
+        pushed: RefCell<Vec<(String, Vec<String>)>>,
+    }
+
+    impl StashPort for MockStash {
+        fn push(&self, reason: &str, names: &[String]) -> Result<()> {
+            self.pushed
+                .borrow_mut()
+                .push((reason.to_string(), names.to_vec()));
+            Ok(())
+        }
+
+        fn list(&self) -> Result<Vec<StashEntry>> {
+            unimplemented!()
+        }
+
+        fn pop(&self) -> Result<Option<StashEntry>> {
+            unimplemented!()
+        }
+
+        fn discard(&self) -> Result<()> {
+            unimplemented!()
+        }
+    }
+
+    const TODO: &str = "- [ ] release\n  - [x] write notes\n  - [ ] what?\n- [x] fix login\n";
+
+    #[test]
+    fn test_import_creates_hierarchy() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        let use_case = ImportChecklist::new(&storage, &output, &MockLog);
+
+        use_case.execute(TODO, "TODO.md", false).unwrap();
+
+        assert!(!storage.get_yak("release").unwrap().done);
+        assert!(storage.get_yak("release/write notes").unwrap().done);
+        assert!(storage.get_yak("fix login").unwrap().done);
+        assert!(storage.get_yak("release/what?").is_err());
+        assert_eq!(
+            output.last_message(),
+            Some("Imported 4 items (3 created, 0 updated)".to_string())
+        );
+
+        use_case
+            .execute("- [x] release\n", "TODO.md", false)
+            .unwrap();
+        assert!(storage.get_yak("release").unwrap().done);
+        assert_eq!(
+            output.last_message(),
+            Some("Imported 1 items (0 created, 1 updated)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_import_dry_run_changes_nothing() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        let stash = MockStash {
+            pushed: RefCell::new(Vec::new()),
+        };
+        let use_case = ImportChecklist::new(&storage, &output, &MockLog).with_stash(&stash);
+
+        use_case.execute(TODO, "-", true).unwrap();
+
+        assert!(storage.list_yaks().unwrap().is_empty());
+        assert!(stash.pushed.borrow().is_empty());
+        assert_eq!(
+            *output.messages.borrow(),
+            vec![
+                "Skipped 'release/what?': Invalid yak name: contains forbidden characters (\\ : * ? | < > \")",
+                "Would create 'release' (open)",
+                "Would create 'release/write notes' (done)",
+                "Would create 'fix login' (done)",
+                "Would import 4 items (3 created, 0 updated)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_stashes_affected_yaks_first() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        let stash = MockStash {
+            pushed: RefCell::new(Vec::new()),
+        };
+        let use_case = ImportChecklist::new(&storage, &output, &MockLog).with_stash(&stash);
+
+        use_case
+            .execute("- [ ] a\n  - [ ] b\n", "TODO.md", false)
+            .unwrap();
+
+        assert_eq!(
+            *stash.pushed.borrow(),
+            vec![(
+                "import --markdown TODO.md".to_string(),
+                vec!["a".to_string(), "a/b".to_string()]
+            )]
+        );
+    }
+}
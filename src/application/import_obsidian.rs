@@ -0,0 +1,354 @@
+// ImportObsidian use case - creates/updates yaks from `#yak` tagged tasks in an Obsidian vault
+
+use crate::domain::validate_yak_name;
+use crate::ports::{LogPort, OutputPort, StashPort, StoragePort};
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Tag marking a vault task as a yak
+const YAK_TAG: &str = "#yak";
+
+/// A `- [ ] ... #yak` task found in a note
+#[derive(Debug, PartialEq, Eq)]
+struct VaultTask {
+    name: String,
+    done: bool,
+    source: PathBuf,
+}
+
+pub struct ImportObsidian<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+    stash: Option<&'a dyn StashPort>,
+}
+
+impl<'a> ImportObsidian<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            log,
+            stash: None,
+        }
+    }
+
+    /// Snapshot the affected yaks before importing so `yx stash pop` can undo it
+    pub fn with_stash(mut self, stash: &'a dyn StashPort) -> Self {
+        self.stash = Some(stash);
+        self
+    }
+
+    pub fn execute(&self, vault: &Path) -> Result<()> {
+        if !vault.is_dir() {
+            anyhow::bail!("vault directory '{}' not found", vault.display());
+        }
+
+        let tasks = scan_vault(vault)?;
+        let command = format!("import --obsidian {}", vault.display());
+
+        if let Some(stash) = self.stash {
+            let names: Vec<String> = tasks
+                .iter()
+                .filter(|task| validate_yak_name(&task.name).is_ok())
+                .map(|task| task.name.clone())
+                .collect();
+            if !names.is_empty() {
+                stash.push(&command, &names)?;
+            }
+        }
+
+        let mut created = 0;
+        let mut updated = 0;
+        // Every task is imported or, if one fails, none are
+        self.storage.transaction(&mut || {
+            for task in &tasks {
+                if let Err(e) = validate_yak_name(&task.name) {
+                    self.output.note(&format!("Skipped '{}': {e}", task.name));
+                    continue;
+                }
+
+                let source = task.source.display().to_string();
+                match self.storage.get_yak(&task.name) {
+                    Ok(existing) => {
+                        let source_changed =
+                            self.storage.read_field(&task.name, "source")?.as_deref()
+                                != Some(source.as_str());
+                        if existing.done == task.done && !source_changed {
+                            continue;
+                        }
+                        self.storage.mark_done(&task.name, task.done)?;
+                        self.storage
+                            .write_field(&task.name, "source", Some(&source))?;
+                        updated += 1;
+                    }
+                    Err(_) => {
+                        self.storage.create_yak(&task.name)?;
+                        if task.done {
+                            self.storage.mark_done(&task.name, true)?;
+                        }
+                        self.storage
+                            .write_field(&task.name, "source", Some(&source))?;
+                        created += 1;
+                    }
+                }
+            }
+            Ok(())
+        })?;
+
+        if created + updated > 0 {
+            self.log.log_command(&command)?;
+        }
+
+        self.output.info(&format!(
+            "Imported {} tasks ({created} created, {updated} updated)",
+            tasks.len()
+        ));
+
+        Ok(())
+    }
+}
+
+/// Find all `#yak` tagged tasks in the vault's markdown notes
+fn scan_vault(vault: &Path) -> Result<Vec<VaultTask>> {
+    let mut tasks = Vec::new();
+
+    let notes = WalkDir::new(vault)
+        .sort_by_file_name()
+        .into_iter()
+        // Skip Obsidian's own config and other hidden folders
+        .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "md"));
+
+    for note in notes {
+        let text = fs::read_to_string(note.path())
+            .with_context(|| format!("Failed to read note {}", note.path().display()))?;
+        for line in text.lines() {
+            if let Some((name, done)) = parse_task(line) {
+                tasks.push(VaultTask {
+                    name,
+                    done,
+                    source: note.path().to_path_buf(),
+                });
+            }
+        }
+    }
+
+    Ok(tasks)
+}
+
+/// Parse a `- [ ] text #yak` checkbox line into (name, done)
+fn parse_task(line: &str) -> Option<(String, bool)> {
+    let rest = line.trim_start();
+    let rest = rest
+        .strip_prefix("- ")
+        .or_else(|| rest.strip_prefix("* "))?;
+
+    let (done, text) = if let Some(text) = rest.strip_prefix("[ ] ") {
+        (false, text)
+    } else if let Some(text) = rest
+        .strip_prefix("[x] ")
+        .or_else(|| rest.strip_prefix("[X] "))
+    {
+        (true, text)
+    } else {
+        return None;
+    };
+
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if !words.contains(&YAK_TAG) {
+        return None;
+    }
+
+    let name = words
+        .into_iter()
+        .filter(|word| *word != YAK_TAG)
+        .collect::<Vec<_>>()
+        .join(" ");
+    if name.is_empty() {
+        return None;
+    }
+
+    Some((name, done))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::ports::{LogEntry, StashEntry};
+    use std::cell::RefCell;
+    use tempfile::TempDir;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl MockOutput {
+        fn new() -> Self {
+            Self {
+                messages: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn last_message(&self) -> Option<String> {
+            self.messages.borrow().last().cloned()
+        }
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockLog;
+
+    impl LogPort for MockLog {
+        fn log_command(&self, _command: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_parse_task() {
+        assert_eq!(
+            parse_task("- [ ] fix login #yak"),
+            Some(("fix login".to_string(), false))
+        );
+        assert_eq!(
+            parse_task("  * [x] #yak api/auth"),
+            Some(("api/auth".to_string(), true))
+        );
+        assert_eq!(parse_task("- [ ] not a yak"), None);
+        assert_eq!(parse_task("- [ ] tagged #yaks"), None);
+        assert_eq!(parse_task("fix login #yak"), None);
+        assert_eq!(parse_task("- [ ] #yak"), None);
+    }
+
+    #[test]
+    fn test_import_creates_and_updates_yaks() {
+        let vault = TempDir::new().unwrap();
+        let note = vault.path().join("daily.md");
+        fs::write(
+            &note,
+            "# Today\n- [ ] fix login #yak\n- [x] write docs #yak\n- [ ] buy milk\n",
+        )
+        .unwrap();
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        let use_case = ImportObsidian::new(&storage, &output, &MockLog);
+
+        use_case.execute(vault.path()).unwrap();
+
+        assert!(!storage.get_yak("fix login").unwrap().done);
+        assert!(storage.get_yak("write docs").unwrap().done);
+        assert_eq!(
+            storage.read_field("fix login", "source").unwrap(),
+            Some(note.display().to_string())
+        );
+        assert_eq!(
+            output.last_message(),
+            Some("Imported 2 tasks (2 created, 0 updated)".to_string())
+        );
+
+        fs::write(&note, "- [x] fix login #yak\n- [x] write docs #yak\n").unwrap();
+        use_case.execute(vault.path()).unwrap();
+
+        assert!(storage.get_yak("fix login").unwrap().done);
+        assert_eq!(
+            output.last_message(),
+            Some("Imported 2 tasks (0 created, 1 updated)".to_string())
+        );
+    }
+
+    struct MockStash {
+        pushed: RefCell<Vec<(String, Vec<String>)>>,
+    }
+
+    impl StashPort for MockStash {
+        fn push(&self, reason: &str, names: &[String]) -> Result<()> {
+            self.pushed
+                .borrow_mut()
+                .push((reason.to_string(), names.to_vec()));
+            Ok(())
+        }
+
+        fn list(&self) -> Result<Vec<StashEntry>> {
+            unimplemented!()
+        }
+
+        fn pop(&self) -> Result<Option<StashEntry>> {
+            unimplemented!()
+        }
+
+        fn discard(&self) -> Result<()> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_import_stashes_affected_yaks_first() {
+        let vault = TempDir::new().unwrap();
+        fs::write(
+            vault.path().join("note.md"),
+            "- [ ] fix login #yak\n- [ ] what? #yak\n",
+        )
+        .unwrap();
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        let stash = MockStash {
+            pushed: RefCell::new(Vec::new()),
+        };
+        let use_case = ImportObsidian::new(&storage, &output, &MockLog).with_stash(&stash);
+
+        use_case.execute(vault.path()).unwrap();
+
+        assert_eq!(
+            *stash.pushed.borrow(),
+            vec![(
+                format!("import --obsidian {}", vault.path().display()),
+                vec!["fix login".to_string()]
+            )]
+        );
+    }
+
+    #[test]
+    fn test_import_skips_invalid_names() {
+        let vault = TempDir::new().unwrap();
+        fs::write(vault.path().join("note.md"), "- [ ] what? #yak\n").unwrap();
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        let use_case = ImportObsidian::new(&storage, &output, &MockLog);
+
+        use_case.execute(vault.path()).unwrap();
+
+        assert!(storage.list_yaks().unwrap().is_empty());
+    }
+}
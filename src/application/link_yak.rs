@@ -0,0 +1,156 @@
+// LinkYak use case - attaches external links (PRs, docs, tickets) to a yak
+
+use crate::domain::validate_link;
+use crate::ports::{LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+
+pub struct LinkYak<'a> {
+    storage: &'a dyn StoragePort,
+    log: &'a dyn LogPort,
+}
+
+impl<'a> LinkYak<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        _output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+    ) -> Self {
+        Self { storage, log }
+    }
+
+    pub fn execute(&self, name: &str, url: &str, remove: bool) -> Result<()> {
+        // Validate link
+        validate_link(url).map_err(|e| anyhow::anyhow!(e))?;
+        let url = url.trim();
+
+        // Resolve yak name (exact or fuzzy match)
+        let resolved_name = self.storage.find_yak(name)?;
+
+        // Links are stored one per line
+        let mut links: Vec<String> = self
+            .storage
+            .read_field(&resolved_name, "links")?
+            .map(|text| text.lines().map(|line| line.to_string()).collect())
+            .unwrap_or_default();
+
+        if remove {
+            if !links.iter().any(|link| link == url) {
+                anyhow::bail!("yak '{resolved_name}' has no link '{url}'");
+            }
+            links.retain(|link| link != url);
+        } else if !links.iter().any(|link| link == url) {
+            links.push(url.to_string());
+        }
+
+        let value = if links.is_empty() {
+            None
+        } else {
+            Some(format!("{}\n", links.join("\n")))
+        };
+        self.storage
+            .write_field(&resolved_name, "links", value.as_deref())?;
+
+        if remove {
+            self.log
+                .log_command(&format!("link --remove {resolved_name} {url}"))?;
+        } else {
+            self.log
+                .log_command(&format!("link {resolved_name} {url}"))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::ports::LogEntry;
+
+    struct MockOutput;
+
+    impl OutputPort for MockOutput {
+        fn success(&self, _message: &str) {}
+        fn error(&self, _message: &str) {}
+        fn info(&self, _message: &str) {}
+
+        fn warn(&self, _message: &str) {}
+    }
+
+    struct MockLog;
+
+    impl LogPort for MockLog {
+        fn log_command(&self, _command: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_link_yak_appends_links() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("test-yak").unwrap();
+        let use_case = LinkYak::new(&storage, &MockOutput, &MockLog);
+
+        use_case
+            .execute("test-yak", "https://example.com/pr/1", false)
+            .unwrap();
+        use_case
+            .execute("test-yak", "https://example.com/docs", false)
+            .unwrap();
+
+        assert_eq!(
+            storage.read_field("test-yak", "links").unwrap(),
+            Some("https://example.com/pr/1\nhttps://example.com/docs\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_link_yak_ignores_duplicate_links() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("test-yak").unwrap();
+        let use_case = LinkYak::new(&storage, &MockOutput, &MockLog);
+
+        use_case
+            .execute("test-yak", "https://example.com", false)
+            .unwrap();
+        use_case
+            .execute("test-yak", "https://example.com", false)
+            .unwrap();
+
+        assert_eq!(
+            storage.read_field("test-yak", "links").unwrap(),
+            Some("https://example.com\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_link_yak_removes_link() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("test-yak").unwrap();
+        let use_case = LinkYak::new(&storage, &MockOutput, &MockLog);
+
+        use_case
+            .execute("test-yak", "https://example.com", false)
+            .unwrap();
+        use_case
+            .execute("test-yak", "https://example.com", true)
+            .unwrap();
+
+        assert_eq!(storage.read_field("test-yak", "links").unwrap(), None);
+    }
+
+    #[test]
+    fn test_link_yak_fails_for_nonexistent_yak() {
+        let storage = InMemoryStorage::new();
+        let use_case = LinkYak::new(&storage, &MockOutput, &MockLog);
+
+        let result = use_case.execute("nonexistent", "https://example.com", false);
+
+        assert!(result.is_err());
+    }
+}
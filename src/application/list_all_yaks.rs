@@ -0,0 +1,154 @@
+// ListAllYaks use case - one merged list across every configured workspace
+
+use super::workspaces::{PrefixedOutput, NO_WORKSPACES};
+use super::{ListYaks, OutputFormat, StateLabels};
+use crate::ports::{OutputPort, StoragePort};
+use anyhow::Result;
+
+pub struct ListAllYaks<'a> {
+    workspaces: Vec<(String, &'a dyn StoragePort)>,
+    output: &'a dyn OutputPort,
+    assignee: Option<String>,
+    labels: StateLabels,
+}
+
+impl<'a> ListAllYaks<'a> {
+    /// `workspaces` pairs each workspace name with the storage for its yaks
+    pub fn new(workspaces: Vec<(String, &'a dyn StoragePort)>, output: &'a dyn OutputPort) -> Self {
+        Self {
+            workspaces,
+            output,
+            assignee: None,
+            labels: StateLabels::default(),
+        }
+    }
+
+    /// Show states with these labels instead of `[ ]` / `[x]`
+    pub fn with_state_labels(mut self, labels: StateLabels) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Only list yaks assigned to this person (their git user.email)
+    pub fn with_assignee(mut self, assignee: String) -> Self {
+        self.assignee = Some(assignee);
+        self
+    }
+
+    pub fn execute(&self, format: OutputFormat, only: Option<&str>) -> Result<()> {
+        if self.workspaces.is_empty() {
+            anyhow::bail!(NO_WORKSPACES);
+        }
+
+        for (name, storage) in &self.workspaces {
+            let output = PrefixedOutput::new(name, self.output);
+            let mut use_case =
+                ListYaks::new(*storage, &output).with_state_labels(self.labels.clone());
+            if let Some(assignee) = &self.assignee {
+                use_case = use_case.with_assignee(assignee.clone());
+            }
+
+            // One unreadable workspace shouldn't hide the others
+            if let Err(e) = use_case.execute(format, only) {
+                output.error(&e.to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl MockOutput {
+        fn new() -> Self {
+            Self {
+                messages: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn get_messages(&self) -> Vec<String> {
+            self.messages.borrow().clone()
+        }
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    fn assigned(name: &str, assignee: &str) -> Yak {
+        Yak {
+            name: name.to_string(),
+            assignee: Some(assignee.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_list_all_prefixes_each_workspace() {
+        let app = InMemoryStorage::with_yaks(vec![
+            Yak::new("login".to_string()),
+            Yak::new("docs".to_string()).mark_done(),
+        ]);
+        let infra = InMemoryStorage::with_yaks(vec![Yak::new("dns".to_string())]);
+        let output = MockOutput::new();
+        let use_case = ListAllYaks::new(
+            vec![("app".to_string(), &app), ("infra".to_string(), &infra)],
+            &output,
+        );
+
+        use_case
+            .execute(OutputFormat::Plain, Some("not-done"))
+            .unwrap();
+
+        assert_eq!(output.get_messages(), vec!["[app] login", "[infra] dns"]);
+    }
+
+    #[test]
+    fn test_list_all_mine_filters_by_assignee() {
+        let app = InMemoryStorage::with_yaks(vec![
+            assigned("login", "me@example.com"),
+            assigned("billing", "them@example.com"),
+        ]);
+        let output = MockOutput::new();
+        let use_case = ListAllYaks::new(vec![("app".to_string(), &app)], &output)
+            .with_assignee("me@example.com".to_string());
+
+        use_case.execute(OutputFormat::Markdown, None).unwrap();
+
+        assert_eq!(output.get_messages(), vec!["[app] - [ ] login"]);
+    }
+
+    #[test]
+    fn test_list_all_requires_workspaces() {
+        let output = MockOutput::new();
+        let use_case = ListAllYaks::new(Vec::new(), &output);
+
+        assert!(use_case.execute(OutputFormat::Markdown, None).is_err());
+    }
+}
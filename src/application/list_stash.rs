@@ -0,0 +1,163 @@
+// ListStash use case - shows the snapshots taken before risky operations
+
+use super::TimeStyle;
+use crate::ports::{OutputPort, StashPort};
+use anyhow::Result;
+
+pub struct ListStash<'a> {
+    stash: &'a dyn StashPort,
+    output: &'a dyn OutputPort,
+    time_style: TimeStyle,
+}
+
+impl<'a> ListStash<'a> {
+    pub fn new(stash: &'a dyn StashPort, output: &'a dyn OutputPort) -> Self {
+        Self {
+            stash,
+            output,
+            time_style: TimeStyle::default(),
+        }
+    }
+
+    /// Show times relative to now ("2h ago") or as local date and time
+    pub fn with_time_style(mut self, time_style: TimeStyle) -> Self {
+        self.time_style = time_style;
+        self
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        let entries = self.stash.list()?;
+        if entries.is_empty() {
+            self.output.note("No stashed yaks");
+            return Ok(());
+        }
+
+        for (index, entry) in entries.iter().enumerate() {
+            let noun = if entry.yaks.len() == 1 { "yak" } else { "yaks" };
+            self.output.info(&format!(
+                "stash@{{{index}}}: {} before {} ({} {noun})",
+                self.time_style.render(entry.created),
+                entry.reason,
+                entry.yaks.len()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::StashEntry;
+    use chrono::{DateTime, Local, TimeZone, Utc};
+    use std::cell::RefCell;
+
+    struct MockStash {
+        entries: Vec<StashEntry>,
+    }
+
+    impl StashPort for MockStash {
+        fn push(&self, _reason: &str, _names: &[String]) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn list(&self) -> Result<Vec<StashEntry>> {
+            Ok(self.entries.clone())
+        }
+
+        fn pop(&self) -> Result<Option<StashEntry>> {
+            unimplemented!()
+        }
+
+        fn discard(&self) -> Result<()> {
+            unimplemented!()
+        }
+    }
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl MockOutput {
+        fn new() -> Self {
+            Self {
+                messages: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn get_messages(&self) -> Vec<String> {
+            self.messages.borrow().clone()
+        }
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    #[test]
+    fn test_list_shows_entries_newest_first() {
+        let stash = MockStash {
+            entries: vec![
+                StashEntry {
+                    reason: "import --obsidian vault".to_string(),
+                    created: Utc.with_ymd_and_hms(2026, 3, 2, 9, 30, 0).unwrap(),
+                    yaks: vec!["a".to_string(), "b".to_string()],
+                },
+                StashEntry {
+                    reason: "import --obsidian vault".to_string(),
+                    created: Utc.with_ymd_and_hms(2026, 3, 1, 8, 0, 0).unwrap(),
+                    yaks: vec!["a".to_string()],
+                },
+            ],
+        };
+        let output = MockOutput::new();
+        let use_case = ListStash::new(&stash, &output);
+
+        use_case.execute().unwrap();
+
+        let local = |time: DateTime<Utc>| time.with_timezone(&Local).format("%Y-%m-%d %H:%M");
+        assert_eq!(
+            output.get_messages(),
+            vec![
+                format!(
+                    "stash@{{0}}: {} before import --obsidian vault (2 yaks)",
+                    local(stash.entries[0].created)
+                ),
+                format!(
+                    "stash@{{1}}: {} before import --obsidian vault (1 yak)",
+                    local(stash.entries[1].created)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_empty_stash() {
+        let stash = MockStash {
+            entries: Vec::new(),
+        };
+        let output = MockOutput::new();
+        let use_case = ListStash::new(&stash, &output);
+
+        use_case.execute().unwrap();
+
+        assert_eq!(output.get_messages(), vec!["No stashed yaks"]);
+    }
+}
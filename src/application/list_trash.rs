@@ -0,0 +1,119 @@
+// ListTrash use case - shows the yaks `yx rm` moved to the trash
+
+use super::TimeStyle;
+use crate::ports::{OutputPort, TrashPort};
+use anyhow::Result;
+
+pub struct ListTrash<'a> {
+    trash: &'a dyn TrashPort,
+    output: &'a dyn OutputPort,
+    time_style: TimeStyle,
+}
+
+impl<'a> ListTrash<'a> {
+    pub fn new(trash: &'a dyn TrashPort, output: &'a dyn OutputPort) -> Self {
+        Self {
+            trash,
+            output,
+            time_style: TimeStyle::default(),
+        }
+    }
+
+    /// Show times relative to now ("2h ago") or as local date and time
+    pub fn with_time_style(mut self, time_style: TimeStyle) -> Self {
+        self.time_style = time_style;
+        self
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        let entries = self.trash.trashed()?;
+        if entries.is_empty() {
+            self.output.note("The trash is empty");
+            return Ok(());
+        }
+
+        for entry in &entries {
+            self.output.info(&format!(
+                "{} (removed {})",
+                entry.name,
+                self.time_style.render(entry.removed)
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::TrashEntry;
+    use chrono::{DateTime, Local, TimeZone, Utc};
+    use std::cell::RefCell;
+
+    struct MockTrash {
+        entries: Vec<TrashEntry>,
+    }
+
+    impl TrashPort for MockTrash {
+        fn trash(&self, _name: &str) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn trashed(&self) -> Result<Vec<TrashEntry>> {
+            Ok(self.entries.clone())
+        }
+
+        fn restore_trashed(&self, _name: &str) -> Result<()> {
+            unimplemented!()
+        }
+    }
+
+    #[derive(Default)]
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, _message: &str) {}
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn note(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, _message: &str) {}
+    }
+
+    #[test]
+    fn test_list_shows_removed_yaks_or_an_empty_trash() {
+        let removed = Utc.with_ymd_and_hms(2026, 3, 2, 9, 30, 0).unwrap();
+        let trash = MockTrash {
+            entries: vec![TrashEntry {
+                name: "api/auth".to_string(),
+                removed,
+            }],
+        };
+        let output = MockOutput::default();
+        ListTrash::new(&trash, &output).execute().unwrap();
+        ListTrash::new(&MockTrash { entries: vec![] }, &output)
+            .execute()
+            .unwrap();
+
+        let local = |time: DateTime<Utc>| time.with_timezone(&Local).format("%Y-%m-%d %H:%M");
+        assert_eq!(
+            *output.messages.borrow(),
+            vec![
+                format!("api/auth (removed {})", local(removed)),
+                "The trash is empty".to_string(),
+            ]
+        );
+    }
+}
@@ -1,273 +1,718 @@
 // ListYaks use case - displays all yaks
 
-use crate::domain::Yak;
-use crate::ports::{OutputPort, StoragePort};
+use super::output_format::{csv_field, json_array, json_object, json_optional, json_string};
+use super::porcelain;
+use super::read_tracking::ReadTracker;
+use super::{OutputFormat, StateLabels};
+use crate::domain::graph::{self, YakNode};
+use crate::domain::{Yak, YakFilter, YakState};
+use crate::ports::{ConfigPort, LocalStatePort, OutputPort, StoragePort, Style};
 use anyhow::Result;
-use std::collections::HashMap;
-
-/// Represents a node in the yak hierarchy tree
-struct YakNode {
-    name: String,      // Just the leaf name (e.g., "child" not "parent/child")
-    full_path: String, // Full path (e.g., "parent/child")
-    yak: Option<Yak>,  // None for implicit parents
-    children: Vec<YakNode>,
-}
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use std::cmp::{Ordering, Reverse};
+use std::collections::HashSet;
+
+/// Local state key holding the full names from the last numbered list, one per line
+pub(super) const LAST_LIST_KEY: &str = "last-list";
+
+/// Columns of `--format csv`, after the `#` column when listing numbered
+const CSV_COLUMNS: [&str; 9] = [
+    "name", "state", "priority", "due", "assignee", "title", "links", "created", "modified",
+];
+
+/// Formats `execute` can render
+const FORMATS: [OutputFormat; 6] = [
+    OutputFormat::Markdown,
+    OutputFormat::Plain,
+    OutputFormat::Table,
+    OutputFormat::Csv,
+    OutputFormat::Json,
+    OutputFormat::Porcelain,
+];
+
+/// Keys `with_sort` accepts
+const SORT_KEYS: [&str; 5] = ["name", "created", "modified", "priority", "due"];
+
+/// Where done yaks go among their siblings, for `with_done_placement`
+const DONE_PLACEMENTS: [&str; 3] = ["first", "last", "hidden"];
+
+/// Config key for the done placement when none is given, e.g. `git config yaks.list.done last`
+const DONE_PLACEMENT_KEY: &str = "yaks.list.done";
 
 pub struct ListYaks<'a> {
     storage: &'a dyn StoragePort,
     output: &'a dyn OutputPort,
+    read_tracker: Option<ReadTracker<'a>>,
+    numbered: Option<&'a dyn LocalStatePort>,
+    assignee: Option<String>,
+    scope: Option<String>,
+    max_depth: Option<usize>,
+    sort: Option<String>,
+    reverse: bool,
+    done_placement: Option<String>,
+    config: Option<&'a dyn ConfigPort>,
+    summary: bool,
+    collapse_done: bool,
+    labels: StateLabels,
 }
 
 impl<'a> ListYaks<'a> {
     pub fn new(storage: &'a dyn StoragePort, output: &'a dyn OutputPort) -> Self {
-        Self { storage, output }
+        Self {
+            storage,
+            output,
+            read_tracker: None,
+            numbered: None,
+            assignee: None,
+            scope: None,
+            max_depth: None,
+            sort: None,
+            reverse: false,
+            done_placement: None,
+            config: None,
+            summary: false,
+            collapse_done: false,
+            labels: StateLabels::default(),
+        }
     }
 
-    pub fn execute(&self, format: &str, only: Option<&str>) -> Result<()> {
-        let yaks = self.storage.list_yaks()?;
+    /// Show states with these labels instead of `[ ]` / `[x]`
+    pub fn with_state_labels(mut self, labels: StateLabels) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Only list yaks assigned to this person (their git user.email)
+    pub fn with_assignee(mut self, assignee: String) -> Self {
+        self.assignee = Some(assignee);
+        self
+    }
+
+    /// Only list this yak (fuzzy matched) and the yaks under it
+    pub fn with_scope(mut self, name: String) -> Self {
+        self.scope = Some(name);
+        self
+    }
+
+    /// Only show this many levels (1 = top level only); collapsed parents show
+    /// how many yaks they hide in markdown output
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Order siblings by name, created, modified, priority or due instead of
+    /// done first; yaks missing the value go last
+    pub fn with_sort(mut self, key: String) -> Self {
+        self.sort = Some(key);
+        self
+    }
 
-        // Normalize format (treat "md" and "raw" as aliases)
-        let normalized_format = match format {
-            "md" => "markdown",
-            "raw" => "plain",
-            other => other,
+    /// Flip the sibling order; yaks missing the sort value still go last
+    pub fn with_reverse(mut self, reverse: bool) -> Self {
+        self.reverse = reverse;
+        self
+    }
+
+    /// Put done yaks first or last among their siblings, or hide them (a done
+    /// yak with open yaks under it stays, so they keep their place)
+    pub fn with_done_placement(mut self, placement: String) -> Self {
+        self.done_placement = Some(placement);
+        self
+    }
+
+    /// Follow markdown output with totals by state, e.g. `4 todo, 2 doing, 7 done`,
+    /// and how many yaks the filters hid
+    pub fn with_summary(mut self, summary: bool) -> Self {
+        self.summary = summary;
+        self
+    }
+
+    /// Show a done yak whose descendants are all done as one line with a count,
+    /// e.g. `- [x] migration (12 done)`, instead of its whole subtree
+    pub fn with_collapse_done(mut self, collapse_done: bool) -> Self {
+        self.collapse_done = collapse_done;
+        self
+    }
+
+    /// Read the done placement from `yaks.list.done` when none is given
+    pub fn with_config(mut self, config: &'a dyn ConfigPort) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Number the listed yaks and remember the numbers, so other commands can
+    /// take `3` instead of a name
+    pub fn with_numbering(mut self, state: &'a dyn LocalStatePort) -> Self {
+        self.numbered = Some(state);
+        self
+    }
+
+    /// Badge yaks whose context changed since it was last viewed
+    pub fn with_read_tracking(mut self, state: &'a dyn LocalStatePort) -> Self {
+        self.read_tracker = Some(ReadTracker::new(state));
+        self
+    }
+
+    pub fn execute(&self, format: OutputFormat, only: Option<&str>) -> Result<()> {
+        format.ensure("list", &FORMATS)?;
+        if self.max_depth == Some(0) {
+            anyhow::bail!("depth must be at least 1");
+        }
+        if let Some(key) = &self.sort {
+            if !SORT_KEYS.contains(&key.as_str()) {
+                anyhow::bail!(
+                    "Unknown sort key '{key}': use name, created, modified, priority or due"
+                );
+            }
+        }
+        let placement = self.done_placement.clone().or_else(|| {
+            self.config
+                .and_then(|config| config.get(DONE_PLACEMENT_KEY))
+        });
+        if let Some(placement) = &placement {
+            if !DONE_PLACEMENTS.contains(&placement.as_str()) {
+                anyhow::bail!("Unknown done placement '{placement}': use first, last or hidden");
+            }
+        }
+        let filter = only
+            .map(YakFilter::parse)
+            .transpose()
+            .map_err(anyhow::Error::msg)?;
+        let only = filter.as_ref();
+
+        // Resolve yak name (exact or fuzzy match)
+        let scope = match &self.scope {
+            Some(name) => Some(self.storage.find_yak(name)?),
+            None => None,
         };
 
+        let mut yaks = self.storage.list_yaks()?;
+        if let Some(scope) = &scope {
+            yaks.retain(|yak| &yak.name == scope || graph::is_descendant_of(&yak.name, scope));
+        }
+        if let Some(assignee) = &self.assignee {
+            yaks.retain(|yak| yak.assignee.as_ref() == Some(assignee));
+        }
+
         if yaks.is_empty() {
-            // Only show message in markdown format
-            if normalized_format == "markdown" {
-                self.output.info("You have no yaks. Are you done?");
+            // Only show message in markdown format; CSV and JSON still print their
+            // empty shape so tools can parse them
+            match format {
+                OutputFormat::Markdown => self.output.note("You have no yaks. Are you done?"),
+                OutputFormat::Csv => self.display_csv(&[], only, &mut Vec::new()),
+                OutputFormat::Json => self.display_json(&[], only, &mut Vec::new()),
+                _ => {}
             }
             return Ok(());
         }
 
-        // Build hierarchy tree
-        let tree = self.build_tree(yaks);
+        // Find yaks with unread context changes; only this reads contexts
+        let updated: HashSet<String> = match &self.read_tracker {
+            Some(tracker) => {
+                let seen = tracker.load()?;
+                yaks.iter()
+                    .filter(|yak| {
+                        let context = self.storage.read_context(&yak.name).unwrap_or_default();
+                        tracker.is_updated(&yak.name, &context, &seen)
+                    })
+                    .map(|yak| yak.name.clone())
+                    .collect()
+            }
+            None => HashSet::new(),
+        };
+
+        // Build hierarchy tree, starting from the scope yak when there is one
+        let mut tree = self.build_tree(yaks, placement.as_deref());
+        if let Some(scope) = &scope {
+            tree = graph::find_node(&tree, scope)
+                .cloned()
+                .into_iter()
+                .collect();
+        }
+        let totals = Totals::of(&tree);
+        if placement.as_deref() == Some("hidden") {
+            hide_done(&mut tree);
+        }
 
         // Display tree with filtering
-        let mut has_output = false;
-        self.display_tree(&tree, normalized_format, only, 0, &mut has_output);
+        let mut shown = Vec::new();
+        match format {
+            OutputFormat::Table => self.display_table(&tree, only, &mut shown),
+            OutputFormat::Csv => self.display_csv(&tree, only, &mut shown),
+            OutputFormat::Json => self.display_json(&tree, only, &mut shown),
+            OutputFormat::Porcelain => self.display_porcelain(&tree, only, &mut shown),
+            _ => self.display_tree(&tree, format, only, 0, &updated, &mut shown),
+        }
+
+        if let Some(state) = self.numbered {
+            let mapping: String = shown.iter().map(|name| format!("{name}\n")).collect();
+            state.write(LAST_LIST_KEY, Some(&mapping))?;
+        }
 
         // If filtered and nothing to show
-        if !has_output && normalized_format == "markdown" {
-            self.output.info("You have no yaks. Are you done?");
+        if shown.is_empty() && format == OutputFormat::Markdown {
+            self.output.note("You have no yaks. Are you done?");
+        } else if self.summary && format == OutputFormat::Markdown {
+            let kept = Totals::of(&tree).total();
+            let hidden = totals.total() - kept
+                + count_filtered(&tree, &|node| !self.should_display_node(node, only));
+            self.output.info("");
+            self.output.info(&totals.summary(hidden));
         }
 
         Ok(())
     }
 
-    /// Build a hierarchical tree from flat list of yaks
-    fn build_tree(&self, yaks: Vec<Yak>) -> Vec<YakNode> {
-        let mut nodes_by_path: HashMap<String, YakNode> = HashMap::new();
-
-        // First pass: create nodes for all yaks and implicit parents
-        for yak in &yaks {
-            let parts: Vec<&str> = yak.name.split('/').collect();
-
-            // Create implicit parent nodes if they don't exist
-            for i in 1..parts.len() {
-                let parent_path = parts[..i].join("/");
-                if !nodes_by_path.contains_key(&parent_path) {
-                    let parent_name = parts[i - 1].to_string();
-                    nodes_by_path.insert(
-                        parent_path.clone(),
-                        YakNode {
-                            name: parent_name,
-                            full_path: parent_path.clone(),
-                            yak: None, // Implicit parent (no actual yak)
-                            children: Vec::new(),
-                        },
-                    );
-                }
+    /// Build the hierarchy tree sorted for display
+    /// Without a placement done yaks go first unless a sort key is given; with
+    /// one, they're grouped first or last whatever the sort.
+    fn build_tree(&self, yaks: Vec<Yak>, placement: Option<&str>) -> Vec<YakNode> {
+        let mut roots = graph::build_tree(yaks);
+        let compare = |a: &YakNode, b: &YakNode| {
+            let order = match self.sort.as_deref() {
+                Some(key) => sort_by_key(key, a, b, self.reverse),
+                None if placement.is_some() => a.name.cmp(&b.name),
+                None => graph::done_first(a, b),
+            };
+            let order = if self.reverse && self.sort.is_none() {
+                order.reverse()
+            } else {
+                order
+            };
+            match placement {
+                Some("first") => b.is_done().cmp(&a.is_done()).then(order),
+                Some("last") => a.is_done().cmp(&b.is_done()).then(order),
+                _ => order,
             }
+        };
+        graph::sort_tree(&mut roots, &compare);
+        roots
+    }
 
-            // Create node for this yak
-            let name = parts.last().unwrap_or(&"").to_string();
-            nodes_by_path.insert(
-                yak.name.clone(),
-                YakNode {
-                    name,
-                    full_path: yak.name.clone(),
-                    yak: Some(yak.clone()),
-                    children: Vec::new(),
-                },
-            );
-        }
-
-        // Second pass: build parent-child relationships
-        // Sort paths by depth (deepest first) to ensure children are processed before parents
-        let mut all_paths: Vec<String> = nodes_by_path.keys().cloned().collect();
-        all_paths.sort_by_key(|p| std::cmp::Reverse(p.matches('/').count()));
-
-        // Extract children from deepest to shallowest
-        for path in &all_paths {
-            let parts: Vec<&str> = path.split('/').collect();
+    /// Display tree recursively
+    fn display_tree(
+        &self,
+        nodes: &[YakNode],
+        format: OutputFormat,
+        only: Option<&YakFilter>,
+        depth: usize,
+        updated: &HashSet<String>,
+        shown: &mut Vec<String>,
+    ) {
+        for node in nodes {
+            // Check if node should be displayed based on filter
+            let should_display = self.should_display_node(node, only);
 
-            if parts.len() == 1 {
-                // Root node - leave it
-                continue;
+            let done_subtree = self.folds_done(node);
+            let collapsed = done_subtree || self.is_collapsed(depth);
+            if should_display {
+                shown.push(node.full_path.clone());
+                let count = match node.descendant_count() {
+                    hidden if done_subtree => format!(" ({hidden} done)"),
+                    hidden if collapsed && hidden > 0 => format!(" (+{hidden})"),
+                    _ => String::new(),
+                };
+                self.display_node(
+                    node,
+                    format,
+                    depth,
+                    updated.contains(&node.full_path),
+                    &count,
+                    self.numbered.map(|_| shown.len()),
+                );
             }
 
-            // Child node - attach to parent
-            let parent_path = parts[..parts.len() - 1].join("/");
-
-            // Remove child from map and attach to parent
-            if let Some(child_node) = nodes_by_path.remove(path) {
-                if let Some(parent_node) = nodes_by_path.get_mut(&parent_path) {
-                    parent_node.children.push(child_node);
-                } else {
-                    // This shouldn't happen since we created all parents in first pass
-                    // But if it does, put the node back
-                    nodes_by_path.insert(path.clone(), child_node);
-                }
+            // Always recurse to children (they might be visible even if parent is filtered)
+            if !collapsed {
+                self.display_tree(&node.children, format, only, depth + 1, updated, shown);
             }
         }
+    }
 
-        // Extract root nodes and sort
-        let mut roots: Vec<YakNode> = nodes_by_path
-            .into_iter()
-            .filter(|(path, _)| !path.contains('/'))
-            .map(|(_, node)| node)
+    /// Display tree as an aligned table with one row per yak
+    fn display_table(&self, nodes: &[YakNode], only: Option<&YakFilter>, shown: &mut Vec<String>) {
+        let mut nodes_shown = Vec::new();
+        self.collect_rows(nodes, only, 0, &mut nodes_shown);
+
+        if nodes_shown.is_empty() {
+            return;
+        }
+        let rows: Vec<(String, String, String)> = nodes_shown
+            .iter()
+            .map(|node| {
+                let links = node
+                    .yak
+                    .as_ref()
+                    .map(|y| y.links.join(" "))
+                    .unwrap_or_default();
+                (
+                    node.full_path.clone(),
+                    self.labels.word(node.state()),
+                    links,
+                )
+            })
             .collect();
+        shown.extend(rows.iter().map(|(name, _, _)| name.clone()));
+        let number_width = rows.len().to_string().len().max("#".len());
 
-        Self::sort_children(&mut roots);
-        roots
+        let name_width = rows
+            .iter()
+            .map(|(name, _, _)| name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("NAME".len());
+        let state_width = rows
+            .iter()
+            .map(|(_, state, _)| state.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("STATE".len());
+
+        let number = |text: &str| match self.numbered {
+            Some(_) => format!("{text:>number_width$}  "),
+            None => String::new(),
+        };
+        self.output.info(
+            format!(
+                "{}{:<name_width$}  {:<state_width$}  LINKS",
+                number("#"),
+                "NAME",
+                "STATE"
+            )
+            .trim_end(),
+        );
+        for (i, (name, state, links)) in rows.into_iter().enumerate() {
+            self.output.info(
+                format!(
+                    "{}{name:<name_width$}  {state:<state_width$}  {links}",
+                    number(&(i + 1).to_string())
+                )
+                .trim_end(),
+            );
+        }
     }
 
-    /// Sort children at this level: done first, then not-done, both alphabetically
-    fn sort_children(children: &mut [YakNode]) {
-        children.sort_by(|a, b| {
-            let a_done = a.yak.as_ref().map(|y| y.done).unwrap_or(false);
-            let b_done = b.yak.as_ref().map(|y| y.done).unwrap_or(false);
+    /// Display one CSV row per yak under a header row, with its metadata, for spreadsheets
+    fn display_csv(&self, nodes: &[YakNode], only: Option<&YakFilter>, shown: &mut Vec<String>) {
+        let mut rows = Vec::new();
+        self.collect_rows(nodes, only, 0, &mut rows);
+        shown.extend(rows.iter().map(|node| node.full_path.clone()));
 
-            match (a_done, b_done) {
-                (true, false) => std::cmp::Ordering::Less,
-                (false, true) => std::cmp::Ordering::Greater,
-                _ => a.name.cmp(&b.name),
-            }
-        });
+        let number = |text: String| match self.numbered {
+            Some(_) => vec![text],
+            None => Vec::new(),
+        };
+        let mut header = number("#".to_string());
+        header.extend(CSV_COLUMNS.map(str::to_string));
+        self.output.info(&header.join(","));
 
-        // Recursively sort children's children
-        for child in children.iter_mut() {
-            Self::sort_children(&mut child.children);
+        for (i, node) in rows.into_iter().enumerate() {
+            let yak = node.yak.clone().unwrap_or_default();
+            let time = |time: Option<DateTime<Utc>>| {
+                time.map(|time| time.to_rfc3339_opts(SecondsFormat::Secs, true))
+                    .unwrap_or_default()
+            };
+            let mut fields = number((i + 1).to_string());
+            fields.extend([
+                node.full_path.clone(),
+                yak.current_state().to_string(),
+                yak.priority.map(|p| p.to_string()).unwrap_or_default(),
+                yak.due.map(|due| due.to_string()).unwrap_or_default(),
+                yak.assignee.clone().unwrap_or_default(),
+                yak.title.clone().unwrap_or_default(),
+                yak.links.join(" "),
+                time(yak.created_at),
+                time(yak.modified_at),
+            ]);
+            let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+            self.output.info(&row.join(","));
         }
     }
 
-    /// Display tree recursively
-    fn display_tree(
+    /// Display the yaks as a JSON array of objects with the CSV columns, in list
+    /// order; missing values are null
+    fn display_json(&self, nodes: &[YakNode], only: Option<&YakFilter>, shown: &mut Vec<String>) {
+        let mut rows = Vec::new();
+        self.collect_rows(nodes, only, 0, &mut rows);
+        shown.extend(rows.iter().map(|node| node.full_path.clone()));
+
+        let objects: Vec<String> = rows
+            .into_iter()
+            .enumerate()
+            .map(|(i, node)| {
+                let yak = node.yak.clone().unwrap_or_default();
+                let time = |time: Option<DateTime<Utc>>| {
+                    json_optional(
+                        time.map(|time| time.to_rfc3339_opts(SecondsFormat::Secs, true))
+                            .as_deref(),
+                    )
+                };
+                let links: Vec<String> = yak.links.iter().map(|link| json_string(link)).collect();
+                let mut fields = Vec::new();
+                if self.numbered.is_some() {
+                    fields.push(("number", (i + 1).to_string()));
+                }
+                fields.extend([
+                    ("name", json_string(&node.full_path)),
+                    ("state", json_string(&yak.current_state().to_string())),
+                    (
+                        "priority",
+                        json_optional(yak.priority.map(|p| p.to_string()).as_deref()),
+                    ),
+                    (
+                        "due",
+                        json_optional(yak.due.map(|due| due.to_string()).as_deref()),
+                    ),
+                    ("assignee", json_optional(yak.assignee.as_deref())),
+                    ("title", json_optional(yak.title.as_deref())),
+                    ("links", format!("[{}]", links.join(", "))),
+                    ("created", time(yak.created_at)),
+                    ("modified", time(yak.modified_at)),
+                ]);
+                json_object(&fields, 1)
+            })
+            .collect();
+        self.output.info(&json_array(&objects, 0));
+    }
+
+    /// Display one stable porcelain line per yak, in list order
+    fn display_porcelain(
         &self,
         nodes: &[YakNode],
-        format: &str,
-        only: Option<&str>,
+        only: Option<&YakFilter>,
+        shown: &mut Vec<String>,
+    ) {
+        let mut rows = Vec::new();
+        self.collect_rows(nodes, only, 0, &mut rows);
+        for node in rows {
+            shown.push(node.full_path.clone());
+            self.output
+                .info(&porcelain::yak_line(&node.full_path, node.yak.as_ref()));
+        }
+    }
+
+    /// Flatten the tree into the nodes shown, in display order
+    fn collect_rows<'n>(
+        &self,
+        nodes: &'n [YakNode],
+        only: Option<&YakFilter>,
         depth: usize,
-        has_output: &mut bool,
+        rows: &mut Vec<&'n YakNode>,
     ) {
         for node in nodes {
-            // Check if node should be displayed based on filter
-            let should_display = self.should_display_node(node, only);
-
-            if should_display {
-                *has_output = true;
-                self.display_node(node, format, depth);
+            if self.should_display_node(node, only) {
+                rows.push(node);
             }
 
-            // Always recurse to children (they might be visible even if parent is filtered)
-            self.display_tree(&node.children, format, only, depth + 1, has_output);
+            if !self.is_collapsed(depth) && !self.folds_done(node) {
+                self.collect_rows(&node.children, only, depth + 1, rows);
+            }
         }
     }
 
-    /// Check if node matches the filter
-    fn should_display_node(&self, node: &YakNode, only: Option<&str>) -> bool {
-        match only {
-            Some("done") => node.yak.as_ref().map(|y| y.done).unwrap_or(false),
-            Some("not-done") => {
-                !node.yak.as_ref().map(|y| y.done).unwrap_or(false) || node.yak.is_none()
+    /// Check if node matches the filter; a parent with no yak of its own counts as open
+    fn should_display_node(&self, node: &YakNode, only: Option<&YakFilter>) -> bool {
+        let Some(filter) = only else {
+            return true;
+        };
+        let implicit;
+        let yak = match &node.yak {
+            Some(yak) => yak,
+            None => {
+                implicit = Yak::new(node.full_path.clone());
+                &implicit
             }
-            _ => true,
-        }
+        };
+        let children_done = node.children.iter().all(YakNode::is_done);
+        filter.matches(yak, children_done, Local::now().date_naive())
+    }
+
+    /// Whether nodes at `depth` (0 = top level) are the last level shown
+    fn is_collapsed(&self, depth: usize) -> bool {
+        self.max_depth.is_some_and(|max| depth + 1 >= max)
+    }
+
+    /// Whether `--collapse-done` shows `node` as one line in place of its subtree
+    fn folds_done(&self, node: &YakNode) -> bool {
+        self.collapse_done && !node.children.is_empty() && is_all_done(node)
     }
 
-    /// Display a single node
-    fn display_node(&self, node: &YakNode, format: &str, depth: usize) {
+    /// Display a single node, with the `count` of yaks collapsed under it
+    /// (e.g. " (+3)") and its `number` when listing numbered
+    fn display_node(
+        &self,
+        node: &YakNode,
+        format: OutputFormat,
+        depth: usize,
+        updated: bool,
+        count: &str,
+        number: Option<usize>,
+    ) {
         let message = match format {
-            "plain" => node.full_path.clone(),
+            OutputFormat::Plain => match number {
+                Some(number) => format!("{number}\t{}", node.full_path),
+                None => node.full_path.clone(),
+            },
             _ => {
                 let indent = "  ".repeat(depth);
-                let done = node.yak.as_ref().map(|y| y.done).unwrap_or(false);
-                let checkbox = if done { "[x]" } else { "[ ]" };
-                format!("{}- {} {}", indent, checkbox, node.name)
+                let checkbox = self.labels.checkbox(node.is_done());
+                let badge = if updated { " (updated)" } else { "" };
+                // Prefer the human phrasing when the name was normalized
+                let label = node
+                    .yak
+                    .as_ref()
+                    .and_then(|yak| yak.title.as_deref())
+                    .unwrap_or(&node.name);
+                let number = number
+                    .map(|number| format!("{number:>3}  "))
+                    .unwrap_or_default();
+                format!(
+                    "{}{}- {} {}{}{}",
+                    number, indent, checkbox, label, count, badge
+                )
             }
         };
 
-        // Apply gray color for done yaks in markdown format
-        let is_done = node.yak.as_ref().map(|y| y.done).unwrap_or(false);
-        if is_done && format == "markdown" {
-            self.output.info(&format!("\x1b[90m{message}\x1b[0m"));
+        // Dim done yaks in markdown format
+        if node.is_done() && format == OutputFormat::Markdown {
+            self.output.info(&self.output.paint(&message, Style::Done));
         } else {
             self.output.info(&message);
         }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::domain::Yak;
-    use std::cell::RefCell;
+/// Whether `node` and every yak below it are done
+fn is_all_done(node: &YakNode) -> bool {
+    node.is_done() && node.children.iter().all(is_all_done)
+}
 
-    struct MockStorage {
-        yaks: RefCell<Vec<Yak>>,
-    }
+/// The yak numbered `text` in the last numbered list, if `text` is such a number
+pub(super) fn numbered_yak(text: &str, state: &dyn LocalStatePort) -> Result<Option<String>> {
+    let Ok(number) = text.trim().parse::<usize>() else {
+        return Ok(None);
+    };
+    Ok(state.read(LAST_LIST_KEY)?.and_then(|mapping| {
+        mapping
+            .lines()
+            .nth(number.checked_sub(1)?)
+            .map(|name| name.to_string())
+    }))
+}
 
-    impl MockStorage {
-        fn new() -> Self {
-            Self {
-                yaks: RefCell::new(Vec::new()),
-            }
-        }
+/// How many listed yaks are in each state, for the summary footer
+#[derive(Default)]
+struct Totals {
+    todo: usize,
+    doing: usize,
+    blocked: usize,
+    done: usize,
+}
 
-        fn add_yak(&self, yak: Yak) {
-            self.yaks.borrow_mut().push(yak);
-        }
+impl Totals {
+    fn of(nodes: &[YakNode]) -> Self {
+        let mut totals = Self::default();
+        totals.add(nodes);
+        totals
     }
 
-    impl StoragePort for MockStorage {
-        fn create_yak(&self, _name: &str) -> Result<()> {
-            unimplemented!()
+    fn add(&mut self, nodes: &[YakNode]) {
+        for node in nodes {
+            match node.state() {
+                YakState::Todo => self.todo += 1,
+                YakState::Doing => self.doing += 1,
+                YakState::Blocked => self.blocked += 1,
+                YakState::Done => self.done += 1,
+            }
+            self.add(&node.children);
         }
+    }
 
-        fn get_yak(&self, _name: &str) -> Result<Yak> {
-            unimplemented!()
-        }
+    fn total(&self) -> usize {
+        self.todo + self.doing + self.blocked + self.done
+    }
 
-        fn list_yaks(&self) -> Result<Vec<Yak>> {
-            Ok(self.yaks.borrow().clone())
+    /// `4 todo, 2 doing, 7 done (3 hidden by filter)`; doing and blocked only
+    /// when there are some
+    fn summary(&self, hidden: usize) -> String {
+        let mut parts = vec![format!("{} todo", self.todo)];
+        if self.doing > 0 {
+            parts.push(format!("{} doing", self.doing));
         }
-
-        fn mark_done(&self, _name: &str, _done: bool) -> Result<()> {
-            unimplemented!()
+        if self.blocked > 0 {
+            parts.push(format!("{} blocked", self.blocked));
         }
-
-        fn delete_yak(&self, _name: &str) -> Result<()> {
-            unimplemented!()
+        parts.push(format!("{} done", self.done));
+        let mut line = parts.join(", ");
+        if hidden > 0 {
+            line.push_str(&format!(" ({hidden} hidden by filter)"));
         }
+        line
+    }
+}
 
-        fn rename_yak(&self, _from: &str, _to: &str) -> Result<()> {
-            unimplemented!()
-        }
+/// How many yaks in the tree `filtered` says to leave out
+fn count_filtered(nodes: &[YakNode], filtered: &dyn Fn(&YakNode) -> bool) -> usize {
+    nodes
+        .iter()
+        .map(|node| usize::from(filtered(node)) + count_filtered(&node.children, filtered))
+        .sum()
+}
 
-        fn read_context(&self, _name: &str) -> Result<String> {
-            unimplemented!()
-        }
+/// Drop done yaks, keeping a done yak that still has open yaks under it
+fn hide_done(nodes: &mut Vec<YakNode>) {
+    for node in nodes.iter_mut() {
+        hide_done(&mut node.children);
+    }
+    nodes.retain(|node| !node.is_done() || !node.children.is_empty());
+}
 
-        fn write_context(&self, _name: &str, _text: &str) -> Result<()> {
-            unimplemented!()
-        }
+/// Sibling order for a `--sort` key, falling back to the name on ties;
+/// `reverse` flips the order of the values but not where missing ones go
+fn sort_by_key(key: &str, a: &YakNode, b: &YakNode, reverse: bool) -> Ordering {
+    let (x, y) = (a.yak.as_ref(), b.yak.as_ref());
+    let order = match key {
+        "created" => missing_last(
+            x.and_then(|y| y.created_at),
+            y.and_then(|y| y.created_at),
+            reverse,
+        ),
+        "modified" => missing_last(
+            x.and_then(|y| y.modified_at),
+            y.and_then(|y| y.modified_at),
+            reverse,
+        ),
+        "priority" => missing_last(
+            x.and_then(|y| y.priority),
+            y.and_then(|y| y.priority),
+            reverse,
+        ),
+        "due" => missing_last(x.and_then(|y| y.due), y.and_then(|y| y.due), reverse),
+        _ => Ordering::Equal,
+    };
+    let by_name = a.name.cmp(&b.name);
+    order.then(if reverse { by_name.reverse() } else { by_name })
+}
 
-        fn find_yak(&self, _name: &str) -> Result<String> {
-            unimplemented!()
-        }
+/// Ascending (or descending), with missing values after all present ones
+fn missing_last<T: Ord>(a: Option<T>, b: Option<T>, reverse: bool) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) if reverse => Reverse(a).cmp(&Reverse(b)),
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::{Priority, Yak, YakState};
+    use std::cell::RefCell;
+    use std::collections::HashMap;
 
     struct MockOutput {
         messages: RefCell<Vec<String>>,
@@ -299,15 +744,48 @@ mod tests {
         fn info(&self, message: &str) {
             self.messages.borrow_mut().push(message.to_string());
         }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockState {
+        values: RefCell<HashMap<String, String>>,
+    }
+
+    impl MockState {
+        fn new() -> Self {
+            Self {
+                values: RefCell::new(HashMap::new()),
+            }
+        }
+    }
+
+    impl LocalStatePort for MockState {
+        fn read(&self, key: &str) -> Result<Option<String>> {
+            Ok(self.values.borrow().get(key).cloned())
+        }
+
+        fn write(&self, key: &str, value: Option<&str>) -> Result<()> {
+            match value {
+                Some(text) => self
+                    .values
+                    .borrow_mut()
+                    .insert(key.to_string(), text.to_string()),
+                None => self.values.borrow_mut().remove(key),
+            };
+            Ok(())
+        }
     }
 
     #[test]
     fn test_list_empty_yaks() {
-        let storage = MockStorage::new();
+        let storage = InMemoryStorage::new();
         let output = MockOutput::new();
         let use_case = ListYaks::new(&storage, &output);
 
-        use_case.execute("markdown", None).unwrap();
+        use_case.execute(OutputFormat::Markdown, None).unwrap();
 
         let messages = output.get_messages();
         assert_eq!(messages.len(), 1);
@@ -316,27 +794,92 @@ mod tests {
 
     #[test]
     fn test_list_single_yak() {
-        let storage = MockStorage::new();
+        let storage = InMemoryStorage::new();
         let output = MockOutput::new();
         storage.add_yak(Yak::new("test-yak".to_string()));
         let use_case = ListYaks::new(&storage, &output);
 
-        use_case.execute("markdown", None).unwrap();
+        use_case.execute(OutputFormat::Markdown, None).unwrap();
 
         let messages = output.get_messages();
         assert_eq!(messages.len(), 1);
         assert_eq!(messages[0], "- [ ] test-yak");
     }
 
+    #[test]
+    fn test_list_done_placement() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("b".to_string()).mark_done());
+        storage.add_yak(Yak::new("c".to_string()));
+        storage.add_yak(Yak::new("a".to_string()).mark_done());
+        storage.add_yak(Yak::new("a/x".to_string()).mark_done());
+        storage.add_yak(Yak::new("a/y".to_string()));
+        let list = |placement: &str| {
+            let output = MockOutput::new();
+            ListYaks::new(&storage, &output)
+                .with_done_placement(placement.to_string())
+                .execute(OutputFormat::Plain, None)
+                .map(|_| output.get_messages())
+        };
+
+        assert_eq!(list("first").unwrap(), vec!["a", "a/x", "a/y", "b", "c"]);
+        assert_eq!(list("last").unwrap(), vec!["c", "a", "a/y", "a/x", "b"]);
+        assert_eq!(list("hidden").unwrap(), vec!["a", "a/y", "c"]);
+        assert!(list("middle").is_err());
+    }
+
+    #[test]
+    fn test_list_summary_counts_states_and_hidden() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("a".to_string()).mark_done());
+        storage.add_yak(Yak::new("b".to_string()).with_state(YakState::Doing));
+        storage.add_yak(Yak::new("c".to_string()));
+        storage.add_yak(Yak::new("c/d".to_string()));
+        let output = MockOutput::new();
+
+        ListYaks::new(&storage, &output)
+            .with_summary(true)
+            .execute(OutputFormat::Markdown, Some("not-done"))
+            .unwrap();
+
+        let messages = output.get_messages();
+        assert_eq!(
+            messages[messages.len() - 2..],
+            ["", "2 todo, 1 doing, 1 done (1 hidden by filter)"]
+        );
+    }
+
+    #[test]
+    fn test_list_only_blocked_or_overdue() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("a".to_string()).with_state(YakState::Blocked));
+        storage.add_yak(Yak {
+            due: chrono::NaiveDate::from_ymd_opt(2020, 1, 1),
+            ..Yak::new("b".to_string())
+        });
+        storage.add_yak(Yak::new("c".to_string()).with_state(YakState::Doing));
+        let output = MockOutput::new();
+        let use_case = ListYaks::new(&storage, &output);
+
+        use_case
+            .execute(OutputFormat::Plain, Some("blocked,overdue"))
+            .unwrap();
+
+        assert_eq!(output.get_messages(), vec!["a", "b"]);
+        assert!(use_case
+            .execute(OutputFormat::Plain, Some("stale"))
+            .is_err());
+    }
+
     #[test]
     fn test_list_sorts_done_first() {
-        let storage = MockStorage::new();
+        let storage = InMemoryStorage::new();
         let output = MockOutput::new();
         storage.add_yak(Yak::new("done-yak".to_string()).mark_done());
         storage.add_yak(Yak::new("active-yak".to_string()));
         let use_case = ListYaks::new(&storage, &output);
 
-        use_case.execute("markdown", None).unwrap();
+        use_case.execute(OutputFormat::Markdown, None).unwrap();
 
         let messages = output.get_messages();
         assert_eq!(messages.len(), 2);
@@ -346,18 +889,306 @@ mod tests {
         assert_eq!(messages[1], "- [ ] active-yak");
     }
 
+    #[test]
+    fn test_list_table_format_shows_links() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        storage.add_yak(
+            Yak::new("parent/child".to_string())
+                .with_links(vec!["https://example.com/pr/1".to_string()]),
+        );
+        let use_case = ListYaks::new(&storage, &output);
+
+        use_case.execute(OutputFormat::Table, None).unwrap();
+
+        let messages = output.get_messages();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0], "NAME          STATE  LINKS");
+        assert_eq!(messages[1], "parent        todo");
+        assert_eq!(messages[2], "parent/child  todo   https://example.com/pr/1");
+    }
+
+    #[test]
+    fn test_list_table_format_shows_doing_and_blocked() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        storage.add_yak(Yak::new("api".to_string()).with_state(YakState::Doing));
+        storage.add_yak(Yak::new("deploy".to_string()).with_state(YakState::Blocked));
+        let use_case = ListYaks::new(&storage, &output);
+
+        use_case.execute(OutputFormat::Table, None).unwrap();
+
+        let messages = output.get_messages();
+        assert_eq!(messages[1], "api     doing");
+        assert_eq!(messages[2], "deploy  blocked");
+    }
+
+    #[test]
+    fn test_list_csv_format_quotes_fields() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        let mut yak = Yak::new("app/fix, then ship".to_string())
+            .with_links(vec![
+                "https://a.example".to_string(),
+                "https://b.example".to_string(),
+            ])
+            .with_state(YakState::Doing);
+        yak.priority = Some(Priority::parse("P1").unwrap());
+        yak.title = Some("Fix \"the\" bug".to_string());
+        storage.add_yak(yak);
+        storage.add_yak(Yak::new("docs".to_string()).mark_done());
+
+        ListYaks::new(&storage, &output)
+            .execute(OutputFormat::Csv, None)
+            .unwrap();
+        ListYaks::new(&InMemoryStorage::new(), &output)
+            .execute(OutputFormat::Csv, None)
+            .unwrap();
+
+        let header = "name,state,priority,due,assignee,title,links,created,modified";
+        assert_eq!(
+            output.get_messages(),
+            vec![
+                header,
+                "docs,done,,,,,,,",
+                "app,todo,,,,,,,",
+                "\"app/fix, then ship\",doing,P1,,,\"Fix \"\"the\"\" bug\",https://a.example https://b.example,,",
+                header,
+            ]
+        );
+    }
+
     #[test]
     fn test_list_hierarchical_yak() {
-        let storage = MockStorage::new();
+        let storage = InMemoryStorage::new();
         let output = MockOutput::new();
         storage.add_yak(Yak::new("parent/child".to_string()));
         let use_case = ListYaks::new(&storage, &output);
 
-        use_case.execute("markdown", None).unwrap();
+        use_case.execute(OutputFormat::Markdown, None).unwrap();
 
         let messages = output.get_messages();
         assert_eq!(messages.len(), 2);
         assert_eq!(messages[0], "- [ ] parent");
         assert_eq!(messages[1], "  - [ ] child");
     }
+
+    #[test]
+    fn test_list_scoped_to_a_subtree() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        storage.add_yak(Yak::new("backend".to_string()));
+        storage.add_yak(Yak::new("backend/api".to_string()));
+        storage.add_yak(Yak::new("backend/api/auth".to_string()).mark_done());
+        storage.add_yak(Yak::new("backend/db".to_string()));
+        storage.add_yak(Yak::new("backend/api-docs".to_string()));
+        let use_case = ListYaks::new(&storage, &output).with_scope("backend/api".to_string());
+
+        use_case
+            .execute(OutputFormat::Markdown, Some("not-done"))
+            .unwrap();
+        use_case.execute(OutputFormat::Plain, None).unwrap();
+
+        assert_eq!(
+            output.get_messages(),
+            vec!["- [ ] api", "backend/api", "backend/api/auth"]
+        );
+        assert!(ListYaks::new(&storage, &output)
+            .with_scope("nope".to_string())
+            .execute(OutputFormat::Plain, None)
+            .is_err());
+    }
+
+    #[test]
+    fn test_list_depth_collapses_deeper_levels() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        storage.add_yak(Yak::new("backend/api/auth".to_string()));
+        storage.add_yak(Yak::new("backend/db".to_string()));
+        storage.add_yak(Yak::new("docs".to_string()));
+        let use_case = ListYaks::new(&storage, &output).with_depth(2);
+
+        use_case.execute(OutputFormat::Markdown, None).unwrap();
+        use_case.execute(OutputFormat::Plain, None).unwrap();
+
+        assert_eq!(
+            output.get_messages(),
+            vec![
+                "- [ ] backend",
+                "  - [ ] api (+1)",
+                "  - [ ] db",
+                "- [ ] docs",
+                "backend",
+                "backend/api",
+                "backend/db",
+                "docs",
+            ]
+        );
+        assert!(ListYaks::new(&storage, &output)
+            .with_depth(0)
+            .execute(OutputFormat::Plain, None)
+            .is_err());
+    }
+
+    #[test]
+    fn test_list_collapse_done_folds_finished_subtrees() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        storage.add_yak(Yak::new("migration".to_string()).mark_done());
+        storage.add_yak(Yak::new("migration/schema".to_string()).mark_done());
+        storage.add_yak(Yak::new("migration/data".to_string()).mark_done());
+        storage.add_yak(Yak::new("release".to_string()).mark_done());
+        storage.add_yak(Yak::new("release/notes".to_string()));
+
+        ListYaks::new(&storage, &output)
+            .with_collapse_done(true)
+            .execute(OutputFormat::Markdown, None)
+            .unwrap();
+
+        let messages = output.get_messages();
+        assert_eq!(messages.len(), 3);
+        assert!(messages[0].contains("migration (2 done)"));
+        assert!(messages[1].contains("release"));
+        assert!(messages[2].contains("notes"));
+    }
+
+    #[test]
+    fn test_list_sorts_by_key_with_missing_values_last() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        storage.add_yak(Yak {
+            name: "api".to_string(),
+            priority: Some(Priority::LOWEST),
+            ..Default::default()
+        });
+        storage.add_yak(Yak {
+            name: "ci".to_string(),
+            priority: Some(Priority::HIGHEST),
+            done: true,
+            ..Default::default()
+        });
+        storage.add_yak(Yak::new("docs".to_string()));
+        storage.add_yak(Yak {
+            name: "release".to_string(),
+            priority: Some(Priority::HIGHEST),
+            ..Default::default()
+        });
+
+        ListYaks::new(&storage, &output)
+            .with_sort("priority".to_string())
+            .execute(OutputFormat::Plain, None)
+            .unwrap();
+        ListYaks::new(&storage, &output)
+            .with_sort("name".to_string())
+            .with_reverse(true)
+            .execute(OutputFormat::Plain, None)
+            .unwrap();
+        // Reversed, "docs" has no priority so it stays last
+        ListYaks::new(&storage, &output)
+            .with_sort("priority".to_string())
+            .with_reverse(true)
+            .execute(OutputFormat::Plain, None)
+            .unwrap();
+
+        assert_eq!(
+            output.get_messages(),
+            vec![
+                "ci", "release", "api", "docs", "release", "docs", "ci", "api", "api", "release",
+                "ci", "docs"
+            ]
+        );
+        assert!(ListYaks::new(&storage, &output)
+            .with_sort("size".to_string())
+            .execute(OutputFormat::Plain, None)
+            .is_err());
+    }
+
+    #[test]
+    fn test_list_filters_by_assignee() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        storage.add_yak(Yak {
+            name: "mine".to_string(),
+            assignee: Some("me@example.com".to_string()),
+            ..Default::default()
+        });
+        storage.add_yak(Yak {
+            name: "theirs".to_string(),
+            assignee: Some("them@example.com".to_string()),
+            ..Default::default()
+        });
+        storage.add_yak(Yak::new("unassigned".to_string()));
+        let use_case = ListYaks::new(&storage, &output).with_assignee("me@example.com".to_string());
+
+        use_case.execute(OutputFormat::Plain, None).unwrap();
+
+        assert_eq!(output.get_messages(), vec!["mine"]);
+    }
+
+    #[test]
+    fn test_list_shows_titles_in_markdown() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        storage.add_yak(Yak::new("fix-login".to_string()).with_title("Fix Login!".to_string()));
+        let use_case = ListYaks::new(&storage, &output);
+
+        use_case.execute(OutputFormat::Markdown, None).unwrap();
+        use_case.execute(OutputFormat::Plain, None).unwrap();
+
+        assert_eq!(output.get_messages(), vec!["- [ ] Fix Login!", "fix-login"]);
+    }
+
+    #[test]
+    fn test_list_badges_unread_context_changes() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        let state = MockState::new();
+        storage.add_yak(Yak::new("read".to_string()).with_context("notes".to_string()));
+        storage.add_yak(Yak::new("unread".to_string()).with_context("notes".to_string()));
+        ReadTracker::new(&state).mark_seen("read", "notes").unwrap();
+        let use_case = ListYaks::new(&storage, &output).with_read_tracking(&state);
+
+        use_case.execute(OutputFormat::Markdown, None).unwrap();
+
+        let messages = output.get_messages();
+        assert_eq!(messages[0], "- [ ] read");
+        assert_eq!(messages[1], "- [ ] unread (updated)");
+    }
+
+    #[test]
+    fn test_list_numbered_remembers_the_numbers() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        let state = MockState::new();
+        storage.add_yak(Yak::new("backend/api".to_string()));
+        storage.add_yak(Yak::new("backend/db".to_string()).mark_done());
+        storage.add_yak(Yak::new("docs".to_string()));
+        let use_case = ListYaks::new(&storage, &output).with_numbering(&state);
+
+        use_case
+            .execute(OutputFormat::Plain, Some("not-done"))
+            .unwrap();
+        use_case
+            .execute(OutputFormat::Markdown, Some("not-done"))
+            .unwrap();
+
+        assert_eq!(
+            output.get_messages(),
+            vec![
+                "1\tbackend",
+                "2\tbackend/api",
+                "3\tdocs",
+                "  1  - [ ] backend",
+                "  2    - [ ] api",
+                "  3  - [ ] docs",
+            ]
+        );
+        assert_eq!(
+            numbered_yak("2", &state).unwrap(),
+            Some("backend/api".to_string())
+        );
+        assert_eq!(numbered_yak("4", &state).unwrap(), None);
+        assert_eq!(numbered_yak("0", &state).unwrap(), None);
+        assert_eq!(numbered_yak("docs", &state).unwrap(), None);
+    }
 }
@@ -0,0 +1,260 @@
+// MergeYaks use case - folds one yak into another, e.g. duplicates created by different teammates
+
+use super::transaction;
+use crate::domain::graph;
+use crate::ports::{LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+
+/// Single-value fields the target takes over from the source when it has none of its own
+const INHERITED_FIELDS: &[&str] = &["assignee", "priority", "due", "estimate"];
+
+pub struct MergeYaks<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+}
+
+impl<'a> MergeYaks<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            log,
+        }
+    }
+
+    /// Merge `source` into `target`: append its context, take over its links and
+    /// missing fields, move its children under `target`, then remove it
+    pub fn execute(&self, source: &str, target: &str) -> Result<()> {
+        // Resolve yak names (exact or fuzzy match)
+        let source = self.storage.find_yak(source)?;
+        let target = self.storage.find_yak(target)?;
+
+        if source == target {
+            anyhow::bail!("cannot merge '{source}' into itself");
+        }
+        if graph::is_descendant_of(&target, &source) {
+            anyhow::bail!("cannot merge '{source}' into '{target}' - it is under '{source}'");
+        }
+
+        // Check every child can move before changing anything
+        let all_yaks = self.storage.list_yaks()?;
+        let moves: Vec<(String, String)> = graph::children_of(&all_yaks, &source)
+            .iter()
+            .map(|child| {
+                let moved = format!("{target}{}", &child.name[source.len()..]);
+                (child.name.clone(), moved)
+            })
+            .collect();
+        if let Some((_, taken)) = moves
+            .iter()
+            .find(|(_, moved)| all_yaks.iter().any(|yak| &yak.name == moved))
+        {
+            anyhow::bail!("cannot merge '{source}' into '{target}' - '{taken}' already exists");
+        }
+
+        // Either the whole merge happens or none of it does
+        transaction::apply(
+            self.storage,
+            self.log,
+            &format!("merge {source} {target}"),
+            || self.merge(&source, &target, &moves),
+        )?;
+
+        match moves.len() {
+            0 => self
+                .output
+                .success(&format!("Merged '{source}' into '{target}'")),
+            count => self.output.success(&format!(
+                "Merged '{source}' into '{target}' and moved {count} yak(s) under it"
+            )),
+        }
+
+        Ok(())
+    }
+
+    /// Fold `source` into `target`, moving its children as `moves` says
+    fn merge(&self, source: &str, target: &str, moves: &[(String, String)]) -> Result<()> {
+        let source_context = self.storage.read_context(source).unwrap_or_default();
+        if !source_context.trim().is_empty() {
+            let target_context = self.storage.read_context(target).unwrap_or_default();
+            let merged = if target_context.trim().is_empty() {
+                source_context
+            } else {
+                format!("{}\n\n{source_context}", target_context.trim_end())
+            };
+            self.storage.write_context(target, &merged)?;
+        }
+
+        let mut links = lines(self.storage.read_field(target, "links")?);
+        for link in lines(self.storage.read_field(source, "links")?) {
+            if !links.contains(&link) {
+                links.push(link);
+            }
+        }
+        if !links.is_empty() {
+            self.storage
+                .write_field(target, "links", Some(&format!("{}\n", links.join("\n"))))?;
+        }
+
+        for field in INHERITED_FIELDS {
+            if self.storage.read_field(target, field)?.is_none() {
+                if let Some(value) = self.storage.read_field(source, field)? {
+                    self.storage.write_field(target, field, Some(&value))?;
+                }
+            }
+        }
+
+        for (from, to) in moves {
+            self.storage.rename_yak(from, to)?;
+        }
+        self.storage.delete_yak(source)?;
+        Ok(())
+    }
+}
+
+fn lines(text: Option<String>) -> Vec<String> {
+    text.unwrap_or_default()
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::ports::LogEntry;
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, _message: &str) {}
+
+        fn info(&self, _message: &str) {}
+
+        fn warn(&self, _message: &str) {}
+    }
+
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    fn output_and_log() -> (MockOutput, MockLog) {
+        (
+            MockOutput {
+                messages: RefCell::new(Vec::new()),
+            },
+            MockLog {
+                commands: RefCell::new(Vec::new()),
+            },
+        )
+    }
+
+    fn storage_with(names: &[&str]) -> InMemoryStorage {
+        let storage = InMemoryStorage::new();
+        for name in names {
+            storage.create_yak(name).unwrap();
+        }
+        storage
+    }
+
+    fn names(storage: &InMemoryStorage) -> Vec<String> {
+        storage
+            .list_yaks()
+            .unwrap()
+            .into_iter()
+            .map(|yak| yak.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_combines_context_links_and_children() {
+        let storage = storage_with(&["login bug", "login bug/repro", "fix login"]);
+        storage.write_context("login bug", "Safari only\n").unwrap();
+        storage
+            .write_context("fix login", "Session expires\n")
+            .unwrap();
+        storage
+            .write_field("login bug", "links", Some("https://a\nhttps://b\n"))
+            .unwrap();
+        storage
+            .write_field("fix login", "links", Some("https://b\n"))
+            .unwrap();
+        storage
+            .write_field("login bug", "priority", Some("high\n"))
+            .unwrap();
+        let (output, log) = output_and_log();
+        let use_case = MergeYaks::new(&storage, &output, &log);
+
+        use_case.execute("login bug", "fix login").unwrap();
+
+        assert_eq!(names(&storage), vec!["fix login", "fix login/repro"]);
+        assert_eq!(
+            storage.read_context("fix login").unwrap(),
+            "Session expires\n\nSafari only\n"
+        );
+        assert_eq!(
+            storage.read_field("fix login", "links").unwrap(),
+            Some("https://b\nhttps://a\n".to_string())
+        );
+        assert_eq!(
+            storage.read_field("fix login", "priority").unwrap(),
+            Some("high\n".to_string())
+        );
+        assert_eq!(*log.commands.borrow(), vec!["merge login bug fix login"]);
+        assert_eq!(
+            *output.messages.borrow(),
+            vec!["Merged 'login bug' into 'fix login' and moved 1 yak(s) under it"]
+        );
+    }
+
+    #[test]
+    fn test_merge_refuses_clashing_children() {
+        let storage = storage_with(&["a", "a/docs", "b", "b/docs"]);
+        let (output, log) = output_and_log();
+        let use_case = MergeYaks::new(&storage, &output, &log);
+
+        let result = use_case.execute("a", "b");
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "cannot merge 'a' into 'b' - 'b/docs' already exists"
+        );
+        assert_eq!(names(&storage).len(), 4);
+        assert!(log.commands.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_merge_refuses_target_under_source() {
+        let storage = storage_with(&["a", "a/b"]);
+        let (output, log) = output_and_log();
+        let use_case = MergeYaks::new(&storage, &output, &log);
+
+        assert!(use_case.execute("a", "a/b").is_err());
+        assert!(use_case.execute("a", "a").is_err());
+    }
+}
@@ -0,0 +1,259 @@
+// MigrateYaks use case - upgrades yaks kept in an older storage format
+
+use super::transaction;
+use crate::ports::{LogPort, Migration, MigrationPort, OutputPort, StoragePort};
+use anyhow::Result;
+
+pub struct MigrateYaks<'a> {
+    storage: &'a dyn StoragePort,
+    migrations: &'a dyn MigrationPort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+    target: Option<String>,
+    dry_run: bool,
+}
+
+impl<'a> MigrateYaks<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        migrations: &'a dyn MigrationPort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+    ) -> Self {
+        Self {
+            storage,
+            migrations,
+            output,
+            log,
+            target: None,
+            dry_run: false,
+        }
+    }
+
+    /// Stop after the migration called `name` (e.g. "state") rather than
+    /// applying every pending one
+    pub fn with_target(mut self, name: impl Into<String>) -> Self {
+        self.target = Some(name.into());
+        self
+    }
+
+    /// List the migrations that would run without applying them
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        let mut pending = self.migrations.pending_migrations()?;
+        if let Some(name) = &self.target {
+            let known = self.migrations.migrations();
+            let Some(target) = known.iter().find(|migration| migration.name == name) else {
+                let names: Vec<&str> = known.iter().map(|migration| migration.name).collect();
+                anyhow::bail!("Unknown migration '{name}': use {}", names.join(", "));
+            };
+            pending.retain(|migration| migration.version <= target.version);
+            if pending.is_empty() {
+                self.output
+                    .info(&format!("Already migrated: {}", describe(target)));
+                return Ok(());
+            }
+        }
+        if pending.is_empty() {
+            let version = self.migrations.format_version()?;
+            self.output
+                .info(&format!("Yaks are up to date (storage format v{version})"));
+            return Ok(());
+        }
+
+        if self.dry_run {
+            for migration in &pending {
+                self.output
+                    .info(&format!("Would migrate: {}", describe(migration)));
+            }
+            return Ok(());
+        }
+
+        self.apply(&pending)?;
+        for migration in &pending {
+            self.output
+                .success(&format!("Migrated: {}", describe(migration)));
+        }
+        Ok(())
+    }
+
+    /// Apply any pending migrations before another command reads the yaks,
+    /// noting the upgrade on stderr so that command's output is unchanged
+    pub fn upgrade(&self) -> Result<()> {
+        let pending = self.migrations.pending_migrations()?;
+        let Some(last) = pending.last() else {
+            return Ok(());
+        };
+        self.apply(&pending)?;
+        self.output.note(&format!(
+            "Upgraded yaks to storage format v{} (see `yx migrate`)",
+            last.version
+        ));
+        Ok(())
+    }
+
+    /// All of `pending` as one change, logged with their names: if one
+    /// fails, none are kept
+    fn apply(&self, pending: &[Migration]) -> Result<()> {
+        let names: Vec<&str> = pending.iter().map(|migration| migration.name).collect();
+        let command = format!("migrate {}", names.join(" "));
+        transaction::apply(self.storage, self.log, &command, || {
+            for migration in pending {
+                self.migrations.apply_migration(migration)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// e.g. "v1 version - record the storage format in .yaks/.version"
+fn describe(migration: &Migration) -> String {
+    format!(
+        "v{} {} - {}",
+        migration.version, migration.name, migration.description
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::ports::LogEntry;
+    use std::cell::{Cell, RefCell};
+
+    const MIGRATIONS: [Migration; 2] = [
+        Migration {
+            version: 1,
+            name: "version",
+            description: "record the version",
+        },
+        Migration {
+            version: 2,
+            name: "state",
+            description: "move done markers",
+        },
+    ];
+
+    struct MockMigrations {
+        version: Cell<u32>,
+    }
+
+    impl MigrationPort for MockMigrations {
+        fn migrations(&self) -> Vec<Migration> {
+            MIGRATIONS.to_vec()
+        }
+
+        fn format_version(&self) -> Result<u32> {
+            Ok(self.version.get())
+        }
+
+        fn pending_migrations(&self) -> Result<Vec<Migration>> {
+            Ok(MIGRATIONS
+                .into_iter()
+                .filter(|migration| migration.version > self.version.get())
+                .collect())
+        }
+
+        fn apply_migration(&self, migration: &Migration) -> Result<()> {
+            self.version.set(migration.version);
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, _message: &str) {}
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn note(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, _message: &str) {}
+    }
+
+    #[derive(Default)]
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_migrate_previews_then_applies_pending_migrations() {
+        let storage = InMemoryStorage::new();
+        let migrations = MockMigrations {
+            version: Cell::new(1),
+        };
+        let output = MockOutput::default();
+        let log = MockLog::default();
+
+        MigrateYaks::new(&storage, &migrations, &output, &log)
+            .with_dry_run(true)
+            .execute()
+            .unwrap();
+        assert_eq!(migrations.version.get(), 1);
+
+        let migrate = || MigrateYaks::new(&storage, &migrations, &output, &log);
+        assert!(migrate().with_target("paused").execute().is_err());
+        migrate().with_target("state").execute().unwrap();
+        migrate().with_target("state").execute().unwrap();
+        migrate().execute().unwrap();
+
+        assert_eq!(
+            *output.messages.borrow(),
+            vec![
+                "Would migrate: v2 state - move done markers",
+                "Migrated: v2 state - move done markers",
+                "Already migrated: v2 state - move done markers",
+                "Yaks are up to date (storage format v2)",
+            ]
+        );
+        assert_eq!(*log.commands.borrow(), vec!["migrate state"]);
+    }
+
+    #[test]
+    fn test_upgrade_is_silent_when_up_to_date() {
+        let storage = InMemoryStorage::new();
+        let migrations = MockMigrations {
+            version: Cell::new(0),
+        };
+        let output = MockOutput::default();
+        let log = MockLog::default();
+        let use_case = MigrateYaks::new(&storage, &migrations, &output, &log);
+
+        use_case.upgrade().unwrap();
+        use_case.upgrade().unwrap();
+
+        assert_eq!(migrations.version.get(), 2);
+        assert_eq!(
+            *output.messages.borrow(),
+            vec!["Upgraded yaks to storage format v2 (see `yx migrate`)"]
+        );
+        assert_eq!(*log.commands.borrow(), vec!["migrate version state"]);
+    }
+}
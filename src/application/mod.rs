@@ -1,21 +1,150 @@
 // Application layer - use cases that orchestrate domain + ports
 
 mod add_yak;
+mod apply_retention;
+mod archive_yak;
+mod blame_yak;
+mod bulk_yaks;
+mod check_yaks;
+mod complete_names;
+mod context_history;
+mod copy_yak;
+mod count_yaks;
+mod diff_yaks;
 mod done_yak;
+mod edit_all;
 mod edit_context;
+mod editor;
+mod effort_yaks;
+mod escalate_yaks;
+mod estimate_yak;
+mod export_obsidian;
+mod export_yaks;
+mod focus_yak;
+mod graph_yaks;
+mod grep_yaks;
+mod hints;
+mod history;
+mod ics;
+mod import_checklist;
+mod import_obsidian;
+mod link_yak;
+mod list_all_yaks;
+mod list_stash;
+mod list_trash;
 mod list_yaks;
+mod merge_yaks;
+mod migrate_yaks;
 mod move_yak;
+mod naming;
+mod note_yak;
+mod open_yak;
+mod output_format;
+mod plan_week;
+mod pop_stash;
+mod porcelain;
+mod prioritize_yak;
 mod prune_yaks;
+mod read_tracking;
 mod remove_yak;
+mod report_bug;
+mod report_heatmap;
+mod report_work;
+mod restore_from_trash;
+mod restore_yak;
+mod schedule_yak;
+mod set_state;
+mod show_all_status;
+mod show_board;
 mod show_context;
+mod show_history;
+mod show_prompt;
+mod show_standup;
+mod show_stats;
+mod show_status;
+mod split_yak;
+mod start_pomodoro;
+mod start_yak;
+mod state_labels;
+mod suggest_owner;
+mod suggest_yak;
 mod sync_yaks;
+mod time_format;
+mod title_yak;
+mod transaction;
+mod tree_yaks;
+mod unarchive_yak;
+mod verify_names;
+mod watch_yaks;
+mod workspaces;
 
 pub use add_yak::AddYak;
+pub use apply_retention::ApplyRetention;
+pub use archive_yak::ArchiveYak;
+pub use blame_yak::BlameYak;
+pub use bulk_yaks::BulkYaks;
+pub use check_yaks::CheckYaks;
+pub use complete_names::CompleteNames;
+pub use context_history::ContextHistory;
+pub use copy_yak::CopyYak;
+pub use count_yaks::CountYaks;
+pub use diff_yaks::DiffYaks;
 pub use done_yak::DoneYak;
+pub use edit_all::EditAll;
 pub use edit_context::EditContext;
+pub use effort_yaks::EffortYaks;
+pub use escalate_yaks::EscalateYaks;
+pub use estimate_yak::EstimateYak;
+pub use export_obsidian::ExportObsidian;
+pub use export_yaks::ExportYaks;
+pub use focus_yak::{name_or_focus, FocusYak};
+pub use graph_yaks::GraphYaks;
+pub use grep_yaks::GrepYaks;
+pub use hints::hints_enabled;
+pub use import_checklist::ImportChecklist;
+pub use import_obsidian::ImportObsidian;
+pub use link_yak::LinkYak;
+pub use list_all_yaks::ListAllYaks;
+pub use list_stash::ListStash;
+pub use list_trash::ListTrash;
 pub use list_yaks::ListYaks;
+pub use merge_yaks::MergeYaks;
+pub use migrate_yaks::MigrateYaks;
 pub use move_yak::MoveYak;
+pub use note_yak::NoteYak;
+pub use open_yak::OpenYak;
+pub use output_format::OutputFormat;
+pub use plan_week::PlanWeek;
+pub use pop_stash::PopStash;
+pub use prioritize_yak::PrioritizeYak;
 pub use prune_yaks::PruneYaks;
 pub use remove_yak::RemoveYak;
+pub use report_bug::ReportBug;
+pub use report_heatmap::ReportHeatmap;
+pub use report_work::ReportWork;
+pub use restore_from_trash::RestoreFromTrash;
+pub use restore_yak::RestoreYak;
+pub use schedule_yak::ScheduleYak;
+pub use set_state::SetState;
+pub use show_all_status::{ShowAllStatus, WorkspacePorts};
+pub use show_board::ShowBoard;
 pub use show_context::ShowContext;
+pub use show_history::ShowHistory;
+pub use show_prompt::ShowPrompt;
+pub use show_standup::ShowStandup;
+pub use show_stats::ShowStats;
+pub use show_status::ShowStatus;
+pub use split_yak::SplitYak;
+pub use start_pomodoro::StartPomodoro;
+pub use start_yak::StartYak;
+pub use state_labels::StateLabels;
+pub use suggest_owner::SuggestOwner;
+pub use suggest_yak::SuggestYak;
 pub use sync_yaks::SyncYaks;
+pub use time_format::TimeStyle;
+pub use title_yak::TitleYak;
+pub use tree_yaks::TreeYaks;
+pub use unarchive_yak::UnarchiveYak;
+pub use verify_names::VerifyNames;
+pub use watch_yaks::WatchYaks;
+pub use workspaces::{configured_workspaces, PrefixedOutput};
@@ -1,35 +1,80 @@
-// MoveYak use case - renames/relocates a yak
+// MoveYak use case - renames/relocates a yak along with everything under it
 
-use crate::domain::validate_yak_name;
-use crate::ports::{LogPort, OutputPort, StoragePort};
+use super::naming;
+use super::read_tracking::ReadTracker;
+use crate::domain::{graph, validate_yak_name};
+use crate::ports::{ConfigPort, LocalStatePort, LogPort, OutputPort, StoragePort};
 use anyhow::Result;
 
 pub struct MoveYak<'a> {
     storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
     log: &'a dyn LogPort,
+    config: Option<&'a dyn ConfigPort>,
+    read_tracker: Option<ReadTracker<'a>>,
 }
 
 impl<'a> MoveYak<'a> {
     pub fn new(
         storage: &'a dyn StoragePort,
-        _output: &'a dyn OutputPort,
+        output: &'a dyn OutputPort,
         log: &'a dyn LogPort,
     ) -> Self {
-        Self { storage, log }
+        Self {
+            storage,
+            output,
+            log,
+            config: None,
+            read_tracker: None,
+        }
+    }
+
+    /// Carry over which contexts were viewed, so moved yaks aren't badged as updated
+    pub fn with_read_tracking(mut self, state: &'a dyn LocalStatePort) -> Self {
+        self.read_tracker = Some(ReadTracker::new(state));
+        self
+    }
+
+    /// Apply the repo's name normalization rules, if any are configured
+    pub fn with_config(mut self, config: &'a dyn ConfigPort) -> Self {
+        self.config = Some(config);
+        self
     }
 
     pub fn execute(&self, from: &str, to: &str) -> Result<()> {
+        let rules = naming::configured_rules(self.config)?;
+        let (to, title) = naming::normalize(to, &rules);
+
         // Validate new name
-        validate_yak_name(to).map_err(|e| anyhow::anyhow!(e))?;
+        validate_yak_name(&to).map_err(|e| anyhow::anyhow!(e))?;
 
         // Resolve source yak name (exact or fuzzy match)
         let resolved_from = self.storage.find_yak(from)?;
+        if graph::is_descendant_of(&to, &resolved_from) {
+            anyhow::bail!("cannot move '{resolved_from}' under itself");
+        }
+
+        // Children live inside the yak, so renaming it moves the whole subtree
+        let all_yaks = self.storage.list_yaks()?;
+        let descendants = graph::descendants_of(&all_yaks, &resolved_from).len();
 
         // Rename the yak
-        self.storage.rename_yak(&resolved_from, to)?;
+        self.storage.rename_yak(&resolved_from, &to)?;
+        if let Some(title) = &title {
+            self.storage.write_field(&to, "title", Some(title))?;
+        }
+        if let Some(tracker) = &self.read_tracker {
+            tracker.rename(&resolved_from, &to)?;
+        }
         self.log
             .log_command(&format!("move {resolved_from} {to}"))?;
 
+        if descendants > 0 {
+            self.output.success(&format!(
+                "Moved '{resolved_from}' to '{to}' with {descendants} yak(s) under it"
+            ));
+        }
+
         Ok(())
     }
 }
@@ -37,94 +82,11 @@ impl<'a> MoveYak<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::adapters::storage::InMemoryStorage;
     use crate::domain::Yak;
+    use crate::ports::LogEntry;
     use std::cell::RefCell;
 
-    struct MockStorage {
-        yaks: RefCell<Vec<Yak>>,
-    }
-
-    impl MockStorage {
-        fn new() -> Self {
-            Self {
-                yaks: RefCell::new(Vec::new()),
-            }
-        }
-
-        fn add_yak(&self, name: &str, done: bool) {
-            self.yaks.borrow_mut().push(Yak {
-                name: name.to_string(),
-                done,
-                context: None,
-            });
-        }
-
-        fn yak_exists(&self, name: &str) -> bool {
-            self.yaks.borrow().iter().any(|y| y.name == name)
-        }
-    }
-
-    impl StoragePort for MockStorage {
-        fn create_yak(&self, _name: &str) -> Result<()> {
-            unimplemented!()
-        }
-
-        fn get_yak(&self, name: &str) -> Result<Yak> {
-            self.yaks
-                .borrow()
-                .iter()
-                .find(|y| y.name == name)
-                .cloned()
-                .ok_or_else(|| anyhow::anyhow!("yak '{}' not found", name))
-        }
-
-        fn list_yaks(&self) -> Result<Vec<Yak>> {
-            unimplemented!()
-        }
-
-        fn mark_done(&self, _name: &str, _done: bool) -> Result<()> {
-            unimplemented!()
-        }
-
-        fn delete_yak(&self, _name: &str) -> Result<()> {
-            unimplemented!()
-        }
-
-        fn rename_yak(&self, from: &str, to: &str) -> Result<()> {
-            let mut yaks = self.yaks.borrow_mut();
-
-            // Check source exists
-            if !yaks.iter().any(|y| y.name == from) {
-                anyhow::bail!("yak '{}' not found", from);
-            }
-
-            // Check target doesn't exist
-            if yaks.iter().any(|y| y.name == to) {
-                anyhow::bail!("Yak '{}' already exists", to);
-            }
-
-            // Rename the yak
-            if let Some(yak) = yaks.iter_mut().find(|y| y.name == from) {
-                yak.name = to.to_string();
-            }
-
-            Ok(())
-        }
-
-        fn read_context(&self, _name: &str) -> Result<String> {
-            unimplemented!()
-        }
-
-        fn write_context(&self, _name: &str, _text: &str) -> Result<()> {
-            unimplemented!()
-        }
-
-        fn find_yak(&self, name: &str) -> Result<String> {
-            self.get_yak(name)?;
-            Ok(name.to_string())
-        }
-    }
-
     struct MockOutput {
         messages: RefCell<Vec<String>>,
     }
@@ -157,6 +119,12 @@ mod tests {
                 .borrow_mut()
                 .push(format!("INFO: {}", message));
         }
+
+        fn warn(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("WARN: {}", message));
+        }
     }
 
     struct MockLog;
@@ -165,24 +133,80 @@ mod tests {
         fn log_command(&self, _command: &str) -> Result<()> {
             Ok(())
         }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
     }
 
     #[test]
     fn test_move_yak_renames_yak() {
-        let storage = MockStorage::new();
-        storage.add_yak("old-name", false);
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("old-name".to_string()));
         let output = MockOutput::new();
         let use_case = MoveYak::new(&storage, &output, &MockLog);
 
         use_case.execute("old-name", "new-name").unwrap();
 
-        assert!(!storage.yak_exists("old-name"));
-        assert!(storage.yak_exists("new-name"));
+        assert!(storage.get_yak("old-name").is_err());
+        assert!(storage.get_yak("new-name").is_ok());
+    }
+
+    struct MockConfig(&'static str);
+
+    impl ConfigPort for MockConfig {
+        fn get(&self, _key: &str) -> Option<String> {
+            Some(self.0.to_string())
+        }
+
+        fn get_all(&self, _key: &str) -> Vec<String> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_move_yak_normalizes_target_and_keeps_title() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("old-name".to_string()));
+        let output = MockOutput::new();
+        let config = MockConfig("lowercase,hyphenate");
+        let use_case = MoveYak::new(&storage, &output, &MockLog).with_config(&config);
+
+        use_case.execute("old-name", "Web/New Name").unwrap();
+
+        assert!(storage.get_yak("web/new-name").is_ok());
+        assert_eq!(
+            storage.get_yak("web/new-name").unwrap().title.as_deref(),
+            Some("New Name")
+        );
+    }
+
+    #[test]
+    fn test_move_yak_moves_subtree_and_reports_it() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("backend".to_string()));
+        storage.add_yak(Yak::new("backend/api".to_string()));
+        storage.add_yak(Yak::new("backend/api/auth".to_string()).mark_done());
+        storage.add_yak(Yak::new("backend-docs".to_string()));
+        let output = MockOutput::new();
+        let use_case = MoveYak::new(&storage, &output, &MockLog);
+
+        use_case.execute("backend", "services").unwrap();
+
+        assert!(storage.get_yak("services/api").is_ok());
+        assert!(storage.get_yak("services/api/auth").is_ok());
+        assert!(storage.get_yak("backend-docs").is_ok());
+        assert_eq!(
+            output.last_message(),
+            Some("Moved 'backend' to 'services' with 2 yak(s) under it".to_string())
+        );
+
+        assert!(use_case.execute("services", "services/api/x").is_err());
     }
 
     #[test]
     fn test_move_yak_fails_for_nonexistent_source() {
-        let storage = MockStorage::new();
+        let storage = InMemoryStorage::new();
         let output = MockOutput::new();
         let use_case = MoveYak::new(&storage, &output, &MockLog);
 
@@ -193,9 +217,9 @@ mod tests {
 
     #[test]
     fn test_move_yak_fails_for_existing_target() {
-        let storage = MockStorage::new();
-        storage.add_yak("old-name", false);
-        storage.add_yak("new-name", false);
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("old-name".to_string()));
+        storage.add_yak(Yak::new("new-name".to_string()));
         let output = MockOutput::new();
         let use_case = MoveYak::new(&storage, &output, &MockLog);
 
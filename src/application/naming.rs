@@ -0,0 +1,76 @@
+// Name normalization shared by use cases that create or rename yaks
+
+use crate::domain::{normalize_yak_name, parse_name_rules, NameRule};
+use crate::ports::ConfigPort;
+use anyhow::Result;
+
+/// Config key holding the comma-separated name rules, e.g. "lowercase,hyphenate"
+pub const NAME_RULES_KEY: &str = "yaks.names.normalize";
+
+/// Rules configured for this repo, none when unset
+pub fn configured_rules(config: Option<&dyn ConfigPort>) -> Result<Vec<NameRule>> {
+    let Some(value) = config.and_then(|config| config.get(NAME_RULES_KEY)) else {
+        return Ok(Vec::new());
+    };
+    parse_name_rules(&value).map_err(|e| anyhow::anyhow!("{NAME_RULES_KEY}: {e}"))
+}
+
+/// Normalize a requested name, returning it along with the original phrasing
+/// of its last segment when normalization changed it (to keep as the title)
+pub fn normalize(name: &str, rules: &[NameRule]) -> (String, Option<String>) {
+    if rules.is_empty() {
+        return (name.to_string(), None);
+    }
+
+    let normalized = normalize_yak_name(name, rules);
+    let original_leaf = name.rsplit('/').next().unwrap_or(name).trim();
+    let leaf = normalized.rsplit('/').next().unwrap_or(&normalized);
+    let title =
+        (original_leaf != leaf && !original_leaf.is_empty()).then(|| original_leaf.to_string());
+
+    (normalized, title)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockConfig(Option<&'static str>);
+
+    impl ConfigPort for MockConfig {
+        fn get(&self, _key: &str) -> Option<String> {
+            self.0.map(|value| value.to_string())
+        }
+
+        fn get_all(&self, _key: &str) -> Vec<String> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_configured_rules() {
+        assert!(configured_rules(None).unwrap().is_empty());
+        assert!(configured_rules(Some(&MockConfig(None)))
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            configured_rules(Some(&MockConfig(Some("lowercase")))).unwrap(),
+            vec![NameRule::Lowercase]
+        );
+        assert!(configured_rules(Some(&MockConfig(Some("bogus")))).is_err());
+    }
+
+    #[test]
+    fn test_normalize_keeps_title_only_when_changed() {
+        let rules = parse_name_rules("all").unwrap();
+        assert_eq!(
+            normalize("Web/Fix Login!", &rules),
+            ("web/fix-login".to_string(), Some("Fix Login!".to_string()))
+        );
+        assert_eq!(
+            normalize("Web/login", &rules),
+            ("web/login".to_string(), None)
+        );
+        assert_eq!(normalize("Fix Login", &[]), ("Fix Login".to_string(), None));
+    }
+}
@@ -0,0 +1,119 @@
+// NoteYak use case - appends a timestamped progress note to a yak's context
+
+use super::read_tracking::ReadTracker;
+use crate::ports::{LocalStatePort, LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+use chrono::Local;
+
+pub struct NoteYak<'a> {
+    storage: &'a dyn StoragePort,
+    log: &'a dyn LogPort,
+    read_tracker: Option<ReadTracker<'a>>,
+}
+
+impl<'a> NoteYak<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        _output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+    ) -> Self {
+        Self {
+            storage,
+            log,
+            read_tracker: None,
+        }
+    }
+
+    /// Treat the written context as read, so our own notes aren't badged as updated
+    pub fn with_read_tracking(mut self, state: &'a dyn LocalStatePort) -> Self {
+        self.read_tracker = Some(ReadTracker::new(state));
+        self
+    }
+
+    /// Append `- 2026-10-17 10:32: <text>` to the context
+    /// Further lines of a multi-line note are indented under the first.
+    pub fn execute(&self, name: &str, text: &str) -> Result<()> {
+        let text = text.trim();
+        if text.is_empty() {
+            anyhow::bail!("nothing to note - give the text as arguments or on stdin");
+        }
+
+        // Resolve yak name (exact or fuzzy match)
+        let resolved_name = self.storage.find_yak(name)?;
+
+        let mut context = self.storage.read_context(&resolved_name)?;
+        if !context.is_empty() && !context.ends_with('\n') {
+            context.push('\n');
+        }
+        let timestamp = Local::now().format("%Y-%m-%d %H:%M");
+        context.push_str(&format!("- {timestamp}: {}\n", text.replace('\n', "\n  ")));
+
+        self.storage.write_context(&resolved_name, &context)?;
+        self.log.log_command(&format!("note {resolved_name}"))?;
+
+        if let Some(tracker) = &self.read_tracker {
+            tracker.mark_seen(&resolved_name, &context)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::ports::LogEntry;
+
+    struct MockOutput;
+
+    impl OutputPort for MockOutput {
+        fn success(&self, _message: &str) {}
+        fn error(&self, _message: &str) {}
+        fn info(&self, _message: &str) {}
+
+        fn warn(&self, _message: &str) {}
+    }
+
+    struct MockLog;
+
+    impl LogPort for MockLog {
+        fn log_command(&self, _command: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_note_appends_timestamped_lines() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("api").unwrap();
+        storage.write_context("api", "Plan").unwrap();
+        let use_case = NoteYak::new(&storage, &MockOutput, &MockLog);
+
+        use_case.execute("api", "tests pass").unwrap();
+        use_case.execute("api", "deployed\nto staging\n").unwrap();
+
+        let context = storage.read_context("api").unwrap();
+        let lines: Vec<&str> = context.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "Plan");
+        assert!(lines[1].starts_with("- ") && lines[1].ends_with(": tests pass"));
+        assert_eq!(lines[1].len(), "- 2026-10-17 10:32: tests pass".len());
+        assert!(lines[2].ends_with(": deployed"));
+        assert_eq!(lines[3], "  to staging");
+    }
+
+    #[test]
+    fn test_note_rejects_empty_text() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("api").unwrap();
+        let use_case = NoteYak::new(&storage, &MockOutput, &MockLog);
+
+        assert!(use_case.execute("api", "  \n").is_err());
+        assert!(storage.read_context("api").unwrap().is_empty());
+    }
+}
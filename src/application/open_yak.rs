@@ -0,0 +1,247 @@
+// OpenYak use case - opens a yak's context file in $EDITOR, or the URLs it mentions in the browser
+
+use super::editor;
+use super::read_tracking::ReadTracker;
+use crate::ports::{BrowserPort, LocalStatePort, LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+use std::path::Path;
+
+/// Field holding the note an imported yak came from (see `yx import --obsidian`)
+const SOURCE_FIELD: &str = "source";
+
+/// Characters that end a sentence or wrap a URL in prose and markdown, rather than belong to it
+const URL_TRAILERS: &[char] = &['.', ',', ';', ':', '!', '?', ')', ']', '>', '"', '\'', '`'];
+
+pub struct OpenYak<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+    browser: &'a dyn BrowserPort,
+    read_tracker: Option<ReadTracker<'a>>,
+}
+
+impl<'a> OpenYak<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+        browser: &'a dyn BrowserPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            log,
+            browser,
+            read_tracker: None,
+        }
+    }
+
+    /// Treat the edited context as read, so our own edits aren't badged as updated
+    pub fn with_read_tracking(mut self, state: &'a dyn LocalStatePort) -> Self {
+        self.read_tracker = Some(ReadTracker::new(state));
+        self
+    }
+
+    /// Open the yak's context file in $EDITOR, editing it in place, or the
+    /// note it was imported from when it has a source
+    /// With `urls`, open the links and the URLs in the context in the browser instead.
+    pub fn execute(&self, name: &str, urls: bool) -> Result<()> {
+        // Resolve yak name (exact or fuzzy match)
+        let resolved_name = self.storage.find_yak(name)?;
+
+        if urls {
+            return self.open_urls(&resolved_name);
+        }
+
+        if let Some(source) = self.storage.read_field(&resolved_name, SOURCE_FIELD)? {
+            let source = Path::new(source.trim());
+            if !source.is_file() {
+                anyhow::bail!(
+                    "the note '{resolved_name}' came from is gone: {}",
+                    source.display()
+                );
+            }
+            return editor::open_in_editor(source);
+        }
+
+        let Some(path) = self.storage.context_file(&resolved_name) else {
+            anyhow::bail!("'{resolved_name}' has no context file - use `yx context` instead");
+        };
+        let before = self
+            .storage
+            .read_context(&resolved_name)
+            .unwrap_or_default();
+        editor::open_in_editor(&path)?;
+        let context = self.storage.read_context(&resolved_name)?;
+
+        // Closing the editor without saving a change isn't an edit
+        if context != before {
+            self.log.log_command(&format!("context {resolved_name}"))?;
+        }
+        if let Some(tracker) = &self.read_tracker {
+            tracker.mark_seen(&resolved_name, &context)?;
+        }
+
+        Ok(())
+    }
+
+    fn open_urls(&self, name: &str) -> Result<()> {
+        let yak = self.storage.get_yak(name)?;
+        let context = self.storage.read_context(name).unwrap_or_default();
+
+        let mut urls = yak.links;
+        for url in find_urls(&context) {
+            if !urls.contains(&url) {
+                urls.push(url);
+            }
+        }
+        if urls.is_empty() {
+            anyhow::bail!("no URLs in '{name}'");
+        }
+
+        for url in &urls {
+            if self.browser.open(url).is_ok() {
+                self.output.success(&format!("Opened {url}"));
+            } else {
+                self.output.info(&format!("Open this URL: {url}"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The http(s) URLs in `text`, in order, without trailing punctuation
+fn find_urls(text: &str) -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    for word in text.split(|c: char| c.is_whitespace() || c == '(' || c == '<' || c == '[') {
+        let Some(start) = word.find("https://").or_else(|| word.find("http://")) else {
+            continue;
+        };
+        let url = word[start..].trim_end_matches(URL_TRAILERS);
+        if url.contains("://") && !url.ends_with("://") && !urls.iter().any(|u| u == url) {
+            urls.push(url.to_string());
+        }
+    }
+    urls
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use crate::ports::LogEntry;
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, _message: &str) {}
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockLog;
+
+    impl LogPort for MockLog {
+        fn log_command(&self, _command: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    struct MockBrowser {
+        opened: RefCell<Vec<String>>,
+    }
+
+    impl BrowserPort for MockBrowser {
+        fn open(&self, url: &str) -> Result<()> {
+            self.opened.borrow_mut().push(url.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_find_urls_strips_punctuation_and_markdown() {
+        let text = "See https://example.com/a. Also [docs](https://docs.rs/x?y=1),\n\
+                    <http://old.example.com> and https://example.com/a again.\nhttps:// alone";
+
+        assert_eq!(
+            find_urls(text),
+            vec![
+                "https://example.com/a",
+                "https://docs.rs/x?y=1",
+                "http://old.example.com",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_open_urls_opens_links_then_context_urls() {
+        let storage = InMemoryStorage::with_yaks([Yak::new("api".to_string())
+            .with_links(vec!["https://github.com/pr/1".to_string()])
+            .with_context(
+                "PR: https://github.com/pr/1\nDesign: https://docs/design.\n".to_string(),
+            )]);
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let browser = MockBrowser {
+            opened: RefCell::new(Vec::new()),
+        };
+        let use_case = OpenYak::new(&storage, &output, &MockLog, &browser);
+
+        use_case.execute("api", true).unwrap();
+
+        assert_eq!(
+            *browser.opened.borrow(),
+            vec!["https://github.com/pr/1", "https://docs/design"]
+        );
+
+        let empty = InMemoryStorage::with_yaks([
+            Yak::new("api".to_string()).with_context("no links here".to_string())
+        ]);
+        let result = OpenYak::new(&empty, &output, &MockLog, &browser).execute("api", true);
+        assert_eq!(result.unwrap_err().to_string(), "no URLs in 'api'");
+    }
+
+    #[test]
+    fn test_open_goes_to_the_source_note_of_an_imported_yak() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("fix login").unwrap();
+        storage
+            .write_field("fix login", "source", Some("/no/such/vault/Tasks.md"))
+            .unwrap();
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let browser = MockBrowser {
+            opened: RefCell::new(Vec::new()),
+        };
+
+        // The note, not the context file (which in-memory yaks don't have)
+        let error = OpenYak::new(&storage, &output, &MockLog, &browser)
+            .execute("fix login", false)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "the note 'fix login' came from is gone: /no/such/vault/Tasks.md"
+        );
+    }
+}
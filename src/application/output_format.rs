@@ -0,0 +1,175 @@
+// Output formats - the one registry of formats read commands can render, and shared escaping
+
+use anyhow::Result;
+use std::fmt;
+
+/// A way of rendering a command's results, chosen with the global `--format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Markdown,
+    Plain,
+    Json,
+    /// One JSON object per line
+    Jsonl,
+    Table,
+    Csv,
+    Dot,
+    Svg,
+    Terminal,
+    Porcelain,
+    Ics,
+}
+
+/// Every format and the names that select it; the first name is the canonical one
+const REGISTRY: [(OutputFormat, &[&str]); 11] = [
+    (OutputFormat::Markdown, &["markdown", "md"]),
+    (OutputFormat::Plain, &["plain", "raw"]),
+    (OutputFormat::Json, &["json"]),
+    (OutputFormat::Jsonl, &["jsonl", "ndjson"]),
+    (OutputFormat::Table, &["table"]),
+    (OutputFormat::Csv, &["csv"]),
+    (OutputFormat::Dot, &["dot"]),
+    (OutputFormat::Svg, &["svg"]),
+    (OutputFormat::Terminal, &["terminal"]),
+    (OutputFormat::Porcelain, &["porcelain"]),
+    (OutputFormat::Ics, &["ics", "ical"]),
+];
+
+impl OutputFormat {
+    /// Look a format up by name or alias
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let wanted = text.trim().to_lowercase();
+        REGISTRY
+            .iter()
+            .find(|(_, names)| names.contains(&wanted.as_str()))
+            .map(|(format, _)| *format)
+            .ok_or_else(|| {
+                let names: Vec<&str> = REGISTRY.iter().map(|(format, _)| format.name()).collect();
+                format!("Unknown format '{text}': use {}", names.join(", "))
+            })
+    }
+
+    pub fn name(self) -> &'static str {
+        REGISTRY
+            .iter()
+            .find(|(format, _)| *format == self)
+            .map(|(_, names)| names[0])
+            .unwrap_or("markdown")
+    }
+
+    /// Fail unless `command` can render this format, naming the ones it can
+    pub(super) fn ensure(self, command: &str, supported: &[OutputFormat]) -> Result<()> {
+        if !supported.contains(&self) {
+            let names: Vec<&str> = supported.iter().map(|format| format.name()).collect();
+            anyhow::bail!(
+                "`yx {command}` can't show {self} output; use {}",
+                names.join(", ")
+            );
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Quote a value as a JSON string
+pub(super) fn json_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+pub(super) fn json_optional(value: Option<&str>) -> String {
+    value.map_or_else(|| "null".to_string(), json_string)
+}
+
+/// A JSON object from already-rendered values, one field per line, closing at
+/// `indent` levels of two spaces
+pub(super) fn json_object(fields: &[(&str, String)], indent: usize) -> String {
+    let lines: Vec<String> = fields
+        .iter()
+        .map(|(key, value)| format!("{}: {value}", json_string(key)))
+        .collect();
+    json_block(('{', '}'), &lines, indent)
+}
+
+/// A JSON object from already-rendered values, all on one line
+pub(super) fn json_line(fields: &[(&str, String)]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(key, value)| format!("{}: {value}", json_string(key)))
+        .collect();
+    format!("{{{}}}", fields.join(", "))
+}
+
+/// A JSON array from already-rendered items, one per line, closing at `indent`
+pub(super) fn json_array(items: &[String], indent: usize) -> String {
+    json_block(('[', ']'), items, indent)
+}
+
+fn json_block((open, close): (char, char), lines: &[String], indent: usize) -> String {
+    if lines.is_empty() {
+        return format!("{open}{close}");
+    }
+    let inner = "  ".repeat(indent + 1);
+    let lines: Vec<String> = lines.iter().map(|line| format!("{inner}{line}")).collect();
+    format!(
+        "{open}\n{}\n{}{close}",
+        lines.join(",\n"),
+        "  ".repeat(indent)
+    )
+}
+
+/// Quote a CSV field when it contains separators, quotes or newlines (RFC 4180)
+pub(super) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_names_and_aliases() {
+        assert_eq!(OutputFormat::parse("md"), Ok(OutputFormat::Markdown));
+        assert_eq!(OutputFormat::parse("RAW"), Ok(OutputFormat::Plain));
+        assert_eq!(OutputFormat::parse("json"), Ok(OutputFormat::Json));
+        assert_eq!(OutputFormat::Plain.to_string(), "plain");
+        assert!(OutputFormat::parse("yaml").unwrap_err().ends_with(
+            "use markdown, plain, json, jsonl, table, csv, dot, svg, terminal, porcelain, ics"
+        ));
+    }
+
+    #[test]
+    fn test_ensure_names_the_supported_formats() {
+        let supported = [OutputFormat::Markdown, OutputFormat::Json];
+        assert!(OutputFormat::Json.ensure("tree", &supported).is_ok());
+        assert_eq!(
+            OutputFormat::Table
+                .ensure("tree", &supported)
+                .unwrap_err()
+                .to_string(),
+            "`yx tree` can't show table output; use markdown, json"
+        );
+    }
+}
@@ -0,0 +1,286 @@
+// PlanWeek use case - walk through open yaks, picking this week's and setting due dates
+
+use crate::domain::{parse_due_date, Yak};
+use crate::ports::{LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+use chrono::{Datelike, Local, NaiveDate};
+
+/// Field holding the ISO week a yak is planned for, e.g. `2026-W42`
+const WEEK_FIELD: &str = "week";
+
+pub struct PlanWeek<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+    today: NaiveDate,
+}
+
+impl<'a> PlanWeek<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            log,
+            today: Local::now().date_naive(),
+        }
+    }
+
+    /// Ask about each open yak not yet planned for this week: plan it, give it
+    /// a due date, skip it, or stop
+    pub fn execute(&self) -> Result<()> {
+        let week = iso_week(self.today);
+        let mut yaks = Vec::new();
+        for yak in self.storage.list_yaks()? {
+            if !yak.done && self.planned_week(&yak.name)?.as_deref() != Some(week.as_str()) {
+                yaks.push(yak);
+            }
+        }
+        if yaks.is_empty() {
+            self.output
+                .note(&format!("Every open yak is already planned for {week}"));
+            return Ok(());
+        }
+
+        let mut planned = 0;
+        let mut scheduled = 0;
+        'yaks: for (i, yak) in yaks.iter().enumerate() {
+            let question = format!(
+                "[{}/{}] {} - this [w]eek, a due date (YYYY-MM-DD), [s]kip or [q]uit?",
+                i + 1,
+                yaks.len(),
+                describe(yak)
+            );
+            loop {
+                let Some(answer) = self.output.ask(&question) else {
+                    if i == 0 {
+                        anyhow::bail!("yx plan asks about each yak, so it needs a terminal");
+                    }
+                    break 'yaks;
+                };
+                match answer.trim() {
+                    "w" | "week" => {
+                        self.storage.write_field(
+                            &yak.name,
+                            WEEK_FIELD,
+                            Some(&format!("{week}\n")),
+                        )?;
+                        self.log.log_command(&format!("plan {} {week}", yak.name))?;
+                        planned += 1;
+                    }
+                    "" | "s" | "skip" => {}
+                    "q" | "quit" => break 'yaks,
+                    other => match parse_due_date(other) {
+                        Ok(due) => {
+                            self.storage.write_field(
+                                &yak.name,
+                                "due",
+                                Some(&format!("{due}\n")),
+                            )?;
+                            self.log.log_command(&format!("due {} {due}", yak.name))?;
+                            scheduled += 1;
+                        }
+                        Err(_) => {
+                            self.output
+                                .error("Answer w, a date like 2026-10-24, s or q");
+                            continue;
+                        }
+                    },
+                }
+                break;
+            }
+        }
+
+        self.output.success(&format!(
+            "Planned {planned} yak(s) for {week} and set {scheduled} due date(s)"
+        ));
+        Ok(())
+    }
+
+    /// List the yaks planned for this week
+    pub fn show(&self) -> Result<()> {
+        let week = iso_week(self.today);
+        let mut shown = 0;
+        for yak in self.storage.list_yaks()? {
+            if self.planned_week(&yak.name)?.as_deref() == Some(week.as_str()) {
+                let checkbox = if yak.done { "[x]" } else { "[ ]" };
+                self.output
+                    .info(&format!("- {checkbox} {}", describe(&yak)));
+                shown += 1;
+            }
+        }
+        if shown == 0 {
+            self.output
+                .note(&format!("Nothing planned for {week} - run `yx plan`"));
+        }
+        Ok(())
+    }
+
+    fn planned_week(&self, name: &str) -> Result<Option<String>> {
+        Ok(self
+            .storage
+            .read_field(name, WEEK_FIELD)?
+            .map(|text| text.trim().to_string()))
+    }
+}
+
+/// `2026-W42`, the ISO week containing `day`
+fn iso_week(day: NaiveDate) -> String {
+    let week = day.iso_week();
+    format!("{}-W{:02}", week.year(), week.week())
+}
+
+/// The yak's title (or name) with its priority and due date, if set
+pub(super) fn describe(yak: &Yak) -> String {
+    let mut details = Vec::new();
+    if let Some(priority) = yak.priority {
+        details.push(priority.to_string());
+    }
+    if let Some(due) = yak.due {
+        details.push(format!("due {due}"));
+    }
+    let label = yak.title.as_deref().unwrap_or(&yak.name);
+    if details.is_empty() {
+        label.to_string()
+    } else {
+        format!("{label} ({})", details.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Priority;
+    use crate::ports::LogEntry;
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+
+    /// Answers questions from a script, then stops like a closed terminal
+    struct MockOutput {
+        answers: RefCell<VecDeque<&'static str>>,
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl MockOutput {
+        fn new(answers: &[&'static str]) -> Self {
+            Self {
+                answers: RefCell::new(answers.iter().copied().collect()),
+                messages: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn ask(&self, prompt: &str) -> Option<String> {
+            self.messages.borrow_mut().push(prompt.to_string());
+            self.answers
+                .borrow_mut()
+                .pop_front()
+                .map(|answer| answer.to_string())
+        }
+    }
+
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    fn storage() -> InMemoryStorage {
+        let mut urgent = Yak::new("api".to_string());
+        urgent.priority = Some(Priority::HIGHEST);
+        InMemoryStorage::with_yaks([
+            urgent,
+            Yak::new("old".to_string()).mark_done(),
+            Yak::new("docs".to_string()),
+            Yak::new("ci".to_string()),
+            Yak::new("release".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_plan_tags_the_week_and_sets_due_dates() {
+        let storage = storage();
+        let output = MockOutput::new(&["w", "next week", "2026-10-24", "", "q"]);
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let mut use_case = PlanWeek::new(&storage, &output, &log);
+        use_case.today = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+
+        use_case.execute().unwrap();
+
+        assert_eq!(
+            storage.read_field("api", "week").unwrap(),
+            Some("2026-W42\n".to_string())
+        );
+        assert_eq!(
+            storage.read_field("ci", "due").unwrap(),
+            Some("2026-10-24\n".to_string())
+        );
+        assert_eq!(
+            *log.commands.borrow(),
+            vec!["plan api 2026-W42", "due ci 2026-10-24"]
+        );
+        assert_eq!(
+            *output.messages.borrow(),
+            vec![
+                "[1/4] api (P0) - this [w]eek, a due date (YYYY-MM-DD), [s]kip or [q]uit?",
+                "[2/4] ci - this [w]eek, a due date (YYYY-MM-DD), [s]kip or [q]uit?",
+                "ERROR: Answer w, a date like 2026-10-24, s or q",
+                "[2/4] ci - this [w]eek, a due date (YYYY-MM-DD), [s]kip or [q]uit?",
+                "[3/4] docs - this [w]eek, a due date (YYYY-MM-DD), [s]kip or [q]uit?",
+                "[4/4] release - this [w]eek, a due date (YYYY-MM-DD), [s]kip or [q]uit?",
+                "Planned 1 yak(s) for 2026-W42 and set 1 due date(s)",
+            ]
+        );
+
+        let output = MockOutput::new(&[]);
+        use_case.output = &output;
+        use_case.show().unwrap();
+        assert_eq!(*output.messages.borrow(), vec!["- [ ] api (P0)"]);
+    }
+
+    #[test]
+    fn test_plan_needs_someone_to_answer() {
+        let storage = storage();
+        let output = MockOutput::new(&[]);
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+
+        assert!(PlanWeek::new(&storage, &output, &log).execute().is_err());
+        assert!(log.commands.borrow().is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+// PopStash use case - restores the newest snapshot taken before a risky operation
+
+use crate::ports::{LogPort, OutputPort, StashPort};
+use anyhow::Result;
+
+pub struct PopStash<'a> {
+    stash: &'a dyn StashPort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+}
+
+impl<'a> PopStash<'a> {
+    pub fn new(stash: &'a dyn StashPort, output: &'a dyn OutputPort, log: &'a dyn LogPort) -> Self {
+        Self { stash, output, log }
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        let Some(entry) = self.stash.pop()? else {
+            anyhow::bail!("no stashed yaks to restore");
+        };
+
+        self.log.log_command("stash pop")?;
+
+        let noun = if entry.yaks.len() == 1 { "yak" } else { "yaks" };
+        self.output.success(&format!(
+            "Restored {} {noun} from before {}",
+            entry.yaks.len(),
+            entry.reason
+        ));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::{LogEntry, StashEntry};
+    use chrono::Utc;
+    use std::cell::RefCell;
+
+    struct MockStash {
+        entries: RefCell<Vec<StashEntry>>,
+    }
+
+    impl StashPort for MockStash {
+        fn push(&self, _reason: &str, _names: &[String]) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn list(&self) -> Result<Vec<StashEntry>> {
+            unimplemented!()
+        }
+
+        fn pop(&self) -> Result<Option<StashEntry>> {
+            let mut entries = self.entries.borrow_mut();
+            if entries.is_empty() {
+                Ok(None)
+            } else {
+                Ok(Some(entries.remove(0)))
+            }
+        }
+
+        fn discard(&self) -> Result<()> {
+            unimplemented!()
+        }
+    }
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl MockOutput {
+        fn new() -> Self {
+            Self {
+                messages: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn last_message(&self) -> Option<String> {
+            self.messages.borrow().last().cloned()
+        }
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_pop_restores_and_logs() {
+        let stash = MockStash {
+            entries: RefCell::new(vec![StashEntry {
+                reason: "import --obsidian vault".to_string(),
+                created: Utc::now(),
+                yaks: vec!["a".to_string(), "b".to_string()],
+            }]),
+        };
+        let output = MockOutput::new();
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = PopStash::new(&stash, &output, &log);
+
+        use_case.execute().unwrap();
+
+        assert_eq!(*log.commands.borrow(), vec!["stash pop"]);
+        assert_eq!(
+            output.last_message(),
+            Some("Restored 2 yaks from before import --obsidian vault".to_string())
+        );
+    }
+
+    #[test]
+    fn test_pop_fails_when_empty() {
+        let stash = MockStash {
+            entries: RefCell::new(Vec::new()),
+        };
+        let output = MockOutput::new();
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = PopStash::new(&stash, &output, &log);
+
+        assert!(use_case.execute().is_err());
+        assert!(log.commands.borrow().is_empty());
+    }
+}
@@ -0,0 +1,176 @@
+// Porcelain formatter - the stable, documented line formats for scripts (like `git status --porcelain`)
+//
+// These lines are a promise: fields are only ever added at the end, never
+// reordered or renamed. Human-facing output can change freely; this can't.
+
+use super::show_status::StateCounts;
+use crate::domain::Yak;
+use crate::ports::{IncomingChange, SyncStatus};
+use chrono::{DateTime, SecondsFormat, Utc};
+
+/// Placeholder for a field that has no value
+const NONE: &str = "-";
+
+/// Quote a yak name C-style when it holds a tab, line break or other control
+/// character; names can't contain `"`, so a leading quote always means quoted
+pub(super) fn name(name: &str) -> String {
+    if !name.chars().any(char::is_control) {
+        return name.to_string();
+    }
+    let mut quoted = String::from("\"");
+    for c in name.chars() {
+        match c {
+            '\t' => quoted.push_str("\\t"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            c if c.is_control() => quoted.push_str(&format!("\\x{:02x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// `<state> TAB <priority> TAB <due> TAB <name>` for one listed yak
+/// State is todo, doing, blocked or done; priority P0-P3 and due YYYY-MM-DD,
+/// or `-` when unset. The full name comes last.
+pub(super) fn yak_line(full_name: &str, yak: Option<&Yak>) -> String {
+    let yak = yak.cloned().unwrap_or_default();
+    let priority = yak.priority.map(|p| p.to_string());
+    let due = yak.due.map(|due| due.format("%Y-%m-%d").to_string());
+    format!(
+        "{}\t{}\t{}\t{}",
+        yak.current_state(),
+        priority.as_deref().unwrap_or(NONE),
+        due.as_deref().unwrap_or(NONE),
+        name(full_name)
+    )
+}
+
+/// `key TAB value` lines, always the same keys in the same order:
+/// open, done, local (clean/dirty), ahead, behind, synced, focus, then the
+/// todo, doing and blocked counts, with `-` for values that aren't known (no
+/// remote, never synced, no focus)
+pub(super) fn status_lines(
+    counts: StateCounts,
+    status: &SyncStatus,
+    synced: Option<DateTime<Utc>>,
+    focus: Option<&str>,
+) -> Vec<String> {
+    let (ahead, behind) = match status.remote {
+        Some((ahead, behind)) => (ahead.to_string(), behind.to_string()),
+        None => (NONE.to_string(), NONE.to_string()),
+    };
+    let local = if status.has_local_changes {
+        "dirty"
+    } else {
+        "clean"
+    };
+    let synced = synced.map(|time| time.to_rfc3339_opts(SecondsFormat::Secs, true));
+    vec![
+        format!("open\t{}", counts.open()),
+        format!("done\t{}", counts.done),
+        format!("local\t{local}"),
+        format!("ahead\t{ahead}"),
+        format!("behind\t{behind}"),
+        format!("synced\t{}", synced.as_deref().unwrap_or(NONE)),
+        format!("focus\t{}", focus.map(name).as_deref().unwrap_or(NONE)),
+        format!("todo\t{}", counts.todo),
+        format!("doing\t{}", counts.doing),
+        format!("blocked\t{}", counts.blocked),
+    ]
+}
+
+/// `<code> TAB <name>` for one yak a sync changed: A added, C completed,
+/// R reopened, M updated, D removed
+pub(super) fn sync_line(yak_name: &str, change: IncomingChange) -> String {
+    let code = match change {
+        IncomingChange::Added => 'A',
+        IncomingChange::Completed => 'C',
+        IncomingChange::Reopened => 'R',
+        IncomingChange::Updated => 'M',
+        IncomingChange::Removed => 'D',
+    };
+    format!("{code}\t{}", name(yak_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Priority, YakState};
+    use chrono::{NaiveDate, TimeZone};
+
+    #[test]
+    fn test_yak_and_sync_lines_are_pinned() {
+        let mut yak = Yak::new("app/api".to_string()).with_state(YakState::Blocked);
+        yak.priority = Some(Priority::parse("P1").unwrap());
+        yak.due = NaiveDate::from_ymd_opt(2026, 10, 20);
+
+        assert_eq!(
+            yak_line("app/api", Some(&yak)),
+            "blocked\tP1\t2026-10-20\tapp/api"
+        );
+        assert_eq!(yak_line("app", None), "todo\t-\t-\tapp");
+        assert_eq!(
+            yak_line("fix it", Some(&Yak::new("fix it".to_string()).mark_done())),
+            "done\t-\t-\tfix it"
+        );
+        assert_eq!(name("odd\tname\n"), "\"odd\\tname\\n\"");
+        assert_eq!(sync_line("docs", IncomingChange::Completed), "C\tdocs");
+        assert_eq!(sync_line("old", IncomingChange::Removed), "D\told");
+    }
+
+    #[test]
+    fn test_status_lines_are_pinned() {
+        let synced = Utc.with_ymd_and_hms(2026, 10, 17, 8, 2, 11).unwrap();
+        let status = SyncStatus {
+            has_local_changes: true,
+            remote: Some((2, 0)),
+        };
+        assert_eq!(
+            status_lines(
+                StateCounts {
+                    todo: 1,
+                    doing: 1,
+                    blocked: 1,
+                    done: 1,
+                },
+                &status,
+                Some(synced),
+                Some("app/api")
+            ),
+            vec![
+                "open\t3",
+                "done\t1",
+                "local\tdirty",
+                "ahead\t2",
+                "behind\t0",
+                "synced\t2026-10-17T08:02:11Z",
+                "focus\tapp/api",
+                "todo\t1",
+                "doing\t1",
+                "blocked\t1",
+            ]
+        );
+
+        let status = SyncStatus {
+            has_local_changes: false,
+            remote: None,
+        };
+        assert_eq!(
+            status_lines(StateCounts::default(), &status, None, None),
+            vec![
+                "open\t0",
+                "done\t0",
+                "local\tclean",
+                "ahead\t-",
+                "behind\t-",
+                "synced\t-",
+                "focus\t-",
+                "todo\t0",
+                "doing\t0",
+                "blocked\t0",
+            ]
+        );
+    }
+}
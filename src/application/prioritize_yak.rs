@@ -0,0 +1,119 @@
+// PrioritizeYak use case - records how urgent a yak is
+
+use crate::domain::Priority;
+use crate::ports::{LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+
+pub struct PrioritizeYak<'a> {
+    storage: &'a dyn StoragePort,
+    log: &'a dyn LogPort,
+}
+
+impl<'a> PrioritizeYak<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        _output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+    ) -> Self {
+        Self { storage, log }
+    }
+
+    /// Set the priority (P0 to P3), or clear it when `priority` is None
+    pub fn execute(&self, name: &str, priority: Option<&str>) -> Result<()> {
+        let priority = priority
+            .map(Priority::parse)
+            .transpose()
+            .map_err(|e| anyhow::anyhow!(e))?;
+
+        // Resolve yak name (exact or fuzzy match)
+        let resolved_name = self.storage.find_yak(name)?;
+
+        match priority {
+            Some(priority) => {
+                self.storage.write_field(
+                    &resolved_name,
+                    "priority",
+                    Some(&format!("{priority}\n")),
+                )?;
+                self.log
+                    .log_command(&format!("priority {resolved_name} {priority}"))?;
+            }
+            None => {
+                self.storage.write_field(&resolved_name, "priority", None)?;
+                self.log
+                    .log_command(&format!("priority --clear {resolved_name}"))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::ports::LogEntry;
+    use std::cell::RefCell;
+
+    struct MockOutput;
+
+    impl OutputPort for MockOutput {
+        fn success(&self, _message: &str) {}
+        fn error(&self, _message: &str) {}
+        fn info(&self, _message: &str) {}
+
+        fn warn(&self, _message: &str) {}
+    }
+
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_prioritize_yak_sets_and_clears_priority() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("docs").unwrap();
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = PrioritizeYak::new(&storage, &MockOutput, &log);
+
+        use_case.execute("docs", Some("p1")).unwrap();
+        assert_eq!(
+            storage.read_field("docs", "priority").unwrap(),
+            Some("P1\n".to_string())
+        );
+
+        use_case.execute("docs", None).unwrap();
+        assert_eq!(storage.read_field("docs", "priority").unwrap(), None);
+        assert_eq!(
+            *log.commands.borrow(),
+            vec!["priority docs P1", "priority --clear docs"]
+        );
+    }
+
+    #[test]
+    fn test_prioritize_yak_rejects_invalid_priority() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("docs").unwrap();
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = PrioritizeYak::new(&storage, &MockOutput, &log);
+
+        assert!(use_case.execute("docs", Some("urgent")).is_err());
+        assert!(log.commands.borrow().is_empty());
+    }
+}
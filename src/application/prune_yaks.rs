@@ -1,20 +1,43 @@
 // PruneYaks use case - removes all done yaks
 
-use crate::ports::{LogPort, OutputPort, StoragePort};
+use crate::ports::{LogPort, OutputPort, PrunedPort, PrunedYak, StoragePort};
 use anyhow::Result;
+use chrono::Utc;
 
 pub struct PruneYaks<'a> {
     storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
     log: &'a dyn LogPort,
+    record: Option<&'a dyn PrunedPort>,
+    force: bool,
 }
 
 impl<'a> PruneYaks<'a> {
     pub fn new(
         storage: &'a dyn StoragePort,
-        _output: &'a dyn OutputPort,
+        output: &'a dyn OutputPort,
         log: &'a dyn LogPort,
     ) -> Self {
-        Self { storage, log }
+        Self {
+            storage,
+            output,
+            log,
+            record: None,
+            force: false,
+        }
+    }
+
+    /// Keep each pruned yak's name, completion time and context in `record`,
+    /// for `yx stats` and `yx report`
+    pub fn with_record(mut self, record: &'a dyn PrunedPort) -> Self {
+        self.record = Some(record);
+        self
+    }
+
+    /// Prune without asking for confirmation when `force` is set
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
     }
 
     pub fn execute(&self) -> Result<()> {
@@ -29,9 +52,37 @@ impl<'a> PruneYaks<'a> {
             return Ok(());
         }
 
-        // Delete each done yak and log as "rm" individually (matches bash behavior)
+        let question = format!("Remove {} done yak(s)?", done_yaks.len());
+        if !self.force && self.output.confirm(&question) == Some(false) {
+            self.output.note("Nothing removed");
+            return Ok(());
+        }
+
+        // Changed in a transaction, so the yaks are only locked once confirmed
+        self.storage.transaction(&mut || {
+            // Recorded before anything is removed, so a failure loses nothing
+            if let Some(record) = self.record {
+                let pruned = Utc::now();
+                let yaks: Vec<PrunedYak> = done_yaks
+                    .iter()
+                    .map(|yak| PrunedYak {
+                        name: yak.name.clone(),
+                        title: yak.title.clone(),
+                        completed: yak.done_at,
+                        pruned,
+                        context: self.storage.read_context(&yak.name).unwrap_or_default(),
+                    })
+                    .collect();
+                record.record_pruned(&yaks)?;
+            }
+            for yak in &done_yaks {
+                self.storage.delete_yak(&yak.name)?;
+            }
+            Ok(())
+        })?;
+
+        // Log each removal as "rm" individually (matches bash behavior)
         for yak in done_yaks {
-            self.storage.delete_yak(&yak.name)?;
             let yak_name = &yak.name;
             self.log.log_command(&format!("rm {yak_name}"))?;
         }
@@ -43,89 +94,43 @@ impl<'a> PruneYaks<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::adapters::storage::InMemoryStorage;
     use crate::domain::Yak;
+    use crate::ports::LogEntry;
     use std::cell::RefCell;
 
-    struct MockStorage {
-        yaks: RefCell<Vec<Yak>>,
+    fn add_yak(storage: &InMemoryStorage, name: &str, done: bool) {
+        storage.add_yak(Yak {
+            name: name.to_string(),
+            done,
+            context: Some(format!("notes on {name}")),
+            ..Default::default()
+        });
     }
 
-    impl MockStorage {
-        fn new() -> Self {
-            Self {
-                yaks: RefCell::new(Vec::new()),
-            }
-        }
-
-        fn add_yak(&self, name: &str, done: bool) {
-            self.yaks.borrow_mut().push(Yak {
-                name: name.to_string(),
-                done,
-                context: None,
-            });
-        }
-
-        fn count_yaks(&self) -> usize {
-            self.yaks.borrow().len()
-        }
-
-        fn count_done_yaks(&self) -> usize {
-            self.yaks.borrow().iter().filter(|y| y.done).count()
-        }
+    fn count_yaks(storage: &InMemoryStorage) -> usize {
+        storage.list_yaks().unwrap().len()
     }
 
-    impl StoragePort for MockStorage {
-        fn create_yak(&self, _name: &str) -> Result<()> {
-            unimplemented!()
-        }
-
-        fn get_yak(&self, _name: &str) -> Result<Yak> {
-            unimplemented!()
-        }
-
-        fn list_yaks(&self) -> Result<Vec<Yak>> {
-            Ok(self.yaks.borrow().clone())
-        }
-
-        fn mark_done(&self, _name: &str, _done: bool) -> Result<()> {
-            unimplemented!()
-        }
-
-        fn delete_yak(&self, name: &str) -> Result<()> {
-            let mut yaks = self.yaks.borrow_mut();
-            if let Some(pos) = yaks.iter().position(|y| y.name == name) {
-                yaks.remove(pos);
-                Ok(())
-            } else {
-                anyhow::bail!("yak '{}' not found", name)
-            }
-        }
-
-        fn rename_yak(&self, _from: &str, _to: &str) -> Result<()> {
-            unimplemented!()
-        }
-
-        fn read_context(&self, _name: &str) -> Result<String> {
-            unimplemented!()
-        }
-
-        fn write_context(&self, _name: &str, _text: &str) -> Result<()> {
-            unimplemented!()
-        }
-
-        fn find_yak(&self, _name: &str) -> Result<String> {
-            unimplemented!()
-        }
+    fn count_done_yaks(storage: &InMemoryStorage) -> usize {
+        storage
+            .list_yaks()
+            .unwrap()
+            .iter()
+            .filter(|y| y.done)
+            .count()
     }
 
     struct MockOutput {
         messages: RefCell<Vec<String>>,
+        answer: Option<bool>,
     }
 
     impl MockOutput {
         fn new() -> Self {
             Self {
                 messages: RefCell::new(Vec::new()),
+                answer: None,
             }
         }
 
@@ -148,6 +153,14 @@ mod tests {
         fn info(&self, message: &str) {
             self.messages.borrow_mut().push(message.to_string());
         }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn confirm(&self, _question: &str) -> Option<bool> {
+            self.answer
+        }
     }
 
     struct MockLog;
@@ -156,27 +169,31 @@ mod tests {
         fn log_command(&self, _command: &str) -> Result<()> {
             Ok(())
         }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
     }
 
     #[test]
     fn test_prune_removes_all_done_yaks() {
-        let storage = MockStorage::new();
-        storage.add_yak("done1", true);
-        storage.add_yak("done2", true);
-        storage.add_yak("active", false);
+        let storage = InMemoryStorage::new();
+        add_yak(&storage, "done1", true);
+        add_yak(&storage, "done2", true);
+        add_yak(&storage, "active", false);
         let output = MockOutput::new();
         let use_case = PruneYaks::new(&storage, &output, &MockLog);
 
         use_case.execute().unwrap();
 
-        assert_eq!(storage.count_yaks(), 1);
-        assert_eq!(storage.count_done_yaks(), 0);
+        assert_eq!(count_yaks(&storage), 1);
+        assert_eq!(count_done_yaks(&storage), 0);
     }
 
     #[test]
     fn test_prune_is_silent_when_removing_one_yak() {
-        let storage = MockStorage::new();
-        storage.add_yak("done1", true);
+        let storage = InMemoryStorage::new();
+        add_yak(&storage, "done1", true);
         let output = MockOutput::new();
         let use_case = PruneYaks::new(&storage, &output, &MockLog);
 
@@ -184,15 +201,15 @@ mod tests {
 
         // Prune should be silent (matches bash behavior)
         assert_eq!(output.last_message(), None);
-        assert_eq!(storage.count_yaks(), 0);
+        assert_eq!(count_yaks(&storage), 0);
     }
 
     #[test]
     fn test_prune_is_silent_when_removing_multiple_yaks() {
-        let storage = MockStorage::new();
-        storage.add_yak("done1", true);
-        storage.add_yak("done2", true);
-        storage.add_yak("done3", true);
+        let storage = InMemoryStorage::new();
+        add_yak(&storage, "done1", true);
+        add_yak(&storage, "done2", true);
+        add_yak(&storage, "done3", true);
         let output = MockOutput::new();
         let use_case = PruneYaks::new(&storage, &output, &MockLog);
 
@@ -200,34 +217,92 @@ mod tests {
 
         // Prune should be silent (matches bash behavior)
         assert_eq!(output.last_message(), None);
-        assert_eq!(storage.count_yaks(), 0);
+        assert_eq!(count_yaks(&storage), 0);
     }
 
     #[test]
     fn test_prune_handles_no_done_yaks() {
-        let storage = MockStorage::new();
-        storage.add_yak("active1", false);
-        storage.add_yak("active2", false);
+        let storage = InMemoryStorage::new();
+        add_yak(&storage, "active1", false);
+        add_yak(&storage, "active2", false);
         let output = MockOutput::new();
         let use_case = PruneYaks::new(&storage, &output, &MockLog);
 
         use_case.execute().unwrap();
 
-        assert_eq!(storage.count_yaks(), 2);
+        assert_eq!(count_yaks(&storage), 2);
         // No message expected when no done yaks (matches bash behavior)
         assert_eq!(output.last_message(), None);
     }
 
     #[test]
     fn test_prune_handles_empty_list() {
-        let storage = MockStorage::new();
+        let storage = InMemoryStorage::new();
         let output = MockOutput::new();
         let use_case = PruneYaks::new(&storage, &output, &MockLog);
 
         use_case.execute().unwrap();
 
-        assert_eq!(storage.count_yaks(), 0);
+        assert_eq!(count_yaks(&storage), 0);
         // No message expected when no yaks at all (matches bash behavior)
         assert_eq!(output.last_message(), None);
     }
+
+    #[test]
+    fn test_prune_keeps_yaks_when_declined() {
+        let storage = InMemoryStorage::new();
+        add_yak(&storage, "done1", true);
+        let output = MockOutput {
+            answer: Some(false),
+            ..MockOutput::new()
+        };
+        let use_case = PruneYaks::new(&storage, &output, &MockLog);
+
+        use_case.execute().unwrap();
+
+        assert_eq!(output.last_message(), Some("Nothing removed".to_string()));
+        assert_eq!(count_yaks(&storage), 1);
+
+        let use_case = PruneYaks::new(&storage, &output, &MockLog).with_force(true);
+        use_case.execute().unwrap();
+        assert_eq!(count_yaks(&storage), 0);
+    }
+
+    #[derive(Default)]
+    struct MockRecord {
+        yaks: RefCell<Vec<PrunedYak>>,
+    }
+
+    impl PrunedPort for MockRecord {
+        fn record_pruned(&self, yaks: &[PrunedYak]) -> Result<()> {
+            self.yaks.borrow_mut().extend_from_slice(yaks);
+            Ok(())
+        }
+
+        fn pruned(&self) -> Result<Vec<PrunedYak>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_prune_records_removed_yaks() {
+        let storage = InMemoryStorage::new();
+        add_yak(&storage, "done1", true);
+        add_yak(&storage, "active", false);
+        let output = MockOutput::new();
+        let record = MockRecord::default();
+
+        PruneYaks::new(&storage, &output, &MockLog)
+            .with_record(&record)
+            .execute()
+            .unwrap();
+
+        let yaks = record.yaks.borrow();
+        assert_eq!(yaks.len(), 1);
+        assert_eq!(
+            (yaks[0].name.as_str(), yaks[0].context.as_str()),
+            ("done1", "notes on done1")
+        );
+        assert_eq!(count_yaks(&storage), 1);
+    }
 }
@@ -0,0 +1,153 @@
+// Read tracking - remembers which version of each context was last viewed locally
+
+use crate::domain::graph;
+use crate::ports::LocalStatePort;
+use anyhow::Result;
+use std::collections::HashMap;
+
+/// Local state key holding "<fingerprint> <yak name>" lines
+const SEEN_KEY: &str = "seen";
+
+pub struct ReadTracker<'a> {
+    state: &'a dyn LocalStatePort,
+}
+
+impl<'a> ReadTracker<'a> {
+    pub fn new(state: &'a dyn LocalStatePort) -> Self {
+        Self { state }
+    }
+
+    /// Whether a yak's context changed since it was last viewed
+    /// Yaks with empty context are never considered updated
+    pub fn is_updated(&self, name: &str, context: &str, seen: &HashMap<String, String>) -> bool {
+        if context.is_empty() {
+            return false;
+        }
+        seen.get(name) != Some(&fingerprint(context))
+    }
+
+    /// Load the fingerprints of all viewed contexts, keyed by yak name
+    pub fn load(&self) -> Result<HashMap<String, String>> {
+        let text = self.state.read(SEEN_KEY)?.unwrap_or_default();
+        Ok(text
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .map(|(hash, name)| (name.to_string(), hash.to_string()))
+            .collect())
+    }
+
+    /// Record that the given context of a yak has been viewed
+    pub fn mark_seen(&self, name: &str, context: &str) -> Result<()> {
+        let mut seen = self.load()?;
+        seen.insert(name.to_string(), fingerprint(context));
+        self.save(&seen)
+    }
+
+    /// Carry the viewed state of `from` and the yaks under it over to `to`
+    pub fn rename(&self, from: &str, to: &str) -> Result<()> {
+        let seen = self.load()?;
+        let renamed = seen
+            .into_iter()
+            .map(|(name, hash)| {
+                if name == from || graph::is_descendant_of(&name, from) {
+                    (format!("{to}{}", &name[from.len()..]), hash)
+                } else {
+                    (name, hash)
+                }
+            })
+            .collect();
+        self.save(&renamed)
+    }
+
+    fn save(&self, seen: &HashMap<String, String>) -> Result<()> {
+        let mut lines: Vec<String> = seen
+            .iter()
+            .map(|(name, hash)| format!("{hash} {name}"))
+            .collect();
+        lines.sort();
+
+        self.state
+            .write(SEEN_KEY, Some(&format!("{}\n", lines.join("\n"))))
+    }
+}
+
+/// Stable fingerprint of context contents (64-bit FNV-1a, hex encoded)
+fn fingerprint(context: &str) -> String {
+    let hash = context.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    });
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    struct MockState {
+        values: RefCell<HashMap<String, String>>,
+    }
+
+    impl LocalStatePort for MockState {
+        fn read(&self, key: &str) -> Result<Option<String>> {
+            Ok(self.values.borrow().get(key).cloned())
+        }
+
+        fn write(&self, key: &str, value: Option<&str>) -> Result<()> {
+            match value {
+                Some(text) => self
+                    .values
+                    .borrow_mut()
+                    .insert(key.to_string(), text.to_string()),
+                None => self.values.borrow_mut().remove(key),
+            };
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_context_is_updated_until_seen() {
+        let state = MockState {
+            values: RefCell::new(HashMap::new()),
+        };
+        let tracker = ReadTracker::new(&state);
+        assert!(tracker.is_updated("a yak", "notes", &tracker.load().unwrap()));
+
+        tracker.mark_seen("a yak", "notes").unwrap();
+        assert!(!tracker.is_updated("a yak", "notes", &tracker.load().unwrap()));
+        assert!(tracker.is_updated("a yak", "new notes", &tracker.load().unwrap()));
+    }
+
+    #[test]
+    fn test_empty_context_is_never_updated() {
+        let state = MockState {
+            values: RefCell::new(HashMap::new()),
+        };
+        let tracker = ReadTracker::new(&state);
+        assert!(!tracker.is_updated("a yak", "", &HashMap::new()));
+    }
+
+    #[test]
+    fn test_rename_keeps_subtree_seen() {
+        let state = MockState {
+            values: RefCell::new(HashMap::new()),
+        };
+        let tracker = ReadTracker::new(&state);
+        tracker.mark_seen("app", "notes").unwrap();
+        tracker.mark_seen("app/api", "api notes").unwrap();
+        tracker.mark_seen("apple", "fruit").unwrap();
+
+        tracker.rename("app", "web").unwrap();
+
+        let seen = tracker.load().unwrap();
+        let mut names: Vec<&String> = seen.keys().collect();
+        names.sort();
+        assert_eq!(names, vec!["apple", "web", "web/api"]);
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        assert_eq!(fingerprint(""), "cbf29ce484222325");
+        assert_ne!(fingerprint("a"), fingerprint("b"));
+    }
+}
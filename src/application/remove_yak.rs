@@ -1,127 +1,157 @@
-// RemoveYak use case - deletes a yak
+// RemoveYak use case - deletes a yak, or moves it to the trash
 
-use crate::ports::{LogPort, OutputPort, StoragePort};
+use super::transaction;
+use crate::domain::graph;
+use crate::ports::{ConfigPort, LogPort, OutputPort, StoragePort, TrashPort};
 use anyhow::Result;
 
+/// Config key turning the trash off ("false"), so `yx rm` deletes outright
+pub const TRASH_KEY: &str = "yaks.trash";
+
 pub struct RemoveYak<'a> {
     storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
     log: &'a dyn LogPort,
+    trash: Option<&'a dyn TrashPort>,
+    config: Option<&'a dyn ConfigPort>,
+    force: bool,
 }
 
 impl<'a> RemoveYak<'a> {
     pub fn new(
         storage: &'a dyn StoragePort,
-        _output: &'a dyn OutputPort,
+        output: &'a dyn OutputPort,
         log: &'a dyn LogPort,
     ) -> Self {
-        Self { storage, log }
+        Self {
+            storage,
+            output,
+            log,
+            trash: None,
+            config: None,
+            force: false,
+        }
     }
 
-    pub fn execute(&self, name: &str) -> Result<()> {
-        // Resolve yak name (exact or fuzzy match)
-        let resolved_name = self.storage.find_yak(name)?;
-
-        // Delete the yak
-        self.storage.delete_yak(&resolved_name)?;
-        self.log.log_command(&format!("rm {resolved_name}"))?;
-
-        Ok(())
+    /// Move removed yaks to the trash rather than deleting them, unless the
+    /// repo's config turns the trash off
+    pub fn with_trash(mut self, trash: &'a dyn TrashPort, config: &'a dyn ConfigPort) -> Self {
+        self.trash = Some(trash);
+        self.config = Some(config);
+        self
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::domain::Yak;
-    use std::cell::RefCell;
 
-    struct MockStorage {
-        yaks: RefCell<Vec<Yak>>,
+    /// Remove without asking for confirmation when `force` is set
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
     }
 
-    impl MockStorage {
-        fn new() -> Self {
-            Self {
-                yaks: RefCell::new(Vec::new()),
-            }
-        }
+    /// Remove a yak; with `recursive`, also every yak under it
+    pub fn execute(&self, name: &str, recursive: bool) -> Result<()> {
+        // Resolve yak name (exact or fuzzy match)
+        let resolved_name = self.storage.find_yak(name)?;
 
-        fn add_yak(&self, name: &str, done: bool) {
-            self.yaks.borrow_mut().push(Yak {
-                name: name.to_string(),
-                done,
-                context: None,
+        let all_yaks = self.storage.list_yaks()?;
+        let mut descendants: Vec<String> = graph::descendants_of(&all_yaks, &resolved_name)
+            .iter()
+            .map(|yak| yak.name.clone())
+            .collect();
+
+        if !recursive {
+            // Removing the folder would silently take the children with it
+            if !descendants.is_empty() {
+                anyhow::bail!(
+                    "cannot remove '{resolved_name}' - it has {} yak(s) under it; use --recursive",
+                    descendants.len()
+                );
+            }
+            if !self.confirmed(&format!("Remove '{resolved_name}'?")) {
+                return Ok(());
+            }
+            // Changed in a transaction, so the yaks are only locked once confirmed
+            let command = format!("rm {resolved_name}");
+            return transaction::apply(self.storage, self.log, &command, || match self.trash() {
+                Some(trash) => trash.trash(&resolved_name),
+                None => self.storage.delete_yak(&resolved_name),
             });
         }
 
-        fn yak_exists(&self, name: &str) -> bool {
-            self.yaks.borrow().iter().any(|y| y.name == name)
-        }
-    }
-
-    impl StoragePort for MockStorage {
-        fn create_yak(&self, _name: &str) -> Result<()> {
-            unimplemented!()
-        }
-
-        fn get_yak(&self, name: &str) -> Result<Yak> {
-            self.yaks
-                .borrow()
-                .iter()
-                .find(|y| y.name == name)
-                .cloned()
-                .ok_or_else(|| anyhow::anyhow!("yak '{}' not found", name))
-        }
-
-        fn list_yaks(&self) -> Result<Vec<Yak>> {
-            unimplemented!()
-        }
-
-        fn mark_done(&self, _name: &str, _done: bool) -> Result<()> {
-            unimplemented!()
+        let question = format!(
+            "Remove '{resolved_name}' and {} yak(s) under it?",
+            descendants.len()
+        );
+        if !self.confirmed(&question) {
+            return Ok(());
         }
 
-        fn delete_yak(&self, name: &str) -> Result<()> {
-            let mut yaks = self.yaks.borrow_mut();
-            if let Some(pos) = yaks.iter().position(|y| y.name == name) {
-                yaks.remove(pos);
-                Ok(())
-            } else {
-                anyhow::bail!("yak '{}' not found", name)
+        let command = format!("rm --recursive {resolved_name}");
+        transaction::apply(self.storage, self.log, &command, || {
+            if let Some(trash) = self.trash() {
+                // The yaks under it go to the trash with it
+                return trash.trash(&resolved_name);
             }
+            // Deepest first, so each yak is still there when it's removed
+            let mut deepest_first = descendants.clone();
+            deepest_first.sort_by_key(|name| std::cmp::Reverse(name.matches('/').count()));
+            for descendant in &deepest_first {
+                self.storage.delete_yak(descendant)?;
+            }
+            self.storage.delete_yak(&resolved_name)
+        })?;
+
+        self.output
+            .success(&format!("Removed {} yak(s):", descendants.len() + 1));
+        self.output.note(&format!("  {resolved_name}"));
+        descendants.sort();
+        for descendant in &descendants {
+            self.output.note(&format!("  {descendant}"));
         }
 
-        fn rename_yak(&self, _from: &str, _to: &str) -> Result<()> {
-            unimplemented!()
-        }
-
-        fn read_context(&self, _name: &str) -> Result<String> {
-            unimplemented!()
-        }
+        Ok(())
+    }
 
-        fn write_context(&self, _name: &str, _text: &str) -> Result<()> {
-            unimplemented!()
-        }
+    /// Where removed yaks go, if not deleted outright
+    fn trash(&self) -> Option<&'a dyn TrashPort> {
+        let disabled = self
+            .config
+            .and_then(|config| config.get(TRASH_KEY))
+            .is_some_and(|value| matches!(value.trim(), "false" | "no" | "off" | "0"));
+        self.trash.filter(|_| !disabled)
+    }
 
-        fn find_yak(&self, name: &str) -> Result<String> {
-            self.get_yak(name)?;
-            Ok(name.to_string())
+    fn confirmed(&self, question: &str) -> bool {
+        if self.force || self.output.confirm(question) != Some(false) {
+            return true;
         }
+        self.output.note("Nothing removed");
+        false
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use crate::ports::LogEntry;
+    use std::cell::RefCell;
 
     struct MockOutput {
         messages: RefCell<Vec<String>>,
+        answer: Option<bool>,
     }
 
     impl MockOutput {
         fn new() -> Self {
             Self {
                 messages: RefCell::new(Vec::new()),
+                answer: None,
             }
         }
 
-        fn last_message(&self) -> Option<String> {
-            self.messages.borrow().last().cloned()
+        fn get_messages(&self) -> Vec<String> {
+            self.messages.borrow().clone()
         }
     }
 
@@ -141,6 +171,19 @@ mod tests {
                 .borrow_mut()
                 .push(format!("INFO: {}", message));
         }
+
+        fn warn(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("WARN: {}", message));
+        }
+
+        fn confirm(&self, question: &str) -> Option<bool> {
+            self.messages
+                .borrow_mut()
+                .push(format!("ASK: {}", question));
+            self.answer
+        }
     }
 
     struct MockLog;
@@ -149,28 +192,172 @@ mod tests {
         fn log_command(&self, _command: &str) -> Result<()> {
             Ok(())
         }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
     }
 
     #[test]
     fn test_remove_yak_deletes_yak() {
-        let storage = MockStorage::new();
-        storage.add_yak("test-yak", false);
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("test-yak".to_string()));
         let output = MockOutput::new();
         let use_case = RemoveYak::new(&storage, &output, &MockLog);
 
-        use_case.execute("test-yak").unwrap();
+        use_case.execute("test-yak", false).unwrap();
 
-        assert!(!storage.yak_exists("test-yak"));
+        assert!(storage.get_yak("test-yak").is_err());
     }
 
     #[test]
     fn test_remove_yak_fails_for_nonexistent_yak() {
-        let storage = MockStorage::new();
+        let storage = InMemoryStorage::new();
         let output = MockOutput::new();
         let use_case = RemoveYak::new(&storage, &output, &MockLog);
 
-        let result = use_case.execute("nonexistent");
+        let result = use_case.execute("nonexistent", false);
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_remove_yak_with_children_needs_recursive() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("parent".to_string()));
+        storage.add_yak(Yak::new("parent/child".to_string()));
+        let output = MockOutput::new();
+        let use_case = RemoveYak::new(&storage, &output, &MockLog);
+
+        let result = use_case.execute("parent", false);
+
+        assert!(result.unwrap_err().to_string().contains("--recursive"));
+        assert!(storage.get_yak("parent/child").is_ok());
+    }
+
+    #[test]
+    fn test_remove_yak_recursive_removes_subtree() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("parent".to_string()));
+        storage.add_yak(Yak::new("parent/a".to_string()).mark_done());
+        storage.add_yak(Yak::new("parent/a/deep".to_string()));
+        storage.add_yak(Yak::new("parent/b".to_string()));
+        storage.add_yak(Yak::new("other".to_string()));
+        let output = MockOutput::new();
+        let use_case = RemoveYak::new(&storage, &output, &MockLog);
+
+        use_case.execute("parent", true).unwrap();
+
+        assert!(storage.get_yak("parent").is_err());
+        assert!(storage.get_yak("parent/a/deep").is_err());
+        assert!(storage.get_yak("other").is_ok());
+        assert_eq!(
+            output.get_messages(),
+            vec![
+                "ASK: Remove 'parent' and 3 yak(s) under it?",
+                "Removed 4 yak(s):",
+                "INFO:   parent",
+                "INFO:   parent/a",
+                "INFO:   parent/a/deep",
+                "INFO:   parent/b",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remove_yak_asks_before_removing() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("parent".to_string()));
+        storage.add_yak(Yak::new("parent/child".to_string()));
+        let output = MockOutput {
+            answer: Some(false),
+            ..MockOutput::new()
+        };
+        let use_case = RemoveYak::new(&storage, &output, &MockLog);
+
+        use_case.execute("parent", true).unwrap();
+
+        assert!(storage.get_yak("parent/child").is_ok());
+        assert_eq!(
+            output.get_messages(),
+            vec![
+                "ASK: Remove 'parent' and 1 yak(s) under it?",
+                "INFO: Nothing removed",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remove_yak_force_skips_question() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("test-yak".to_string()));
+        let output = MockOutput {
+            answer: Some(false),
+            ..MockOutput::new()
+        };
+        let use_case = RemoveYak::new(&storage, &output, &MockLog).with_force(true);
+
+        use_case.execute("test-yak", false).unwrap();
+
+        assert!(storage.get_yak("test-yak").is_err());
+        assert!(output.get_messages().is_empty());
+    }
+
+    #[derive(Default)]
+    struct MockTrash {
+        trashed: RefCell<Vec<String>>,
+    }
+
+    impl TrashPort for MockTrash {
+        fn trash(&self, name: &str) -> Result<()> {
+            self.trashed.borrow_mut().push(name.to_string());
+            Ok(())
+        }
+
+        fn trashed(&self) -> Result<Vec<crate::ports::TrashEntry>> {
+            unimplemented!()
+        }
+
+        fn restore_trashed(&self, _name: &str) -> Result<()> {
+            unimplemented!()
+        }
+    }
+
+    struct MockConfig(Option<&'static str>);
+
+    impl ConfigPort for MockConfig {
+        fn get(&self, _key: &str) -> Option<String> {
+            self.0.map(|value| value.to_string())
+        }
+
+        fn get_all(&self, _key: &str) -> Vec<String> {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn test_remove_yak_moves_subtree_to_trash_unless_turned_off() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("parent".to_string()));
+        storage.add_yak(Yak::new("parent/child".to_string()));
+        storage.add_yak(Yak::new("other".to_string()));
+        let output = MockOutput::new();
+        let trash = MockTrash::default();
+
+        let config = MockConfig(None);
+        RemoveYak::new(&storage, &output, &MockLog)
+            .with_trash(&trash, &config)
+            .execute("parent", true)
+            .unwrap();
+        assert_eq!(*trash.trashed.borrow(), vec!["parent"]);
+        assert!(storage.get_yak("parent/child").is_ok());
+
+        let config = MockConfig(Some("false"));
+        RemoveYak::new(&storage, &output, &MockLog)
+            .with_trash(&trash, &config)
+            .execute("other", false)
+            .unwrap();
+        assert_eq!(trash.trashed.borrow().len(), 1);
+        assert!(storage.get_yak("other").is_err());
+    }
 }
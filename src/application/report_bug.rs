@@ -0,0 +1,190 @@
+// ReportBug use case - opens a new issue prefilled with version and crash details
+
+use crate::ports::{BrowserPort, OutputPort};
+use anyhow::Result;
+use std::path::PathBuf;
+
+const ISSUES_URL: &str = concat!(env!("CARGO_PKG_REPOSITORY"), "/issues/new");
+
+/// Longest crash report put in the URL; browsers and GitHub reject very long URLs
+const MAX_REPORT_CHARS: usize = 4000;
+
+pub struct ReportBug<'a> {
+    output: &'a dyn OutputPort,
+    browser: &'a dyn BrowserPort,
+    crash_report: Option<(PathBuf, String)>,
+}
+
+impl<'a> ReportBug<'a> {
+    pub fn new(output: &'a dyn OutputPort, browser: &'a dyn BrowserPort) -> Self {
+        Self {
+            output,
+            browser,
+            crash_report: None,
+        }
+    }
+
+    /// Include a saved crash report (where it is, and its text)
+    pub fn with_crash_report(mut self, path: PathBuf, text: String) -> Self {
+        self.crash_report = Some((path, text));
+        self
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        let url = self.issue_url();
+
+        if self.browser.open(&url).is_ok() {
+            self.output.success("Opened a new issue in your browser");
+        } else {
+            self.output.note("Open this URL to report the bug:");
+            self.output.info(&url);
+        }
+        if let Some((path, _)) = &self.crash_report {
+            self.output.note(&format!(
+                "Included the crash report from {}",
+                path.display()
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn issue_url(&self) -> String {
+        let mut body = format!(
+            "**What happened?**\n\n\n**What did you expect?**\n\n\n\
+             **Version:** yx {} ({} {})\n",
+            env!("CARGO_PKG_VERSION"),
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        );
+        let title = match &self.crash_report {
+            Some((_, text)) => {
+                let report: String = text.chars().take(MAX_REPORT_CHARS).collect();
+                body.push_str(&format!("\n**Crash report:**\n\n```\n{report}\n```\n"));
+                text.lines()
+                    .find_map(|line| line.strip_prefix("panic: "))
+                    .map(|panic| format!("Crash: {panic}"))
+                    .unwrap_or_default()
+            }
+            None => String::new(),
+        };
+
+        format!(
+            "{ISSUES_URL}?title={}&body={}",
+            encode_query(&title),
+            encode_query(&body)
+        )
+    }
+}
+
+/// Percent-encode text for a URL query value
+fn encode_query(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl MockOutput {
+        fn new() -> Self {
+            Self {
+                messages: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn get_messages(&self) -> Vec<String> {
+            self.messages.borrow().clone()
+        }
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockBrowser {
+        works: bool,
+        opened: RefCell<Vec<String>>,
+    }
+
+    impl BrowserPort for MockBrowser {
+        fn open(&self, url: &str) -> Result<()> {
+            self.opened.borrow_mut().push(url.to_string());
+            if !self.works {
+                anyhow::bail!("no browser");
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_report_bug_opens_prefilled_issue_with_crash_report() {
+        let output = MockOutput::new();
+        let browser = MockBrowser {
+            works: true,
+            opened: RefCell::new(Vec::new()),
+        };
+        let use_case = ReportBug::new(&output, &browser).with_crash_report(
+            PathBuf::from("/tmp/yx-crash-1.txt"),
+            "yx 0.1.0\npanic: oh no at src/main.rs:1\n".to_string(),
+        );
+
+        use_case.execute().unwrap();
+
+        let url = &browser.opened.borrow()[0];
+        assert!(url.starts_with(ISSUES_URL));
+        assert!(url.contains("?title=Crash%3A%20oh%20no%20at%20src%2Fmain.rs%3A1&body="));
+        assert!(url.contains(&encode_query("**Crash report:**")));
+        assert_eq!(
+            output.get_messages(),
+            vec![
+                "Opened a new issue in your browser",
+                "Included the crash report from /tmp/yx-crash-1.txt",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_report_bug_prints_url_without_a_browser() {
+        let output = MockOutput::new();
+        let browser = MockBrowser {
+            works: false,
+            opened: RefCell::new(Vec::new()),
+        };
+        let use_case = ReportBug::new(&output, &browser);
+
+        use_case.execute().unwrap();
+
+        let messages = output.get_messages();
+        assert_eq!(messages[0], "Open this URL to report the bug:");
+        assert!(messages[1].starts_with(&format!("{ISSUES_URL}?title=&body=")));
+    }
+}
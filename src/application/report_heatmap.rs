@@ -0,0 +1,371 @@
+// ReportHeatmap use case - GitHub-style grid of completed yaks per day over the last year
+
+use super::OutputFormat;
+use crate::ports::{LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Utc};
+use std::collections::{BTreeMap, HashMap};
+
+/// Weeks shown, ending with the current one
+const WEEKS: i64 = 53;
+
+/// Shades from no completions to the busiest days
+const TERMINAL_CELLS: [&str; 5] = ["·", "░", "▒", "▓", "█"];
+const SVG_COLORS: [&str; 5] = ["#ebedf0", "#9be9a8", "#40c463", "#30a14e", "#216e39"];
+
+/// SVG cell size and spacing in pixels
+const CELL: i64 = 10;
+const GAP: i64 = 2;
+
+pub struct ReportHeatmap<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+}
+
+impl<'a> ReportHeatmap<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            log,
+        }
+    }
+
+    /// Render the heatmap as terminal text or an SVG document
+    pub fn execute(&self, format: OutputFormat) -> Result<()> {
+        format.ensure(
+            "report heatmap",
+            &[OutputFormat::Terminal, OutputFormat::Svg],
+        )?;
+
+        let counts = self.completions_per_day()?;
+        let today = Local::now().date_naive();
+        let lines = if format == OutputFormat::Svg {
+            render_svg(&counts, today)
+        } else {
+            render_terminal(&counts, today)
+        };
+
+        for line in lines {
+            self.output.info(&line);
+        }
+
+        Ok(())
+    }
+
+    /// Completions per local day, from the command log plus completion
+    /// timestamps of done yaks the log doesn't know about (e.g. imported ones)
+    fn completions_per_day(&self) -> Result<HashMap<NaiveDate, usize>> {
+        let mut completed: HashMap<String, Vec<DateTime<Utc>>> = HashMap::new();
+        for entry in self.log.entries()? {
+            let Some(rest) = entry.command.strip_prefix("done ") else {
+                continue;
+            };
+            if let Some(name) = rest.strip_prefix("--undo ") {
+                if let Some(times) = completed.get_mut(name) {
+                    times.pop();
+                }
+            } else {
+                let name = rest.strip_prefix("--recursive ").unwrap_or(rest);
+                completed
+                    .entry(name.to_string())
+                    .or_default()
+                    .push(entry.time);
+            }
+        }
+
+        for yak in self.storage.list_yaks()? {
+            if let Some(done_at) = yak.done_at.filter(|_| yak.done) {
+                let times = completed.entry(yak.name).or_default();
+                if times.is_empty() {
+                    times.push(done_at);
+                }
+            }
+        }
+
+        let mut counts = HashMap::new();
+        for time in completed.into_values().flatten() {
+            *counts
+                .entry(time.with_timezone(&Local).date_naive())
+                .or_insert(0) += 1;
+        }
+        Ok(counts)
+    }
+}
+
+/// First day (a Sunday) of the grid that ends with the week containing `today`
+fn grid_start(today: NaiveDate) -> NaiveDate {
+    let sunday = today - Duration::days(today.weekday().num_days_from_sunday() as i64);
+    sunday - Duration::weeks(WEEKS - 1)
+}
+
+/// Shade index for a day's count, scaled against the busiest day
+fn level(count: usize, max: usize) -> usize {
+    if count == 0 || max == 0 {
+        0
+    } else {
+        (count * 4).div_ceil(max).clamp(1, 4)
+    }
+}
+
+/// Counts for each visible day, keyed by date
+fn visible_counts(
+    counts: &HashMap<NaiveDate, usize>,
+    today: NaiveDate,
+) -> BTreeMap<NaiveDate, usize> {
+    let start = grid_start(today);
+    counts
+        .iter()
+        .filter(|(day, _)| **day >= start && **day <= today)
+        .map(|(day, count)| (*day, *count))
+        .collect()
+}
+
+fn render_terminal(counts: &HashMap<NaiveDate, usize>, today: NaiveDate) -> Vec<String> {
+    let start = grid_start(today);
+    let visible = visible_counts(counts, today);
+    let max = visible.values().copied().max().unwrap_or(0);
+
+    // Month labels above the first week that starts in each month
+    let mut months = String::from("    ");
+    let mut week = 0;
+    while week < WEEKS {
+        let sunday = start + Duration::weeks(week);
+        if sunday.day() <= 7 {
+            let label = sunday.format("%b").to_string();
+            months.push_str(&label);
+            week += label.len() as i64;
+        } else {
+            months.push(' ');
+            week += 1;
+        }
+    }
+
+    let mut lines = vec![months.trim_end().to_string()];
+    for weekday in 0..7 {
+        let label = match weekday {
+            1 => "Mon ",
+            3 => "Wed ",
+            5 => "Fri ",
+            _ => "    ",
+        };
+        let mut row = label.to_string();
+        for week in 0..WEEKS {
+            let day = start + Duration::days(week * 7 + weekday);
+            if day > today {
+                break;
+            }
+            let count = visible.get(&day).copied().unwrap_or(0);
+            row.push_str(TERMINAL_CELLS[level(count, max)]);
+        }
+        lines.push(row.trim_end().to_string());
+    }
+
+    let total: usize = visible.values().sum();
+    let noun = if total == 1 { "yak" } else { "yaks" };
+    lines.push(String::new());
+    lines.push(format!(
+        "{total} {noun} completed in the last year   Less {} More",
+        TERMINAL_CELLS.concat()
+    ));
+    lines
+}
+
+fn render_svg(counts: &HashMap<NaiveDate, usize>, today: NaiveDate) -> Vec<String> {
+    let start = grid_start(today);
+    let visible = visible_counts(counts, today);
+    let max = visible.values().copied().max().unwrap_or(0);
+
+    let width = WEEKS * (CELL + GAP);
+    let height = 7 * (CELL + GAP);
+    let mut lines = vec![format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" class=\"yak-heatmap\">"
+    )];
+
+    for week in 0..WEEKS {
+        for weekday in 0..7 {
+            let day = start + Duration::days(week * 7 + weekday);
+            if day > today {
+                break;
+            }
+            let count = visible.get(&day).copied().unwrap_or(0);
+            let noun = if count == 1 { "yak" } else { "yaks" };
+            lines.push(format!(
+                "  <rect x=\"{}\" y=\"{}\" width=\"{CELL}\" height=\"{CELL}\" rx=\"2\" fill=\"{}\"><title>{day}: {count} {noun} completed</title></rect>",
+                week * (CELL + GAP),
+                weekday * (CELL + GAP),
+                SVG_COLORS[level(count, max)]
+            ));
+        }
+    }
+
+    lines.push("</svg>".to_string());
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use crate::ports::LogEntry;
+    use chrono::TimeZone;
+    use std::cell::RefCell;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_level_scales_against_busiest_day() {
+        assert_eq!(level(0, 8), 0);
+        assert_eq!(level(1, 8), 1);
+        assert_eq!(level(4, 8), 2);
+        assert_eq!(level(8, 8), 4);
+    }
+
+    #[test]
+    fn test_grid_starts_on_a_sunday_a_year_back() {
+        // 2026-10-17 is a Saturday
+        let start = grid_start(date(2026, 10, 17));
+        assert_eq!(start.weekday(), chrono::Weekday::Sun);
+        assert_eq!(start, date(2025, 10, 12));
+    }
+
+    #[test]
+    fn test_render_terminal_grid() {
+        let today = date(2026, 10, 17);
+        let counts = HashMap::from([
+            (date(2026, 10, 12), 1), // Monday this week
+            (date(2026, 10, 14), 2), // Wednesday this week
+            (date(2020, 1, 1), 5),   // Outside the grid
+        ]);
+
+        let lines = render_terminal(&counts, today);
+
+        assert_eq!(lines.len(), 10);
+        assert!(lines[0].starts_with("       Nov"));
+        assert!(lines[2].starts_with("Mon "));
+        assert!(lines[2].ends_with("▒"));
+        assert!(lines[4].ends_with("█"));
+        assert_eq!(lines[2].chars().count(), 4 + 53);
+        assert_eq!(
+            lines[9],
+            "3 yaks completed in the last year   Less ·░▒▓█ More"
+        );
+    }
+
+    #[test]
+    fn test_render_svg_has_one_rect_per_day() {
+        let today = date(2026, 10, 17);
+        let counts = HashMap::from([(today, 1)]);
+
+        let lines = render_svg(&counts, today);
+
+        let rects = lines.iter().filter(|line| line.contains("<rect")).count();
+        assert_eq!(rects, 53 * 7);
+        assert!(lines
+            .iter()
+            .any(|line| line.contains("#216e39") && line.contains("2026-10-17: 1 yak completed")));
+    }
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockLog {
+        entries: Vec<LogEntry>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, _command: &str) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            Ok(self.entries.clone())
+        }
+    }
+
+    #[test]
+    fn test_completions_combine_log_and_done_timestamps() {
+        let at = |d: u32| Utc.with_ymd_and_hms(2026, 3, d, 12, 0, 0).unwrap();
+        let storage = InMemoryStorage::with_yaks(vec![
+            Yak {
+                name: "logged".to_string(),
+                done: true,
+                done_at: Some(at(9)),
+                ..Default::default()
+            },
+            Yak {
+                name: "imported".to_string(),
+                done: true,
+                done_at: Some(at(5)),
+                ..Default::default()
+            },
+        ]);
+        let entry = |command: &str, d: u32| LogEntry {
+            command: command.to_string(),
+            time: at(d),
+        };
+        let log = MockLog {
+            entries: vec![
+                entry("done logged", 2),
+                entry("done undone", 3),
+                entry("done --undo undone", 3),
+                entry("done --recursive removed", 2),
+            ],
+        };
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let use_case = ReportHeatmap::new(&storage, &output, &log);
+
+        let counts = use_case.completions_per_day().unwrap();
+
+        let local = |d: u32| at(d).with_timezone(&Local).date_naive();
+        assert_eq!(counts.get(&local(2)), Some(&2));
+        assert_eq!(counts.get(&local(5)), Some(&1));
+        assert_eq!(counts.get(&local(3)), None);
+        assert_eq!(counts.get(&local(9)), None);
+    }
+
+    #[test]
+    fn test_rejects_unsupported_format() {
+        let storage = InMemoryStorage::new();
+        let log = MockLog {
+            entries: Vec::new(),
+        };
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let use_case = ReportHeatmap::new(&storage, &output, &log);
+
+        assert!(use_case.execute(OutputFormat::Dot).is_err());
+    }
+}
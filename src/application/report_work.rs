@@ -0,0 +1,288 @@
+// ReportWork use case - markdown summary of the work done over a period, for weekly updates
+
+use super::history::History;
+use crate::domain::{parse_due_date, Yak, YakState};
+use crate::ports::{LogPort, OutputPort, PrunedPort, StoragePort};
+use anyhow::Result;
+use chrono::{DateTime, Duration, Local, Utc};
+
+/// How far back the report goes without `--since`
+const DEFAULT_DAYS: i64 = 7;
+
+pub struct ReportWork<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+    pruned: Option<&'a dyn PrunedPort>,
+}
+
+impl<'a> ReportWork<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            log,
+            pruned: None,
+        }
+    }
+
+    /// Count the yaks `yx prune` recorded among the completed ones
+    pub fn with_pruned(mut self, pruned: &'a dyn PrunedPort) -> Self {
+        self.pruned = Some(pruned);
+        self
+    }
+
+    /// Report the yaks completed and added since `since` (YYYY-MM-DD, a week ago by
+    /// default), and the ones still being worked on or blocked
+    pub fn execute(&self, since: Option<&str>) -> Result<()> {
+        let since = match since {
+            Some(text) => parse_due_date(text)
+                .map_err(|_| anyhow::anyhow!("Invalid date '{}': use YYYY-MM-DD", text.trim()))?,
+            None => Local::now().date_naive() - Duration::days(DEFAULT_DAYS),
+        };
+
+        let yaks = self.storage.list_yaks()?;
+        let history = History::replay(&self.log.entries()?, &yaks);
+        let in_period = |time: &DateTime<Utc>| time.with_timezone(&Local).date_naive() >= since;
+
+        // Pruned yaks are gone from storage, so their record stands in for them
+        let pruned: Vec<(Yak, DateTime<Utc>)> = match self.pruned {
+            Some(record) => record
+                .pruned()?
+                .into_iter()
+                .map(|pruned| {
+                    let time = pruned.completed.unwrap_or(pruned.pruned);
+                    let yak = Yak {
+                        name: pruned.name,
+                        title: pruned.title,
+                        done: true,
+                        done_at: pruned.completed,
+                        ..Yak::default()
+                    };
+                    (yak, time)
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let completed = by_time(
+            yaks.iter()
+                .filter_map(|yak| {
+                    let time = history.completed_at(yak)?;
+                    in_period(&time).then_some((yak, time))
+                })
+                .chain(
+                    pruned
+                        .iter()
+                        .filter(|(_, time)| in_period(time))
+                        .map(|(yak, time)| (yak, *time)),
+                ),
+        );
+        let added = by_time(yaks.iter().filter_map(|yak| {
+            let time = history.created.get(&yak.name)?;
+            in_period(time).then_some((yak, *time))
+        }));
+        let in_state = |state: YakState| -> Vec<&Yak> {
+            yaks.iter()
+                .filter(|yak| yak.current_state() == state)
+                .collect()
+        };
+
+        self.output.info(&format!("# Yak report since {since}"));
+        self.section("Completed", &completed);
+        self.section("Added", &added);
+        self.section("In progress", &in_state(YakState::Doing));
+        self.section("Blocked", &in_state(YakState::Blocked));
+
+        Ok(())
+    }
+
+    fn section(&self, heading: &str, yaks: &[&Yak]) {
+        self.output.info("");
+        self.output.info(&format!("## {heading} ({})", yaks.len()));
+        self.output.info("");
+        if yaks.is_empty() {
+            self.output.info("_None_");
+        }
+        for yak in yaks {
+            let label = yak.title.as_deref().unwrap_or(&yak.name);
+            if yak.done {
+                self.output.info(&format!("- [x] {label}"));
+            } else {
+                self.output.info(&format!("- [ ] {label}"));
+            }
+        }
+    }
+}
+
+/// Yaks in the order their times say, oldest first
+fn by_time<'y>(yaks: impl Iterator<Item = (&'y Yak, DateTime<Utc>)>) -> Vec<&'y Yak> {
+    let mut timed: Vec<(&Yak, DateTime<Utc>)> = yaks.collect();
+    timed.sort_by_key(|(yak, time)| (*time, yak.name.clone()));
+    timed.into_iter().map(|(yak, _)| yak).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::ports::{LogEntry, PrunedYak};
+    use chrono::NaiveDate;
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, _message: &str) {}
+
+        fn error(&self, _message: &str) {}
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockLog {
+        entries: Vec<LogEntry>,
+    }
+
+    impl MockLog {
+        /// Entries given as (command, days ago)
+        fn new(entries: &[(&str, i64)]) -> Self {
+            let now = Utc::now();
+            Self {
+                entries: entries
+                    .iter()
+                    .map(|(command, days_ago)| LogEntry {
+                        command: command.to_string(),
+                        time: now - Duration::days(*days_ago),
+                    })
+                    .collect(),
+            }
+        }
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, _command: &str) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            Ok(self.entries.clone())
+        }
+    }
+
+    struct MockPruned {
+        yaks: Vec<PrunedYak>,
+    }
+
+    impl PrunedPort for MockPruned {
+        fn record_pruned(&self, _yaks: &[PrunedYak]) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn pruned(&self) -> Result<Vec<PrunedYak>> {
+            Ok(self.yaks.clone())
+        }
+    }
+
+    fn days_ago(days: i64) -> NaiveDate {
+        Local::now().date_naive() - Duration::days(days)
+    }
+
+    #[test]
+    fn test_report_covers_the_period_and_current_state() {
+        let storage = InMemoryStorage::with_yaks(vec![
+            Yak::new("old".to_string()).mark_done(),
+            Yak::new("auth".to_string()).mark_done(),
+            Yak::new("docs".to_string()).with_title("Write the docs".to_string()),
+            Yak::new("api".to_string()).with_state(YakState::Doing),
+            Yak::new("ci".to_string()).with_state(YakState::Blocked),
+        ]);
+        let log = MockLog::new(&[
+            ("add old", 30),
+            ("add api", 20),
+            ("add ci", 20),
+            ("done old", 20),
+            ("add auth", 5),
+            ("add docs", 3),
+            ("done auth", 1),
+        ]);
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let since = days_ago(10).to_string();
+
+        ReportWork::new(&storage, &output, &log)
+            .execute(Some(&since))
+            .unwrap();
+
+        assert_eq!(
+            output.messages.borrow().join("\n"),
+            format!(
+                "# Yak report since {since}\n\n\
+                 ## Completed (1)\n\n- [x] auth\n\n\
+                 ## Added (2)\n\n- [x] auth\n- [ ] Write the docs\n\n\
+                 ## In progress (1)\n\n- [ ] api\n\n\
+                 ## Blocked (1)\n\n- [ ] ci"
+            )
+        );
+    }
+
+    #[test]
+    fn test_report_counts_pruned_yaks_as_completed() {
+        let storage = InMemoryStorage::with_yaks(vec![Yak::new("auth".to_string()).mark_done()]);
+        let log = MockLog::new(&[("add auth", 5), ("done auth", 1)]);
+        let pruned = |name: &str, days: i64| PrunedYak {
+            name: name.to_string(),
+            title: None,
+            completed: Some(Utc::now() - Duration::days(days)),
+            pruned: Utc::now(),
+            context: String::new(),
+        };
+        let record = MockPruned {
+            yaks: vec![pruned("ancient", 30), pruned("ci", 2)],
+        };
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+
+        ReportWork::new(&storage, &output, &log)
+            .with_pruned(&record)
+            .execute(None)
+            .unwrap();
+
+        let messages = output.messages.borrow();
+        assert_eq!(
+            messages[2..6],
+            ["## Completed (2)", "", "- [x] ci", "- [x] auth"]
+        );
+    }
+
+    #[test]
+    fn test_report_rejects_bad_dates() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let log = MockLog::new(&[]);
+        let use_case = ReportWork::new(&storage, &output, &log);
+
+        assert!(use_case.execute(Some("last week")).is_err());
+        use_case.execute(None).unwrap();
+        assert_eq!(
+            output.messages.borrow()[0],
+            format!("# Yak report since {}", days_ago(DEFAULT_DAYS))
+        );
+    }
+}
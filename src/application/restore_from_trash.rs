@@ -0,0 +1,138 @@
+// RestoreFromTrash use case - brings back a yak `yx rm` moved to the trash
+
+use crate::ports::{LogPort, OutputPort, TrashPort};
+use anyhow::Result;
+
+pub struct RestoreFromTrash<'a> {
+    trash: &'a dyn TrashPort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+}
+
+impl<'a> RestoreFromTrash<'a> {
+    pub fn new(trash: &'a dyn TrashPort, output: &'a dyn OutputPort, log: &'a dyn LogPort) -> Self {
+        Self { trash, output, log }
+    }
+
+    /// Restore the most recently removed yak called `name`, or the only one
+    /// in the trash whose name contains it
+    pub fn execute(&self, name: &str) -> Result<()> {
+        let mut names: Vec<String> = self
+            .trash
+            .trashed()?
+            .into_iter()
+            .map(|entry| entry.name)
+            .collect();
+        names.sort();
+        names.dedup();
+        let name = if names.iter().any(|trashed| trashed == name) {
+            name.to_string()
+        } else {
+            let matches: Vec<&String> = names.iter().filter(|n| n.contains(name)).collect();
+            match matches.as_slice() {
+                [] => anyhow::bail!("yak '{name}' not found in the trash"),
+                [matched] => matched.to_string(),
+                _ => anyhow::bail!("yak name '{name}' is ambiguous"),
+            }
+        };
+
+        self.trash.restore_trashed(&name)?;
+        self.log.log_command(&format!("trash restore {name}"))?;
+        self.output
+            .success(&format!("Restored '{name}' from the trash"));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::{LogEntry, TrashEntry};
+    use chrono::Utc;
+    use std::cell::RefCell;
+
+    struct MockTrash {
+        entries: RefCell<Vec<TrashEntry>>,
+    }
+
+    impl TrashPort for MockTrash {
+        fn trash(&self, _name: &str) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn trashed(&self) -> Result<Vec<TrashEntry>> {
+            Ok(self.entries.borrow().clone())
+        }
+
+        fn restore_trashed(&self, name: &str) -> Result<()> {
+            let mut entries = self.entries.borrow_mut();
+            let index = entries.iter().position(|entry| entry.name == name).unwrap();
+            entries.remove(index);
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, _message: &str) {}
+
+        fn info(&self, _message: &str) {}
+
+        fn warn(&self, _message: &str) {}
+    }
+
+    #[derive(Default)]
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_restore_matches_trashed_names() {
+        let entry = |name: &str| TrashEntry {
+            name: name.to_string(),
+            removed: Utc::now(),
+        };
+        let trash = MockTrash {
+            entries: RefCell::new(vec![entry("api"), entry("api/auth"), entry("docs")]),
+        };
+        let output = MockOutput::default();
+        let log = MockLog::default();
+        let restore = RestoreFromTrash::new(&trash, &output, &log);
+
+        assert!(restore.execute("missing").is_err());
+        restore.execute("api").unwrap();
+        restore.execute("au").unwrap();
+
+        assert_eq!(
+            *output.messages.borrow(),
+            vec![
+                "Restored 'api' from the trash",
+                "Restored 'api/auth' from the trash"
+            ]
+        );
+        assert_eq!(
+            *log.commands.borrow(),
+            vec!["trash restore api", "trash restore api/auth"]
+        );
+        assert_eq!(trash.entries.borrow().len(), 1);
+    }
+}
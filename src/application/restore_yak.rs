@@ -0,0 +1,216 @@
+// RestoreYak use case - brings a deleted yak back from the refs/notes/yaks history
+
+use crate::domain::validate_yak_name;
+use crate::ports::{HistoryPort, LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+use chrono::Local;
+
+pub struct RestoreYak<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+    history: &'a dyn HistoryPort,
+}
+
+impl<'a> RestoreYak<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+        history: &'a dyn HistoryPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            log,
+            history,
+        }
+    }
+
+    /// Restore the yak (and anything that was under it) as of the last revision that had it
+    pub fn execute(&self, name: &str) -> Result<()> {
+        validate_yak_name(name).map_err(|e| anyhow::anyhow!(e))?;
+        if self.storage.get_yak(name).is_ok() {
+            anyhow::bail!("yak '{name}' already exists");
+        }
+
+        let Some((revision, snapshots)) = self.history.last_snapshot(name)? else {
+            anyhow::bail!("yak '{name}' not found in history");
+        };
+
+        let mut restored = 0;
+        for snapshot in &snapshots {
+            // Children re-added since the delete win over their old versions
+            if snapshot.name != name && self.storage.get_yak(&snapshot.name).is_ok() {
+                continue;
+            }
+
+            self.storage.create_yak(&snapshot.name)?;
+            for (file, text) in &snapshot.files {
+                match file.as_str() {
+                    "context.md" => self.storage.write_context(&snapshot.name, text)?,
+                    // The done marker holds its completion time, so it's kept verbatim
+                    _ => self.storage.write_field(&snapshot.name, file, Some(text))?,
+                }
+            }
+            restored += 1;
+        }
+
+        self.log.log_command(&format!("restore {name}"))?;
+
+        let short_id = &revision.id[..revision.id.len().min(7)];
+        let when = revision.time.with_timezone(&Local).format("%Y-%m-%d %H:%M");
+        let extra = match restored {
+            0 | 1 => String::new(),
+            2 => " with 1 yak under it".to_string(),
+            n => format!(" with {} yaks under it", n - 1),
+        };
+        self.output.success(&format!(
+            "Restored '{name}'{extra} from {short_id} ({}, {when})",
+            revision.command
+        ));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::ports::{FileChange, FileVersion, LogEntry, Revision, YakSnapshot};
+    use chrono::Utc;
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+        fn error(&self, _message: &str) {}
+        fn info(&self, _message: &str) {}
+
+        fn warn(&self, _message: &str) {}
+    }
+
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    struct MockHistory {
+        snapshots: Vec<YakSnapshot>,
+    }
+
+    impl HistoryPort for MockHistory {
+        fn revisions(&self, _name: Option<&str>) -> Result<Vec<Revision>> {
+            unimplemented!()
+        }
+
+        fn changed_files(&self, _revision: &str, _name: &str) -> Result<Vec<FileChange>> {
+            unimplemented!()
+        }
+
+        fn last_snapshot(&self, name: &str) -> Result<Option<(Revision, Vec<YakSnapshot>)>> {
+            if self.snapshots.is_empty() || self.snapshots[0].name != name {
+                return Ok(None);
+            }
+            let revision = Revision {
+                id: "0123456789abcdef".to_string(),
+                command: "done app".to_string(),
+                author: "ann".to_string(),
+                time: Utc::now(),
+            };
+            Ok(Some((revision, self.snapshots.clone())))
+        }
+
+        fn file_versions(&self, _name: &str, _file: &str) -> Result<Vec<FileVersion>> {
+            unimplemented!()
+        }
+
+        fn remote_file_version(&self, _name: &str, _file: &str) -> Result<Option<FileVersion>> {
+            unimplemented!()
+        }
+    }
+
+    fn snapshot(name: &str, files: &[(&str, &str)]) -> YakSnapshot {
+        YakSnapshot {
+            name: name.to_string(),
+            files: files
+                .iter()
+                .map(|(file, text)| (file.to_string(), text.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_restore_recreates_yak_and_children() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let history = MockHistory {
+            snapshots: vec![
+                snapshot("app", &[("context.md", "notes"), ("done", "2026-01-02")]),
+                snapshot("app/api", &[("priority", "P1\n")]),
+                snapshot("app/ui", &[("context.md", "old")]),
+            ],
+        };
+        let use_case = RestoreYak::new(&storage, &output, &log, &history);
+
+        use_case.execute("app").unwrap();
+
+        assert_eq!(storage.read_context("app").unwrap(), "notes");
+        assert_eq!(
+            storage.read_field("app", "done").unwrap().as_deref(),
+            Some("2026-01-02")
+        );
+        assert_eq!(
+            storage
+                .read_field("app/api", "priority")
+                .unwrap()
+                .as_deref(),
+            Some("P1\n")
+        );
+        assert_eq!(storage.read_context("app/ui").unwrap(), "old");
+        assert_eq!(*log.commands.borrow(), vec!["restore app"]);
+        assert!(output.messages.borrow()[0]
+            .starts_with("Restored 'app' with 2 yaks under it from 0123456 (done app, "));
+    }
+
+    #[test]
+    fn test_restore_refuses_existing_or_unknown_yaks() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("app").unwrap();
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let history = MockHistory {
+            snapshots: vec![snapshot("app", &[])],
+        };
+        let use_case = RestoreYak::new(&storage, &output, &log, &history);
+
+        assert!(use_case.execute("app").is_err());
+        assert!(use_case.execute("gone").is_err());
+        assert!(log.commands.borrow().is_empty());
+    }
+}
@@ -0,0 +1,116 @@
+// ScheduleYak use case - records when a yak is due
+
+use crate::domain::parse_due_date;
+use crate::ports::{LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+
+pub struct ScheduleYak<'a> {
+    storage: &'a dyn StoragePort,
+    log: &'a dyn LogPort,
+}
+
+impl<'a> ScheduleYak<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        _output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+    ) -> Self {
+        Self { storage, log }
+    }
+
+    /// Set the due date (YYYY-MM-DD), or clear it when `due` is None
+    pub fn execute(&self, name: &str, due: Option<&str>) -> Result<()> {
+        let due = due
+            .map(parse_due_date)
+            .transpose()
+            .map_err(|e| anyhow::anyhow!(e))?;
+
+        // Resolve yak name (exact or fuzzy match)
+        let resolved_name = self.storage.find_yak(name)?;
+
+        match due {
+            Some(due) => {
+                self.storage
+                    .write_field(&resolved_name, "due", Some(&format!("{due}\n")))?;
+                self.log
+                    .log_command(&format!("due {resolved_name} {due}"))?;
+            }
+            None => {
+                self.storage.write_field(&resolved_name, "due", None)?;
+                self.log
+                    .log_command(&format!("due --clear {resolved_name}"))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::ports::LogEntry;
+    use std::cell::RefCell;
+
+    struct MockOutput;
+
+    impl OutputPort for MockOutput {
+        fn success(&self, _message: &str) {}
+        fn error(&self, _message: &str) {}
+        fn info(&self, _message: &str) {}
+
+        fn warn(&self, _message: &str) {}
+    }
+
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_schedule_yak_sets_and_clears_due_date() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("docs").unwrap();
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = ScheduleYak::new(&storage, &MockOutput, &log);
+
+        use_case.execute("docs", Some("2026-03-02")).unwrap();
+        assert_eq!(
+            storage.read_field("docs", "due").unwrap(),
+            Some("2026-03-02\n".to_string())
+        );
+
+        use_case.execute("docs", None).unwrap();
+        assert_eq!(storage.read_field("docs", "due").unwrap(), None);
+        assert_eq!(
+            *log.commands.borrow(),
+            vec!["due docs 2026-03-02", "due --clear docs"]
+        );
+    }
+
+    #[test]
+    fn test_schedule_yak_rejects_invalid_date() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("docs").unwrap();
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = ScheduleYak::new(&storage, &MockOutput, &log);
+
+        assert!(use_case.execute("docs", Some("tomorrow")).is_err());
+        assert!(log.commands.borrow().is_empty());
+    }
+}
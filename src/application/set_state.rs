@@ -0,0 +1,120 @@
+// SetState use case - records whether an open yak is todo, being worked on, or blocked
+
+use crate::domain::YakState;
+use crate::ports::{LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+
+pub struct SetState<'a> {
+    storage: &'a dyn StoragePort,
+    log: &'a dyn LogPort,
+}
+
+impl<'a> SetState<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        _output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+    ) -> Self {
+        Self { storage, log }
+    }
+
+    /// Set the state of an open yak; todo clears the stored state
+    /// Done yaks are left to `yx done`, which also checks their children.
+    pub fn execute(&self, name: &str, state: &str) -> Result<()> {
+        let state = YakState::parse(state).map_err(|e| anyhow::anyhow!(e))?;
+
+        // Resolve yak name (exact or fuzzy match)
+        let resolved_name = self.storage.find_yak(name)?;
+
+        if state == YakState::Done {
+            anyhow::bail!("use `yx done` to mark '{resolved_name}' done");
+        }
+        if self.storage.get_yak(&resolved_name)?.done {
+            anyhow::bail!("'{resolved_name}' is done - reopen it with `yx done --undo` first");
+        }
+
+        let value = match state {
+            YakState::Todo => None,
+            state => Some(format!("{state}\n")),
+        };
+        self.storage
+            .write_field(&resolved_name, "state", value.as_deref())?;
+        self.log
+            .log_command(&format!("state {resolved_name} {state}"))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use crate::ports::LogEntry;
+    use std::cell::RefCell;
+
+    struct MockOutput;
+
+    impl OutputPort for MockOutput {
+        fn success(&self, _message: &str) {}
+        fn error(&self, _message: &str) {}
+        fn info(&self, _message: &str) {}
+
+        fn warn(&self, _message: &str) {}
+    }
+
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_set_state_writes_and_clears_field() {
+        let storage = InMemoryStorage::with_yaks([Yak::new("api".to_string())]);
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = SetState::new(&storage, &MockOutput, &log);
+
+        use_case.execute("api", "Blocked").unwrap();
+        assert_eq!(
+            storage.read_field("api", "state").unwrap(),
+            Some("blocked\n".to_string())
+        );
+
+        use_case.execute("api", "todo").unwrap();
+        assert_eq!(storage.read_field("api", "state").unwrap(), None);
+        assert_eq!(
+            *log.commands.borrow(),
+            vec!["state api blocked", "state api todo"]
+        );
+    }
+
+    #[test]
+    fn test_set_state_leaves_done_yaks_to_yx_done() {
+        let storage = InMemoryStorage::with_yaks([
+            Yak::new("api".to_string()),
+            Yak::new("old".to_string()).mark_done(),
+        ]);
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = SetState::new(&storage, &MockOutput, &log);
+
+        assert!(use_case.execute("api", "done").is_err());
+        assert!(use_case.execute("old", "doing").is_err());
+        assert!(use_case.execute("api", "paused").is_err());
+        assert!(log.commands.borrow().is_empty());
+    }
+}
@@ -0,0 +1,179 @@
+// ShowAllStatus use case - `yx status` for every configured workspace
+
+use super::workspaces::{PrefixedOutput, NO_WORKSPACES};
+use super::ShowStatus;
+use crate::ports::{LocalStatePort, OutputPort, StoragePort, SyncPort};
+use anyhow::Result;
+
+/// Everything `yx status` needs for one workspace
+pub struct WorkspacePorts<'a> {
+    pub storage: &'a dyn StoragePort,
+    pub sync: &'a dyn SyncPort,
+    pub state: &'a dyn LocalStatePort,
+}
+
+pub struct ShowAllStatus<'a> {
+    workspaces: Vec<(String, Result<WorkspacePorts<'a>>)>,
+    output: &'a dyn OutputPort,
+}
+
+impl<'a> ShowAllStatus<'a> {
+    /// `workspaces` pairs each workspace name with its ports, or why they couldn't be opened
+    pub fn new(
+        workspaces: Vec<(String, Result<WorkspacePorts<'a>>)>,
+        output: &'a dyn OutputPort,
+    ) -> Self {
+        Self { workspaces, output }
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        if self.workspaces.is_empty() {
+            anyhow::bail!(NO_WORKSPACES);
+        }
+
+        for (name, ports) in &self.workspaces {
+            let output = PrefixedOutput::new(name, self.output);
+            let result = ports.as_ref().map_err(|e| e.to_string()).and_then(|ports| {
+                ShowStatus::new(ports.storage, ports.sync, &output)
+                    .with_state(ports.state)
+                    .execute()
+                    .map_err(|e| e.to_string())
+            });
+
+            // One broken workspace shouldn't hide the others
+            if let Err(e) = result {
+                output.error(&e);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use crate::ports::{SyncProgress, SyncStatus};
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl MockOutput {
+        fn new() -> Self {
+            Self {
+                messages: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn get_messages(&self) -> Vec<String> {
+            self.messages.borrow().clone()
+        }
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockSync;
+
+    impl SyncPort for MockSync {
+        fn push(&self) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn pull(&self) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn sync(&self, _progress: &dyn Fn(SyncProgress)) -> Result<Vec<String>> {
+            unimplemented!()
+        }
+
+        fn status(&self) -> Result<SyncStatus> {
+            Ok(SyncStatus {
+                has_local_changes: true,
+                remote: Some((1, 0)),
+            })
+        }
+
+        fn preview(&self) -> Result<Vec<(String, crate::ports::IncomingChange)>> {
+            unimplemented!()
+        }
+    }
+
+    struct MockState;
+
+    impl LocalStatePort for MockState {
+        fn read(&self, _key: &str) -> Result<Option<String>> {
+            Ok(None)
+        }
+
+        fn write(&self, _key: &str, _value: Option<&str>) -> Result<()> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_all_status_prefixes_each_workspace() {
+        let app = InMemoryStorage::with_yaks([
+            Yak::new("login".to_string()),
+            Yak::new("docs".to_string()).mark_done(),
+        ]);
+        let output = MockOutput::new();
+        let use_case = ShowAllStatus::new(
+            vec![
+                (
+                    "app".to_string(),
+                    Ok(WorkspacePorts {
+                        storage: &app,
+                        sync: &MockSync,
+                        state: &MockState,
+                    }),
+                ),
+                ("gone".to_string(), Err(anyhow::anyhow!("not a git repo"))),
+            ],
+            &output,
+        );
+
+        use_case.execute().unwrap();
+
+        assert_eq!(
+            output.get_messages(),
+            vec![
+                "[app] Yaks:   1 todo, 1 done",
+                "[app] Local:  unsynced changes in .yaks",
+                "[app] Remote: 1 ahead",
+                "[app] Synced: never",
+                "ERROR: [gone] not a git repo",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_all_status_requires_workspaces() {
+        let output = MockOutput::new();
+        let use_case = ShowAllStatus::new(Vec::new(), &output);
+
+        assert!(use_case.execute().is_err());
+    }
+}
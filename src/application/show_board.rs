@@ -0,0 +1,187 @@
+// ShowBoard use case - kanban-style columns of yaks by state
+
+use crate::domain::{graph, YakState};
+use crate::ports::{OutputPort, StoragePort};
+use anyhow::Result;
+
+/// Board width when the terminal's isn't known
+const DEFAULT_WIDTH: usize = 80;
+/// Spaces between columns
+const GAP: usize = 3;
+/// Narrowest a column gets, even if the board then overflows the terminal
+const MIN_COLUMN: usize = 12;
+
+pub struct ShowBoard<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    width: usize,
+}
+
+impl<'a> ShowBoard<'a> {
+    pub fn new(storage: &'a dyn StoragePort, output: &'a dyn OutputPort) -> Self {
+        Self {
+            storage,
+            output,
+            width: DEFAULT_WIDTH,
+        }
+    }
+
+    /// Fit the board to this many columns of text
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Show todo, doing, blocked and done columns, limited to the yaks under `scope` when given
+    pub fn execute(&self, scope: Option<&str>) -> Result<()> {
+        let mut yaks = self.storage.list_yaks()?;
+        if let Some(scope) = scope {
+            // Resolve yak name (exact or fuzzy match)
+            let scope = self.storage.find_yak(scope)?;
+            yaks.retain(|yak| yak.name == scope || graph::is_descendant_of(&yak.name, &scope));
+        }
+        if yaks.is_empty() {
+            self.output.note("You have no yaks. Are you done?");
+            return Ok(());
+        }
+
+        let columns: Vec<(String, Vec<String>)> = YakState::ALL
+            .iter()
+            .map(|state| {
+                let cards: Vec<String> = yaks
+                    .iter()
+                    .filter(|yak| yak.current_state() == *state)
+                    .map(|yak| yak.title.clone().unwrap_or_else(|| yak.name.clone()))
+                    .collect();
+                let heading = format!("{} ({})", state.to_string().to_uppercase(), cards.len());
+                (heading, cards)
+            })
+            .collect();
+
+        for line in render_columns(&columns, self.width) {
+            self.output.info(&line);
+        }
+
+        Ok(())
+    }
+}
+
+/// Lay out headed columns side by side in `width` characters, truncating long cards
+fn render_columns(columns: &[(String, Vec<String>)], width: usize) -> Vec<String> {
+    let gaps = GAP * columns.len().saturating_sub(1);
+    let column_width = (width.saturating_sub(gaps) / columns.len().max(1)).max(MIN_COLUMN);
+    let rows = columns
+        .iter()
+        .map(|(_, cards)| cards.len())
+        .max()
+        .unwrap_or(0);
+
+    let line = |cells: Vec<String>| {
+        cells
+            .iter()
+            .map(|cell| format!("{:<column_width$}", fit(cell, column_width)))
+            .collect::<Vec<_>>()
+            .join(&" ".repeat(GAP))
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![
+        line(columns.iter().map(|(heading, _)| heading.clone()).collect()),
+        line(columns.iter().map(|_| "-".repeat(column_width)).collect()),
+    ];
+    for row in 0..rows {
+        lines.push(line(
+            columns
+                .iter()
+                .map(|(_, cards)| cards.get(row).cloned().unwrap_or_default())
+                .collect(),
+        ));
+    }
+    lines
+}
+
+/// `text` cut to `width` characters, ending in `…` when shortened
+fn fit(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(width.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, _message: &str) {}
+
+        fn error(&self, _message: &str) {}
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    fn yaks() -> Vec<Yak> {
+        vec![
+            Yak::new("api".to_string()),
+            Yak::new("api/auth".to_string()).with_state(YakState::Doing),
+            Yak::new("api/rate limits".to_string()).with_state(YakState::Blocked),
+            Yak::new("api/docs".to_string()).mark_done(),
+            Yak::new("release".to_string()).with_state(YakState::Doing),
+        ]
+    }
+
+    #[test]
+    fn test_board_groups_yaks_into_state_columns() {
+        let storage = InMemoryStorage::with_yaks(yaks());
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+
+        ShowBoard::new(&storage, &output)
+            .with_width(60)
+            .execute(Some("api"))
+            .unwrap();
+
+        assert_eq!(
+            *output.messages.borrow(),
+            vec![
+                "TODO (1)       DOING (1)      BLOCKED (1)    DONE (1)",
+                "------------   ------------   ------------   ------------",
+                "api            api/auth       api/rate li…   api/docs",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_board_columns_never_get_too_narrow() {
+        let columns = vec![
+            (
+                "TODO (2)".to_string(),
+                vec!["a".to_string(), "b".to_string()],
+            ),
+            ("DONE (0)".to_string(), Vec::new()),
+        ];
+
+        let lines = render_columns(&columns, 10);
+
+        assert_eq!(lines[0], "TODO (2)       DONE (0)");
+        assert_eq!(lines[3], "b");
+        assert_eq!(fit("abcdef", 4), "abc…");
+    }
+}
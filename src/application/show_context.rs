@@ -1,19 +1,50 @@
 // ShowContext use case - displays yak context to stdout
 
-use crate::ports::{OutputPort, StoragePort};
+use super::output_format::{json_array, json_object, json_string};
+use super::read_tracking::ReadTracker;
+use super::OutputFormat;
+use crate::ports::{LocalStatePort, OutputPort, StoragePort};
 use anyhow::Result;
 
 pub struct ShowContext<'a> {
     storage: &'a dyn StoragePort,
     output: &'a dyn OutputPort,
+    read_tracker: Option<ReadTracker<'a>>,
+    format: OutputFormat,
 }
 
 impl<'a> ShowContext<'a> {
     pub fn new(storage: &'a dyn StoragePort, output: &'a dyn OutputPort) -> Self {
-        Self { storage, output }
+        Self {
+            storage,
+            output,
+            read_tracker: None,
+            format: OutputFormat::Markdown,
+        }
+    }
+
+    /// Record the displayed context as read, clearing its "updated" badge
+    pub fn with_read_tracking(mut self, state: &'a dyn LocalStatePort) -> Self {
+        self.read_tracker = Some(ReadTracker::new(state));
+        self
+    }
+
+    /// Render as markdown (name, context, links), plain (the context alone) or JSON
+    pub fn with_format(mut self, format: OutputFormat) -> Self {
+        self.format = format;
+        self
     }
 
     pub fn execute(&self, name: &str) -> Result<()> {
+        self.format.ensure(
+            "context --show",
+            &[
+                OutputFormat::Markdown,
+                OutputFormat::Plain,
+                OutputFormat::Json,
+            ],
+        )?;
+
         // Resolve yak name (exact or fuzzy match)
         let resolved_name = self.storage.find_yak(name)?;
 
@@ -23,102 +54,106 @@ impl<'a> ShowContext<'a> {
             .read_context(&resolved_name)
             .unwrap_or_default();
 
+        match self.format {
+            OutputFormat::Plain => {
+                if !context.is_empty() {
+                    self.output.info(&context);
+                }
+            }
+            OutputFormat::Json => {
+                let links: Vec<String> = self
+                    .storage
+                    .get_yak(&resolved_name)?
+                    .links
+                    .iter()
+                    .map(|link| json_string(link))
+                    .collect();
+                self.output.info(&json_object(
+                    &[
+                        ("name", json_string(&resolved_name)),
+                        ("context", json_string(&context)),
+                        ("links", json_array(&links, 1)),
+                    ],
+                    0,
+                ));
+            }
+            _ => self.display(&resolved_name, &context)?,
+        }
+
+        if let Some(tracker) = &self.read_tracker {
+            tracker.mark_seen(&resolved_name, &context)?;
+        }
+
+        Ok(())
+    }
+
+    /// The name as a header, then the context and attached links
+    fn display(&self, resolved_name: &str, context: &str) -> Result<()> {
         // Display the header (yak name)
-        self.output.info(&resolved_name);
+        self.output.info(resolved_name);
 
         // Display a blank line if there's content
         if !context.is_empty() {
             self.output.info("");
-            // Display the context
-            self.output.info(&context);
+            // Display the context, with code in fenced blocks highlighted
+            self.output.info(&self.highlight_fences(context));
+        }
+
+        // Display attached links, if any
+        let links = self.storage.get_yak(resolved_name)?.links;
+        if !links.is_empty() {
+            self.output.info("");
+            self.output.info("Links:");
+            for link in &links {
+                self.output.info(&format!("- {link}"));
+            }
         }
 
         Ok(())
     }
+
+    /// `context` with each fenced block's code passed through the output's
+    /// highlighter for the block's language; fences and other text are kept
+    /// as they are, and so is a block that's never closed
+    fn highlight_fences(&self, context: &str) -> String {
+        let mut highlighted = String::with_capacity(context.len());
+        let mut lines = context.split_inclusive('\n');
+        while let Some(line) = lines.next() {
+            highlighted.push_str(line);
+            let opening = line.trim_start();
+            let Some(fence) = ["```", "~~~"].into_iter().find(|f| opening.starts_with(f)) else {
+                continue;
+            };
+            let language = opening[fence.len()..].split_whitespace().next();
+
+            let mut code = String::new();
+            let mut closing = None;
+            for line in lines.by_ref() {
+                if line.trim() == fence {
+                    closing = Some(line);
+                    break;
+                }
+                code.push_str(line);
+            }
+            match (language, closing) {
+                (Some(language), Some(_)) => {
+                    highlighted.push_str(&self.output.highlight(&code, language))
+                }
+                _ => highlighted.push_str(&code),
+            }
+            highlighted.push_str(closing.unwrap_or_default());
+        }
+        highlighted
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::adapters::storage::InMemoryStorage;
     use crate::domain::Yak;
     use std::cell::RefCell;
 
-    struct MockStorage {
-        yaks: RefCell<Vec<Yak>>,
-        contexts: RefCell<std::collections::HashMap<String, String>>,
-    }
-
-    impl MockStorage {
-        fn new() -> Self {
-            Self {
-                yaks: RefCell::new(Vec::new()),
-                contexts: RefCell::new(std::collections::HashMap::new()),
-            }
-        }
-
-        fn add_yak(&self, name: &str) {
-            self.yaks.borrow_mut().push(Yak {
-                name: name.to_string(),
-                done: false,
-                context: None,
-            });
-        }
-
-        fn set_context(&self, name: &str, context: &str) {
-            self.contexts
-                .borrow_mut()
-                .insert(name.to_string(), context.to_string());
-        }
-
-        fn get_context(&self, name: &str) -> Option<String> {
-            self.contexts.borrow().get(name).cloned()
-        }
-    }
-
-    impl StoragePort for MockStorage {
-        fn create_yak(&self, _name: &str) -> Result<()> {
-            unimplemented!()
-        }
-
-        fn get_yak(&self, name: &str) -> Result<Yak> {
-            self.yaks
-                .borrow()
-                .iter()
-                .find(|y| y.name == name)
-                .cloned()
-                .ok_or_else(|| anyhow::anyhow!("yak '{}' not found", name))
-        }
-
-        fn list_yaks(&self) -> Result<Vec<Yak>> {
-            unimplemented!()
-        }
-
-        fn mark_done(&self, _name: &str, _done: bool) -> Result<()> {
-            unimplemented!()
-        }
-
-        fn delete_yak(&self, _name: &str) -> Result<()> {
-            unimplemented!()
-        }
-
-        fn rename_yak(&self, _from: &str, _to: &str) -> Result<()> {
-            unimplemented!()
-        }
-
-        fn read_context(&self, name: &str) -> Result<String> {
-            Ok(self.get_context(name).unwrap_or_default())
-        }
-
-        fn write_context(&self, _name: &str, _text: &str) -> Result<()> {
-            unimplemented!()
-        }
-
-        fn find_yak(&self, name: &str) -> Result<String> {
-            self.get_yak(name)?;
-            Ok(name.to_string())
-        }
-    }
-
     struct MockOutput {
         messages: RefCell<Vec<String>>,
     }
@@ -149,11 +184,40 @@ mod tests {
         fn info(&self, message: &str) {
             self.messages.borrow_mut().push(message.to_string());
         }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn highlight(&self, code: &str, language: &str) -> String {
+            format!("<{language}>{code}</>")
+        }
+    }
+
+    #[test]
+    fn test_show_context_highlights_fenced_code() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        storage.create_yak("test-yak").unwrap();
+        storage
+            .write_context(
+                "test-yak",
+                "Run:\n```sh\ncargo test\n```\n~~~\nno language\n~~~\n```rust\nnever closed",
+            )
+            .unwrap();
+        let use_case = ShowContext::new(&storage, &output);
+
+        use_case.execute("test-yak").unwrap();
+
+        assert_eq!(
+            output.get_messages()[2],
+            "Run:\n```sh\n<sh>cargo test\n</>```\n~~~\nno language\n~~~\n```rust\nnever closed"
+        );
     }
 
     #[test]
     fn test_show_context_fails_for_nonexistent_yak() {
-        let storage = MockStorage::new();
+        let storage = InMemoryStorage::new();
         let output = MockOutput::new();
         let use_case = ShowContext::new(&storage, &output);
 
@@ -165,9 +229,9 @@ mod tests {
 
     #[test]
     fn test_show_context_displays_yak_name() {
-        let storage = MockStorage::new();
+        let storage = InMemoryStorage::new();
         let output = MockOutput::new();
-        storage.add_yak("test-yak");
+        storage.create_yak("test-yak").unwrap();
         let use_case = ShowContext::new(&storage, &output);
 
         use_case.execute("test-yak").unwrap();
@@ -178,9 +242,9 @@ mod tests {
 
     #[test]
     fn test_show_context_displays_empty_context() {
-        let storage = MockStorage::new();
+        let storage = InMemoryStorage::new();
         let output = MockOutput::new();
-        storage.add_yak("test-yak");
+        storage.create_yak("test-yak").unwrap();
         let use_case = ShowContext::new(&storage, &output);
 
         use_case.execute("test-yak").unwrap();
@@ -193,10 +257,12 @@ mod tests {
 
     #[test]
     fn test_show_context_displays_context_with_blank_line() {
-        let storage = MockStorage::new();
+        let storage = InMemoryStorage::new();
         let output = MockOutput::new();
-        storage.add_yak("test-yak");
-        storage.set_context("test-yak", "This is some context");
+        storage.create_yak("test-yak").unwrap();
+        storage
+            .write_context("test-yak", "This is some context")
+            .unwrap();
         let use_case = ShowContext::new(&storage, &output);
 
         use_case.execute("test-yak").unwrap();
@@ -210,10 +276,12 @@ mod tests {
 
     #[test]
     fn test_show_context_displays_multiline_context() {
-        let storage = MockStorage::new();
+        let storage = InMemoryStorage::new();
         let output = MockOutput::new();
-        storage.add_yak("test-yak");
-        storage.set_context("test-yak", "Line 1\nLine 2\nLine 3");
+        storage.create_yak("test-yak").unwrap();
+        storage
+            .write_context("test-yak", "Line 1\nLine 2\nLine 3")
+            .unwrap();
         let use_case = ShowContext::new(&storage, &output);
 
         use_case.execute("test-yak").unwrap();
@@ -224,4 +292,24 @@ mod tests {
         assert_eq!(messages[1], "");
         assert_eq!(messages[2], "Line 1\nLine 2\nLine 3");
     }
+
+    #[test]
+    fn test_show_context_displays_links() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        storage.add_yak(
+            Yak::new("test-yak".to_string())
+                .with_links(vec!["https://example.com/pr/1".to_string()]),
+        );
+        let use_case = ShowContext::new(&storage, &output);
+
+        use_case.execute("test-yak").unwrap();
+
+        let messages = output.get_messages();
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages[0], "test-yak");
+        assert_eq!(messages[1], "");
+        assert_eq!(messages[2], "Links:");
+        assert_eq!(messages[3], "- https://example.com/pr/1");
+    }
 }
@@ -0,0 +1,165 @@
+// ShowHistory use case - the refs/notes/yaks operation log as an audit trail
+
+use super::TimeStyle;
+use crate::ports::{HistoryPort, OutputPort, StoragePort};
+use anyhow::Result;
+
+pub struct ShowHistory<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    history: &'a dyn HistoryPort,
+    time_style: TimeStyle,
+}
+
+impl<'a> ShowHistory<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        history: &'a dyn HistoryPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            history,
+            time_style: TimeStyle::default(),
+        }
+    }
+
+    /// Show times relative to now ("2h ago") or as local date and time
+    pub fn with_time_style(mut self, time_style: TimeStyle) -> Self {
+        self.time_style = time_style;
+        self
+    }
+
+    /// Print operations newest first, optionally only those touching one yak
+    pub fn execute(&self, name: Option<&str>, limit: Option<usize>) -> Result<()> {
+        // Removed yaks still have history, so fall back to the name as given
+        let name = name.map(|name| {
+            self.storage
+                .find_yak(name)
+                .unwrap_or_else(|_| name.to_string())
+        });
+
+        let revisions = self.history.revisions(name.as_deref())?;
+        if revisions.is_empty() {
+            match &name {
+                Some(name) => anyhow::bail!("no history for yak '{name}'"),
+                None => self.output.note("No history yet"),
+            }
+            return Ok(());
+        }
+
+        for revision in revisions.iter().take(limit.unwrap_or(usize::MAX)) {
+            self.output.info(&format!(
+                "{} {:<16} {:<12} {}",
+                &revision.id[..revision.id.len().min(7)],
+                self.time_style.render(revision.time),
+                revision.author,
+                revision.command
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use crate::ports::{FileChange, FileVersion, Revision, YakSnapshot};
+    use chrono::{TimeZone, Utc};
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+        fn error(&self, _message: &str) {}
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockHistory {
+        requested: RefCell<Vec<Option<String>>>,
+    }
+
+    impl HistoryPort for MockHistory {
+        fn revisions(&self, name: Option<&str>) -> Result<Vec<Revision>> {
+            self.requested.borrow_mut().push(name.map(String::from));
+            if name == Some("gone") {
+                return Ok(Vec::new());
+            }
+            let time = Utc.with_ymd_and_hms(2026, 10, 17, 9, 30, 0).unwrap();
+            Ok(["done app", "add app"]
+                .iter()
+                .map(|command| Revision {
+                    id: "0123456789abcdef".to_string(),
+                    command: command.to_string(),
+                    author: "ann".to_string(),
+                    time,
+                })
+                .collect())
+        }
+
+        fn changed_files(&self, _revision: &str, _name: &str) -> Result<Vec<FileChange>> {
+            unimplemented!()
+        }
+
+        fn last_snapshot(&self, _name: &str) -> Result<Option<(Revision, Vec<YakSnapshot>)>> {
+            unimplemented!()
+        }
+
+        fn file_versions(&self, _name: &str, _file: &str) -> Result<Vec<FileVersion>> {
+            unimplemented!()
+        }
+
+        fn remote_file_version(&self, _name: &str, _file: &str) -> Result<Option<FileVersion>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_history_lists_revisions_for_resolved_yak() {
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let history = MockHistory {
+            requested: RefCell::new(Vec::new()),
+        };
+        let storage = InMemoryStorage::with_yaks([Yak::new("app".to_string())]);
+        let use_case = ShowHistory::new(&storage, &output, &history);
+
+        use_case.execute(Some("ap"), Some(1)).unwrap();
+
+        assert_eq!(*history.requested.borrow(), vec![Some("app".to_string())]);
+        let messages = output.messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].starts_with("0123456 2026-10-1"));
+        assert!(messages[0].ends_with(" ann          done app"));
+    }
+
+    #[test]
+    fn test_history_of_unknown_yak_is_an_error() {
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let history = MockHistory {
+            requested: RefCell::new(Vec::new()),
+        };
+        let storage = InMemoryStorage::with_yaks([Yak::new("app".to_string())]);
+        let use_case = ShowHistory::new(&storage, &output, &history);
+
+        assert!(use_case.execute(Some("gone"), None).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+// ShowPrompt use case - a compact yak summary for shell prompts
+
+use super::focus_yak::focused;
+use crate::ports::{LocalStatePort, OutputPort, StoragePort};
+use anyhow::Result;
+
+pub struct ShowPrompt<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    state: Option<&'a dyn LocalStatePort>,
+}
+
+impl<'a> ShowPrompt<'a> {
+    pub fn new(storage: &'a dyn StoragePort, output: &'a dyn OutputPort) -> Self {
+        Self {
+            storage,
+            output,
+            state: None,
+        }
+    }
+
+    /// Also show the focused yak
+    pub fn with_state(mut self, state: &'a dyn LocalStatePort) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// Print `🦬 3 open (api/auth)`, or nothing when there are no yaks
+    /// With `porcelain`, print the raw fields instead: open, done and focus, tab-separated.
+    pub fn execute(&self, porcelain: bool) -> Result<()> {
+        let yaks = self.storage.list_yaks()?;
+        let done = yaks.iter().filter(|yak| yak.done).count();
+        let open = yaks.len() - done;
+        let focus = match self.state {
+            Some(state) => focused(state)?,
+            None => None,
+        };
+
+        if porcelain {
+            self.output.info(&format!(
+                "{open}\t{done}\t{}",
+                focus.as_deref().unwrap_or_default()
+            ));
+        } else if !yaks.is_empty() {
+            let focus = focus.map(|name| format!(" ({name})")).unwrap_or_default();
+            self.output.info(&format!("🦬 {open} open{focus}"));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, _message: &str) {}
+
+        fn error(&self, _message: &str) {}
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockState;
+
+    impl LocalStatePort for MockState {
+        fn read(&self, _key: &str) -> Result<Option<String>> {
+            Ok(Some("api/auth\n".to_string()))
+        }
+
+        fn write(&self, _key: &str, _value: Option<&str>) -> Result<()> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_prompt_shows_open_count_and_focus() {
+        // `api` is open too, holding `api/auth`
+        let storage = InMemoryStorage::with_yaks([
+            Yak::new("api/auth".to_string()),
+            Yak::new("old".to_string()).mark_done(),
+        ]);
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let use_case = ShowPrompt::new(&storage, &output).with_state(&MockState);
+
+        use_case.execute(false).unwrap();
+        use_case.execute(true).unwrap();
+
+        assert_eq!(
+            *output.messages.borrow(),
+            vec!["🦬 2 open (api/auth)", "2\t1\tapi/auth"]
+        );
+    }
+
+    #[test]
+    fn test_prompt_is_empty_without_yaks() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+
+        ShowPrompt::new(&storage, &output).execute(false).unwrap();
+
+        assert!(output.messages.borrow().is_empty());
+    }
+}
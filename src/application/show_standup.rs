@@ -0,0 +1,165 @@
+// ShowStandup use case - yesterday / today / blockers, for daily standups
+
+use super::history::History;
+use crate::domain::{Yak, YakState};
+use crate::ports::{LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+use chrono::{Datelike, Duration, Local, NaiveDate, Weekday};
+
+pub struct ShowStandup<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+}
+
+impl<'a> ShowStandup<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            log,
+        }
+    }
+
+    /// Yaks completed since the last working day, the doing yaks, and the blocked ones
+    pub fn execute(&self) -> Result<()> {
+        let since = last_working_day(Local::now().date_naive());
+        let yaks = self.storage.list_yaks()?;
+        let history = History::replay(&self.log.entries()?, &yaks);
+
+        let mut completed: Vec<(&Yak, _)> = yaks
+            .iter()
+            .filter_map(|yak| Some((yak, history.completed_at(yak)?)))
+            .filter(|(_, time)| time.with_timezone(&Local).date_naive() >= since)
+            .collect();
+        completed.sort_by_key(|(_, time)| *time);
+        let in_state = |state: YakState| -> Vec<&Yak> {
+            yaks.iter()
+                .filter(|yak| yak.current_state() == state)
+                .collect()
+        };
+
+        self.section(
+            &format!("Yesterday (since {}):", since.format("%a %Y-%m-%d")),
+            &completed
+                .into_iter()
+                .map(|(yak, _)| yak)
+                .collect::<Vec<_>>(),
+        );
+        self.section("Today:", &in_state(YakState::Doing));
+        self.section("Blockers:", &in_state(YakState::Blocked));
+
+        Ok(())
+    }
+
+    fn section(&self, heading: &str, yaks: &[&Yak]) {
+        self.output.info(heading);
+        if yaks.is_empty() {
+            self.output.info("  - nothing");
+        }
+        for yak in yaks {
+            let label = yak.title.as_deref().unwrap_or(&yak.name);
+            self.output.info(&format!("  - {label}"));
+        }
+    }
+}
+
+/// The working day before `today`: Friday on Mondays and weekends, yesterday otherwise
+fn last_working_day(today: NaiveDate) -> NaiveDate {
+    let days_back = match today.weekday() {
+        Weekday::Mon => 3,
+        Weekday::Sun => 2,
+        _ => 1,
+    };
+    today - Duration::days(days_back)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::ports::LogEntry;
+    use chrono::Utc;
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, _message: &str) {}
+
+        fn error(&self, _message: &str) {}
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockLog {
+        entries: Vec<LogEntry>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, _command: &str) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            Ok(self.entries.clone())
+        }
+    }
+
+    #[test]
+    fn test_last_working_day_skips_weekends() {
+        let day = |d| NaiveDate::from_ymd_opt(2026, 10, d).unwrap();
+
+        assert_eq!(last_working_day(day(19)), day(16)); // Monday -> Friday
+        assert_eq!(last_working_day(day(18)), day(16)); // Sunday -> Friday
+        assert_eq!(last_working_day(day(17)), day(16)); // Saturday -> Friday
+        assert_eq!(last_working_day(day(21)), day(20)); // Wednesday -> Tuesday
+    }
+
+    #[test]
+    fn test_standup_lists_completed_doing_and_blocked_yaks() {
+        let now = Utc::now();
+        let storage = InMemoryStorage::with_yaks([
+            Yak::new("old".to_string()).mark_done(),
+            Yak::new("docs".to_string()).mark_done(),
+            Yak::new("api".to_string()).with_state(YakState::Doing),
+            Yak::new("ci".to_string()),
+        ]);
+        let log = MockLog {
+            entries: vec![
+                LogEntry {
+                    command: "done old".to_string(),
+                    time: now - Duration::days(30),
+                },
+                LogEntry {
+                    command: "done docs".to_string(),
+                    time: now,
+                },
+            ],
+        };
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+
+        ShowStandup::new(&storage, &output, &log).execute().unwrap();
+
+        let messages = output.messages.borrow();
+        assert!(messages[0].starts_with("Yesterday (since "));
+        assert_eq!(
+            messages[1..],
+            ["  - docs", "Today:", "  - api", "Blockers:", "  - nothing"]
+        );
+    }
+}
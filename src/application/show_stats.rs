@@ -0,0 +1,339 @@
+// ShowStats use case - completion metrics derived from yak state and the command log
+
+use super::history::History;
+use super::output_format::json_object;
+use super::OutputFormat;
+use crate::ports::{LogPort, OutputPort, PrunedPort, StoragePort};
+use anyhow::Result;
+use chrono::{Duration, Utc};
+
+pub struct ShowStats<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+    pruned: Option<&'a dyn PrunedPort>,
+    format: OutputFormat,
+}
+
+impl<'a> ShowStats<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            log,
+            pruned: None,
+            format: OutputFormat::Markdown,
+        }
+    }
+
+    /// Count the yaks `yx prune` recorded, which are no longer in storage
+    pub fn with_pruned(mut self, pruned: &'a dyn PrunedPort) -> Self {
+        self.pruned = Some(pruned);
+        self
+    }
+
+    /// Render as markdown (aligned labels), plain (`key<TAB>value` lines) or JSON
+    pub fn with_format(mut self, format: OutputFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        self.format.ensure(
+            "stats",
+            &[
+                OutputFormat::Markdown,
+                OutputFormat::Plain,
+                OutputFormat::Json,
+            ],
+        )?;
+
+        let yaks = self.storage.list_yaks()?;
+        let total = yaks.len();
+        let done = yaks.iter().filter(|yak| yak.done).count();
+        let open = total - done;
+        let rate = (done * 100).checked_div(total);
+
+        let now = Utc::now();
+        let history = History::replay(&self.log.entries()?, &yaks);
+
+        let pruned = match self.pruned {
+            Some(record) => Some(record.pruned()?),
+            None => None,
+        };
+
+        let week_ago = now - Duration::days(7);
+        let pruned_this_week = pruned
+            .iter()
+            .flatten()
+            .filter(|yak| yak.completed.unwrap_or(yak.pruned) >= week_ago);
+        let this_week = pruned_this_week.count()
+            + yaks
+                .iter()
+                .filter(|yak| yak.done)
+                .filter(|yak| {
+                    history
+                        .completed
+                        .get(&yak.name)
+                        .is_some_and(|t| *t >= week_ago)
+                })
+                .count();
+
+        let ages: Vec<Duration> = yaks
+            .iter()
+            .filter(|yak| !yak.done)
+            .filter_map(|yak| history.created.get(&yak.name))
+            .map(|created| now - *created)
+            .collect();
+        let average_days = (!ages.is_empty()).then(|| {
+            let total_secs: i64 = ages.iter().map(|age| age.num_seconds()).sum();
+            total_secs as f64 / ages.len() as f64 / 86_400.0
+        });
+
+        match self.format {
+            OutputFormat::Markdown => {
+                self.output.info(&format!(
+                    "Total:       {total} yaks ({open} open, {done} done)"
+                ));
+                let rate = rate.map_or("n/a".to_string(), |percent| format!("{percent}%"));
+                self.output.info(&format!("Completion:  {rate}"));
+                self.output
+                    .info(&format!("This week:   {this_week} completed"));
+                let average_age =
+                    average_days.map_or("n/a".to_string(), |days| format!("{days:.1} days"));
+                self.output
+                    .info(&format!("Average age: {average_age} (open yaks)"));
+                if let Some(pruned) = &pruned {
+                    self.output
+                        .info(&format!("Pruned:      {} yaks", pruned.len()));
+                }
+            }
+            OutputFormat::Plain => {
+                let mut lines = vec![
+                    format!("total\t{total}"),
+                    format!("open\t{open}"),
+                    format!("done\t{done}"),
+                ];
+                if let Some(percent) = rate {
+                    lines.push(format!("completion\t{percent}"));
+                }
+                lines.push(format!("this-week\t{this_week}"));
+                if let Some(days) = average_days {
+                    lines.push(format!("average-age-days\t{days:.1}"));
+                }
+                if let Some(pruned) = &pruned {
+                    lines.push(format!("pruned\t{}", pruned.len()));
+                }
+                for line in lines {
+                    self.output.info(&line);
+                }
+            }
+            _ => {
+                let optional = |value: Option<String>| value.unwrap_or_else(|| "null".to_string());
+                let mut fields = vec![
+                    ("total", total.to_string()),
+                    ("open", open.to_string()),
+                    ("done", done.to_string()),
+                    ("completion", optional(rate.map(|p| p.to_string()))),
+                    ("this_week", this_week.to_string()),
+                    (
+                        "average_age_days",
+                        optional(average_days.map(|days| format!("{days:.1}"))),
+                    ),
+                ];
+                if let Some(pruned) = &pruned {
+                    fields.push(("pruned", pruned.len().to_string()));
+                }
+                self.output.info(&json_object(&fields, 0));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use crate::ports::{LogEntry, PrunedYak};
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl MockOutput {
+        fn new() -> Self {
+            Self {
+                messages: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn get_messages(&self) -> Vec<String> {
+            self.messages.borrow().clone()
+        }
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockLog {
+        entries: Vec<LogEntry>,
+    }
+
+    impl MockLog {
+        /// Entries given as (command, days ago)
+        fn new(entries: &[(&str, i64)]) -> Self {
+            let now = Utc::now();
+            Self {
+                entries: entries
+                    .iter()
+                    .map(|(command, days_ago)| LogEntry {
+                        command: command.to_string(),
+                        time: now - Duration::days(*days_ago),
+                    })
+                    .collect(),
+            }
+        }
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, _command: &str) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            Ok(self.entries.clone())
+        }
+    }
+
+    #[test]
+    fn test_stats_reports_metrics_from_history() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("old".to_string()).mark_done());
+        storage.add_yak(Yak::new("fresh".to_string()).mark_done());
+        storage.add_yak(Yak::new("open one".to_string()));
+        storage.add_yak(Yak::new("renamed".to_string()));
+        let log = MockLog::new(&[
+            ("add old", 30),
+            ("add fresh", 10),
+            ("add open one", 4),
+            ("add draft", 2),
+            ("done old", 20),
+            ("done fresh", 2),
+            ("move draft renamed", 1),
+            ("Merge yaks", 1),
+        ]);
+        let output = MockOutput::new();
+        let use_case = ShowStats::new(&storage, &output, &log);
+
+        use_case.execute().unwrap();
+
+        assert_eq!(
+            output.get_messages(),
+            vec![
+                "Total:       4 yaks (2 open, 2 done)",
+                "Completion:  50%",
+                "This week:   1 completed",
+                "Average age: 3.0 days (open yaks)",
+            ]
+        );
+    }
+
+    struct MockPruned {
+        yaks: Vec<PrunedYak>,
+    }
+
+    impl PrunedPort for MockPruned {
+        fn record_pruned(&self, _yaks: &[PrunedYak]) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn pruned(&self) -> Result<Vec<PrunedYak>> {
+            Ok(self.yaks.clone())
+        }
+    }
+
+    #[test]
+    fn test_stats_counts_pruned_yaks() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("open one".to_string()));
+        let log = MockLog::new(&[("add open one", 1)]);
+        let pruned = |name: &str, days: i64| PrunedYak {
+            name: name.to_string(),
+            title: None,
+            completed: Some(Utc::now() - Duration::days(days)),
+            pruned: Utc::now(),
+            context: String::new(),
+        };
+        let record = MockPruned {
+            yaks: vec![pruned("old", 20), pruned("recent", 2)],
+        };
+        let output = MockOutput::new();
+
+        ShowStats::new(&storage, &output, &log)
+            .with_pruned(&record)
+            .execute()
+            .unwrap();
+
+        let messages = output.get_messages();
+        assert_eq!(messages[2], "This week:   1 completed");
+        assert_eq!(messages[4], "Pruned:      2 yaks");
+    }
+
+    #[test]
+    fn test_stats_follows_undo_and_recursive_done() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("app".to_string()).mark_done());
+        storage.add_yak(Yak::new("app/api".to_string()).mark_done());
+        storage.add_yak(Yak::new("docs".to_string()));
+        let log = MockLog::new(&[
+            ("done docs", 3),
+            ("done --undo docs", 2),
+            ("done --recursive app", 1),
+        ]);
+        let output = MockOutput::new();
+        let use_case = ShowStats::new(&storage, &output, &log);
+
+        use_case.execute().unwrap();
+
+        let messages = output.get_messages();
+        assert_eq!(messages[2], "This week:   2 completed");
+        assert_eq!(messages[3], "Average age: n/a (open yaks)");
+    }
+
+    #[test]
+    fn test_stats_with_no_yaks() {
+        let storage = InMemoryStorage::new();
+        let log = MockLog::new(&[]);
+        let output = MockOutput::new();
+        let use_case = ShowStats::new(&storage, &output, &log);
+
+        use_case.execute().unwrap();
+
+        assert_eq!(output.get_messages()[1], "Completion:  n/a");
+    }
+}
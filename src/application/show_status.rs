@@ -0,0 +1,459 @@
+// ShowStatus use case - one-screen overview of yak counts and sync state
+
+use super::focus_yak::focused;
+use super::output_format::{json_object, json_optional};
+use super::porcelain;
+use super::sync_yaks::LAST_SYNC_KEY;
+use super::{OutputFormat, TimeStyle};
+use crate::domain::{Yak, YakState};
+use crate::ports::{LocalStatePort, OutputPort, StoragePort, SyncPort, SyncStatus};
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset, Utc};
+
+/// How many yaks are in each state; `open` is everything not done
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(super) struct StateCounts {
+    pub todo: usize,
+    pub doing: usize,
+    pub blocked: usize,
+    pub done: usize,
+}
+
+impl StateCounts {
+    fn of(yaks: &[Yak]) -> Self {
+        let mut counts = Self::default();
+        for yak in yaks {
+            match yak.current_state() {
+                YakState::Todo => counts.todo += 1,
+                YakState::Doing => counts.doing += 1,
+                YakState::Blocked => counts.blocked += 1,
+                YakState::Done => counts.done += 1,
+            }
+        }
+        counts
+    }
+
+    pub fn open(&self) -> usize {
+        self.todo + self.doing + self.blocked
+    }
+}
+
+pub struct ShowStatus<'a> {
+    storage: &'a dyn StoragePort,
+    sync: &'a dyn SyncPort,
+    output: &'a dyn OutputPort,
+    state: Option<&'a dyn LocalStatePort>,
+    format: OutputFormat,
+    time_style: TimeStyle,
+}
+
+impl<'a> ShowStatus<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        sync: &'a dyn SyncPort,
+        output: &'a dyn OutputPort,
+    ) -> Self {
+        Self {
+            storage,
+            sync,
+            output,
+            state: None,
+            format: OutputFormat::Markdown,
+            time_style: TimeStyle::default(),
+        }
+    }
+
+    /// Show times relative to now ("2h ago") or as local date and time
+    pub fn with_time_style(mut self, time_style: TimeStyle) -> Self {
+        self.time_style = time_style;
+        self
+    }
+
+    /// Also show when the yaks were last synced, and the focused yak
+    pub fn with_state(mut self, state: &'a dyn LocalStatePort) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// Render as markdown (aligned labels), plain (`key<TAB>value` lines), JSON or
+    /// the stable porcelain lines
+    pub fn with_format(mut self, format: OutputFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn execute(&self) -> Result<()> {
+        self.format.ensure(
+            "status",
+            &[
+                OutputFormat::Markdown,
+                OutputFormat::Plain,
+                OutputFormat::Json,
+                OutputFormat::Porcelain,
+            ],
+        )?;
+
+        let counts = StateCounts::of(&self.storage.list_yaks()?);
+        let status = self.sync.status()?;
+        let (synced, focus) = match self.state {
+            Some(state) => (
+                Some(
+                    state
+                        .read(LAST_SYNC_KEY)?
+                        .and_then(|text| DateTime::parse_from_rfc3339(text.trim()).ok()),
+                ),
+                focused(state)?,
+            ),
+            None => (None, None),
+        };
+
+        match self.format {
+            OutputFormat::Markdown => {
+                self.display(counts, &status, synced, focus.as_deref());
+            }
+            OutputFormat::Porcelain => {
+                let synced = synced.flatten().map(|time| time.with_timezone(&Utc));
+                for line in porcelain::status_lines(counts, &status, synced, focus.as_deref()) {
+                    self.output.info(&line);
+                }
+            }
+            OutputFormat::Plain => {
+                let mut lines = vec![
+                    format!("open\t{}", counts.open()),
+                    format!("done\t{}", counts.done),
+                    format!("todo\t{}", counts.todo),
+                    format!("doing\t{}", counts.doing),
+                    format!("blocked\t{}", counts.blocked),
+                    format!("local-changes\t{}", status.has_local_changes),
+                ];
+                if let Some((ahead, behind)) = status.remote {
+                    lines.push(format!("ahead\t{ahead}"));
+                    lines.push(format!("behind\t{behind}"));
+                }
+                if let Some(Some(time)) = synced {
+                    lines.push(format!("synced\t{}", time.to_rfc3339()));
+                }
+                if let Some(focus) = focus {
+                    lines.push(format!("focus\t{focus}"));
+                }
+                for line in lines {
+                    self.output.info(&line);
+                }
+            }
+            _ => {
+                let (ahead, behind) = match status.remote {
+                    Some((ahead, behind)) => (ahead.to_string(), behind.to_string()),
+                    None => ("null".to_string(), "null".to_string()),
+                };
+                self.output.info(&json_object(
+                    &[
+                        ("open", counts.open().to_string()),
+                        ("done", counts.done.to_string()),
+                        ("todo", counts.todo.to_string()),
+                        ("doing", counts.doing.to_string()),
+                        ("blocked", counts.blocked.to_string()),
+                        ("local_changes", status.has_local_changes.to_string()),
+                        ("ahead", ahead),
+                        ("behind", behind),
+                        (
+                            "synced",
+                            json_optional(synced.flatten().map(|t| t.to_rfc3339()).as_deref()),
+                        ),
+                        ("focus", json_optional(focus.as_deref())),
+                    ],
+                    0,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The human-readable overview; `synced` is only shown with local state
+    fn display(
+        &self,
+        counts: StateCounts,
+        status: &SyncStatus,
+        synced: Option<Option<DateTime<FixedOffset>>>,
+        focus: Option<&str>,
+    ) {
+        let mut yaks = vec![format!("{} todo", counts.todo)];
+        if counts.doing > 0 {
+            yaks.push(format!("{} doing", counts.doing));
+        }
+        if counts.blocked > 0 {
+            yaks.push(format!("{} blocked", counts.blocked));
+        }
+        yaks.push(format!("{} done", counts.done));
+        self.output.info(&format!("Yaks:   {}", yaks.join(", ")));
+
+        let local = if status.has_local_changes {
+            "unsynced changes in .yaks"
+        } else {
+            "no unsynced changes"
+        };
+        self.output.info(&format!("Local:  {local}"));
+
+        let remote = match status.remote {
+            None => "no remote yaks".to_string(),
+            Some((0, 0)) => "up to date".to_string(),
+            Some((ahead, 0)) => format!("{ahead} ahead"),
+            Some((0, behind)) => format!("{behind} behind"),
+            Some((ahead, behind)) => format!("{ahead} ahead, {behind} behind"),
+        };
+        self.output.info(&format!("Remote: {remote}"));
+
+        if let Some(synced) = synced {
+            let synced = synced
+                .map(|time| self.time_style.render(time.with_timezone(&Utc)))
+                .unwrap_or_else(|| "never".to_string());
+            self.output.info(&format!("Synced: {synced}"));
+        }
+        if let Some(focus) = focus {
+            self.output.info(&format!("Focus:  {focus}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::ports::SyncProgress;
+    use chrono::Local;
+    use std::cell::RefCell;
+
+    struct MockSync {
+        status: SyncStatus,
+    }
+
+    impl SyncPort for MockSync {
+        fn push(&self) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn pull(&self) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn sync(&self, _progress: &dyn Fn(SyncProgress)) -> Result<Vec<String>> {
+            unimplemented!()
+        }
+
+        fn status(&self) -> Result<SyncStatus> {
+            Ok(self.status.clone())
+        }
+
+        fn preview(&self) -> Result<Vec<(String, crate::ports::IncomingChange)>> {
+            unimplemented!()
+        }
+    }
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl MockOutput {
+        fn new() -> Self {
+            Self {
+                messages: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn get_messages(&self) -> Vec<String> {
+            self.messages.borrow().clone()
+        }
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    #[test]
+    fn test_status_shows_counts_and_sync_state() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("a".to_string()));
+        storage.add_yak(Yak::new("b".to_string()));
+        storage.add_yak(Yak::new("c".to_string()).mark_done());
+        let sync = MockSync {
+            status: SyncStatus {
+                has_local_changes: true,
+                remote: Some((2, 1)),
+            },
+        };
+        let output = MockOutput::new();
+        let use_case = ShowStatus::new(&storage, &sync, &output);
+
+        use_case.execute().unwrap();
+
+        assert_eq!(
+            output.get_messages(),
+            vec![
+                "Yaks:   2 todo, 1 done",
+                "Local:  unsynced changes in .yaks",
+                "Remote: 2 ahead, 1 behind",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_status_in_plain_and_json() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("a".to_string()));
+        storage.add_yak(Yak::new("c".to_string()).mark_done());
+        let sync = MockSync {
+            status: SyncStatus {
+                has_local_changes: false,
+                remote: Some((0, 3)),
+            },
+        };
+        let output = MockOutput::new();
+
+        ShowStatus::new(&storage, &sync, &output)
+            .with_format(OutputFormat::Plain)
+            .execute()
+            .unwrap();
+        ShowStatus::new(&storage, &sync, &output)
+            .with_format(OutputFormat::Json)
+            .execute()
+            .unwrap();
+
+        assert_eq!(
+            output.get_messages(),
+            vec![
+                "open\t1",
+                "done\t1",
+                "todo\t1",
+                "doing\t0",
+                "blocked\t0",
+                "local-changes\tfalse",
+                "ahead\t0",
+                "behind\t3",
+                "{\n  \"open\": 1,\n  \"done\": 1,\n  \"todo\": 1,\n  \"doing\": 0,\n  \"blocked\": 0,\n  \"local_changes\": false,\n  \"ahead\": 0,\n  \"behind\": 3,\n  \"synced\": null,\n  \"focus\": null\n}",
+            ]
+        );
+        assert!(ShowStatus::new(&storage, &sync, &output)
+            .with_format(OutputFormat::Csv)
+            .execute()
+            .is_err());
+    }
+
+    #[test]
+    fn test_status_counts_doing_and_blocked_yaks() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("api".to_string()).with_state(YakState::Doing));
+        storage.add_yak(Yak::new("deploy".to_string()).with_state(YakState::Blocked));
+        storage.add_yak(Yak::new("docs".to_string()).with_state(YakState::Todo));
+        storage.add_yak(Yak::new("ci".to_string()).mark_done());
+        let sync = MockSync {
+            status: SyncStatus {
+                has_local_changes: false,
+                remote: None,
+            },
+        };
+        let output = MockOutput::new();
+
+        ShowStatus::new(&storage, &sync, &output).execute().unwrap();
+        ShowStatus::new(&storage, &sync, &output)
+            .with_format(OutputFormat::Porcelain)
+            .execute()
+            .unwrap();
+
+        let messages = output.get_messages();
+        assert_eq!(messages[0], "Yaks:   1 todo, 1 doing, 1 blocked, 1 done");
+        assert_eq!(messages[3], "open\t3");
+        assert_eq!(&messages[10..], ["todo\t1", "doing\t1", "blocked\t1"]);
+    }
+
+    #[test]
+    fn test_status_without_remote() {
+        let storage = InMemoryStorage::new();
+        let sync = MockSync {
+            status: SyncStatus {
+                has_local_changes: false,
+                remote: None,
+            },
+        };
+        let output = MockOutput::new();
+        let use_case = ShowStatus::new(&storage, &sync, &output);
+
+        use_case.execute().unwrap();
+
+        let messages = output.get_messages();
+        assert_eq!(messages[1], "Local:  no unsynced changes");
+        assert_eq!(messages[2], "Remote: no remote yaks");
+    }
+
+    struct MockState {
+        synced: Option<&'static str>,
+        focus: Option<&'static str>,
+    }
+
+    impl LocalStatePort for MockState {
+        fn read(&self, key: &str) -> Result<Option<String>> {
+            let value = match key {
+                LAST_SYNC_KEY => self.synced,
+                "focus" => self.focus,
+                _ => unimplemented!(),
+            };
+            Ok(value.map(str::to_string))
+        }
+
+        fn write(&self, _key: &str, _value: Option<&str>) -> Result<()> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_status_shows_last_sync_time() {
+        let storage = InMemoryStorage::new();
+        let sync = MockSync {
+            status: SyncStatus {
+                has_local_changes: false,
+                remote: Some((0, 0)),
+            },
+        };
+        let output = MockOutput::new();
+
+        let never = MockState {
+            synced: None,
+            focus: None,
+        };
+        ShowStatus::new(&storage, &sync, &output)
+            .with_state(&never)
+            .execute()
+            .unwrap();
+        assert_eq!(output.get_messages()[3], "Synced: never");
+
+        let synced = MockState {
+            synced: Some("2026-03-02T09:30:00+00:00"),
+            focus: Some("api/auth\n"),
+        };
+        ShowStatus::new(&storage, &sync, &output)
+            .with_state(&synced)
+            .execute()
+            .unwrap();
+        let expected = DateTime::parse_from_rfc3339("2026-03-02T09:30:00+00:00")
+            .unwrap()
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M");
+        assert_eq!(output.get_messages()[7], format!("Synced: {expected}"));
+        assert_eq!(output.get_messages()[8], "Focus:  api/auth");
+    }
+}
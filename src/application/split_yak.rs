@@ -0,0 +1,222 @@
+// SplitYak use case - turns the bullet list in a yak's context into child yaks
+
+use super::{naming, transaction};
+use crate::domain::checklist::{split_bullets, ChecklistItem};
+use crate::domain::{validate_yak_name, NameRule};
+use crate::ports::{ConfigPort, LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+
+pub struct SplitYak<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+    config: Option<&'a dyn ConfigPort>,
+}
+
+impl<'a> SplitYak<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            log,
+            config: None,
+        }
+    }
+
+    /// Apply the repo's name normalization rules, if any are configured
+    pub fn with_config(mut self, config: &'a dyn ConfigPort) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Create a child yak for every bullet in the context, keeping checked
+    /// items done. With `strip`, the bullets are removed from the context.
+    pub fn execute(&self, name: &str, strip: bool) -> Result<()> {
+        // Resolve yak name (exact or fuzzy match)
+        let resolved_name = self.storage.find_yak(name)?;
+        let rules = naming::configured_rules(self.config)?;
+
+        let context = self.storage.read_context(&resolved_name)?;
+        let (items, rest) = split_bullets(&context);
+        if items.is_empty() {
+            anyhow::bail!("no bullets to split in the context of '{resolved_name}'");
+        }
+
+        // Either every child is created or, if one fails, none are
+        let created = transaction::apply(
+            self.storage,
+            self.log,
+            &format!("split {resolved_name}"),
+            || self.split(&resolved_name, &items, &rest, &rules, strip),
+        )?;
+
+        self.output.success(&format!(
+            "Created {} yak(s) under '{resolved_name}':",
+            created.len()
+        ));
+        for child in &created {
+            self.output.note(&format!("  {child}"));
+        }
+
+        Ok(())
+    }
+
+    /// Create a child of `name` for each of `items`, then strip them from its
+    /// context (leaving `rest`) when asked; returns the children created
+    fn split(
+        &self,
+        name: &str,
+        items: &[ChecklistItem],
+        rest: &str,
+        rules: &[NameRule],
+        strip: bool,
+    ) -> Result<Vec<String>> {
+        let mut created = Vec::new();
+        let mut skipped = false;
+        for item in items {
+            let (label, title) = naming::normalize(&item.name, rules);
+            let child = format!("{name}/{label}");
+            if let Err(e) = validate_yak_name(&child) {
+                self.output.error(&format!("Skipped '{child}': {e}"));
+                skipped = true;
+                continue;
+            }
+            if self.storage.get_yak(&child).is_ok() {
+                continue;
+            }
+
+            self.storage.create_yak(&child)?;
+            if let Some(title) = &title {
+                self.storage.write_field(&child, "title", Some(title))?;
+            }
+            if item.done {
+                self.storage.mark_done(&child, true)?;
+            }
+            created.push(child);
+        }
+
+        // Stripping would lose the skipped bullets, so keep them all instead
+        if strip && skipped {
+            self.output
+                .error("Kept the bullets in the context since some couldn't be split");
+        } else if strip {
+            self.storage.write_context(name, rest)?;
+        }
+        Ok(created)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::ports::LogEntry;
+    use std::cell::RefCell;
+
+    fn storage_with(name: &str, context: &str) -> InMemoryStorage {
+        let storage = InMemoryStorage::new();
+        storage.create_yak(name).unwrap();
+        storage.write_context(name, context).unwrap();
+        storage
+    }
+
+    fn names(storage: &InMemoryStorage) -> Vec<(String, bool)> {
+        storage
+            .list_yaks()
+            .unwrap()
+            .into_iter()
+            .map(|y| (y.name, y.done))
+            .collect()
+    }
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockLog;
+
+    impl LogPort for MockLog {
+        fn log_command(&self, _command: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_split_creates_children_and_strips_bullets() {
+        let storage = storage_with(
+            "release",
+            "Ship it.\n\n- [ ] write notes\n  - check links\n- [x] tag\n",
+        );
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let use_case = SplitYak::new(&storage, &output, &MockLog);
+
+        use_case.execute("release", true).unwrap();
+
+        assert_eq!(
+            names(&storage),
+            vec![
+                ("release".to_string(), false),
+                ("release/tag".to_string(), true),
+                ("release/write notes".to_string(), false),
+                ("release/write notes/check links".to_string(), false),
+            ]
+        );
+        assert_eq!(storage.read_context("release").unwrap(), "Ship it.\n");
+        assert_eq!(
+            output.messages.borrow()[0],
+            "Created 3 yak(s) under 'release':"
+        );
+    }
+
+    #[test]
+    fn test_split_keeps_bullets_it_could_not_split() {
+        let storage = storage_with("release", "- tag\n- bad: name\n");
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let use_case = SplitYak::new(&storage, &output, &MockLog);
+
+        use_case.execute("release", true).unwrap();
+
+        assert_eq!(
+            storage.read_context("release").unwrap(),
+            "- tag\n- bad: name\n"
+        );
+        assert!(storage.get_yak("release/tag").is_ok());
+        assert!(
+            SplitYak::new(&storage_with("x", "prose\n"), &output, &MockLog)
+                .execute("x", false)
+                .is_err()
+        );
+    }
+}
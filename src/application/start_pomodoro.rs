@@ -0,0 +1,230 @@
+// StartPomodoro use case - runs a focus timer on a yak and logs the work when it ends
+
+use crate::ports::{LogPort, OutputPort, StoragePort, TimerPort};
+use anyhow::Result;
+use chrono::{SecondsFormat, Utc};
+use std::time::Duration;
+
+/// Field holding one `<started-at> <minutes>m` line per completed session
+const WORKLOG_FIELD: &str = "worklog";
+
+pub struct StartPomodoro<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+    timer: &'a dyn TimerPort,
+}
+
+impl<'a> StartPomodoro<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+        timer: &'a dyn TimerPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            log,
+            timer,
+        }
+    }
+
+    pub fn execute(&self, name: &str, minutes: u64) -> Result<()> {
+        if minutes == 0 {
+            anyhow::bail!("a pomodoro must last at least 1 minute");
+        }
+        let Some(seconds) = minutes.checked_mul(60) else {
+            anyhow::bail!("a pomodoro of {minutes} minutes is too long");
+        };
+
+        // Resolve yak name (exact or fuzzy match)
+        let resolved_name = self.storage.find_yak(name)?;
+
+        let started_at = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+        self.output.note(&format!(
+            "Focusing on '{resolved_name}' for {minutes} minutes..."
+        ));
+
+        self.timer.wait(Duration::from_secs(seconds))?;
+
+        // Only completed sessions are logged; an interrupted timer never gets here
+        let mut worklog = self
+            .storage
+            .read_field(&resolved_name, WORKLOG_FIELD)?
+            .unwrap_or_default();
+        worklog.push_str(&format!("{started_at} {minutes}m\n"));
+        self.storage
+            .write_field(&resolved_name, WORKLOG_FIELD, Some(&worklog))?;
+
+        self.log
+            .log_command(&format!("pom {minutes} {resolved_name}"))?;
+
+        let message = format!("{minutes} minutes on '{resolved_name}' done");
+        self.timer.notify("Pomodoro finished", &message);
+        self.output
+            .success(&format!("Pomodoro finished: {message}"));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::ports::LogEntry;
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl MockOutput {
+        fn new() -> Self {
+            Self {
+                messages: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn last_message(&self) -> Option<String> {
+            self.messages.borrow().last().cloned()
+        }
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl MockLog {
+        fn new() -> Self {
+            Self {
+                commands: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    struct MockTimer {
+        waited: RefCell<Vec<Duration>>,
+        notifications: RefCell<Vec<String>>,
+    }
+
+    impl MockTimer {
+        fn new() -> Self {
+            Self {
+                waited: RefCell::new(Vec::new()),
+                notifications: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl TimerPort for MockTimer {
+        fn wait(&self, duration: Duration) -> Result<()> {
+            self.waited.borrow_mut().push(duration);
+            Ok(())
+        }
+
+        fn notify(&self, _title: &str, message: &str) {
+            self.notifications.borrow_mut().push(message.to_string());
+        }
+    }
+
+    #[test]
+    fn test_pomodoro_appends_worklog_entry() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("write docs").unwrap();
+        let output = MockOutput::new();
+        let log = MockLog::new();
+        let timer = MockTimer::new();
+        let use_case = StartPomodoro::new(&storage, &output, &log, &timer);
+
+        use_case.execute("write docs", 25).unwrap();
+        use_case.execute("write docs", 5).unwrap();
+
+        assert_eq!(
+            *timer.waited.borrow(),
+            vec![Duration::from_secs(1500), Duration::from_secs(300)]
+        );
+        let worklog = storage
+            .read_field("write docs", WORKLOG_FIELD)
+            .unwrap()
+            .unwrap();
+        let entries: Vec<&str> = worklog.lines().collect();
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].ends_with("Z 25m"));
+        assert!(entries[1].ends_with("Z 5m"));
+        assert_eq!(
+            *log.commands.borrow(),
+            vec!["pom 25 write docs", "pom 5 write docs"]
+        );
+        assert_eq!(
+            *timer.notifications.borrow(),
+            vec![
+                "25 minutes on 'write docs' done",
+                "5 minutes on 'write docs' done"
+            ]
+        );
+        assert_eq!(
+            output.last_message(),
+            Some("Pomodoro finished: 5 minutes on 'write docs' done".to_string())
+        );
+    }
+
+    #[test]
+    fn test_pomodoro_fails_for_missing_yak() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput::new();
+        let log = MockLog::new();
+        let timer = MockTimer::new();
+        let use_case = StartPomodoro::new(&storage, &output, &log, &timer);
+
+        assert!(use_case.execute("nope", 25).is_err());
+        assert!(timer.waited.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_pomodoro_rejects_zero_and_overlong_sessions() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("write docs").unwrap();
+        let output = MockOutput::new();
+        let log = MockLog::new();
+        let timer = MockTimer::new();
+        let use_case = StartPomodoro::new(&storage, &output, &log, &timer);
+
+        assert!(use_case.execute("write docs", 0).is_err());
+        let error = use_case.execute("write docs", u64::MAX).unwrap_err();
+        assert!(error.to_string().contains("too long"));
+        assert!(timer.waited.borrow().is_empty());
+    }
+}
@@ -0,0 +1,175 @@
+// StartYak use case - moves a yak to doing and notes when work on it started
+
+use crate::domain::YakState;
+use crate::ports::{LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+use chrono::{SecondsFormat, Utc};
+
+pub struct StartYak<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+}
+
+impl<'a> StartYak<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            log,
+        }
+    }
+
+    /// Mark the yak as doing and record the start time in its `started` field
+    /// With `only`, every other doing yak goes back to todo first.
+    pub fn execute(&self, name: &str, only: bool) -> Result<()> {
+        // Resolve yak name (exact or fuzzy match)
+        let resolved_name = self.storage.find_yak(name)?;
+
+        if self.storage.get_yak(&resolved_name)?.done {
+            anyhow::bail!("'{resolved_name}' is done - reopen it with `yx done --undo` first");
+        }
+
+        if only {
+            for other in self.storage.list_yaks()? {
+                if other.name == resolved_name || other.current_state() != YakState::Doing {
+                    continue;
+                }
+                self.storage.write_field(&other.name, "state", None)?;
+                self.log
+                    .log_command(&format!("state {} {}", other.name, YakState::Todo))?;
+                self.output
+                    .note(&format!("Moved '{}' back to todo", other.name));
+            }
+        }
+
+        let started = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+        self.storage.write_field(
+            &resolved_name,
+            "state",
+            Some(&format!("{}\n", YakState::Doing)),
+        )?;
+        self.storage
+            .write_field(&resolved_name, "started", Some(&format!("{started}\n")))?;
+        self.log
+            .log_command(&format!("state {resolved_name} {}", YakState::Doing))?;
+        self.output.success(&format!("Started '{resolved_name}'"));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use crate::ports::LogEntry;
+    use chrono::DateTime;
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, _message: &str) {}
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    fn output_and_log() -> (MockOutput, MockLog) {
+        (
+            MockOutput {
+                messages: RefCell::new(Vec::new()),
+            },
+            MockLog {
+                commands: RefCell::new(Vec::new()),
+            },
+        )
+    }
+
+    #[test]
+    fn test_doing_records_state_and_start_time() {
+        let storage = InMemoryStorage::with_yaks([
+            Yak::new("api".to_string()),
+            Yak::new("docs".to_string()).with_state(YakState::Doing),
+        ]);
+        let (output, log) = output_and_log();
+
+        StartYak::new(&storage, &output, &log)
+            .execute("api", false)
+            .unwrap();
+
+        assert_eq!(
+            storage.read_field("api", "state").unwrap(),
+            Some("doing\n".to_string())
+        );
+        let started = storage.read_field("api", "started").unwrap().unwrap();
+        assert!(DateTime::parse_from_rfc3339(started.trim()).is_ok());
+        assert_eq!(*log.commands.borrow(), vec!["state api doing"]);
+        assert_eq!(*output.messages.borrow(), vec!["Started 'api'"]);
+    }
+
+    #[test]
+    fn test_doing_only_moves_other_doing_yaks_back_to_todo() {
+        let storage = InMemoryStorage::with_yaks([
+            Yak::new("api".to_string()).with_state(YakState::Doing),
+            Yak::new("docs".to_string()).with_state(YakState::Doing),
+            Yak::new("ci".to_string()).with_state(YakState::Blocked),
+            Yak::new("old".to_string())
+                .with_state(YakState::Doing)
+                .mark_done(),
+        ]);
+        storage
+            .write_field("docs", "state", Some("doing\n"))
+            .unwrap();
+        let (output, log) = output_and_log();
+
+        StartYak::new(&storage, &output, &log)
+            .execute("api", true)
+            .unwrap();
+
+        assert_eq!(storage.read_field("docs", "state").unwrap(), None);
+        assert_eq!(
+            *log.commands.borrow(),
+            vec!["state docs todo", "state api doing"]
+        );
+        assert_eq!(
+            *output.messages.borrow(),
+            vec!["Moved 'docs' back to todo", "Started 'api'"]
+        );
+        assert!(StartYak::new(&storage, &output, &log)
+            .execute("old", false)
+            .is_err());
+    }
+}
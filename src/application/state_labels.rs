@@ -0,0 +1,121 @@
+// State labels - how open and done yaks are shown, overridable in git config
+
+use crate::domain::YakState;
+use crate::ports::ConfigPort;
+
+/// Config key prefix, e.g. `git config yaks.display.done ✅`
+const DISPLAY_KEY: &str = "yaks.display";
+
+/// Display strings for each yak state, shared by every command that renders state
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StateLabels {
+    open: Option<String>,
+    done: Option<String>,
+    /// The theme's markers, used when no label is configured
+    markers: Option<(String, String)>,
+}
+
+impl StateLabels {
+    /// Labels from `yaks.display.<state>`; when the terminal can't show non-ASCII,
+    /// `yaks.display.<state>Ascii` is used instead, then the built-in label
+    pub fn from_config(config: &dyn ConfigPort, unicode: bool) -> Self {
+        let label = |state: &str| {
+            let configured = config
+                .get(&format!("{DISPLAY_KEY}.{state}"))
+                .filter(|label| unicode || label.is_ascii());
+            configured
+                .or_else(|| config.get(&format!("{DISPLAY_KEY}.{state}Ascii")))
+                .filter(|label| !label.trim().is_empty())
+        };
+
+        Self {
+            open: label("open"),
+            done: label("done"),
+            markers: None,
+        }
+    }
+
+    /// Draw checkboxes with a theme's markers unless `yaks.display` overrides them
+    pub fn with_markers(mut self, open: &str, done: &str) -> Self {
+        self.markers = Some((open.to_string(), done.to_string()));
+        self
+    }
+
+    /// Marker drawn before a yak in lists and trees, `[ ]` / `[x]` by default
+    pub fn checkbox(&self, done: bool) -> &str {
+        let (open, finished) = self
+            .markers
+            .as_ref()
+            .map_or(("[ ]", "[x]"), |(open, done)| {
+                (open.as_str(), done.as_str())
+            });
+        match (done, &self.open, &self.done) {
+            (false, Some(label), _) | (true, _, Some(label)) => label,
+            (false, None, _) => open,
+            (true, _, None) => finished,
+        }
+    }
+
+    /// State as a word in tables and reports: `todo`, `doing`, `blocked` or
+    /// `done`, with the configured labels in place of `todo` and `done`
+    pub fn word(&self, state: YakState) -> String {
+        match (state, &self.open, &self.done) {
+            (YakState::Todo, Some(label), _) | (YakState::Done, _, Some(label)) => label.clone(),
+            (state, _, _) => state.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct MockConfig(HashMap<&'static str, &'static str>);
+
+    impl ConfigPort for MockConfig {
+        fn get(&self, key: &str) -> Option<String> {
+            self.0.get(key).map(|value| value.to_string())
+        }
+
+        fn get_all(&self, _key: &str) -> Vec<String> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_defaults_without_config() {
+        let labels = StateLabels::from_config(&MockConfig(HashMap::new()), true);
+
+        assert_eq!(labels.checkbox(false), "[ ]");
+        assert_eq!(labels.checkbox(true), "[x]");
+        assert_eq!(labels.word(YakState::Todo), "todo");
+        assert_eq!(labels.word(YakState::Blocked), "blocked");
+        assert_eq!(labels.word(YakState::Done), "done");
+    }
+
+    #[test]
+    fn test_configured_symbols_with_ascii_fallback() {
+        let config = MockConfig(HashMap::from([
+            ("yaks.display.open", "⏳"),
+            ("yaks.display.done", "✅"),
+            ("yaks.display.doneAscii", "(done)"),
+        ]));
+
+        let unicode = StateLabels::from_config(&config, true);
+        assert_eq!(unicode.checkbox(false), "⏳");
+        assert_eq!(unicode.word(YakState::Done), "✅");
+        assert_eq!(unicode.word(YakState::Doing), "doing");
+
+        let ascii = StateLabels::from_config(&config, false);
+        assert_eq!(ascii.checkbox(false), "[ ]");
+        assert_eq!(ascii.checkbox(true), "(done)");
+        assert_eq!(ascii.word(YakState::Todo), "todo");
+
+        // Theme markers fill in where nothing is configured, words stay as they are
+        let themed = ascii.with_markers("☐", "☑");
+        assert_eq!(themed.checkbox(false), "☐");
+        assert_eq!(themed.checkbox(true), "(done)");
+        assert_eq!(themed.word(YakState::Todo), "todo");
+    }
+}
@@ -0,0 +1,353 @@
+// SuggestOwner use case - ranks likely owners of a yak from the code and commits it links to
+
+use crate::ports::{Author, BlamePort, LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+use std::collections::HashMap;
+
+/// Something a link points at that has authors
+#[derive(Debug, PartialEq, Eq)]
+enum LinkTarget {
+    File(String),
+    Commit(String),
+}
+
+/// Evidence gathered for one author
+#[derive(Default)]
+struct Tally {
+    commits: usize,
+    lines: usize,
+    files: Vec<String>,
+}
+
+pub struct SuggestOwner<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+    blame: &'a dyn BlamePort,
+}
+
+impl<'a> SuggestOwner<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+        blame: &'a dyn BlamePort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            log,
+            blame,
+        }
+    }
+
+    /// Suggest owners for the yak; with `assign`, make the top suggestion its assignee
+    pub fn execute(&self, name: &str, assign: bool) -> Result<()> {
+        let resolved_name = self.storage.find_yak(name)?;
+        let yak = self.storage.get_yak(&resolved_name)?;
+
+        let mut targets: Vec<LinkTarget> = Vec::new();
+        for target in yak.links.iter().filter_map(|link| parse_link(link)) {
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+        if targets.is_empty() {
+            anyhow::bail!(
+                "'{resolved_name}' links to no files or commits - attach some with `yx link`"
+            );
+        }
+
+        // Authors in order of first appearance, so ties rank stably
+        let mut authors: Vec<Author> = Vec::new();
+        let mut tallies: HashMap<Author, Tally> = HashMap::new();
+        for target in &targets {
+            match target {
+                LinkTarget::Commit(rev) => match self.blame.commit_author(rev) {
+                    Ok(author) => {
+                        if !tallies.contains_key(&author) {
+                            authors.push(author.clone());
+                        }
+                        tallies.entry(author).or_default().commits += 1;
+                    }
+                    Err(e) => self.output.note(&format!("Skipped commit {rev}: {e}")),
+                },
+                LinkTarget::File(path) => match self.blame.blame_file(path) {
+                    Ok(lines) => {
+                        for (author, count) in lines {
+                            if !tallies.contains_key(&author) {
+                                authors.push(author.clone());
+                            }
+                            let tally = tallies.entry(author).or_default();
+                            tally.lines += count;
+                            tally.files.push(path.clone());
+                        }
+                    }
+                    Err(e) => self.output.note(&format!("Skipped {path}: {e}")),
+                },
+            }
+        }
+
+        if authors.is_empty() {
+            self.output
+                .note(&format!("No likely owners found for '{resolved_name}'"));
+            return Ok(());
+        }
+
+        // Whoever made a linked commit is the strongest signal, then who wrote the most lines
+        authors.sort_by(|a, b| {
+            let (a, b) = (&tallies[a], &tallies[b]);
+            b.commits.cmp(&a.commits).then(b.lines.cmp(&a.lines))
+        });
+
+        self.output
+            .info(&format!("Suggested owners for '{resolved_name}':"));
+        for (rank, author) in authors.iter().enumerate() {
+            let tally = &tallies[author];
+            let mut evidence = Vec::new();
+            if tally.commits > 0 {
+                let noun = if tally.commits == 1 {
+                    "commit"
+                } else {
+                    "commits"
+                };
+                evidence.push(format!("{} linked {noun}", tally.commits));
+            }
+            if tally.lines > 0 {
+                evidence.push(format!(
+                    "{} lines in {}",
+                    tally.lines,
+                    tally.files.join(", ")
+                ));
+            }
+            self.output.info(&format!(
+                "  {}. {} <{}> - {}",
+                rank + 1,
+                author.name,
+                author.email,
+                evidence.join(", ")
+            ));
+        }
+
+        let top = &authors[0];
+        if assign {
+            self.storage.write_field(
+                &resolved_name,
+                "assignee",
+                Some(&format!("{}\n", top.email)),
+            )?;
+            self.log
+                .log_command(&format!("assign {resolved_name} {}", top.email))?;
+            self.output.success(&format!(
+                "Assigned '{resolved_name}' to {} <{}>",
+                top.name, top.email
+            ));
+        } else {
+            self.output.info(&format!(
+                "Run `yx suggest-owner '{resolved_name}' --assign` to assign {}",
+                top.name
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Work out what a link points at: a commit (hash or `/commit/` URL),
+/// a file (`path`, `path:42`, or a `/blob/<ref>/path` URL), or nothing we can blame
+fn parse_link(link: &str) -> Option<LinkTarget> {
+    let link = link.trim();
+
+    if link.contains("://") {
+        if let Some((_, rest)) = link.split_once("/commit/") {
+            let rev = rest.split(['/', '#', '?']).next()?;
+            return is_hash(rev).then(|| LinkTarget::Commit(rev.to_string()));
+        }
+        let (_, rest) = link.split_once("/blob/")?;
+        let (_, path) = rest.split_once('/')?;
+        let path = path.split(['#', '?']).next()?;
+        return (!path.is_empty()).then(|| LinkTarget::File(path.to_string()));
+    }
+
+    if is_hash(link) {
+        return Some(LinkTarget::Commit(link.to_string()));
+    }
+
+    let path = link.split('#').next()?;
+    let path = match path.rsplit_once(':') {
+        Some((file, line)) if line.chars().all(|c| c.is_ascii_digit()) => file,
+        _ => path,
+    };
+    (!path.is_empty()).then(|| LinkTarget::File(path.to_string()))
+}
+
+/// Whether text looks like a (possibly abbreviated) commit hash
+fn is_hash(text: &str) -> bool {
+    (7..=40).contains(&text.len()) && text.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use crate::ports::LogEntry;
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl MockOutput {
+        fn new() -> Self {
+            Self {
+                messages: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn get_messages(&self) -> Vec<String> {
+            self.messages.borrow().clone()
+        }
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    fn author(name: &str) -> Author {
+        Author {
+            name: name.to_string(),
+            email: format!("{name}@example.com"),
+        }
+    }
+
+    struct MockBlame;
+
+    impl BlamePort for MockBlame {
+        fn blame_file(&self, path: &str) -> Result<Vec<(Author, usize)>> {
+            match path {
+                "src/login.rs" => Ok(vec![(author("ann"), 40), (author("bob"), 60)]),
+                _ => anyhow::bail!("not tracked"),
+            }
+        }
+
+        fn commit_author(&self, _rev: &str) -> Result<Author> {
+            Ok(author("cat"))
+        }
+    }
+
+    fn storage_with_links(links: &[&str]) -> InMemoryStorage {
+        InMemoryStorage::with_yaks([Yak::new("incident".to_string())
+            .with_links(links.iter().map(|link| link.to_string()).collect())])
+    }
+
+    #[test]
+    fn test_parse_link() {
+        assert_eq!(
+            parse_link("src/login.rs:42"),
+            Some(LinkTarget::File("src/login.rs".to_string()))
+        );
+        assert_eq!(
+            parse_link("https://github.com/org/repo/blob/main/src/login.rs#L10"),
+            Some(LinkTarget::File("src/login.rs".to_string()))
+        );
+        assert_eq!(
+            parse_link("https://github.com/org/repo/commit/abc1234"),
+            Some(LinkTarget::Commit("abc1234".to_string()))
+        );
+        assert_eq!(
+            parse_link("deadbeef"),
+            Some(LinkTarget::Commit("deadbeef".to_string()))
+        );
+        assert_eq!(parse_link("https://github.com/org/repo/pull/1"), None);
+    }
+
+    #[test]
+    fn test_suggest_owner_ranks_commit_authors_then_lines() {
+        let storage = storage_with_links(&["src/login.rs", "abc1234", "gone.rs"]);
+        let output = MockOutput::new();
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = SuggestOwner::new(&storage, &output, &log, &MockBlame);
+
+        use_case.execute("incident", false).unwrap();
+
+        assert_eq!(
+            output.get_messages(),
+            vec![
+                "Skipped gone.rs: not tracked",
+                "Suggested owners for 'incident':",
+                "  1. cat <cat@example.com> - 1 linked commit",
+                "  2. bob <bob@example.com> - 60 lines in src/login.rs",
+                "  3. ann <ann@example.com> - 40 lines in src/login.rs",
+                "Run `yx suggest-owner 'incident' --assign` to assign cat",
+            ]
+        );
+        assert_eq!(storage.read_field("incident", "assignee").unwrap(), None);
+    }
+
+    #[test]
+    fn test_suggest_owner_assigns_top_suggestion() {
+        let storage = storage_with_links(&["src/login.rs"]);
+        let output = MockOutput::new();
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = SuggestOwner::new(&storage, &output, &log, &MockBlame);
+
+        use_case.execute("incident", true).unwrap();
+
+        assert_eq!(
+            storage.read_field("incident", "assignee").unwrap(),
+            Some("bob@example.com\n".to_string())
+        );
+        assert_eq!(
+            *log.commands.borrow(),
+            vec!["assign incident bob@example.com"]
+        );
+    }
+
+    #[test]
+    fn test_suggest_owner_needs_links() {
+        let storage = storage_with_links(&["https://example.com/ticket/1"]);
+        let output = MockOutput::new();
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = SuggestOwner::new(&storage, &output, &log, &MockBlame);
+
+        assert!(use_case.execute("incident", false).is_err());
+    }
+}
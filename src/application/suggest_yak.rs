@@ -0,0 +1,176 @@
+// SuggestYak use case - picks one yak to work on next
+
+use super::plan_week::describe;
+use crate::domain::filter::Only;
+use crate::domain::{graph, Yak, YakState};
+use crate::ports::{OutputPort, StoragePort};
+use anyhow::Result;
+use chrono::Local;
+
+/// Context lines shown under the suggestion
+const SUMMARY_LINES: usize = 3;
+
+pub struct SuggestYak<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+}
+
+impl<'a> SuggestYak<'a> {
+    pub fn new(storage: &'a dyn StoragePort, output: &'a dyn OutputPort) -> Self {
+        Self { storage, output }
+    }
+
+    /// Show the best ready yak with the start of its context
+    /// Ready yaks are open, not blocked, and have no open children. Yaks already
+    /// being worked on come first, then by priority and due date (a parent's
+    /// count for the yaks it waits on), then oldest first.
+    pub fn execute(&self) -> Result<()> {
+        let yaks = self.storage.list_yaks()?;
+        if yaks.is_empty() {
+            self.output.note("You have no yaks. Are you done?");
+            return Ok(());
+        }
+        let today = Local::now().date_naive();
+        let ready = yaks
+            .iter()
+            .filter(|yak| Only::Ready.matches(yak, graph::children_done(&yaks, &yak.name), today));
+
+        let Some(yak) = ready.min_by_key(|yak| rank(yak, &yaks)) else {
+            self.output.info(
+                "Nothing is ready to work on - every open yak is blocked or waiting on others",
+            );
+            return Ok(());
+        };
+
+        self.output.info(&format!("Next up: {}", describe(yak)));
+        if yak.title.is_some() {
+            self.output.info(&format!("  ({})", yak.name));
+        }
+        let context = self.storage.read_context(&yak.name).unwrap_or_default();
+        let mut lines = context.lines().filter(|line| !line.trim().is_empty());
+        for line in lines.by_ref().take(SUMMARY_LINES) {
+            self.output.info(&format!("  {}", line.trim_end()));
+        }
+        if lines.next().is_some() {
+            self.output.info("  …");
+        }
+
+        Ok(())
+    }
+}
+
+/// Sort key for ready yaks, best first; missing values rank after present ones
+fn rank<'y>(yak: &'y Yak, yaks: &'y [Yak]) -> impl Ord + 'y {
+    let lineage: Vec<&Yak> =
+        std::iter::successors(Some(yak.name.as_str()), |name| graph::parent_of(name))
+            .filter_map(|name| yaks.iter().find(|other| other.name == name))
+            .collect();
+    let priority = lineage.iter().filter_map(|yak| yak.priority).min();
+    let due = lineage.iter().filter_map(|yak| yak.due).min();
+
+    (
+        yak.current_state() != YakState::Doing,
+        (priority.is_none(), priority),
+        (due.is_none(), due),
+        (yak.created_at.is_none(), yak.created_at),
+        &yak.name,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Priority;
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, _message: &str) {}
+
+        fn error(&self, _message: &str) {}
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    fn suggest(yaks: Vec<Yak>) -> Vec<String> {
+        let storage = InMemoryStorage::with_yaks(yaks);
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        SuggestYak::new(&storage, &output).execute().unwrap();
+        output.messages.take()
+    }
+
+    #[test]
+    fn test_suggest_picks_the_most_urgent_ready_yak() {
+        let created = |day| Some(Utc.with_ymd_and_hms(2026, 10, day, 9, 0, 0).unwrap());
+        let yaks = vec![
+            Yak {
+                name: "release".to_string(),
+                priority: Some(Priority::HIGHEST),
+                ..Default::default()
+            },
+            Yak::new("release/tag".to_string()),
+            Yak {
+                name: "ci".to_string(),
+                priority: Some(Priority::HIGHEST),
+                state: Some(YakState::Blocked),
+                ..Default::default()
+            },
+            Yak {
+                name: "docs".to_string(),
+                created_at: created(2),
+                ..Default::default()
+            },
+            Yak {
+                name: "api".to_string(),
+                created_at: created(1),
+                due: NaiveDate::from_ymd_opt(2026, 10, 30),
+                context: Some("Rate limits\n\nper user\nper key\nper org\n".to_string()),
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(suggest(yaks.clone()), vec!["Next up: release/tag"]);
+
+        let mut rest = yaks;
+        rest.retain(|yak| !yak.name.starts_with("release"));
+        assert_eq!(
+            suggest(rest.clone()),
+            vec![
+                "Next up: api (due 2026-10-30)",
+                "  Rate limits",
+                "  per user",
+                "  per key",
+                "  …",
+            ]
+        );
+
+        rest.push(Yak::new("docs/intro".to_string()).with_state(YakState::Doing));
+        assert_eq!(suggest(rest)[0], "Next up: docs/intro");
+    }
+
+    #[test]
+    fn test_suggest_says_when_nothing_is_ready() {
+        let yaks = vec![
+            Yak::new("old".to_string()).mark_done(),
+            Yak::new("ci".to_string()).with_state(YakState::Blocked),
+        ];
+
+        assert_eq!(
+            suggest(yaks),
+            vec!["Nothing is ready to work on - every open yak is blocked or waiting on others"]
+        );
+    }
+}
@@ -1,36 +1,125 @@
 // SyncYaks use case - synchronizes yaks via git refs
 
-use crate::ports::{OutputPort, SyncPort};
+use super::diff_yaks::change_line;
+use super::{porcelain, OutputFormat};
+use crate::ports::{LocalStatePort, OutputPort, SyncPhase, SyncPort, SyncProgress, Verbosity};
 use anyhow::Result;
+use chrono::Utc;
+
+/// Local state key holding the RFC 3339 time of the last successful sync
+pub(super) const LAST_SYNC_KEY: &str = "last-sync";
 
 pub struct SyncYaks<'a> {
     sync: &'a dyn SyncPort,
+    output: &'a dyn OutputPort,
+    state: Option<&'a dyn LocalStatePort>,
+    format: OutputFormat,
 }
 
 impl<'a> SyncYaks<'a> {
-    pub fn new(sync: &'a dyn SyncPort, _output: &'a dyn OutputPort) -> Self {
-        Self { sync }
+    pub fn new(sync: &'a dyn SyncPort, output: &'a dyn OutputPort) -> Self {
+        Self {
+            sync,
+            output,
+            state: None,
+            format: OutputFormat::Markdown,
+        }
+    }
+
+    /// With porcelain, print a stable line for each yak the sync changed
+    pub fn with_format(mut self, format: OutputFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Remember when this repo last synced, for `yx status`
+    pub fn with_state(mut self, state: &'a dyn LocalStatePort) -> Self {
+        self.state = Some(state);
+        self
     }
 
     pub fn execute(&self) -> Result<()> {
-        self.sync.sync()?;
+        self.format
+            .ensure("sync", &[OutputFormat::Markdown, OutputFormat::Porcelain])?;
+        let porcelain = self.format == OutputFormat::Porcelain;
+        let verbose = self.output.verbosity() == Verbosity::Verbose;
+        if verbose {
+            self.show_steps()?;
+        }
+        let changes = if porcelain || verbose {
+            self.sync.preview()?
+        } else {
+            Vec::new()
+        };
+
+        let report = |progress| self.output.progress(&progress_line(progress));
+        let warnings = self.sync.sync(&report);
+        self.output.progress_done();
+        for warning in warnings? {
+            self.output.warn(&warning);
+        }
+        for (name, change) in changes {
+            if porcelain {
+                self.output.info(&porcelain::sync_line(&name, change));
+            } else {
+                self.output.detail(&change_line(&name, change));
+            }
+        }
+        if let Some(state) = self.state {
+            state.write(LAST_SYNC_KEY, Some(&Utc::now().to_rfc3339()))?;
+        }
+        Ok(())
+    }
+
+    /// What the sync is about to do, for `--verbose`
+    fn show_steps(&self) -> Result<()> {
+        let status = self.sync.status()?;
+        self.output.detail(if status.has_local_changes {
+            "Committing local changes to refs/notes/yaks"
+        } else {
+            "No local changes to commit"
+        });
+        self.output.detail(&match status.remote {
+            Some((ahead, behind)) => {
+                format!("Fetching origin: {ahead} ahead, {behind} behind")
+            }
+            None => "Fetching origin: no yaks there yet".to_string(),
+        });
+        self.output
+            .detail("Merging with origin, pushing and updating .yaks");
         Ok(())
     }
 }
 
+/// A progress update as shown while syncing, e.g. "Fetching from origin (12/40 objects)"
+fn progress_line(progress: SyncProgress) -> String {
+    let step = match progress.phase {
+        SyncPhase::Fetch => "Fetching from origin",
+        SyncPhase::Merge => "Merging",
+        SyncPhase::Push => "Pushing to origin",
+    };
+    match progress.objects {
+        Some((done, total)) if total > 0 => format!("{step} ({done}/{total} objects)"),
+        _ => step.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ports::IncomingChange;
     use std::cell::RefCell;
 
     struct MockSync {
         sync_called: RefCell<bool>,
+        warnings: Vec<String>,
     }
 
     impl MockSync {
         fn new() -> Self {
             Self {
                 sync_called: RefCell::new(false),
+                warnings: Vec::new(),
             }
         }
 
@@ -48,23 +137,50 @@ mod tests {
             unimplemented!()
         }
 
-        fn sync(&self) -> Result<()> {
+        fn sync(&self, progress: &dyn Fn(SyncProgress)) -> Result<Vec<String>> {
             *self.sync_called.borrow_mut() = true;
-            Ok(())
+            progress(SyncProgress {
+                phase: SyncPhase::Fetch,
+                objects: Some((3, 4)),
+            });
+            progress(SyncProgress {
+                phase: SyncPhase::Push,
+                objects: None,
+            });
+            Ok(self.warnings.clone())
+        }
+
+        fn status(&self) -> Result<crate::ports::SyncStatus> {
+            Ok(crate::ports::SyncStatus {
+                has_local_changes: true,
+                remote: Some((1, 2)),
+            })
+        }
+
+        fn preview(&self) -> Result<Vec<(String, IncomingChange)>> {
+            Ok(vec![
+                ("docs".to_string(), IncomingChange::Completed),
+                ("api/auth".to_string(), IncomingChange::Added),
+            ])
         }
     }
 
     struct MockOutput {
         messages: RefCell<Vec<String>>,
+        progress: RefCell<Vec<String>>,
+        verbosity: Verbosity,
     }
 
     impl MockOutput {
         fn new() -> Self {
             Self {
                 messages: RefCell::new(Vec::new()),
+                progress: RefCell::new(Vec::new()),
+                verbosity: Verbosity::Normal,
             }
         }
 
+        #[allow(dead_code)]
         fn last_message(&self) -> Option<String> {
             self.messages.borrow().last().cloned()
         }
@@ -86,6 +202,30 @@ mod tests {
                 .borrow_mut()
                 .push(format!("INFO: {}", message));
         }
+
+        fn warn(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("WARN: {}", message));
+        }
+
+        fn detail(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("DETAIL: {}", message));
+        }
+
+        fn verbosity(&self) -> Verbosity {
+            self.verbosity
+        }
+
+        fn progress(&self, message: &str) {
+            self.progress.borrow_mut().push(message.to_string());
+        }
+
+        fn progress_done(&self) {
+            self.progress.borrow_mut().push("DONE".to_string());
+        }
     }
 
     #[test]
@@ -98,4 +238,112 @@ mod tests {
 
         assert!(sync.was_sync_called());
     }
+
+    #[test]
+    fn test_sync_shows_progress_until_done() {
+        let sync = MockSync::new();
+        let output = MockOutput::new();
+
+        SyncYaks::new(&sync, &output).execute().unwrap();
+
+        assert_eq!(
+            *output.progress.borrow(),
+            vec![
+                "Fetching from origin (3/4 objects)",
+                "Pushing to origin",
+                "DONE"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sync_warns_about_skipped_steps() {
+        let sync = MockSync {
+            warnings: vec!["push to origin skipped: rejected".to_string()],
+            ..MockSync::new()
+        };
+        let output = MockOutput::new();
+
+        SyncYaks::new(&sync, &output).execute().unwrap();
+
+        assert_eq!(
+            output.last_message(),
+            Some("WARN: push to origin skipped: rejected".to_string())
+        );
+    }
+
+    #[test]
+    fn test_sync_porcelain_lists_what_changed() {
+        let sync = MockSync::new();
+        let output = MockOutput::new();
+
+        SyncYaks::new(&sync, &output).execute().unwrap();
+        assert!(output.messages.borrow().is_empty());
+
+        SyncYaks::new(&sync, &output)
+            .with_format(OutputFormat::Porcelain)
+            .execute()
+            .unwrap();
+        assert_eq!(
+            *output.messages.borrow(),
+            vec!["INFO: C\tdocs", "INFO: A\tapi/auth"]
+        );
+    }
+
+    #[test]
+    fn test_sync_verbose_shows_steps_and_changes() {
+        let sync = MockSync::new();
+        let output = MockOutput {
+            verbosity: Verbosity::Verbose,
+            ..MockOutput::new()
+        };
+
+        SyncYaks::new(&sync, &output).execute().unwrap();
+
+        assert!(sync.was_sync_called());
+        assert_eq!(
+            *output.messages.borrow(),
+            vec![
+                "DETAIL: Committing local changes to refs/notes/yaks",
+                "DETAIL: Fetching origin: 1 ahead, 2 behind",
+                "DETAIL: Merging with origin, pushing and updating .yaks",
+                "DETAIL: x completed  docs",
+                "DETAIL: + added      api/auth",
+            ]
+        );
+    }
+
+    struct MockState {
+        values: RefCell<Vec<(String, String)>>,
+    }
+
+    impl LocalStatePort for MockState {
+        fn read(&self, _key: &str) -> Result<Option<String>> {
+            unimplemented!()
+        }
+
+        fn write(&self, key: &str, value: Option<&str>) -> Result<()> {
+            self.values
+                .borrow_mut()
+                .push((key.to_string(), value.unwrap_or_default().to_string()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_sync_records_last_sync_time() {
+        let sync = MockSync::new();
+        let output = MockOutput::new();
+        let state = MockState {
+            values: RefCell::new(Vec::new()),
+        };
+        let use_case = SyncYaks::new(&sync, &output).with_state(&state);
+
+        use_case.execute().unwrap();
+
+        let values = state.values.borrow();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].0, LAST_SYNC_KEY);
+        assert!(chrono::DateTime::parse_from_rfc3339(&values[0].1).is_ok());
+    }
 }
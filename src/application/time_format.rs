@@ -0,0 +1,81 @@
+// Time format - how commands show a point in time: "2h ago" or a local date and time
+
+use chrono::{DateTime, Local, Utc};
+
+/// How a command shows times, chosen with the global `--absolute`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimeStyle {
+    /// "2h ago", "in 3 days"
+    Relative,
+    /// "2026-10-17 09:30" in local time
+    #[default]
+    Absolute,
+}
+
+impl TimeStyle {
+    pub(super) fn render(self, time: DateTime<Utc>) -> String {
+        match self {
+            TimeStyle::Relative => relative(time, Utc::now()),
+            TimeStyle::Absolute => time
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+        }
+    }
+}
+
+/// `time` as seen from `now`: "just now" within a minute, then minutes, hours,
+/// days, months and years, e.g. "5m ago", "2h ago", "3 days ago", "in 2 days"
+pub(super) fn relative(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let seconds = (now - time).num_seconds();
+    let (amount, unit) = match seconds.unsigned_abs() {
+        0..60 => return "just now".to_string(),
+        s @ 60..3_600 => (s / 60, "m"),
+        s @ 3_600..86_400 => (s / 3_600, "h"),
+        s @ 86_400..2_592_000 => (s / 86_400, " day"),
+        s @ 2_592_000..31_536_000 => (s / 2_592_000, " month"),
+        s => (s / 31_536_000, " year"),
+    };
+    // Word units take a plural, "m" and "h" don't
+    let plural = if unit.starts_with(' ') && amount != 1 {
+        "s"
+    } else {
+        ""
+    };
+    if seconds >= 0 {
+        format!("{amount}{unit}{plural} ago")
+    } else {
+        format!("in {amount}{unit}{plural}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn test_relative_past_and_future() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+        let ago = |seconds: i64| relative(now - Duration::seconds(seconds), now);
+
+        assert_eq!(ago(30), "just now");
+        assert_eq!(ago(-30), "just now");
+        assert_eq!(ago(5 * 60), "5m ago");
+        assert_eq!(ago(2 * 3_600 + 59), "2h ago");
+        assert_eq!(ago(86_400), "1 day ago");
+        assert_eq!(ago(3 * 86_400), "3 days ago");
+        assert_eq!(ago(-3 * 86_400), "in 3 days");
+        assert_eq!(ago(-90 * 60), "in 1h");
+        assert_eq!(ago(65 * 86_400), "2 months ago");
+        assert_eq!(ago(400 * 86_400), "1 year ago");
+    }
+
+    #[test]
+    fn test_absolute_is_local_date_and_time() {
+        let time = Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+        let local = time.with_timezone(&Local).format("%Y-%m-%d %H:%M");
+
+        assert_eq!(TimeStyle::Absolute.render(time), local.to_string());
+    }
+}
@@ -0,0 +1,121 @@
+// TitleYak use case - sets a free-form display title separate from the path-safe name
+
+use crate::domain::validate_title;
+use crate::ports::{LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+
+pub struct TitleYak<'a> {
+    storage: &'a dyn StoragePort,
+    log: &'a dyn LogPort,
+}
+
+impl<'a> TitleYak<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        _output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+    ) -> Self {
+        Self { storage, log }
+    }
+
+    /// Set the title, or clear it when `title` is None
+    pub fn execute(&self, name: &str, title: Option<&str>) -> Result<()> {
+        if let Some(title) = title {
+            validate_title(title).map_err(|e| anyhow::anyhow!(e))?;
+        }
+
+        // Resolve yak name (exact or fuzzy match)
+        let resolved_name = self.storage.find_yak(name)?;
+
+        match title {
+            Some(title) => {
+                let title = title.trim();
+                self.storage
+                    .write_field(&resolved_name, "title", Some(&format!("{title}\n")))?;
+                self.log
+                    .log_command(&format!("title {resolved_name} {title}"))?;
+            }
+            None => {
+                self.storage.write_field(&resolved_name, "title", None)?;
+                self.log
+                    .log_command(&format!("title --clear {resolved_name}"))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::ports::LogEntry;
+    use std::cell::RefCell;
+
+    struct MockOutput;
+
+    impl OutputPort for MockOutput {
+        fn success(&self, _message: &str) {}
+        fn error(&self, _message: &str) {}
+        fn info(&self, _message: &str) {}
+
+        fn warn(&self, _message: &str) {}
+    }
+
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_title_yak_sets_and_clears_title() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("fix-login").unwrap();
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = TitleYak::new(&storage, &MockOutput, &log);
+
+        use_case
+            .execute("fix-login", Some("Fix: \"login\" bug? 🐛"))
+            .unwrap();
+        assert_eq!(
+            storage.read_field("fix-login", "title").unwrap(),
+            Some("Fix: \"login\" bug? 🐛\n".to_string())
+        );
+
+        use_case.execute("fix-login", None).unwrap();
+        assert_eq!(storage.read_field("fix-login", "title").unwrap(), None);
+
+        assert_eq!(
+            *log.commands.borrow(),
+            vec![
+                "title fix-login Fix: \"login\" bug? 🐛",
+                "title --clear fix-login"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_title_yak_rejects_multiline_title() {
+        let storage = InMemoryStorage::new();
+        storage.create_yak("fix-login").unwrap();
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = TitleYak::new(&storage, &MockOutput, &log);
+
+        assert!(use_case.execute("fix-login", Some("a\nb")).is_err());
+    }
+}
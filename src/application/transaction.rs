@@ -0,0 +1,86 @@
+// Transactions - multi-step changes that apply as one, with one log entry
+
+use crate::ports::{LogEntry, LogPort, StoragePort};
+use anyhow::Result;
+use std::cell::RefCell;
+
+/// Holds back what the steps of a transaction would log, so the whole change
+/// is logged once by `apply`
+#[derive(Default)]
+pub(super) struct DeferredLog {
+    commands: RefCell<Vec<String>>,
+}
+
+impl LogPort for DeferredLog {
+    fn log_command(&self, command: &str) -> Result<()> {
+        self.commands.borrow_mut().push(command.to_string());
+        Ok(())
+    }
+
+    fn entries(&self) -> Result<Vec<LogEntry>> {
+        Ok(Vec::new())
+    }
+}
+
+/// Run `steps` in a storage transaction and log `command` once they all
+/// succeed; if any step fails, the yaks are put back and nothing is logged
+pub fn apply<T>(
+    storage: &dyn StoragePort,
+    log: &dyn LogPort,
+    command: &str,
+    steps: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    let mut steps = Some(steps);
+    let mut value = None;
+    storage.transaction(&mut || {
+        if let Some(steps) = steps.take() {
+            value = Some(steps()?);
+        }
+        Ok(())
+    })?;
+    log.log_command(command)?;
+    value.ok_or_else(|| anyhow::anyhow!("transaction for '{command}' didn't run"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+
+    #[derive(Default)]
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_apply_logs_once_or_rolls_back() {
+        let storage = InMemoryStorage::new();
+        let log = MockLog::default();
+
+        let created = apply(&storage, &log, "split docs", || {
+            storage.create_yak("docs/a")?;
+            storage.create_yak("docs/b")?;
+            Ok(2)
+        });
+        assert_eq!(created.unwrap(), 2);
+
+        let failed = apply(&storage, &log, "merge docs/a docs/b", || {
+            storage.delete_yak("docs/a")?;
+            storage.rename_yak("docs/missing", "docs/b")
+        });
+        assert!(failed.is_err());
+        assert!(storage.get_yak("docs/a").is_ok());
+        assert_eq!(*log.commands.borrow(), vec!["split docs"]);
+    }
+}
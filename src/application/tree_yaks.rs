@@ -0,0 +1,316 @@
+// TreeYaks use case - displays the yak hierarchy with branch-drawing glyphs
+
+use super::output_format::{json_array, json_object, json_optional, json_string};
+use super::{OutputFormat, StateLabels};
+use crate::domain::graph::{self, YakNode};
+use crate::ports::{OutputPort, StoragePort, Style};
+use anyhow::Result;
+
+pub struct TreeYaks<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    labels: StateLabels,
+    format: OutputFormat,
+}
+
+impl<'a> TreeYaks<'a> {
+    pub fn new(storage: &'a dyn StoragePort, output: &'a dyn OutputPort) -> Self {
+        Self {
+            storage,
+            output,
+            labels: StateLabels::default(),
+            format: OutputFormat::Markdown,
+        }
+    }
+
+    /// Show states with these labels instead of `[ ]` / `[x]`
+    pub fn with_state_labels(mut self, labels: StateLabels) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Render as markdown (colored), plain (no colors) or nested JSON
+    pub fn with_format(mut self, format: OutputFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Display the tree, limited to `depth` levels when given (1 = roots only)
+    pub fn execute(&self, depth: Option<usize>) -> Result<()> {
+        self.format.ensure(
+            "tree",
+            &[
+                OutputFormat::Markdown,
+                OutputFormat::Plain,
+                OutputFormat::Json,
+            ],
+        )?;
+        if depth == Some(0) {
+            anyhow::bail!("depth must be at least 1");
+        }
+
+        let yaks = self.storage.list_yaks()?;
+        if yaks.is_empty() && self.format != OutputFormat::Json {
+            self.output.note("You have no yaks. Are you done?");
+            return Ok(());
+        }
+
+        let mut roots = graph::build_tree(yaks);
+        graph::sort_tree(&mut roots, &graph::done_first);
+
+        if self.format == OutputFormat::Json {
+            let objects: Vec<String> = roots
+                .iter()
+                .map(|root| self.json_node(root, 1, 1, depth))
+                .collect();
+            self.output.info(&json_array(&objects, 0));
+            return Ok(());
+        }
+
+        for root in &roots {
+            self.display_node(root, "", "", 1, depth);
+        }
+
+        Ok(())
+    }
+
+    /// A node as a JSON object, nesting its children down to `depth` levels;
+    /// `indent` is how deep the object sits in the document
+    fn json_node(
+        &self,
+        node: &YakNode,
+        level: usize,
+        indent: usize,
+        depth: Option<usize>,
+    ) -> String {
+        let yak = node.yak.clone().unwrap_or_default();
+        let children: Vec<String> = if depth.is_some_and(|max| level >= max) {
+            Vec::new()
+        } else {
+            node.children
+                .iter()
+                .map(|child| self.json_node(child, level + 1, indent + 2, depth))
+                .collect()
+        };
+        json_object(
+            &[
+                ("name", json_string(&node.full_path)),
+                ("title", json_optional(yak.title.as_deref())),
+                ("state", json_string(&yak.current_state().to_string())),
+                ("children", json_array(&children, indent + 1)),
+            ],
+            indent,
+        )
+    }
+
+    /// Display a node and its children
+    /// `prefix` is drawn before this node, `child_prefix` before its children's glyphs
+    fn display_node(
+        &self,
+        node: &YakNode,
+        prefix: &str,
+        child_prefix: &str,
+        level: usize,
+        depth: Option<usize>,
+    ) {
+        let checkbox = self.labels.checkbox(node.is_done());
+        let collapsed = depth.is_some_and(|max| level >= max) && !node.children.is_empty();
+        let hidden = if collapsed {
+            format!(" (+{})", node.descendant_count())
+        } else {
+            String::new()
+        };
+
+        let title = node
+            .yak
+            .as_ref()
+            .and_then(|yak| yak.title.as_deref())
+            .unwrap_or(&node.name);
+        let label = format!("{checkbox} {title}{hidden}");
+        if node.is_done() && self.format == OutputFormat::Markdown {
+            self.output.info(&format!(
+                "{prefix}{}",
+                self.output.paint(&label, Style::Done)
+            ));
+        } else {
+            self.output.info(&format!("{prefix}{label}"));
+        }
+
+        if collapsed {
+            return;
+        }
+
+        let count = node.children.len();
+        for (index, child) in node.children.iter().enumerate() {
+            let last = index + 1 == count;
+            let (glyph, continuation) = if last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            self.display_node(
+                child,
+                &format!("{child_prefix}{glyph}"),
+                &format!("{child_prefix}{continuation}"),
+                level + 1,
+                depth,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl MockOutput {
+        fn new() -> Self {
+            Self {
+                messages: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn get_messages(&self) -> Vec<String> {
+            self.messages.borrow().clone()
+        }
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn error(&self, message: &str) {
+            self.messages
+                .borrow_mut()
+                .push(format!("ERROR: {}", message));
+        }
+
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn paint(&self, text: &str, style: Style) -> String {
+            format!("<{style:?}>{text}</>")
+        }
+    }
+
+    fn storage_with(names: &[&str]) -> InMemoryStorage {
+        let storage = InMemoryStorage::new();
+        for name in names {
+            storage.add_yak(Yak::new(name.to_string()));
+        }
+        storage
+    }
+
+    #[test]
+    fn test_tree_draws_branches() {
+        let storage = storage_with(&["app", "app/api", "app/api/auth", "app/web", "docs"]);
+        let output = MockOutput::new();
+        let use_case = TreeYaks::new(&storage, &output);
+
+        use_case.execute(None).unwrap();
+
+        assert_eq!(
+            output.get_messages(),
+            vec![
+                "[ ] app",
+                "├── [ ] api",
+                "│   └── [ ] auth",
+                "└── [ ] web",
+                "[ ] docs",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tree_grays_out_done_yaks() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("parent".to_string()));
+        storage.add_yak(Yak::new("parent/child".to_string()).mark_done());
+        let output = MockOutput::new();
+        let use_case = TreeYaks::new(&storage, &output);
+
+        use_case.execute(None).unwrap();
+
+        let messages = output.get_messages();
+        assert_eq!(messages[1], "└── <Done>[x] child</>");
+    }
+
+    #[test]
+    fn test_tree_shows_titles() {
+        let storage = InMemoryStorage::new();
+        storage.add_yak(Yak::new("app".to_string()));
+        storage.add_yak(Yak::new("app/login".to_string()).with_title("Fix: login? 🐛".to_string()));
+        let output = MockOutput::new();
+        let use_case = TreeYaks::new(&storage, &output);
+
+        use_case.execute(None).unwrap();
+
+        assert_eq!(output.get_messages()[1], "└── [ ] Fix: login? 🐛");
+    }
+
+    #[test]
+    fn test_tree_limits_depth() {
+        let storage = storage_with(&["app", "app/api", "app/api/auth", "app/web"]);
+        let output = MockOutput::new();
+        let use_case = TreeYaks::new(&storage, &output);
+
+        use_case.execute(Some(2)).unwrap();
+
+        assert_eq!(
+            output.get_messages(),
+            vec!["[ ] app", "├── [ ] api (+1)", "└── [ ] web"]
+        );
+    }
+
+    #[test]
+    fn test_tree_as_nested_json() {
+        let storage = storage_with(&["app", "app/api", "app/api/auth"]);
+        let output = MockOutput::new();
+
+        TreeYaks::new(&storage, &output)
+            .with_format(OutputFormat::Json)
+            .execute(Some(2))
+            .unwrap();
+
+        assert_eq!(
+            output.get_messages().join("\n"),
+            r#"[
+  {
+    "name": "app",
+    "title": null,
+    "state": "todo",
+    "children": [
+      {
+        "name": "app/api",
+        "title": null,
+        "state": "todo",
+        "children": []
+      }
+    ]
+  }
+]"#
+        );
+    }
+
+    #[test]
+    fn test_tree_rejects_zero_depth() {
+        let storage = storage_with(&["app"]);
+        let output = MockOutput::new();
+        let use_case = TreeYaks::new(&storage, &output);
+
+        assert!(use_case.execute(Some(0)).is_err());
+    }
+}
@@ -0,0 +1,99 @@
+// UnarchiveYak use case - brings an archived yak back into the active list
+
+use crate::ports::{ArchivePort, LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+
+pub struct UnarchiveYak<'a> {
+    archived: &'a dyn StoragePort,
+    log: &'a dyn LogPort,
+    archive: &'a dyn ArchivePort,
+}
+
+impl<'a> UnarchiveYak<'a> {
+    /// `archived` is the storage holding archived yaks, used to resolve the name
+    pub fn new(
+        archived: &'a dyn StoragePort,
+        _output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+        archive: &'a dyn ArchivePort,
+    ) -> Self {
+        Self {
+            archived,
+            log,
+            archive,
+        }
+    }
+
+    pub fn execute(&self, name: &str) -> Result<()> {
+        // Resolve against archived yaks (exact or fuzzy match)
+        let resolved_name = self.archived.find_yak(name)?;
+
+        self.archive.unarchive(&resolved_name)?;
+        self.log
+            .log_command(&format!("unarchive {resolved_name}"))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use crate::ports::LogEntry;
+    use std::cell::RefCell;
+
+    struct MockOutput;
+
+    impl OutputPort for MockOutput {
+        fn success(&self, _message: &str) {}
+        fn error(&self, _message: &str) {}
+        fn info(&self, _message: &str) {}
+
+        fn warn(&self, _message: &str) {}
+    }
+
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_unarchive_yak_resolves_archived_name_and_logs() {
+        let storage = InMemoryStorage::with_yaks([Yak::new("fix login bug".to_string())]);
+        storage.archive("fix login bug").unwrap();
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = UnarchiveYak::new(storage.archived(), &MockOutput, &log, &storage);
+
+        use_case.execute("login").unwrap();
+
+        assert!(storage.get_yak("fix login bug").is_ok());
+        assert!(storage.archived().list_yaks().unwrap().is_empty());
+        assert_eq!(*log.commands.borrow(), vec!["unarchive fix login bug"]);
+    }
+
+    #[test]
+    fn test_unarchive_unknown_yak_fails() {
+        let storage = InMemoryStorage::new();
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+        let use_case = UnarchiveYak::new(storage.archived(), &MockOutput, &log, &storage);
+
+        assert!(use_case.execute("nope").is_err());
+        assert!(log.commands.borrow().is_empty());
+    }
+}
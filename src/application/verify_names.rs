@@ -0,0 +1,229 @@
+// VerifyNames use case - finds yak folders whose names fail validation and renames them safely
+
+use crate::domain::{graph, sanitize_yak_name, validate_yak_name};
+use crate::ports::{LogPort, OutputPort, StoragePort};
+use anyhow::Result;
+use std::collections::HashSet;
+
+pub struct VerifyNames<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    log: &'a dyn LogPort,
+}
+
+impl<'a> VerifyNames<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        log: &'a dyn LogPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            log,
+        }
+    }
+
+    /// Report invalid names with a suggested replacement; `fix` applies the renames
+    pub fn execute(&self, fix: bool) -> Result<()> {
+        let renames = self.plan_renames()?;
+        if renames.is_empty() {
+            self.output.success("All yak names are valid");
+            return Ok(());
+        }
+
+        if !fix {
+            let noun = if renames.len() == 1 { "name" } else { "names" };
+            self.output
+                .info(&format!("Found {} invalid yak {noun}:", renames.len()));
+            for (from, to) in &renames {
+                self.output.info(&format!("  '{from}' -> '{to}'"));
+            }
+            self.output
+                .note("Run `yx verify-names --fix` to rename them");
+            return Ok(());
+        }
+
+        for (from, to) in &renames {
+            self.storage.rename_yak(from, to)?;
+            self.log.log_command(&format!("move {from} {to}"))?;
+            self.output.success(&format!("Renamed '{from}' -> '{to}'"));
+        }
+
+        Ok(())
+    }
+
+    /// Renames in the order they can be applied: parents first, each using the
+    /// names left by the renames before it
+    fn plan_renames(&self) -> Result<Vec<(String, String)>> {
+        let mut names: Vec<String> = self
+            .storage
+            .list_yaks()?
+            .into_iter()
+            .map(|yak| yak.name)
+            .collect();
+        names.sort_by(|a, b| {
+            a.matches('/')
+                .count()
+                .cmp(&b.matches('/').count())
+                .then(a.cmp(b))
+        });
+        let mut taken: HashSet<String> = names.iter().cloned().collect();
+
+        let mut renames: Vec<(String, String)> = Vec::new();
+        for name in &names {
+            // Follow renames of the folders above this one
+            let current = renames.iter().fold(name.clone(), |name, (from, to)| {
+                if graph::is_descendant_of(&name, from) {
+                    format!("{to}{}", &name[from.len()..])
+                } else {
+                    name
+                }
+            });
+
+            let (parent, leaf) = match current.rsplit_once('/') {
+                Some((parent, leaf)) => (Some(parent), leaf),
+                None => (None, current.as_str()),
+            };
+            if !leaf.is_empty() && validate_yak_name(leaf).is_ok() {
+                continue;
+            }
+
+            let fixed = sanitize_yak_name(leaf);
+            let candidate = |suffix: usize| {
+                let leaf = match suffix {
+                    1 => fixed.clone(),
+                    n => format!("{fixed}-{n}"),
+                };
+                match parent {
+                    Some(parent) => format!("{parent}/{leaf}"),
+                    None => leaf,
+                }
+            };
+            let target = (1..)
+                .map(candidate)
+                .find(|target| !taken.contains(target))
+                .expect("some suffix is always free");
+
+            taken.insert(target.clone());
+            renames.push((current, target));
+        }
+
+        Ok(renames)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use crate::ports::LogEntry;
+    use std::cell::RefCell;
+
+    struct MockOutput {
+        messages: RefCell<Vec<String>>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+        fn error(&self, _message: &str) {}
+        fn info(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+
+        fn warn(&self, message: &str) {
+            self.messages.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct MockLog {
+        commands: RefCell<Vec<String>>,
+    }
+
+    impl LogPort for MockLog {
+        fn log_command(&self, command: &str) -> Result<()> {
+            self.commands.borrow_mut().push(command.to_string());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<LogEntry>> {
+            unimplemented!()
+        }
+    }
+
+    fn storage_with(names: &[&str]) -> InMemoryStorage {
+        InMemoryStorage::with_yaks(names.iter().map(|name| Yak::new(name.to_string())))
+    }
+
+    #[test]
+    fn test_verify_names_reports_without_renaming() {
+        let storage = storage_with(&["ok", "why?", "why"]);
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+
+        VerifyNames::new(&storage, &output, &log)
+            .execute(false)
+            .unwrap();
+
+        assert_eq!(
+            *output.messages.borrow(),
+            vec![
+                "Found 1 invalid yak name:",
+                "  'why?' -> 'why-2'",
+                "Run `yx verify-names --fix` to rename them",
+            ]
+        );
+        assert!(log.commands.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_verify_names_fix_renames_parents_first() {
+        let storage = storage_with(&["a:b", "a:b/c|d", "fine"]);
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+
+        VerifyNames::new(&storage, &output, &log)
+            .execute(true)
+            .unwrap();
+
+        let names: Vec<String> = storage
+            .list_yaks()
+            .unwrap()
+            .into_iter()
+            .map(|yak| yak.name)
+            .collect();
+        assert_eq!(names, vec!["a-b", "a-b/c-d", "fine"]);
+        assert_eq!(
+            *log.commands.borrow(),
+            vec!["move a:b a-b", "move a-b/c|d a-b/c-d"]
+        );
+    }
+
+    #[test]
+    fn test_verify_names_all_valid() {
+        let storage = storage_with(&["ok", "ok/fine"]);
+        let output = MockOutput {
+            messages: RefCell::new(Vec::new()),
+        };
+        let log = MockLog {
+            commands: RefCell::new(Vec::new()),
+        };
+
+        VerifyNames::new(&storage, &output, &log)
+            .execute(false)
+            .unwrap();
+
+        assert_eq!(*output.messages.borrow(), vec!["All yak names are valid"]);
+    }
+}
@@ -0,0 +1,269 @@
+// WatchYaks use case - redraws a view of the yaks whenever they change, for a dashboard pane
+
+use crate::ports::{OutputPort, StoragePort, TimerPort, WatcherPort};
+use anyhow::Result;
+use chrono::Local;
+use std::time::Duration;
+
+/// How often to check for changes without `with_interval`
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The longest wait between checks without `with_interval` when a watcher
+/// wakes the watch on changes, in case it missed one
+const WATCHED_INTERVAL: Duration = Duration::from_secs(60);
+
+pub struct WatchYaks<'a> {
+    storage: &'a dyn StoragePort,
+    output: &'a dyn OutputPort,
+    timer: &'a dyn TimerPort,
+    interval: Option<Duration>,
+    watcher: Option<&'a dyn WatcherPort>,
+}
+
+impl<'a> WatchYaks<'a> {
+    pub fn new(
+        storage: &'a dyn StoragePort,
+        output: &'a dyn OutputPort,
+        timer: &'a dyn TimerPort,
+    ) -> Self {
+        Self {
+            storage,
+            output,
+            timer,
+            interval: None,
+            watcher: None,
+        }
+    }
+
+    /// Check for changes at least this often
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Check as soon as the watcher sees a change, rather than only every interval
+    pub fn with_watcher(mut self, watcher: &'a dyn WatcherPort) -> Self {
+        self.watcher = Some(watcher);
+        self
+    }
+
+    /// Draw with `render`, then redraw each time the yaks change, until interrupted
+    /// Storages that can't tell when they changed are redrawn every interval.
+    pub fn execute(&self, render: &dyn Fn() -> Result<()>) -> Result<()> {
+        let interval = self.interval.unwrap_or(match self.watcher {
+            Some(_) => WATCHED_INTERVAL,
+            None => DEFAULT_INTERVAL,
+        });
+        if interval.is_zero() {
+            anyhow::bail!("interval must be more than 0 seconds");
+        }
+
+        let mut drawn = None;
+        loop {
+            let revision = self.storage.revision()?;
+            if revision.is_none() || drawn != Some(revision) {
+                self.output.clear();
+                self.output.info(&format!(
+                    "Updated {} - Ctrl-C to stop",
+                    Local::now().format("%H:%M:%S")
+                ));
+                self.output.info("");
+                render()?;
+                drawn = Some(revision);
+            }
+            match self.watcher {
+                Some(watcher) => watcher.wait_for_change(interval)?,
+                None => self.timer.wait(interval)?,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::storage::InMemoryStorage;
+    use crate::domain::Yak;
+    use std::cell::{Cell, RefCell};
+
+    /// Yaks in memory, from a storage that can't tell when they changed
+    struct Unversioned<'a>(&'a InMemoryStorage);
+
+    impl StoragePort for Unversioned<'_> {
+        fn create_yak(&self, name: &str) -> Result<()> {
+            self.0.create_yak(name)
+        }
+
+        fn get_yak(&self, name: &str) -> Result<Yak> {
+            self.0.get_yak(name)
+        }
+
+        fn list_yaks(&self) -> Result<Vec<Yak>> {
+            self.0.list_yaks()
+        }
+
+        fn mark_done(&self, name: &str, done: bool) -> Result<()> {
+            self.0.mark_done(name, done)
+        }
+
+        fn delete_yak(&self, name: &str) -> Result<()> {
+            self.0.delete_yak(name)
+        }
+
+        fn rename_yak(&self, from: &str, to: &str) -> Result<()> {
+            self.0.rename_yak(from, to)
+        }
+
+        fn copy_yak(&self, from: &str, to: &str) -> Result<()> {
+            self.0.copy_yak(from, to)
+        }
+
+        fn read_context(&self, name: &str) -> Result<String> {
+            self.0.read_context(name)
+        }
+
+        fn write_context(&self, name: &str, text: &str) -> Result<()> {
+            self.0.write_context(name, text)
+        }
+
+        fn read_field(&self, name: &str, field: &str) -> Result<Option<String>> {
+            self.0.read_field(name, field)
+        }
+
+        fn write_field(&self, name: &str, field: &str, value: Option<&str>) -> Result<()> {
+            self.0.write_field(name, field, value)
+        }
+
+        fn list_fields(&self, name: &str) -> Result<Vec<String>> {
+            self.0.list_fields(name)
+        }
+
+        fn find_yak(&self, name: &str) -> Result<String> {
+            self.0.find_yak(name)
+        }
+    }
+
+    struct MockOutput {
+        clears: Cell<usize>,
+    }
+
+    impl OutputPort for MockOutput {
+        fn success(&self, _message: &str) {}
+
+        fn error(&self, _message: &str) {}
+
+        fn info(&self, _message: &str) {}
+
+        fn warn(&self, _message: &str) {}
+
+        fn clear(&self) {
+            self.clears.set(self.clears.get() + 1);
+        }
+    }
+
+    /// Adds a yak after each of the waits in `changes`, and stops the watch,
+    /// like Ctrl-C, after a number of waits
+    struct MockTimer<'a> {
+        storage: &'a InMemoryStorage,
+        changes: Vec<usize>,
+        waits: RefCell<Vec<Duration>>,
+        stop_after: usize,
+    }
+
+    impl TimerPort for MockTimer<'_> {
+        fn wait(&self, duration: Duration) -> Result<()> {
+            self.waits.borrow_mut().push(duration);
+            let waits = self.waits.borrow().len();
+            if waits >= self.stop_after {
+                anyhow::bail!("interrupted");
+            }
+            if self.changes.contains(&waits) {
+                self.storage.create_yak(&format!("yak {waits}"))?;
+            }
+            Ok(())
+        }
+
+        fn notify(&self, _title: &str, _message: &str) {}
+    }
+
+    impl WatcherPort for MockTimer<'_> {
+        fn wait_for_change(&self, timeout: Duration) -> Result<()> {
+            self.wait(timeout)
+        }
+    }
+
+    /// Fails if the watch sleeps instead of waiting on its watcher
+    struct NoSleep;
+
+    impl TimerPort for NoSleep {
+        fn wait(&self, _duration: Duration) -> Result<()> {
+            panic!("slept instead of waiting for a change");
+        }
+
+        fn notify(&self, _title: &str, _message: &str) {}
+    }
+
+    fn watch(versioned: bool, changes: Vec<usize>, stop_after: usize) -> (usize, usize) {
+        let memory = InMemoryStorage::new();
+        let unversioned = Unversioned(&memory);
+        let storage: &dyn StoragePort = if versioned { &memory } else { &unversioned };
+        let output = MockOutput {
+            clears: Cell::new(0),
+        };
+        let timer = MockTimer {
+            storage: &memory,
+            changes,
+            waits: RefCell::new(Vec::new()),
+            stop_after,
+        };
+        let renders = Cell::new(0);
+
+        let result = WatchYaks::new(storage, &output, &timer)
+            .with_interval(Duration::from_millis(250))
+            .execute(&|| {
+                renders.set(renders.get() + 1);
+                Ok(())
+            });
+
+        assert!(result.is_err());
+        assert!(timer
+            .waits
+            .borrow()
+            .iter()
+            .all(|wait| *wait == Duration::from_millis(250)));
+        (renders.get(), output.clears.get())
+    }
+
+    #[test]
+    fn test_watch_redraws_only_when_the_yaks_change() {
+        assert_eq!(watch(true, vec![2], 5), (2, 2));
+        assert_eq!(watch(false, vec![], 3), (3, 3));
+    }
+
+    #[test]
+    fn test_watch_waits_on_the_watcher_when_given_one() {
+        let storage = InMemoryStorage::new();
+        let output = MockOutput {
+            clears: Cell::new(0),
+        };
+        let watcher = MockTimer {
+            storage: &storage,
+            changes: vec![1, 3],
+            waits: RefCell::new(Vec::new()),
+            stop_after: 4,
+        };
+        let renders = Cell::new(0);
+
+        let result = WatchYaks::new(&storage, &output, &NoSleep)
+            .with_watcher(&watcher)
+            .execute(&|| {
+                renders.set(renders.get() + 1);
+                Ok(())
+            });
+
+        assert!(result.is_err());
+        assert_eq!(renders.get(), 3);
+        // Events wake it, so it only checks on its own now and then
+        assert_eq!(watcher.waits.borrow()[0], WATCHED_INTERVAL);
+    }
+}
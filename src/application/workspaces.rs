@@ -0,0 +1,138 @@
+// Workspaces - the repositories a person works across, configured in git config
+
+use crate::ports::{ConfigPort, OutputPort, Verbosity};
+use std::path::PathBuf;
+
+/// Multi-valued config key listing workspace repositories
+/// e.g. `git config --global --add yaks.workspace ~/src/app`
+pub const WORKSPACES_KEY: &str = "yaks.workspace";
+
+/// Error for `yx all` commands when nothing is configured
+pub(super) const NO_WORKSPACES: &str =
+    "no workspaces configured - add one with `git config --global --add yaks.workspace <repo path>`";
+
+/// A configured repository, named after its directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workspace {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// Workspaces in config order, with `~/` expanded
+pub fn configured_workspaces(config: &dyn ConfigPort) -> Vec<Workspace> {
+    config
+        .get_all(WORKSPACES_KEY)
+        .iter()
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+        .map(|value| {
+            let path = match (value.strip_prefix("~/"), std::env::var("HOME")) {
+                (Some(rest), Ok(home)) => PathBuf::from(home).join(rest),
+                _ => PathBuf::from(value),
+            };
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| value.to_string());
+            Workspace { name, path }
+        })
+        .collect()
+}
+
+/// Prefixes every line with the workspace it came from
+pub struct PrefixedOutput<'a> {
+    prefix: String,
+    output: &'a dyn OutputPort,
+}
+
+impl<'a> PrefixedOutput<'a> {
+    pub fn new(workspace: &str, output: &'a dyn OutputPort) -> Self {
+        Self {
+            prefix: format!("[{workspace}] "),
+            output,
+        }
+    }
+}
+
+impl OutputPort for PrefixedOutput<'_> {
+    fn success(&self, message: &str) {
+        self.output.success(&format!("{}{message}", self.prefix));
+    }
+
+    fn error(&self, message: &str) {
+        self.output.error(&format!("{}{message}", self.prefix));
+    }
+
+    fn info(&self, message: &str) {
+        self.output.info(&format!("{}{message}", self.prefix));
+    }
+
+    fn note(&self, message: &str) {
+        self.output.note(&format!("{}{message}", self.prefix));
+    }
+
+    fn warn(&self, message: &str) {
+        self.output.warn(&format!("{}{message}", self.prefix));
+    }
+
+    fn hint(&self, message: &str) {
+        self.output.hint(message);
+    }
+
+    fn detail(&self, message: &str) {
+        self.output.detail(&format!("{}{message}", self.prefix));
+    }
+
+    fn verbosity(&self) -> Verbosity {
+        self.output.verbosity()
+    }
+
+    fn progress(&self, message: &str) {
+        self.output.progress(&format!("{}{message}", self.prefix));
+    }
+
+    fn progress_done(&self) {
+        self.output.progress_done();
+    }
+
+    fn confirm(&self, question: &str) -> Option<bool> {
+        self.output.confirm(&format!("{}{question}", self.prefix))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockConfig(Vec<&'static str>);
+
+    impl ConfigPort for MockConfig {
+        fn get(&self, _key: &str) -> Option<String> {
+            unimplemented!()
+        }
+
+        fn get_all(&self, key: &str) -> Vec<String> {
+            assert_eq!(key, WORKSPACES_KEY);
+            self.0.iter().map(|value| value.to_string()).collect()
+        }
+    }
+
+    #[test]
+    fn test_configured_workspaces() {
+        let config = MockConfig(vec!["/src/app", " /src/infra/ ", ""]);
+
+        assert_eq!(
+            configured_workspaces(&config),
+            vec![
+                Workspace {
+                    name: "app".to_string(),
+                    path: PathBuf::from("/src/app"),
+                },
+                Workspace {
+                    name: "infra".to_string(),
+                    path: PathBuf::from("/src/infra/"),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,219 @@
+// Markdown checklists - TODO.md-style nested `- [ ]` lists parsed into yak names
+
+/// Columns a tab counts for when comparing indentation
+const TAB_WIDTH: usize = 4;
+
+/// A checkbox item, named by its path through the enclosing items
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecklistItem {
+    /// Full yak name, e.g. "release/write notes" for an item nested under "release"
+    pub name: String,
+    pub done: bool,
+}
+
+/// Parse every checkbox item in a markdown document, in document order
+/// Nesting follows indentation; lines that aren't checkbox items are ignored,
+/// so headings and plain bullets don't break up the hierarchy.
+pub fn parse_checklist(text: &str) -> Vec<ChecklistItem> {
+    nest(text.lines().filter_map(parse_checkbox))
+}
+
+/// Parse a plain outline, one name per non-blank line
+/// Nesting follows indentation, and a leading `- `, `* ` or `+ ` bullet is dropped,
+/// so pasted meeting notes work as well as bare lines.
+pub fn parse_outline(text: &str) -> Vec<String> {
+    nest(text.lines().filter_map(|line| {
+        let label = strip_bullet(line.trim_start()).unwrap_or(line).trim();
+        (!label.is_empty()).then_some((indent_of(line), false, label))
+    }))
+    .into_iter()
+    .map(|item| item.name)
+    .collect()
+}
+
+/// Pull the bullet items (`- item`, `- [ ] item`, `- [x] item`) out of a yak's context
+/// Returns the items, nested by indentation, and the context without them.
+/// Lines inside ``` fences are never treated as bullets.
+pub fn split_bullets(text: &str) -> (Vec<ChecklistItem>, String) {
+    let mut bullets = Vec::new();
+    let mut rest: Vec<&str> = Vec::new();
+    let mut in_fence = false;
+
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        match parse_bullet(line).filter(|_| !in_fence) {
+            Some(bullet) => bullets.push(bullet),
+            // Don't leave runs of blank lines where bullets used to be
+            None if line.trim().is_empty() && rest.last().is_none_or(|l| l.trim().is_empty()) => {}
+            None => rest.push(line),
+        }
+    }
+    while rest.last().is_some_and(|line| line.trim().is_empty()) {
+        rest.pop();
+    }
+
+    let rest = if rest.is_empty() {
+        String::new()
+    } else {
+        format!("{}\n", rest.join("\n"))
+    };
+    (nest(bullets.into_iter()), rest)
+}
+
+/// Name each (indent, done, label) line by its path through the enclosing lines
+fn nest<'a>(lines: impl Iterator<Item = (usize, bool, &'a str)>) -> Vec<ChecklistItem> {
+    let mut items = Vec::new();
+    // (indent, full name) of the items enclosing the current line
+    let mut parents: Vec<(usize, String)> = Vec::new();
+
+    for (indent, done, label) in lines {
+        while parents.last().is_some_and(|(depth, _)| *depth >= indent) {
+            parents.pop();
+        }
+        let name = match parents.last() {
+            Some((_, parent)) => format!("{parent}/{label}"),
+            None => label.to_string(),
+        };
+
+        parents.push((indent, name.clone()));
+        items.push(ChecklistItem { name, done });
+    }
+
+    items
+}
+
+fn indent_of(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+        .sum()
+}
+
+fn strip_bullet(text: &str) -> Option<&str> {
+    text.strip_prefix("- ")
+        .or_else(|| text.strip_prefix("* "))
+        .or_else(|| text.strip_prefix("+ "))
+}
+
+/// Parse a `- label` or `- [ ] label` line into (indent, done, label)
+fn parse_bullet(line: &str) -> Option<(usize, bool, &str)> {
+    parse_checkbox(line).or_else(|| {
+        let label = strip_bullet(line.trim_start())?.trim();
+        (!label.is_empty()).then_some((indent_of(line), false, label))
+    })
+}
+
+/// Parse a `- [ ] label` line into (indent, done, label)
+fn parse_checkbox(line: &str) -> Option<(usize, bool, &str)> {
+    let indent = indent_of(line);
+    let rest = strip_bullet(line.trim_start())?;
+    let (done, label) = if let Some(label) = rest.strip_prefix("[ ]") {
+        (false, label)
+    } else if let Some(label) = rest
+        .strip_prefix("[x]")
+        .or_else(|| rest.strip_prefix("[X]"))
+    {
+        (true, label)
+    } else {
+        return None;
+    };
+
+    let label = label.trim();
+    if label.is_empty() {
+        return None;
+    }
+    Some((indent, done, label))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(name: &str, done: bool) -> ChecklistItem {
+        ChecklistItem {
+            name: name.to_string(),
+            done,
+        }
+    }
+
+    #[test]
+    fn test_parse_nested_checklist() {
+        let text = "\
+# TODO
+
+- [ ] release
+  - [x] write notes
+  - [ ] tag
+    * [ ] push tag
+- [X] fix login
+\t- [ ] add test
+";
+
+        assert_eq!(
+            parse_checklist(text),
+            vec![
+                item("release", false),
+                item("release/write notes", true),
+                item("release/tag", false),
+                item("release/tag/push tag", false),
+                item("fix login", true),
+                item("fix login/add test", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_checklist_skips_other_lines() {
+        let text = "- plain bullet\n  - [ ] nested under nothing\n- [ ]\nSome prose\n";
+
+        assert_eq!(
+            parse_checklist(text),
+            vec![item("nested under nothing", false)]
+        );
+    }
+
+    #[test]
+    fn test_parse_outline_nests_by_indentation() {
+        let text = "release\n  write notes\n\n  - tag\n\t\tpush tag\nfix login\n";
+
+        assert_eq!(
+            parse_outline(text),
+            vec![
+                "release",
+                "release/write notes",
+                "release/tag",
+                "release/tag/push tag",
+                "fix login",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_bullets_keeps_prose_and_code() {
+        let text = "\
+Ship the release.
+
+- [ ] write notes
+  - check links
+- [x] tag
+
+```
+- not a bullet
+```
+";
+
+        let (items, rest) = split_bullets(text);
+
+        assert_eq!(
+            items,
+            vec![
+                item("write notes", false),
+                item("write notes/check links", false),
+                item("tag", true),
+            ]
+        );
+        assert_eq!(rest, "Ship the release.\n\n```\n- not a bullet\n```\n");
+    }
+}
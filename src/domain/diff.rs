@@ -0,0 +1,157 @@
+// Line diffs - unified, git-style differences between two versions of a text
+
+/// Unchanged lines shown around each change
+const CONTEXT_LINES: usize = 3;
+
+/// One line of a unified diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    /// The start of a hunk with the lines it covers, e.g. "@@ -1,4 +1,5 @@"
+    Hunk(String),
+    Context(String),
+    Removed(String),
+    Added(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// The changes from `old` to `new`, line by line, in hunks with a few
+/// unchanged lines around each; empty when the texts have the same lines
+pub fn unified_diff(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let steps = edit_script(&old, &new);
+
+    let changes: Vec<usize> = (0..steps.len())
+        .filter(|&k| steps[k].0 != Op::Equal)
+        .collect();
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for k in changes {
+        let start = k.saturating_sub(CONTEXT_LINES);
+        let end = (k + CONTEXT_LINES + 1).min(steps.len());
+        match hunks.last_mut() {
+            // Changes close enough to share context make one hunk
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut lines = Vec::new();
+    for (start, end) in hunks {
+        let hunk = &steps[start..end];
+        let old_count = hunk.iter().filter(|step| step.0 != Op::Insert).count();
+        let new_count = hunk.iter().filter(|step| step.0 != Op::Delete).count();
+        let (_, old_at, new_at) = hunk[0];
+        lines.push(DiffLine::Hunk(format!(
+            "@@ -{} +{} @@",
+            range(old_at, old_count),
+            range(new_at, new_count)
+        )));
+        for &(op, i, j) in hunk {
+            lines.push(match op {
+                Op::Equal => DiffLine::Context(old[i].to_string()),
+                Op::Delete => DiffLine::Removed(old[i].to_string()),
+                Op::Insert => DiffLine::Added(new[j].to_string()),
+            });
+        }
+    }
+    lines
+}
+
+/// The steps turning `old` into `new`, each with the old and new line it's
+/// at, keeping the longest run of common lines; removals come before additions
+fn edit_script(old: &[&str], new: &[&str]) -> Vec<(Op, usize, usize)> {
+    // common[i][j]: how many lines old[i..] and new[j..] have in common
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut steps = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            steps.push((Op::Equal, i, j));
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && common[i + 1][j] >= common[i][j + 1]) {
+            steps.push((Op::Delete, i, j));
+            i += 1;
+        } else {
+            steps.push((Op::Insert, i, j));
+            j += 1;
+        }
+    }
+    steps
+}
+
+/// A hunk's lines on one side as git writes them: "3,4" for four lines from
+/// line 3, "3" for just line 3, "2,0" for none after line 2
+fn range(at: usize, count: usize) -> String {
+    match count {
+        0 => format!("{at},0"),
+        1 => format!("{}", at + 1),
+        _ => format!("{},{count}", at + 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(lines: &[DiffLine]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| match line {
+                DiffLine::Hunk(text) => text.clone(),
+                DiffLine::Context(text) => format!(" {text}"),
+                DiffLine::Removed(text) => format!("-{text}"),
+                DiffLine::Added(text) => format!("+{text}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_unified_diff_shows_changes_with_context() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\n";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\n";
+
+        assert_eq!(
+            render(&unified_diff(old, new)),
+            vec![
+                "@@ -1,5 +1,5 @@",
+                " a",
+                "-b",
+                "+B",
+                " c",
+                " d",
+                " e",
+                "@@ -9,4 +9,3 @@",
+                " i",
+                " j",
+                " k",
+                "-l",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unified_diff_of_empty_and_equal_texts() {
+        assert!(unified_diff("same\n", "same").is_empty());
+        assert_eq!(
+            render(&unified_diff("", "new\n")),
+            vec!["@@ -0,0 +1 @@", "+new"]
+        );
+    }
+}
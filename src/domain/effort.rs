@@ -0,0 +1,123 @@
+// Effort estimates - parsing durations and grouping them into size buckets
+
+use std::fmt;
+
+/// Minutes in a working day, used for "1d" estimates
+const MINUTES_PER_DAY: u32 = 8 * 60;
+
+/// Rough size of a piece of work
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EffortBucket {
+    /// Up to an hour
+    Small,
+    /// Up to half a day
+    Medium,
+    /// More than half a day
+    Large,
+}
+
+impl EffortBucket {
+    pub fn from_minutes(minutes: u32) -> Self {
+        match minutes {
+            0..=60 => Self::Small,
+            61..=240 => Self::Medium,
+            _ => Self::Large,
+        }
+    }
+
+    /// Heading describing the bucket's range
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Small => "S (up to 1h)",
+            Self::Medium => "M (1h to 4h)",
+            Self::Large => "L (over 4h)",
+        }
+    }
+}
+
+/// An estimate in minutes, written like "30m", "2h", "1h30m" or "1d" (8h)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Estimate(pub u32);
+
+impl Estimate {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        let invalid = || format!("Invalid estimate '{text}': use e.g. 30m, 2h, 1h30m or 1d");
+
+        let mut minutes = 0u32;
+        let mut number = String::new();
+        for c in text.chars() {
+            if c.is_ascii_digit() {
+                number.push(c);
+                continue;
+            }
+            let value: u32 = number.parse().map_err(|_| invalid())?;
+            let unit = match c {
+                'm' => 1,
+                'h' => 60,
+                'd' => MINUTES_PER_DAY,
+                _ => return Err(invalid()),
+            };
+            minutes = value
+                .checked_mul(unit)
+                .and_then(|m| minutes.checked_add(m))
+                .ok_or_else(invalid)?;
+            number.clear();
+        }
+
+        // A trailing number without a unit, or nothing at all
+        if !number.is_empty() || minutes == 0 {
+            return Err(invalid());
+        }
+
+        Ok(Self(minutes))
+    }
+
+    pub fn bucket(&self) -> EffortBucket {
+        EffortBucket::from_minutes(self.0)
+    }
+}
+
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (hours, minutes) = (self.0 / 60, self.0 % 60);
+        match (hours, minutes) {
+            (0, m) => write!(f, "{m}m"),
+            (h, 0) => write!(f, "{h}h"),
+            (h, m) => write!(f, "{h}h{m}m"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_estimate() {
+        assert_eq!(Estimate::parse("30m"), Ok(Estimate(30)));
+        assert_eq!(Estimate::parse("2h"), Ok(Estimate(120)));
+        assert_eq!(Estimate::parse(" 1h30m "), Ok(Estimate(90)));
+        assert_eq!(Estimate::parse("1d"), Ok(Estimate(480)));
+        assert!(Estimate::parse("30").is_err());
+        assert!(Estimate::parse("").is_err());
+        assert!(Estimate::parse("0m").is_err());
+        assert!(Estimate::parse("soon").is_err());
+    }
+
+    #[test]
+    fn test_estimate_display_round_trips() {
+        for text in ["30m", "2h", "1h30m", "8h"] {
+            assert_eq!(Estimate::parse(text).unwrap().to_string(), text);
+        }
+        assert_eq!(Estimate::parse("1d").unwrap().to_string(), "8h");
+    }
+
+    #[test]
+    fn test_buckets() {
+        assert_eq!(Estimate(30).bucket(), EffortBucket::Small);
+        assert_eq!(Estimate(60).bucket(), EffortBucket::Small);
+        assert_eq!(Estimate(90).bucket(), EffortBucket::Medium);
+        assert_eq!(Estimate(480).bucket(), EffortBucket::Large);
+    }
+}
@@ -0,0 +1,111 @@
+// Yak filter - which yaks `--only` picks out, by completion, state and due date
+
+use super::{Yak, YakState};
+use chrono::NaiveDate;
+
+/// One kind of yak `--only` can pick out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Only {
+    Done,
+    NotDone,
+    Doing,
+    Blocked,
+    /// Open, not blocked and not waiting on open children: what `yx suggest` picks from
+    Ready,
+    /// Open and due before today
+    Overdue,
+}
+
+const NAMES: [(&str, Only); 6] = [
+    ("done", Only::Done),
+    ("not-done", Only::NotDone),
+    ("doing", Only::Doing),
+    ("blocked", Only::Blocked),
+    ("ready", Only::Ready),
+    ("overdue", Only::Overdue),
+];
+
+impl Only {
+    /// Whether `yak` is of this kind; `children_done` says whether every yak
+    /// directly under it is done (true when it has none)
+    pub fn matches(self, yak: &Yak, children_done: bool, today: NaiveDate) -> bool {
+        match self {
+            Only::Done => yak.done,
+            Only::NotDone => !yak.done,
+            Only::Doing => yak.current_state() == YakState::Doing,
+            Only::Blocked => yak.current_state() == YakState::Blocked,
+            Only::Ready => !yak.done && yak.current_state() != YakState::Blocked && children_done,
+            Only::Overdue => !yak.done && yak.due.is_some_and(|due| due < today),
+        }
+    }
+}
+
+/// An `--only` filter: comma-separated kinds, matching a yak of any of them,
+/// e.g. `doing,blocked`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YakFilter(Vec<Only>);
+
+impl YakFilter {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let kinds = text
+            .split(',')
+            .map(|part| {
+                let wanted = part.trim().to_lowercase();
+                NAMES
+                    .iter()
+                    .find(|(name, _)| *name == wanted)
+                    .map(|(_, kind)| *kind)
+                    .ok_or_else(|| {
+                        let names: Vec<&str> = NAMES.iter().map(|(name, _)| *name).collect();
+                        format!("Invalid filter '{}': use {}", part.trim(), names.join(", "))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self(kinds))
+    }
+
+    pub fn matches(&self, yak: &Yak, children_done: bool, today: NaiveDate) -> bool {
+        self.0
+            .iter()
+            .any(|kind| kind.matches(yak, children_done, today))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_matches_any_kind() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+        let doing = Yak::new("doing".to_string()).with_state(YakState::Doing);
+        let blocked = Yak::new("blocked".to_string()).with_state(YakState::Blocked);
+        let late = Yak {
+            due: NaiveDate::from_ymd_opt(2026, 10, 16),
+            ..Yak::new("late".to_string())
+        };
+        let done_late = late.clone().mark_done();
+
+        let filter = YakFilter::parse("doing, Overdue").unwrap();
+        assert!(filter.matches(&doing, true, today));
+        assert!(filter.matches(&late, true, today));
+        assert!(!filter.matches(&done_late, true, today));
+        assert!(!filter.matches(&blocked, true, today));
+
+        let ready = YakFilter::parse("ready").unwrap();
+        assert!(ready.matches(&doing, true, today));
+        assert!(!ready.matches(&doing, false, today));
+        assert!(!ready.matches(&blocked, true, today));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_kinds() {
+        assert_eq!(
+            YakFilter::parse("done,soon"),
+            Err(
+                "Invalid filter 'soon': use done, not-done, doing, blocked, ready, overdue"
+                    .to_string()
+            )
+        );
+    }
+}
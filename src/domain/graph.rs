@@ -0,0 +1,231 @@
+// Yak graph - hierarchy queries over the flat list of yaks
+//
+// Yaks form a tree through their path names ("parent/child"). These helpers
+// give every use case the same notion of parent, child and descendant.
+
+use super::{Yak, YakState};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Represents a node in the yak hierarchy tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YakNode {
+    /// Just the leaf name (e.g., "child" not "parent/child")
+    pub name: String,
+    /// Full path (e.g., "parent/child")
+    pub full_path: String,
+    /// None for implicit parents
+    pub yak: Option<Yak>,
+    pub children: Vec<YakNode>,
+}
+
+impl YakNode {
+    /// Whether this node is a yak that has been marked done
+    pub fn is_done(&self) -> bool {
+        self.yak.as_ref().map(|y| y.done).unwrap_or(false)
+    }
+
+    /// The yak's state; implicit parents count as todo
+    pub fn state(&self) -> YakState {
+        self.yak.as_ref().map_or(YakState::Todo, Yak::current_state)
+    }
+
+    /// Number of nodes below this one (children, grandchildren, ...)
+    pub fn descendant_count(&self) -> usize {
+        self.children
+            .iter()
+            .map(|child| 1 + child.descendant_count())
+            .sum()
+    }
+}
+
+/// Parent path of a yak name (e.g., "a/b/c" -> Some("a/b"), "a" -> None)
+pub fn parent_of(name: &str) -> Option<&str> {
+    name.rsplit_once('/').map(|(parent, _)| parent)
+}
+
+/// Whether `name` sits anywhere below `ancestor` in the hierarchy
+pub fn is_descendant_of(name: &str, ancestor: &str) -> bool {
+    name.len() > ancestor.len()
+        && name.starts_with(ancestor)
+        && name.as_bytes()[ancestor.len()] == b'/'
+}
+
+/// Direct children of a yak
+pub fn children_of<'a>(yaks: &'a [Yak], name: &str) -> Vec<&'a Yak> {
+    yaks.iter()
+        .filter(|yak| parent_of(&yak.name) == Some(name))
+        .collect()
+}
+
+/// Whether every direct child of a yak is done (true when it has none)
+pub fn children_done(yaks: &[Yak], name: &str) -> bool {
+    children_of(yaks, name).iter().all(|child| child.done)
+}
+
+/// All yaks below a yak (children, grandchildren, ...)
+pub fn descendants_of<'a>(yaks: &'a [Yak], name: &str) -> Vec<&'a Yak> {
+    yaks.iter()
+        .filter(|yak| is_descendant_of(&yak.name, name))
+        .collect()
+}
+
+/// Build a hierarchical tree from flat list of yaks
+/// Missing intermediate yaks become implicit parent nodes (yak: None).
+/// Nodes are returned unsorted - use `sort_tree` to order them.
+pub fn build_tree(yaks: Vec<Yak>) -> Vec<YakNode> {
+    let mut nodes_by_path: HashMap<String, YakNode> = HashMap::new();
+
+    // First pass: create nodes for all yaks and implicit parents
+    for yak in yaks {
+        let parts: Vec<&str> = yak.name.split('/').collect();
+
+        // Create implicit parent nodes if they don't exist
+        for i in 1..parts.len() {
+            let parent_path = parts[..i].join("/");
+            nodes_by_path
+                .entry(parent_path.clone())
+                .or_insert_with(|| YakNode {
+                    name: parts[i - 1].to_string(),
+                    full_path: parent_path,
+                    yak: None, // Implicit parent (no actual yak)
+                    children: Vec::new(),
+                });
+        }
+
+        // Create node for this yak (replacing any implicit placeholder)
+        let name = parts.last().unwrap_or(&"").to_string();
+        let full_path = yak.name.clone();
+        nodes_by_path.insert(
+            full_path.clone(),
+            YakNode {
+                name,
+                full_path,
+                yak: Some(yak),
+                children: Vec::new(),
+            },
+        );
+    }
+
+    // Second pass: build parent-child relationships
+    // Sort paths by depth (deepest first) to ensure children are processed before parents
+    let mut all_paths: Vec<String> = nodes_by_path.keys().cloned().collect();
+    all_paths.sort_by_key(|p| std::cmp::Reverse(p.matches('/').count()));
+
+    for path in &all_paths {
+        let Some(parent_path) = parent_of(path) else {
+            // Root node - leave it
+            continue;
+        };
+
+        // Remove child from map and attach to parent
+        if let Some(child_node) = nodes_by_path.remove(path) {
+            if let Some(parent_node) = nodes_by_path.get_mut(parent_path) {
+                parent_node.children.push(child_node);
+            } else {
+                // This shouldn't happen since we created all parents in first pass
+                // But if it does, put the node back
+                nodes_by_path.insert(path.clone(), child_node);
+            }
+        }
+    }
+
+    nodes_by_path
+        .into_iter()
+        .filter(|(path, _)| !path.contains('/'))
+        .map(|(_, node)| node)
+        .collect()
+}
+
+/// Sort every level of the tree with the given comparison
+pub fn sort_tree<F>(nodes: &mut [YakNode], compare: &F)
+where
+    F: Fn(&YakNode, &YakNode) -> Ordering,
+{
+    nodes.sort_by(compare);
+
+    for node in nodes.iter_mut() {
+        sort_tree(&mut node.children, compare);
+    }
+}
+
+/// Default sibling order: done first, then not-done, both alphabetically
+pub fn done_first(a: &YakNode, b: &YakNode) -> Ordering {
+    match (a.is_done(), b.is_done()) {
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        _ => a.name.cmp(&b.name),
+    }
+}
+
+/// Find the node for a full path within a tree
+pub fn find_node<'a>(nodes: &'a [YakNode], full_path: &str) -> Option<&'a YakNode> {
+    for node in nodes {
+        if node.full_path == full_path {
+            return Some(node);
+        }
+        if is_descendant_of(full_path, &node.full_path) {
+            return find_node(&node.children, full_path);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn yaks(names: &[&str]) -> Vec<Yak> {
+        names.iter().map(|n| Yak::new(n.to_string())).collect()
+    }
+
+    fn names(yaks: Vec<&Yak>) -> Vec<String> {
+        let mut names: Vec<String> = yaks.iter().map(|y| y.name.clone()).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_parent_of() {
+        assert_eq!(parent_of("a/b/c"), Some("a/b"));
+        assert_eq!(parent_of("a"), None);
+    }
+
+    #[test]
+    fn test_is_descendant_of() {
+        assert!(is_descendant_of("a/b", "a"));
+        assert!(is_descendant_of("a/b/c", "a"));
+        assert!(!is_descendant_of("a", "a"));
+        assert!(!is_descendant_of("ab/c", "a"));
+    }
+
+    #[test]
+    fn test_children_and_descendants() {
+        let all = yaks(&["a", "a/b", "a/b/c", "a/d", "ab"]);
+        assert_eq!(names(children_of(&all, "a")), vec!["a/b", "a/d"]);
+        assert_eq!(
+            names(descendants_of(&all, "a")),
+            vec!["a/b", "a/b/c", "a/d"]
+        );
+    }
+
+    #[test]
+    fn test_build_tree_creates_implicit_parents() {
+        let tree = build_tree(yaks(&["parent/child"]));
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].name, "parent");
+        assert!(tree[0].yak.is_none());
+        assert_eq!(tree[0].children[0].full_path, "parent/child");
+        assert_eq!(tree[0].descendant_count(), 1);
+    }
+
+    #[test]
+    fn test_sort_tree_and_find_node() {
+        let mut tree = build_tree(yaks(&["b", "a", "a/z", "a/y"]));
+        sort_tree(&mut tree, &|x: &YakNode, y: &YakNode| x.name.cmp(&y.name));
+        assert_eq!(tree[0].name, "a");
+        assert_eq!(tree[0].children[0].name, "y");
+        assert_eq!(find_node(&tree, "a/z").unwrap().full_path, "a/z");
+        assert!(find_node(&tree, "c").is_none());
+    }
+}
@@ -1,6 +1,22 @@
 // Core business logic - independent of infrastructure
 // Contains Yak model, validation rules, and domain operations
 
+pub mod checklist;
+pub mod diff;
+pub mod effort;
+pub mod filter;
+pub mod graph;
+pub mod pattern;
+pub mod priority;
+pub mod spelling;
+pub mod state;
 pub mod yak;
 
-pub use yak::{validate_yak_name, Yak};
+pub use filter::YakFilter;
+pub use pattern::Pattern;
+pub use priority::Priority;
+pub use state::YakState;
+pub use yak::{
+    normalize_yak_name, parse_due_date, parse_name_rules, sanitize_yak_name, validate_link,
+    validate_title, validate_yak_name, NameRule, Yak,
+};
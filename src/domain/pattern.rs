@@ -0,0 +1,95 @@
+// Text patterns for searching contexts - plain text or a regular expression
+
+use regex::{Regex, RegexBuilder};
+
+/// A compiled search pattern
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    regex: Regex,
+}
+
+impl Pattern {
+    /// Match `text` literally
+    pub fn literal(text: &str, ignore_case: bool) -> Self {
+        Self::build(&regex::escape(text), ignore_case).expect("an escaped pattern always compiles")
+    }
+
+    /// Parse a regular expression in the `regex` crate's syntax
+    pub fn regex(text: &str, ignore_case: bool) -> Result<Self, String> {
+        Self::build(text, ignore_case).map_err(|e| format!("Invalid pattern '{text}': {e}"))
+    }
+
+    fn build(text: &str, ignore_case: bool) -> Result<Self, regex::Error> {
+        let regex = RegexBuilder::new(text)
+            .case_insensitive(ignore_case)
+            .build()?;
+        Ok(Self { regex })
+    }
+
+    /// Byte ranges of the non-overlapping matches in `line`, leftmost first
+    /// Empty matches count as a match but have nothing to highlight.
+    pub fn find_all(&self, line: &str) -> Vec<(usize, usize)> {
+        self.regex
+            .find_iter(line)
+            .map(|found| (found.start(), found.end()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matched<'a>(pattern: &Pattern, line: &'a str) -> Vec<&'a str> {
+        pattern
+            .find_all(line)
+            .into_iter()
+            .map(|(start, end)| &line[start..end])
+            .collect()
+    }
+
+    #[test]
+    fn test_literal_patterns() {
+        let pattern = Pattern::literal("a.b", false);
+        assert_eq!(matched(&pattern, "a.b axb a.b"), vec!["a.b", "a.b"]);
+
+        let pattern = Pattern::literal("todo", true);
+        assert_eq!(matched(&pattern, "TODO: ask Todo"), vec!["TODO", "Todo"]);
+    }
+
+    #[test]
+    fn test_regex_patterns() {
+        let regex = |text| Pattern::regex(text, false).unwrap();
+
+        assert_eq!(
+            matched(&regex(r"v\d+\.\d+"), "v1.2 and v10.04"),
+            vec!["v1.2", "v10.04"]
+        );
+        assert_eq!(
+            matched(&regex("^fix|bug$"), "fix the bug"),
+            vec!["fix", "bug"]
+        );
+        assert_eq!(
+            matched(&regex("^fix|bug$"), "a fix bugs"),
+            Vec::<&str>::new()
+        );
+        assert_eq!(
+            matched(&regex("colou?r"), "color colour"),
+            vec!["color", "colour"]
+        );
+        assert_eq!(matched(&regex("[a-c]+[^a-c ]"), "abcd ab"), vec!["abcd"]);
+        assert_eq!(matched(&regex("é.*ü"), "café über"), vec!["é ü"]);
+        assert_eq!(
+            matched(&regex(r"(fix|bug) #\d+"), "fix #12, bug #3"),
+            vec!["fix #12", "bug #3"]
+        );
+        assert!(Pattern::regex("[a-", false).is_err());
+        assert!(Pattern::regex("*a", false).is_err());
+
+        let pattern = Pattern::regex("[a-z]+ing", true).unwrap();
+        assert_eq!(
+            matched(&pattern, "Testing SHIPPING"),
+            vec!["Testing", "SHIPPING"]
+        );
+    }
+}
@@ -0,0 +1,61 @@
+// Priorities - P0 (drop everything) to P3 (someday), and how they escalate
+
+use std::fmt;
+
+/// How urgent a yak is; lower numbers are more urgent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Priority(u8);
+
+impl Priority {
+    /// Most urgent level
+    pub const HIGHEST: Priority = Priority(0);
+    /// Least urgent level, assumed for yaks without a priority
+    pub const LOWEST: Priority = Priority(3);
+
+    /// Parse "P0".."P3" (case-insensitive, the "P" is optional)
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        let digits = text
+            .strip_prefix('P')
+            .or_else(|| text.strip_prefix('p'))
+            .unwrap_or(text);
+        match digits.parse::<u8>() {
+            Ok(level) if level <= Self::LOWEST.0 => Ok(Self(level)),
+            _ => Err(format!("Invalid priority '{text}': use P0, P1, P2 or P3")),
+        }
+    }
+
+    /// One level more urgent, stopping at P0
+    pub fn raise(self) -> Self {
+        Self(self.0.saturating_sub(1))
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "P{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_priority() {
+        assert_eq!(Priority::parse("P0"), Ok(Priority::HIGHEST));
+        assert_eq!(
+            Priority::parse(" p2 ").map(|p| p.to_string()),
+            Ok("P2".to_string())
+        );
+        assert_eq!(Priority::parse("3"), Ok(Priority::LOWEST));
+        assert!(Priority::parse("P4").is_err());
+        assert!(Priority::parse("high").is_err());
+    }
+
+    #[test]
+    fn test_raise_stops_at_highest() {
+        assert_eq!(Priority::LOWEST.raise().to_string(), "P2");
+        assert_eq!(Priority::HIGHEST.raise(), Priority::HIGHEST);
+    }
+}
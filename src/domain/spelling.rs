@@ -0,0 +1,131 @@
+// Spelling correction for mistyped command names (e.g., `yx dnoe` -> `done`)
+
+use std::time::Duration;
+
+/// What to do when a mistyped command has a single likely correction,
+/// mirroring git's `help.autocorrect`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Autocorrect {
+    /// Only suggest the correction
+    Never,
+    /// Ask before running the correction
+    Prompt,
+    /// Run the correction after a short pause (zero runs it straight away)
+    After(Duration),
+}
+
+impl Autocorrect {
+    /// Parse a config value: "never", "prompt", "immediate", or tenths of a second to wait
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "" | "never" | "false" | "off" | "0" => Ok(Autocorrect::Never),
+            "prompt" => Ok(Autocorrect::Prompt),
+            "immediate" | "true" | "on" => Ok(Autocorrect::After(Duration::ZERO)),
+            other => other
+                .parse::<u64>()
+                .map(|tenths| Autocorrect::After(Duration::from_millis(tenths * 100)))
+                .map_err(|_| {
+                    format!(
+                        "invalid autocorrect setting '{value}' (use never, prompt, immediate or tenths of a second)"
+                    )
+                }),
+        }
+    }
+}
+
+/// The candidate closest to `word`, if it is close enough to be a typo
+/// Ties are ambiguous, so they give no suggestion.
+pub fn closest_match<'a>(word: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let word = word.to_lowercase();
+    // Allow roughly one slip per three letters, and at least one
+    let limit = (word.chars().count() / 3).max(1);
+
+    let mut best: Option<(usize, &'a str)> = None;
+    let mut tied = false;
+    for candidate in candidates {
+        let distance = edit_distance(&word, &candidate.to_lowercase());
+        if distance > limit {
+            continue;
+        }
+        match best {
+            Some((best_distance, best_name)) if distance == best_distance => {
+                tied |= best_name != *candidate;
+            }
+            Some((best_distance, _)) if distance > best_distance => {}
+            _ => {
+                best = Some((distance, candidate));
+                tied = false;
+            }
+        }
+    }
+
+    best.filter(|_| !tied).map(|(_, name)| name)
+}
+
+/// Edit distance counting swapped neighbouring letters as one edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMMANDS: &[&str] = &[
+        "add", "done", "list", "ls", "move", "mv", "remove", "rm", "sync",
+    ];
+
+    #[test]
+    fn test_closest_match_fixes_common_typos() {
+        assert_eq!(closest_match("dnoe", COMMANDS), Some("done"));
+        assert_eq!(closest_match("lsit", COMMANDS), Some("list"));
+        assert_eq!(closest_match("snyc", COMMANDS), Some("sync"));
+        assert_eq!(closest_match("remvoe", COMMANDS), Some("remove"));
+        assert_eq!(closest_match("DONE", COMMANDS), Some("done"));
+    }
+
+    #[test]
+    fn test_closest_match_skips_distant_or_ambiguous_words() {
+        assert_eq!(closest_match("deploy", COMMANDS), None);
+        // "m" is one edit from both "mv" and "rm"
+        assert_eq!(closest_match("m", COMMANDS), None);
+    }
+
+    #[test]
+    fn test_autocorrect_parse() {
+        assert_eq!(Autocorrect::parse("never"), Ok(Autocorrect::Never));
+        assert_eq!(Autocorrect::parse("prompt"), Ok(Autocorrect::Prompt));
+        assert_eq!(
+            Autocorrect::parse("immediate"),
+            Ok(Autocorrect::After(Duration::ZERO))
+        );
+        assert_eq!(
+            Autocorrect::parse("15"),
+            Ok(Autocorrect::After(Duration::from_millis(1500)))
+        );
+        assert!(Autocorrect::parse("sometimes").is_err());
+    }
+}
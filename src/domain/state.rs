@@ -0,0 +1,66 @@
+// Work states - where a yak is on the way from todo to done
+
+use std::fmt;
+
+/// Where a yak is in its lifecycle; `Done` comes from the done marker, the
+/// others from the yak's `state` field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum YakState {
+    Todo,
+    Doing,
+    Blocked,
+    Done,
+}
+
+impl YakState {
+    /// Every state, in board order
+    pub const ALL: [YakState; 4] = [
+        YakState::Todo,
+        YakState::Doing,
+        YakState::Blocked,
+        YakState::Done,
+    ];
+
+    /// Parse a state name (case-insensitive); `open` and `in-progress` are accepted too
+    pub fn parse(text: &str) -> Result<Self, String> {
+        match text.trim().to_lowercase().as_str() {
+            "todo" | "open" => Ok(Self::Todo),
+            "doing" | "in-progress" => Ok(Self::Doing),
+            "blocked" => Ok(Self::Blocked),
+            "done" => Ok(Self::Done),
+            _ => Err(format!(
+                "Invalid state '{}': use todo, doing, blocked or done",
+                text.trim()
+            )),
+        }
+    }
+}
+
+impl fmt::Display for YakState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let word = match self {
+            Self::Todo => "todo",
+            Self::Doing => "doing",
+            Self::Blocked => "blocked",
+            Self::Done => "done",
+        };
+        f.write_str(word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_state() {
+        assert_eq!(YakState::parse("doing"), Ok(YakState::Doing));
+        assert_eq!(YakState::parse(" Blocked\n"), Ok(YakState::Blocked));
+        assert_eq!(YakState::parse("open"), Ok(YakState::Todo));
+        assert_eq!(YakState::parse("in-progress"), Ok(YakState::Doing));
+        assert!(YakState::parse("paused").is_err());
+        for state in YakState::ALL {
+            assert_eq!(YakState::parse(&state.to_string()), Ok(state));
+        }
+    }
+}
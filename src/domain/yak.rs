@@ -1,10 +1,35 @@
 // Yak domain model
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+use super::priority::Priority;
+use super::state::YakState;
+use chrono::{DateTime, NaiveDate, Utc};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct Yak {
     pub name: String,
     pub done: bool,
+    /// The yak's context, when it was read: `get_yak` reads it, `list_yaks`
+    /// leaves it out
     pub context: Option<String>,
+    pub links: Vec<String>,
+    /// When the yak was marked done, if known
+    pub done_at: Option<DateTime<Utc>>,
+    /// Human phrasing of the name, kept when the name was normalized
+    pub title: Option<String>,
+    /// Who the yak is assigned to (a git user.email)
+    pub assignee: Option<String>,
+    /// How urgent the yak is, if set
+    pub priority: Option<Priority>,
+    /// When the yak should be done by, if set
+    pub due: Option<NaiveDate>,
+    /// Progress of an open yak (doing, blocked), if set
+    pub state: Option<YakState>,
+    /// When work on the yak last started (`yx doing`), if known
+    pub started_at: Option<DateTime<Utc>>,
+    /// When the yak was added, if known
+    pub created_at: Option<DateTime<Utc>>,
+    /// When any of the yak's own files last changed, if known
+    pub modified_at: Option<DateTime<Utc>>,
 }
 
 impl Yak {
@@ -12,8 +37,7 @@ impl Yak {
     pub fn new(name: String) -> Self {
         Self {
             name,
-            done: false,
-            context: None,
+            ..Default::default()
         }
     }
 
@@ -23,6 +47,18 @@ impl Yak {
         self
     }
 
+    #[allow(dead_code)]
+    pub fn with_links(mut self, links: Vec<String>) -> Self {
+        self.links = links;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_title(mut self, title: String) -> Self {
+        self.title = Some(title);
+        self
+    }
+
     #[allow(dead_code)]
     pub fn mark_done(mut self) -> Self {
         self.done = true;
@@ -34,21 +70,49 @@ impl Yak {
         self.done = false;
         self
     }
+
+    #[allow(dead_code)]
+    pub fn with_state(mut self, state: YakState) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// Done for done yaks, whatever the stored state says; todo when none is set
+    pub fn current_state(&self) -> YakState {
+        if self.done {
+            YakState::Done
+        } else {
+            self.state.unwrap_or(YakState::Todo)
+        }
+    }
 }
 
+/// Characters a yak name can't contain (matches bash version)
+/// Slashes are allowed since they separate levels of the hierarchy
+const FORBIDDEN_CHARS: &[char] = &['\\', ':', '*', '?', '|', '<', '>', '"'];
+
+/// Names storage keeps its own files and folders under (each yak's `.meta`,
+/// the archive, trash, index, format version, prune record and lock), so no
+/// yak or part of a yak's path can be called one of them
+const RESERVED_NAMES: [&str; 7] = [
+    ".meta",
+    ".archive",
+    ".trash",
+    ".index",
+    ".version",
+    ".pruned.jsonl",
+    ".lock",
+];
+
 /// Validate a yak name
 /// Rejects names containing forbidden characters: \ : * ? | < > "
+/// or a part named after one of storage's own files, such as .meta or .archive
 /// Slashes (/) are allowed for hierarchical yaks (e.g., "dx/rust")
 pub fn validate_yak_name(name: &str) -> Result<(), String> {
     if name.is_empty() {
         return Err("Yak name cannot be empty".to_string());
     }
 
-    // Check for forbidden characters (matches bash version)
-    // Forbidden: \ : * ? | < > "
-    // Allowed: / (for hierarchy)
-    const FORBIDDEN_CHARS: &[char] = &['\\', ':', '*', '?', '|', '<', '>', '"'];
-
     for c in FORBIDDEN_CHARS {
         if name.contains(*c) {
             return Err(
@@ -57,6 +121,143 @@ pub fn validate_yak_name(name: &str) -> Result<(), String> {
         }
     }
 
+    if let Some(reserved) = name.split('/').find(|part| RESERVED_NAMES.contains(part)) {
+        return Err(format!("Invalid yak name: {reserved} is reserved"));
+    }
+
+    Ok(())
+}
+
+/// A rule applied to each segment of a yak name before it is created
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameRule {
+    /// "Fix Bug" -> "fix bug"
+    Lowercase,
+    /// "fix bug" -> "fix-bug"
+    Hyphenate,
+    /// "fix bug!" -> "fix bug"
+    StripPunctuation,
+}
+
+/// Parse a comma-separated rule list, e.g. "lowercase,hyphenate"
+/// "all" enables every rule, "none" or an empty string disables normalization
+pub fn parse_name_rules(text: &str) -> Result<Vec<NameRule>, String> {
+    let mut rules = Vec::new();
+    for word in text.split(',').map(str::trim).filter(|w| !w.is_empty()) {
+        match word {
+            "all" => {
+                return Ok(vec![
+                    NameRule::StripPunctuation,
+                    NameRule::Hyphenate,
+                    NameRule::Lowercase,
+                ])
+            }
+            "none" => return Ok(Vec::new()),
+            "lowercase" => rules.push(NameRule::Lowercase),
+            "hyphenate" => rules.push(NameRule::Hyphenate),
+            "strip-punctuation" => rules.push(NameRule::StripPunctuation),
+            other => {
+                return Err(format!(
+                    "Unknown name rule '{other}' (expected lowercase, hyphenate, strip-punctuation, all or none)"
+                ))
+            }
+        }
+    }
+
+    // Apply in a fixed order regardless of how they were listed
+    rules.sort_by_key(|rule| match rule {
+        NameRule::StripPunctuation => 0,
+        NameRule::Hyphenate => 1,
+        NameRule::Lowercase => 2,
+    });
+    rules.dedup();
+    Ok(rules)
+}
+
+/// Apply normalization rules to each segment of a (possibly hierarchical) yak name
+pub fn normalize_yak_name(name: &str, rules: &[NameRule]) -> String {
+    name.split('/')
+        .map(|segment| {
+            let mut segment = segment.trim().to_string();
+            for rule in rules {
+                segment = match rule {
+                    NameRule::StripPunctuation => segment
+                        .trim_end_matches(['.', ',', '!', '?', ';', ':'])
+                        .trim_end()
+                        .to_string(),
+                    NameRule::Hyphenate => segment.split_whitespace().collect::<Vec<_>>().join("-"),
+                    NameRule::Lowercase => segment.to_lowercase(),
+                };
+            }
+            segment
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Closest valid name to one that fails validation (e.g., made by hand or an older version)
+/// Runs of forbidden characters become a hyphen between words and are dropped next to
+/// spaces or segment edges; segments left empty become "unnamed".
+pub fn sanitize_yak_name(name: &str) -> String {
+    name.split('/')
+        .map(|segment| {
+            let chars: Vec<char> = segment.chars().collect();
+            let mut cleaned = String::with_capacity(segment.len());
+            for (i, c) in chars.iter().enumerate() {
+                if !FORBIDDEN_CHARS.contains(c) {
+                    cleaned.push(*c);
+                    continue;
+                }
+                let next = chars[i + 1..].iter().find(|c| !FORBIDDEN_CHARS.contains(c));
+                let joins_words = cleaned
+                    .chars()
+                    .last()
+                    .is_some_and(|prev| !prev.is_whitespace() && prev != '-')
+                    && next.is_some_and(|next| !next.is_whitespace());
+                if joins_words {
+                    cleaned.push('-');
+                }
+            }
+            match cleaned.trim() {
+                "" => "unnamed".to_string(),
+                trimmed => trimmed.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Validate an external link attached to a yak
+/// Links are stored one per line, so they must be non-empty single-line strings
+pub fn validate_link(url: &str) -> Result<(), String> {
+    if url.trim().is_empty() {
+        return Err("Link cannot be empty".to_string());
+    }
+
+    if url.contains('\n') || url.contains('\r') {
+        return Err("Invalid link: must be a single line".to_string());
+    }
+
+    Ok(())
+}
+
+/// Parse a due date written as YYYY-MM-DD
+pub fn parse_due_date(text: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid due date '{}': use YYYY-MM-DD", text.trim()))
+}
+
+/// Validate a display title
+/// Titles may contain any characters, but must be a non-empty single line
+pub fn validate_title(title: &str) -> Result<(), String> {
+    if title.trim().is_empty() {
+        return Err("Title cannot be empty".to_string());
+    }
+
+    if title.contains('\n') || title.contains('\r') {
+        return Err("Invalid title: must be a single line".to_string());
+    }
+
     Ok(())
 }
 
@@ -84,6 +285,49 @@ mod tests {
         assert_eq!(yak.context, Some("Some context".to_string()));
     }
 
+    #[test]
+    fn test_validate_title() {
+        assert!(validate_title("Fix: \"login\" bug? 🐛").is_ok());
+        assert!(validate_title("  ").is_err());
+        assert!(validate_title("two\nlines").is_err());
+    }
+
+    #[test]
+    fn test_parse_name_rules() {
+        assert_eq!(parse_name_rules(""), Ok(vec![]));
+        assert_eq!(parse_name_rules("none"), Ok(vec![]));
+        assert_eq!(
+            parse_name_rules("lowercase, strip-punctuation"),
+            Ok(vec![NameRule::StripPunctuation, NameRule::Lowercase])
+        );
+        assert_eq!(parse_name_rules("all").unwrap().len(), 3);
+        assert!(parse_name_rules("shout").is_err());
+    }
+
+    #[test]
+    fn test_normalize_yak_name() {
+        let all = parse_name_rules("all").unwrap();
+        assert_eq!(
+            normalize_yak_name("Fix the Login bug!", &all),
+            "fix-the-login-bug"
+        );
+        assert_eq!(normalize_yak_name("API/Auth Flow?", &all), "api/auth-flow");
+        assert_eq!(
+            normalize_yak_name("Fix Bug.", &[NameRule::Lowercase]),
+            "fix bug."
+        );
+        assert_eq!(normalize_yak_name("Fix Bug", &[]), "Fix Bug");
+    }
+
+    #[test]
+    fn test_sanitize_yak_name() {
+        assert_eq!(sanitize_yak_name("fix: login"), "fix login");
+        assert_eq!(sanitize_yak_name("a??b/c*"), "a-b/c");
+        assert_eq!(sanitize_yak_name("what?/<>"), "what/unnamed");
+        assert_eq!(sanitize_yak_name("fine/name"), "fine/name");
+        assert!(validate_yak_name(&sanitize_yak_name("\\:*?|<>\"")).is_ok());
+    }
+
     #[test]
     fn test_mark_done() {
         let yak = Yak::new("test".to_string()).mark_done();
@@ -121,6 +365,48 @@ mod tests {
 
         // Slash should be allowed (for hierarchy)
         assert!(validate_yak_name("test/name").is_ok());
+
+        // Storage keeps a yak's own files in .meta, but a child called done is fine
+        assert!(validate_yak_name("api/.meta").is_err());
+        assert!(validate_yak_name("api/done").is_ok());
+    }
+
+    #[test]
+    fn test_validate_yak_name_rejects_storage_names() {
+        for reserved in RESERVED_NAMES {
+            assert!(validate_yak_name(reserved).is_err(), "{reserved}");
+            assert!(validate_yak_name(&format!("{reserved}/x")).is_err());
+            assert!(validate_yak_name(&format!("api/{reserved}")).is_err());
+        }
+        assert_eq!(
+            validate_yak_name(".archive/x").unwrap_err(),
+            "Invalid yak name: .archive is reserved"
+        );
+        assert!(validate_yak_name("api/archive").is_ok());
+    }
+
+    #[test]
+    fn test_yak_with_links() {
+        let yak = Yak::new("test".to_string()).with_links(vec!["https://example.com".to_string()]);
+        assert_eq!(yak.links, vec!["https://example.com".to_string()]);
+    }
+
+    #[test]
+    fn test_validate_link() {
+        assert!(validate_link("https://github.com/org/repo/pull/1").is_ok());
+        assert!(validate_link("").is_err());
+        assert!(validate_link("   ").is_err());
+        assert!(validate_link("https://a\nhttps://b").is_err());
+    }
+
+    #[test]
+    fn test_parse_due_date() {
+        assert_eq!(
+            parse_due_date("2026-03-02"),
+            Ok(NaiveDate::from_ymd_opt(2026, 3, 2).unwrap())
+        );
+        assert!(parse_due_date("2026-02-30").is_err());
+        assert!(parse_due_date("next week").is_err());
     }
 
     #[test]
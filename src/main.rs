@@ -3,21 +3,92 @@ mod application;
 mod domain;
 mod ports;
 
-use adapters::cli::ConsoleOutput;
-use adapters::log::GitLog;
-use adapters::storage::DirectoryStorage;
-use adapters::sync::GitRefSync;
-use anyhow::Result;
+use adapters::blame::GitBlame;
+use adapters::browser::SystemBrowser;
+use adapters::cli::{
+    find_plugin, install_panic_hook, latest_crash_report, plugin_names, set_crash_yaks_path,
+    supports_unicode, ColorChoice, ConsoleOutput, Theme,
+};
+use adapters::config::GitConfig;
+use adapters::local_state::GitDirState;
+use adapters::lock::YakLock;
+use adapters::stash::DirectoryStash;
+use adapters::storage::{DirectoryStorage, UnsealedHistory};
+use adapters::timer::DesktopTimer;
+use adapters::watcher::FsEventWatcher;
+use adapters::workspace::{self, WORKSPACE_ENV};
+
+use anyhow::{Context, Result};
 use application::{
-    AddYak, DoneYak, EditContext, ListYaks, MoveYak, PruneYaks, RemoveYak, ShowContext, SyncYaks,
+    configured_workspaces, hints_enabled, name_or_focus, AddYak, ApplyRetention, ArchiveYak,
+    BlameYak, BulkYaks, CheckYaks, CompleteNames, ContextHistory, CopyYak, CountYaks, DiffYaks,
+    DoneYak, EditAll, EditContext, EffortYaks, EscalateYaks, EstimateYak, ExportObsidian,
+    ExportYaks, FocusYak, GraphYaks, GrepYaks, ImportChecklist, ImportObsidian, LinkYak,
+    ListAllYaks, ListStash, ListTrash, ListYaks, MergeYaks, MigrateYaks, MoveYak, NoteYak, OpenYak,
+    OutputFormat, PlanWeek, PopStash, PrefixedOutput, PrioritizeYak, PruneYaks, RemoveYak,
+    ReportBug, ReportHeatmap, ReportWork, RestoreFromTrash, RestoreYak, ScheduleYak, SetState,
+    ShowAllStatus, ShowBoard, ShowContext, ShowHistory, ShowPrompt, ShowStandup, ShowStats,
+    ShowStatus, SplitYak, StartPomodoro, StartYak, StateLabels, SuggestOwner, SuggestYak, SyncYaks,
+    TimeStyle, TitleYak, TreeYaks, UnarchiveYak, VerifyNames, WatchYaks, WorkspacePorts,
 };
 use clap::{CommandFactory, Parser};
+use domain::spelling::{closest_match, Autocorrect};
+use ports::{CipherPort, ConfigPort, OutputPort, StoragePort, Verbosity};
+use std::rc::Rc;
+
+// The yak log and sync are picked at build time (see the features in Cargo.toml):
+// libgit2 by default, the `git` command with `git-cli`, otherwise storage only
+#[cfg(feature = "encryption")]
+use adapters::cipher::AgeCipher as YakCipher;
+#[cfg(not(feature = "encryption"))]
+use adapters::cipher::NoCipher as YakCipher;
+#[cfg(all(feature = "git-cli", not(feature = "libgit2")))]
+use adapters::log::GitCliLog as YakLog;
+#[cfg(feature = "libgit2")]
+use adapters::log::GitLog as YakLog;
+#[cfg(not(any(feature = "libgit2", feature = "git-cli")))]
+use adapters::log::NoLog as YakLog;
+#[cfg(feature = "libgit2")]
+use adapters::sync::GitRefSync as YakSync;
+#[cfg(not(feature = "libgit2"))]
+use adapters::sync::NoSync as YakSync;
 
 /// DAG-based TODO list CLI for software teams
 #[derive(Parser, Debug)]
 #[command(name = "yx")]
 #[command(version, about, long_about = None)]
 struct Cli {
+    /// Output format for read commands: markdown (md), plain (raw), json, table or csv;
+    /// graph takes dot, report heatmap terminal or svg, export json, jsonl, csv, markdown or ics
+    #[arg(long, global = true, value_name = "FORMAT", value_parser = OutputFormat::parse)]
+    format: Option<OutputFormat>,
+
+    /// Stable, documented output for scripts (list, status, sync and prompt);
+    /// the same as `--format porcelain`
+    #[arg(long, global = true, conflicts_with = "format")]
+    porcelain: bool,
+
+    /// Only print errors and results: no success messages or hints
+    #[arg(long, short, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Also print extra detail, like the yak a partial name matched and sync steps
+    #[arg(long, short, global = true)]
+    verbose: bool,
+
+    /// When to color output: auto (a terminal, unless NO_COLOR is set), always or never
+    #[arg(long, global = true, value_name = "WHEN", value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+
+    /// Show dates and times instead of "2h ago" (status, history, blame, stash list)
+    #[arg(long, global = true)]
+    absolute: bool,
+
+    /// Work in the named yak list whose root is set in `yaks.roots.<NAME>`,
+    /// synced to refs/notes/yaks-<NAME> (not for `yx all`)
+    #[arg(long, global = true, value_name = "NAME", env = WORKSPACE_ENV)]
+    workspace: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -28,21 +99,97 @@ enum Commands {
     Add {
         /// The yak name (space-separated words)
         name: Vec<String>,
+        /// Add it under this existing yak (fuzzy matched)
+        #[arg(long)]
+        parent: Option<String>,
+        /// Context for the new yak (otherwise read from piped stdin)
+        #[arg(long, conflicts_with = "stdin")]
+        context: Option<String>,
+        /// Read the new yak's context from this file (- for stdin)
+        #[arg(long, conflicts_with_all = ["context", "stdin"])]
+        context_file: Option<std::path::PathBuf>,
+        /// Add one yak per stdin line; indented lines nest under the line above
+        #[arg(long, conflicts_with = "name")]
+        stdin: bool,
     },
     /// List yaks
     #[command(alias = "ls")]
     List {
-        /// Output format (markdown, md, plain, raw)
-        #[arg(long, default_value = "markdown")]
-        format: String,
-        /// Filter by completion status (done, not-done)
+        /// Only list this yak and the yaks under it (space-separated words)
+        name: Vec<String>,
+        /// Only show done, not-done, doing, blocked, ready or overdue yaks (comma-separated for any of them)
         #[arg(long)]
         only: Option<String>,
+        /// Only show this many levels (collapsed parents show a count)
+        #[arg(long)]
+        depth: Option<usize>,
+        /// Sort siblings by name, created, modified, priority or due
+        #[arg(long)]
+        sort: Option<String>,
+        /// Reverse the sort order
+        #[arg(long)]
+        reverse: bool,
+        /// Put done yaks first or last, or hide them (default: git config yaks.list.done)
+        #[arg(long, value_name = "first|last|hidden")]
+        done: Option<String>,
+        /// Leave out the totals line shown under the list at a terminal
+        #[arg(long)]
+        no_summary: bool,
+        /// Show a done yak with only done yaks under it as one line, e.g. "migration (12 done)"
+        #[arg(long)]
+        collapse_done: bool,
+        /// Number the yaks, so commands like `yx done 3` can use the numbers
+        #[arg(long, conflicts_with = "archived")]
+        numbered: bool,
+        /// List archived yaks instead of active ones
+        #[arg(long)]
+        archived: bool,
     },
+    /// Print the context lines matching a pattern as `name:line: text`
+    Grep {
+        pattern: String,
+        /// Treat the pattern as a regular expression
+        #[arg(long, short = 'E')]
+        regex: bool,
+        /// Match regardless of case
+        #[arg(long, short = 'i')]
+        ignore_case: bool,
+    },
+    /// Keep the list on screen, redrawing it whenever the yaks change
+    Watch {
+        /// Only watch this yak and the yaks under it (space-separated words)
+        #[arg(conflicts_with = "tree")]
+        name: Vec<String>,
+        /// Only show done, not-done, doing, blocked, ready or overdue yaks (comma-separated for any of them)
+        #[arg(long, conflicts_with = "tree")]
+        only: Option<String>,
+        /// Only show this many levels (collapsed parents show a count)
+        #[arg(long)]
+        depth: Option<usize>,
+        /// Draw the tree view instead of the list
+        #[arg(long)]
+        tree: bool,
+        /// Seconds between checks for changes [default: 1, or 60 while filesystem events arrive]
+        #[arg(long)]
+        interval: Option<u64>,
+    },
+    /// Show yaks as a tree with branch glyphs
+    Tree {
+        /// Only show this many levels (collapsed parents show a count)
+        #[arg(long)]
+        depth: Option<usize>,
+    },
+    /// Show yaks in todo, doing, blocked and done columns
+    Board {
+        /// Only show this yak and the yaks under it
+        prefix: Option<String>,
+    },
+    /// Print the yak graph for rendering with other tools
+    Graph,
     /// Mark yak as done
     #[command(alias = "finish")]
     Done {
-        /// The yak name (space-separated words)
+        /// The yak names (quote each, or separate with commas); defaults to the focused yak
         name: Vec<String>,
         #[arg(long)]
         undo: bool,
@@ -50,29 +197,391 @@ enum Commands {
         #[arg(long)]
         recursive: bool,
     },
-    /// Remove a yak
+    /// Remove a yak (into the trash, unless `git config yaks.trash false`)
     #[command(alias = "rm")]
     Remove {
         /// The yak name (space-separated words)
         name: Vec<String>,
+        /// Also remove every yak under it
+        #[arg(long, short)]
+        recursive: bool,
+        /// Don't ask for confirmation
+        #[arg(long, short)]
+        force: bool,
+    },
+    /// Show the log of yak operations, newest first
+    History {
+        /// Only operations that changed this yak (space-separated words)
+        name: Vec<String>,
+        /// Show at most this many operations
+        #[arg(long, short = 'n')]
+        limit: Option<usize>,
+    },
+    /// Show who last changed a yak, when, and which of its files
+    Blame {
+        /// The yak name (space-separated words)
+        name: Vec<String>,
+    },
+    /// Bring back a removed yak from the yak history
+    Restore {
+        /// The yak name (space-separated words)
+        name: Vec<String>,
+    },
+    /// Move a yak and everything under it out of the list, keeping it
+    Archive {
+        /// The yak name (space-separated words)
+        name: Vec<String>,
+    },
+    /// Bring an archived yak back into the list
+    Unarchive {
+        /// The yak name (space-separated words)
+        name: Vec<String>,
     },
     /// Remove all done yaks
-    Prune,
+    Prune {
+        /// Don't ask for confirmation
+        #[arg(long, short)]
+        force: bool,
+    },
     /// Move/rename a yak
     #[command(alias = "mv")]
     Move { from: String, to: String },
+    /// Turn the bullets in a yak's context into child yaks
+    Split {
+        /// The yak name (space-separated words)
+        name: Vec<String>,
+        /// Remove the bullets from the context afterwards
+        #[arg(long)]
+        strip: bool,
+    },
+    /// Merge a yak into another (context, links, children) and remove it
+    Merge {
+        /// The yak to merge away
+        source: String,
+        /// The yak to keep
+        target: String,
+    },
+    /// Print a short yak summary for shell prompts (PS1, starship)
+    ///
+    /// With --porcelain, print open count, done count and focus, tab-separated
+    Prompt,
+    /// Append a timestamped note to a yak's context
+    Note {
+        /// The yak name; with a single argument in a terminal, that's the note
+        /// for the focused yak
+        name: Option<String>,
+        /// The note (otherwise read from piped stdin)
+        text: Vec<String>,
+    },
+    /// Set the yak you're working on, the default for commands given no name
+    Focus {
+        /// The yak name (space-separated words); shows the focus when omitted
+        name: Vec<String>,
+        /// Stop focusing on a yak
+        #[arg(long, conflicts_with = "name")]
+        clear: bool,
+    },
+    /// Duplicate a yak under a new name
+    Cp {
+        from: String,
+        to: String,
+        /// Copy the yaks under it too
+        #[arg(long, short)]
+        recursive: bool,
+    },
     /// Edit or show yak context
     Context {
-        /// The yak name (space-separated words)
+        /// The yak name (space-separated words); defaults to the focused yak
+        name: Vec<String>,
+        #[arg(long)]
+        show: bool,
+        /// List earlier versions of the context from the yak history
+        #[arg(long, conflicts_with_all = ["show", "at", "restore"])]
+        history: bool,
+        /// Print the context as it was in a revision from --history
+        #[arg(long, value_name = "REV", conflicts_with_all = ["show", "restore"])]
+        at: Option<String>,
+        /// Make the context as it was in a revision from --history current again
+        #[arg(long, value_name = "REV", conflicts_with = "show")]
+        restore: Option<String>,
+        /// Show how the context changed since the last version, a revision
+        /// from --history (--diff REV) or origin's copy (--diff remote);
+        /// put the yak name first when giving no revision
+        #[arg(
+            long,
+            value_name = "REV",
+            num_args = 0..=1,
+            default_missing_value = "",
+            conflicts_with_all = ["show", "history", "at", "restore"]
+        )]
+        diff: Option<String>,
+    },
+    /// Open a yak's context file directly in $EDITOR
+    Open {
+        /// The yak name (space-separated words); defaults to the focused yak
+        name: Vec<String>,
+        /// Open the yak's links and the URLs in its context in the browser instead
+        #[arg(long)]
+        url: bool,
+    },
+    /// Rename, move, add, remove and complete yaks in one $EDITOR buffer
+    EditAll {
+        /// Only edit the yaks under this one
+        prefix: Option<String>,
+        /// Apply the changes without asking
+        #[arg(long, short)]
+        yes: bool,
+    },
+    /// Attach an external link (PR, doc, ticket) to a yak
+    Link {
+        name: String,
+        url: String,
+        /// Remove the link instead of adding it
+        #[arg(long)]
+        remove: bool,
+    },
+    /// Set a free-form display title (any characters) for a yak
+    Title {
+        name: String,
+        /// The title to show instead of the name
+        #[arg(required_unless_present = "clear")]
+        title: Option<String>,
+        /// Remove the title and show the name again
+        #[arg(long, conflicts_with = "title")]
+        clear: bool,
+    },
+    /// Start working on a yak (sets its state to doing)
+    Doing {
+        /// The yak name (space-separated words); defaults to the focused yak
+        name: Vec<String>,
+        /// Move any other doing yak back to todo
+        #[arg(long)]
+        only: bool,
+    },
+    /// Mark an open yak as todo, doing or blocked
+    State {
+        name: String,
+        /// todo, doing or blocked
+        state: String,
+    },
+    /// Set how urgent a yak is, from P0 (drop everything) to P3 (someday)
+    Priority {
+        name: String,
+        #[arg(required_unless_present = "clear")]
+        priority: Option<String>,
+        /// Remove the priority
+        #[arg(long, conflicts_with = "priority")]
+        clear: bool,
+    },
+    /// Set the date a yak is due (YYYY-MM-DD)
+    Due {
+        name: String,
+        #[arg(required_unless_present = "clear")]
+        date: Option<String>,
+        /// Remove the due date
+        #[arg(long, conflicts_with = "date")]
+        clear: bool,
+    },
+    /// Suggest who should own a yak from git blame of the files and commits it links to
+    SuggestOwner {
+        name: String,
+        /// Set the top suggestion as the yak's assignee
+        #[arg(long)]
+        assign: bool,
+    },
+    /// Raise priorities of stale or overdue yaks per the yaks.escalate.* config
+    Escalate,
+    /// Record how long a yak is expected to take (e.g. 30m, 2h, 1d)
+    Estimate {
+        name: String,
+        #[arg(required_unless_present = "clear")]
+        estimate: Option<String>,
+        /// Remove the estimate
+        #[arg(long, conflicts_with = "estimate")]
+        clear: bool,
+    },
+    /// Group open yaks into S/M/L buckets by estimated effort
+    Effort {
+        /// Only show yaks estimated at most this long (e.g. 30m)
+        #[arg(long)]
+        max: Option<String>,
+    },
+    /// Run a focus timer on a yak and log the session when it ends
+    Pom {
+        /// Session length in minutes
+        minutes: u64,
+        /// The yak name (space-separated words); defaults to the focused yak
         name: Vec<String>,
+    },
+    /// Export yaks to other tools, or to JSON/CSV/Markdown for reporting and backup
+    Export {
+        /// Write one note per yak into this Obsidian vault
+        #[arg(long, value_name = "VAULT_DIR", conflicts_with_all = ["format", "output", "only"])]
+        obsidian: Option<std::path::PathBuf>,
+        /// Write the export to this file instead of stdout
+        #[arg(long, value_name = "FILE", requires = "format")]
+        output: Option<std::path::PathBuf>,
+        /// Only show done, not-done, doing, blocked, ready or overdue yaks (comma-separated for any of them)
+        #[arg(long, requires = "format")]
+        only: Option<String>,
+    },
+    /// Import yaks from other tools
+    Import {
+        /// Create/update yaks from `- [ ] ... #yak` tasks in this Obsidian vault
+        #[arg(long, value_name = "VAULT_DIR", conflicts_with = "markdown")]
+        obsidian: Option<std::path::PathBuf>,
+        /// Create a yak hierarchy from a nested `- [ ]` checklist in this file ("-" for stdin)
+        #[arg(long, value_name = "FILE")]
+        markdown: Option<std::path::PathBuf>,
+        /// Show what the import would change without changing anything
+        #[arg(long, requires = "markdown")]
+        dry_run: bool,
+    },
+    /// Apply a script of yak commands (add, done, rm, mv, ...) all or nothing
+    Bulk {
+        /// File with one command per line, e.g. `add foo` ("-" or omitted for stdin)
+        file: Option<std::path::PathBuf>,
+    },
+    /// Restore yaks snapshotted before risky operations like import
+    Stash {
+        #[command(subcommand)]
+        command: StashCommands,
+    },
+    /// List or restore yaks that `yx rm` moved to the trash
+    Trash {
+        #[command(subcommand)]
+        command: TrashCommands,
+    },
+    /// Show yak counts and sync state
+    Status,
+    /// Show completion metrics from the yak history
+    Stats,
+    /// Reports on completed work; without a subcommand, a markdown summary of the week
+    #[command(args_conflicts_with_subcommands = true)]
+    Report {
+        #[command(subcommand)]
+        command: Option<ReportCommands>,
+        /// Start of the period (YYYY-MM-DD); a week ago by default
+        #[arg(long)]
+        since: Option<String>,
+    },
+    /// Go through open yaks, planning them for this week or giving them due dates
+    Plan {
+        /// List the yaks planned for this week instead
         #[arg(long)]
         show: bool,
     },
+    /// What you finished since the last working day, what you're doing, and what's blocked
+    Standup,
+    /// Pick one ready yak to work on next and show the start of its context
+    Suggest,
+    /// Print how many yaks match; exit non-zero with --fail-if-any / --fail-if-none
+    Count {
+        /// Only count the yaks under this yak (space-separated words)
+        name: Vec<String>,
+        /// Only show done, not-done, doing, blocked, ready or overdue yaks (comma-separated for any of them)
+        #[arg(long)]
+        only: Option<String>,
+        /// Filter by state (todo, doing, blocked, done)
+        #[arg(long)]
+        state: Option<String>,
+        /// Fail when any yak matches
+        #[arg(long, conflicts_with = "fail_if_none")]
+        fail_if_any: bool,
+        /// Fail when no yak matches
+        #[arg(long)]
+        fail_if_none: bool,
+    },
+    /// Check yaks for broken invariants; exits non-zero if any are found (for CI)
+    Check,
+    /// Upgrade yaks kept in an older storage format (also done on first run)
+    Migrate {
+        /// Only migrate up to this one (e.g. `state`)
+        name: Option<String>,
+        /// List the migrations that would run without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Find yak folders with invalid names (made by hand or older versions)
+    VerifyNames {
+        /// Rename them to the suggested valid names
+        #[arg(long)]
+        fix: bool,
+    },
+    /// Preview what `yx sync` would change, without changing anything
+    Diff,
     /// Sync yaks with git refs
     Sync,
+    /// Print a shell completion script (bash, zsh, fish, powershell, elvish)
+    Completions { shell: clap_complete::Shell },
+    /// Open a new issue prefilled with version and the last crash report
+    Bug,
+    /// Set up this repository for yaks (gitignores .yaks)
+    Init,
+    /// Run a command across every configured workspace (git config yaks.workspace)
+    All {
+        #[command(subcommand)]
+        command: AllCommands,
+    },
+    /// `yx foo` with no built-in `foo`: a `yx-foo` plugin, or a typo
+    #[command(external_subcommand)]
+    External(Vec<String>),
+    /// List yak names starting with a prefix, for shell completion scripts
+    #[command(name = "__complete", hide = true)]
+    Complete {
+        /// Only complete yaks matching this filter, as in `yx list --only`
+        #[arg(long)]
+        only: Option<String>,
+        /// Start of the yak name typed so far
+        #[arg(default_value = "")]
+        prefix: String,
+    },
+}
+
+#[derive(Parser, Debug)]
+enum AllCommands {
+    /// List yaks from every workspace, prefixed with the workspace name
+    #[command(alias = "ls")]
+    List {
+        /// Only show done, not-done, doing, blocked, ready or overdue yaks (comma-separated for any of them)
+        #[arg(long)]
+        only: Option<String>,
+        /// Only yaks assigned to you (your git user.email)
+        #[arg(long)]
+        mine: bool,
+    },
+    /// Show yak counts and sync state for every workspace
+    Status,
+}
+
+#[derive(Parser, Debug)]
+enum StashCommands {
+    /// List stashed snapshots, newest first
+    List,
+    /// Restore the newest snapshot and drop it
+    Pop,
+}
+
+#[derive(Parser, Debug)]
+enum TrashCommands {
+    /// List removed yaks, most recent first
+    List,
+    /// Bring a removed yak (and the yaks under it) back
+    Restore {
+        /// The yak name (space-separated words)
+        name: Vec<String>,
+    },
+}
+
+#[derive(Parser, Debug)]
+enum ReportCommands {
+    /// Grid of completed yaks per day over the last year
+    Heatmap,
 }
 
 fn main() -> Result<()> {
+    install_panic_hook();
+
     // Check if help was requested (--help or no args)
     let args: Vec<_> = std::env::args().collect();
     if args.len() == 1 || args.contains(&"--help".to_string()) || args.contains(&"-h".to_string()) {
@@ -86,58 +595,941 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+
+    // A named workspace swaps in its own root and ref; adapters and plugins
+    // find both in the environment, as they do YAK_PATH
+    let uses_workspace = !matches!(
+        cli.command,
+        Commands::All { .. } | Commands::Completions { .. } | Commands::Bug
+    );
+    if let Some(name) = cli.workspace.as_deref().filter(|name| !name.is_empty()) {
+        if uses_workspace {
+            let work_tree = std::env::var("GIT_WORK_TREE")
+                .map(std::path::PathBuf::from)
+                .or_else(|_| std::env::current_dir())?;
+            match GitConfig::new().and_then(|config| workspace::root(&config, name, &work_tree)) {
+                Ok(root) => {
+                    std::env::set_var("YAK_PATH", root);
+                    std::env::set_var(WORKSPACE_ENV, name);
+                }
+                // Prompts stay quiet wherever yaks can't be read
+                Err(_) if matches!(cli.command, Commands::Prompt) => return Ok(()),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    // Unknown commands run a plugin or, when mistyped, the command that was meant
+    if let Commands::External(args) = cli.command {
+        cli = resolve_external(args)?;
+    }
+
+    let output = ConsoleOutput::new(if cli.quiet {
+        Verbosity::Quiet
+    } else if cli.verbose {
+        Verbosity::Verbose
+    } else {
+        Verbosity::Normal
+    })
+    .with_color(cli.color);
+
+    // Completions don't need a repository, so handle them before touching one
+    if let Commands::Completions { shell } = cli.command {
+        // Buffer first: generating straight into stdout panics on a closed pipe
+        let mut script = Vec::new();
+        clap_complete::generate(shell, &mut Cli::command(), "yx", &mut script);
+        std::io::Write::write_all(&mut std::io::stdout(), &script)?;
+        return Ok(());
+    }
+
+    // Reporting a bug must work even where yaks can't be opened
+    if let Commands::Bug = cli.command {
+        let mut use_case = ReportBug::new(&output, &SystemBrowser);
+        if let Some((path, text)) = latest_crash_report() {
+            use_case = use_case.with_crash_report(path, text);
+        }
+        return use_case.execute();
+    }
+
+    if let Commands::Init = cli.command {
+        return init_repo(&output, &GitConfig::new()?);
+    }
+
+    if cli.porcelain {
+        cli.format = Some(OutputFormat::Porcelain);
+    }
+
+    // Prompts render on every shell prompt, so stay quiet wherever yaks can't be read
+    if let Commands::Prompt = cli.command {
+        let _ = show_prompt(cli.format == Some(OutputFormat::Porcelain));
+        return Ok(());
+    }
+
+    let output = match GitConfig::new().map(|config| Theme::from_config(&config)) {
+        Ok(Ok(theme)) => output.with_theme(theme),
+        Ok(Err(e)) => {
+            output.warn(&format!("{e}; using the default theme"));
+            output
+        }
+        // Outside a repository there's no config to read a theme from
+        Err(_) => output,
+    };
+
+    // Workspace commands run from anywhere and open each workspace themselves
+    if let Commands::All { command } = cli.command {
+        return run_all(command, cli.format, &output);
+    }
+    let format = cli.format;
+    let time_style = if cli.absolute {
+        TimeStyle::Absolute
+    } else {
+        TimeStyle::Relative
+    };
 
     // Initialize adapters
-    let storage = DirectoryStorage::new()?;
-    let output = ConsoleOutput;
-    let log = GitLog::new()?;
+    let config = GitConfig::new()?;
+    let storage = match DirectoryStorage::new(&config) {
+        Ok(storage) => storage,
+        // First run in a repo: offer to set it up rather than just failing
+        Err(e) if DirectoryStorage::needs_init(&config) => {
+            let question = "This repository isn't set up for yaks yet. Run `yx init` now?";
+            if output.confirm(question) != Some(true) {
+                if hints_enabled(Some(&config)) {
+                    output.hint("run `yx init` to set up yaks in this repository");
+                }
+                return Err(e);
+            }
+            init_repo(&output, &config)?;
+            DirectoryStorage::new(&config)?
+        }
+        Err(e) => return Err(e),
+    };
+    set_crash_yaks_path(storage.path());
+
+    // Yaks named in `yaks.encrypt` are stored, logged and synced as ciphertext
+    let storage = storage.with_cipher(Rc::new(YakCipher::from_config(&config)));
+    let log = YakLog::new(storage.path())?;
 
-    match cli.command {
-        Commands::Add { name } => {
+    // One lock for the whole command, so no other yx can change the yaks
+    // between its steps; storage, log and sync re-enter it as they go
+    let _lock = if changes_yaks(&cli.command) {
+        Some(YakLock::acquire(storage.path())?)
+    } else {
+        None
+    };
+
+    // Yaks from an older yx are upgraded before any command reads them
+    if !matches!(cli.command, Commands::Migrate { .. }) {
+        MigrateYaks::new(&storage, &storage, &output, &log).upgrade()?;
+    }
+
+    let result = match cli.command {
+        Commands::Add {
+            name,
+            parent,
+            context,
+            context_file,
+            stdin,
+        } => {
             let name_str = name.join(" ");
-            let use_case = AddYak::new(&storage, &output, &log);
-            use_case.execute(&name_str)
+            let config = GitConfig::new()?;
+            let state = GitDirState::new()?;
+            let mut use_case = AddYak::new(&storage, &output, &log)
+                .with_config(&config)
+                .with_read_tracking(&state);
+            if let Some(parent) = parent {
+                use_case = use_case.with_parent(parent);
+            }
+            if stdin {
+                let text = std::io::read_to_string(std::io::stdin())?;
+                use_case.execute_lines(&text)
+            } else {
+                let context = match (context, context_file) {
+                    (Some(text), _) => Some(text),
+                    (None, Some(path)) if path.as_os_str() == "-" => {
+                        Some(std::io::read_to_string(std::io::stdin())?)
+                    }
+                    (None, Some(path)) => Some(
+                        std::fs::read_to_string(&path)
+                            .with_context(|| format!("Failed to read {}", path.display()))?,
+                    ),
+                    (None, None) if !atty::is(atty::Stream::Stdin) => {
+                        Some(std::io::read_to_string(std::io::stdin())?)
+                    }
+                    (None, None) => None,
+                };
+                if let Some(context) = context {
+                    use_case = use_case.with_context(context);
+                }
+                use_case.execute(&name_str)
+            }
+        }
+        Commands::List {
+            name,
+            only,
+            depth,
+            sort,
+            reverse,
+            done,
+            no_summary,
+            collapse_done,
+            numbered,
+            archived,
+        } => {
+            let state = GitDirState::new()?;
+            let config = GitConfig::new()?;
+            // The archive is listed like any other yaks, with the same options
+            let archive = storage.archived();
+            let listed: &dyn StoragePort = if archived { &archive } else { &storage };
+            let mut use_case = ListYaks::new(listed, &output)
+                .with_read_tracking(&state)
+                .with_config(&config)
+                .with_state_labels(state_labels()?);
+            if numbered {
+                use_case = use_case.with_numbering(&state);
+            }
+            if !name.is_empty() {
+                use_case = use_case.with_scope(name.join(" "));
+            }
+            if let Some(depth) = depth {
+                use_case = use_case.with_depth(depth);
+            }
+            if let Some(sort) = sort {
+                use_case = use_case.with_sort(sort);
+            }
+            if let Some(done) = done {
+                use_case = use_case.with_done_placement(done);
+            }
+            use_case = use_case
+                .with_reverse(reverse)
+                .with_collapse_done(collapse_done)
+                .with_summary(!no_summary && atty::is(atty::Stream::Stdout));
+            use_case.execute(format.unwrap_or(OutputFormat::Markdown), only.as_deref())
+        }
+        Commands::Check => {
+            let use_case = CheckYaks::new(&storage, &output);
+            use_case.execute()
         }
-        Commands::List { format, only } => {
-            let use_case = ListYaks::new(&storage, &output);
-            use_case.execute(&format, only.as_deref())
+        Commands::Migrate { name, dry_run } => {
+            let mut use_case =
+                MigrateYaks::new(&storage, &storage, &output, &log).with_dry_run(dry_run);
+            if let Some(name) = name {
+                use_case = use_case.with_target(name);
+            }
+            use_case.execute()
+        }
+        Commands::Grep {
+            pattern,
+            regex,
+            ignore_case,
+        } => {
+            let use_case = GrepYaks::new(&storage, &output)
+                .with_regex(regex)
+                .with_ignore_case(ignore_case);
+            use_case.execute(&pattern)
+        }
+        Commands::Watch {
+            name,
+            only,
+            depth,
+            tree,
+            interval,
+        } => {
+            let config = GitConfig::new()?;
+            let render = || {
+                if tree {
+                    return TreeYaks::new(&storage, &output)
+                        .with_state_labels(state_labels()?)
+                        .execute(depth);
+                }
+                let mut use_case = ListYaks::new(&storage, &output)
+                    .with_config(&config)
+                    .with_state_labels(state_labels()?)
+                    .with_summary(atty::is(atty::Stream::Stdout));
+                if !name.is_empty() {
+                    use_case = use_case.with_scope(name.join(" "));
+                }
+                if let Some(depth) = depth {
+                    use_case = use_case.with_depth(depth);
+                }
+                use_case.execute(format.unwrap_or(OutputFormat::Markdown), only.as_deref())
+            };
+            let mut use_case = WatchYaks::new(&storage, &output, &DesktopTimer);
+            if let Some(interval) = interval {
+                use_case = use_case.with_interval(std::time::Duration::from_secs(interval));
+            }
+            // Without filesystem events (e.g. no .yaks yet, or out of inotify
+            // watches), fall back to checking every interval
+            let watcher = FsEventWatcher::new(storage.path());
+            if let Ok(watcher) = &watcher {
+                use_case = use_case.with_watcher(watcher);
+            }
+            use_case.execute(&render)
+        }
+        Commands::Tree { depth } => {
+            let mut use_case = TreeYaks::new(&storage, &output).with_state_labels(state_labels()?);
+            if let Some(format) = format {
+                use_case = use_case.with_format(format);
+            }
+            use_case.execute(depth)
+        }
+        Commands::Board { prefix } => {
+            let mut use_case = ShowBoard::new(&storage, &output);
+            if let Some(width) = terminal_width() {
+                use_case = use_case.with_width(width);
+            }
+            use_case.execute(prefix.as_deref())
+        }
+        Commands::Graph => {
+            let use_case = GraphYaks::new(&storage, &output);
+            use_case.execute(format.unwrap_or(OutputFormat::Dot))
         }
         Commands::Done {
             name,
             undo,
             recursive,
         } => {
-            let name_str = name.join(" ");
+            let state = GitDirState::new()?;
+            // A yak whose name has a comma in it is taken whole; otherwise
+            // commas separate names
+            let mut names = name
+                .iter()
+                .flat_map(|arg| match storage.get_yak(arg.trim()) {
+                    Ok(_) => vec![arg.as_str()],
+                    Err(_) => arg.split(',').collect(),
+                })
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(|name| name_or_focus(name, &storage, &state))
+                .collect::<Result<Vec<_>>>()?;
+            if names.is_empty() {
+                names.push(name_or_focus("", &storage, &state)?);
+            }
+            let names: Vec<&str> = names.iter().map(String::as_str).collect();
             let use_case = DoneYak::new(&storage, &output, &log);
-            use_case.execute(&name_str, undo, recursive)
+            use_case.execute(&names, undo, recursive)
+        }
+        Commands::Remove {
+            name,
+            recursive,
+            force,
+        } => {
+            let name_str = name.join(" ");
+            let config = GitConfig::new()?;
+            let use_case = RemoveYak::new(&storage, &output, &log)
+                .with_trash(&storage, &config)
+                .with_force(force);
+            use_case.execute(&name_str, recursive)
+        }
+        Commands::History { name, limit } => {
+            let name_str = name.join(" ");
+            let name = (!name_str.is_empty()).then_some(name_str.as_str());
+            let use_case = ShowHistory::new(&storage, &output, &log).with_time_style(time_style);
+            use_case.execute(name, limit)
         }
-        Commands::Remove { name } => {
+        Commands::Blame { name } => {
             let name_str = name.join(" ");
-            let use_case = RemoveYak::new(&storage, &output, &log);
+            let use_case = BlameYak::new(&storage, &output, &log).with_time_style(time_style);
             use_case.execute(&name_str)
         }
-        Commands::Prune => {
-            let use_case = PruneYaks::new(&storage, &output, &log);
+        Commands::Restore { name } => {
+            let name_str = name.join(" ");
+            let history = UnsealedHistory::new(&log, &storage);
+            let use_case = RestoreYak::new(&storage, &output, &log, &history);
+            use_case.execute(&name_str)
+        }
+        Commands::Archive { name } => {
+            let name_str = name.join(" ");
+            let use_case = ArchiveYak::new(&storage, &output, &log, &storage);
+            use_case.execute(&name_str)
+        }
+        Commands::Unarchive { name } => {
+            let name_str = name.join(" ");
+            let archived = storage.archived();
+            let use_case = UnarchiveYak::new(&archived, &output, &log, &storage);
+            use_case.execute(&name_str)
+        }
+        Commands::Prune { force } => {
+            let use_case = PruneYaks::new(&storage, &output, &log)
+                .with_force(force)
+                .with_record(&storage);
             use_case.execute()
         }
         Commands::Move { from, to } => {
-            let use_case = MoveYak::new(&storage, &output, &log);
+            let config = GitConfig::new()?;
+            let state = GitDirState::new()?;
+            let use_case = MoveYak::new(&storage, &output, &log)
+                .with_config(&config)
+                .with_read_tracking(&state);
             use_case.execute(&from, &to)
         }
-        Commands::Context { name, show } => {
+        Commands::Split { name, strip } => {
             let name_str = name.join(" ");
-            if show {
-                let use_case = ShowContext::new(&storage, &output);
+            let config = GitConfig::new()?;
+            let use_case = SplitYak::new(&storage, &output, &log).with_config(&config);
+            use_case.execute(&name_str, strip)
+        }
+        Commands::Merge { source, target } => {
+            let use_case = MergeYaks::new(&storage, &output, &log);
+            use_case.execute(&source, &target)
+        }
+        Commands::Note { name, text } => {
+            let state = GitDirState::new()?;
+            let piped = !atty::is(atty::Stream::Stdin);
+            let (name, text) = match (name, text.is_empty()) {
+                // A lone argument in a terminal can only be a note for the focused yak
+                (Some(note), true) if !piped => (name_or_focus("", &storage, &state)?, note),
+                (None, true) if !piped => (name_or_focus("", &storage, &state)?, String::new()),
+                (name, true) => (
+                    name_or_focus(name.as_deref().unwrap_or_default(), &storage, &state)?,
+                    std::io::read_to_string(std::io::stdin())?,
+                ),
+                (name, false) => (name.unwrap_or_default(), text.join(" ")),
+            };
+            let use_case = NoteYak::new(&storage, &output, &log).with_read_tracking(&state);
+            use_case.execute(&name, &text)
+        }
+        Commands::Focus { name, clear } => {
+            let state = GitDirState::new()?;
+            let use_case = FocusYak::new(&storage, &output, &state);
+            if clear {
+                use_case.clear()
+            } else {
+                let name_str = name.join(" ");
+                use_case.execute((!name_str.is_empty()).then_some(name_str.as_str()))
+            }
+        }
+        Commands::Cp {
+            from,
+            to,
+            recursive,
+        } => {
+            let config = GitConfig::new()?;
+            let state = GitDirState::new()?;
+            let use_case = CopyYak::new(&storage, &output, &log)
+                .with_config(&config)
+                .with_read_tracking(&state);
+            use_case.execute(&from, &to, recursive)
+        }
+        Commands::Context {
+            name,
+            show,
+            history,
+            at,
+            restore,
+            diff,
+        } => {
+            let state = GitDirState::new()?;
+            let name_str = name_or_focus(&name.join(" "), &storage, &state)?;
+            let versions = UnsealedHistory::new(&log, &storage);
+            let context_history = || {
+                ContextHistory::new(&storage, &output, &log, &versions)
+                    .with_read_tracking(&state)
+                    .with_time_style(time_style)
+            };
+            if history {
+                context_history().execute(&name_str)
+            } else if let Some(revision) = diff {
+                let revision = Some(revision.as_str()).filter(|revision| !revision.is_empty());
+                context_history().diff(&name_str, revision)
+            } else if let Some(revision) = at {
+                context_history().show(&name_str, &revision)
+            } else if let Some(revision) = restore {
+                context_history().restore(&name_str, &revision)
+            } else if show {
+                let mut use_case = ShowContext::new(&storage, &output).with_read_tracking(&state);
+                if let Some(format) = format {
+                    use_case = use_case.with_format(format);
+                }
                 use_case.execute(&name_str)
             } else {
-                let use_case = EditContext::new(&storage, &output, &log);
+                let use_case = EditContext::new(&storage, &output, &log).with_read_tracking(&state);
                 use_case.execute(&name_str)
             }
         }
+        Commands::Open { name, url } => {
+            let state = GitDirState::new()?;
+            let name_str = name_or_focus(&name.join(" "), &storage, &state)?;
+            let use_case =
+                OpenYak::new(&storage, &output, &log, &SystemBrowser).with_read_tracking(&state);
+            use_case.execute(&name_str, url)
+        }
+        Commands::Link { name, url, remove } => {
+            let use_case = LinkYak::new(&storage, &output, &log);
+            use_case.execute(&name, &url, remove)
+        }
+        Commands::Title { name, title, .. } => {
+            let use_case = TitleYak::new(&storage, &output, &log);
+            use_case.execute(&name, title.as_deref())
+        }
+        Commands::EditAll { prefix, yes } => {
+            let config = GitConfig::new()?;
+            let stash = DirectoryStash::new(&storage)?;
+            let use_case = EditAll::new(&storage, &output, &log)
+                .with_stash(&stash)
+                .with_trash(&storage, &config);
+            use_case.execute(prefix.as_deref(), yes)
+        }
+        Commands::Doing { name, only } => {
+            let state = GitDirState::new()?;
+            let name_str = name_or_focus(&name.join(" "), &storage, &state)?;
+            let use_case = StartYak::new(&storage, &output, &log);
+            use_case.execute(&name_str, only)
+        }
+        Commands::State { name, state } => {
+            let use_case = SetState::new(&storage, &output, &log);
+            use_case.execute(&name, &state)
+        }
+        Commands::Priority { name, priority, .. } => {
+            let use_case = PrioritizeYak::new(&storage, &output, &log);
+            use_case.execute(&name, priority.as_deref())
+        }
+        Commands::Due { name, date, .. } => {
+            let use_case = ScheduleYak::new(&storage, &output, &log);
+            use_case.execute(&name, date.as_deref())
+        }
+        Commands::SuggestOwner { name, assign } => {
+            let blame = GitBlame::new()?;
+            let use_case = SuggestOwner::new(&storage, &output, &log, &blame);
+            use_case.execute(&name, assign)
+        }
+        Commands::Escalate => {
+            let config = GitConfig::new()?;
+            let use_case = EscalateYaks::new(&storage, &output, &log, &config);
+            use_case.execute()
+        }
+        Commands::Estimate { name, estimate, .. } => {
+            let use_case = EstimateYak::new(&storage, &output, &log);
+            use_case.execute(&name, estimate.as_deref())
+        }
+        Commands::Complete { only, prefix } => {
+            let use_case = CompleteNames::new(&storage, &output);
+            use_case.execute(&prefix, only.as_deref())
+        }
+        Commands::Effort { max } => {
+            let use_case = EffortYaks::new(&storage, &output);
+            use_case.execute(max.as_deref())
+        }
+        Commands::Pom { minutes, name } => {
+            let state = GitDirState::new()?;
+            let name_str = name_or_focus(&name.join(" "), &storage, &state)?;
+            let use_case = StartPomodoro::new(&storage, &output, &log, &DesktopTimer);
+            use_case.execute(&name_str, minutes)
+        }
+        Commands::Export {
+            obsidian,
+            output: file,
+            only,
+        } => match (obsidian, format) {
+            (Some(vault), _) => {
+                let use_case = ExportObsidian::new(&storage, &output);
+                use_case.execute(&vault)
+            }
+            (None, Some(format)) => {
+                let use_case =
+                    ExportYaks::new(&storage, &output).with_state_labels(state_labels()?);
+                use_case.execute(format, only.as_deref(), file.as_deref())
+            }
+            (None, None) => anyhow::bail!(
+                "specify an export target, e.g. --obsidian <VAULT_DIR> or --format json"
+            ),
+        },
+        Commands::Import {
+            obsidian,
+            markdown,
+            dry_run,
+        } => match (obsidian, markdown) {
+            (Some(vault), _) => {
+                let stash = DirectoryStash::new(&storage)?;
+                let use_case = ImportObsidian::new(&storage, &output, &log).with_stash(&stash);
+                use_case.execute(&vault)
+            }
+            (None, Some(file)) => {
+                let text = if file.as_os_str() == "-" {
+                    std::io::read_to_string(std::io::stdin())?
+                } else {
+                    std::fs::read_to_string(&file)
+                        .with_context(|| format!("Failed to read {}", file.display()))?
+                };
+                let stash = DirectoryStash::new(&storage)?;
+                let use_case = ImportChecklist::new(&storage, &output, &log).with_stash(&stash);
+                use_case.execute(&text, &file.display().to_string(), dry_run)
+            }
+            (None, None) => anyhow::bail!(
+                "specify an import source, e.g. --obsidian <VAULT_DIR> or --markdown <FILE>"
+            ),
+        },
+        Commands::Bulk { file } => {
+            let (script, source) = match file {
+                Some(file) if file.as_os_str() != "-" => (
+                    std::fs::read_to_string(&file)
+                        .with_context(|| format!("Failed to read {}", file.display()))?,
+                    file.display().to_string(),
+                ),
+                _ => (std::io::read_to_string(std::io::stdin())?, "-".to_string()),
+            };
+            let config = GitConfig::new()?;
+            let stash = DirectoryStash::new(&storage)?;
+            let use_case = BulkYaks::new(&storage, &output, &log, &stash)
+                .with_config(&config)
+                .with_trash(&storage, &config);
+            use_case.execute(&script, &source)
+        }
+        Commands::Stash { command } => {
+            let stash = DirectoryStash::new(&storage)?;
+            match command {
+                StashCommands::List => ListStash::new(&stash, &output)
+                    .with_time_style(time_style)
+                    .execute(),
+                StashCommands::Pop => PopStash::new(&stash, &output, &log).execute(),
+            }
+        }
+        Commands::Trash { command } => match command {
+            TrashCommands::List => ListTrash::new(&storage, &output)
+                .with_time_style(time_style)
+                .execute(),
+            TrashCommands::Restore { name } => {
+                RestoreFromTrash::new(&storage, &output, &log).execute(&name.join(" "))
+            }
+        },
+        Commands::Status => {
+            let sync = YakSync::new(storage.path())?;
+            let state = GitDirState::new()?;
+            let mut use_case = ShowStatus::new(&storage, &sync, &output)
+                .with_state(&state)
+                .with_time_style(time_style);
+            if let Some(format) = format {
+                use_case = use_case.with_format(format);
+            }
+            use_case.execute()
+        }
+        Commands::VerifyNames { fix } => {
+            let use_case = VerifyNames::new(&storage, &output, &log);
+            use_case.execute(fix)
+        }
+        Commands::Diff => {
+            let sync = YakSync::new(storage.path())?;
+            let use_case = DiffYaks::new(&sync, &output);
+            use_case.execute()
+        }
+        Commands::Stats => {
+            let mut use_case = ShowStats::new(&storage, &output, &log).with_pruned(&storage);
+            if let Some(format) = format {
+                use_case = use_case.with_format(format);
+            }
+            use_case.execute()
+        }
+        Commands::Report { command, since } => match command {
+            Some(ReportCommands::Heatmap) => {
+                let use_case = ReportHeatmap::new(&storage, &output, &log);
+                use_case.execute(format.unwrap_or(OutputFormat::Terminal))
+            }
+            None => {
+                let use_case = ReportWork::new(&storage, &output, &log).with_pruned(&storage);
+                use_case.execute(since.as_deref())
+            }
+        },
+        Commands::Plan { show } => {
+            let use_case = PlanWeek::new(&storage, &output, &log);
+            if show {
+                use_case.show()
+            } else {
+                use_case.execute()
+            }
+        }
+        Commands::Standup => {
+            let use_case = ShowStandup::new(&storage, &output, &log);
+            use_case.execute()
+        }
+        Commands::Suggest => {
+            let use_case = SuggestYak::new(&storage, &output);
+            use_case.execute()
+        }
+        Commands::Count {
+            name,
+            only,
+            state,
+            fail_if_any,
+            fail_if_none,
+        } => {
+            let mut use_case = CountYaks::new(&storage, &output);
+            if !name.is_empty() {
+                use_case = use_case.with_scope(name.join(" "));
+            }
+            if let Some(state) = state {
+                use_case = use_case.with_state(state);
+            }
+            use_case.execute(only.as_deref(), fail_if_any, fail_if_none)
+        }
         Commands::Sync => {
-            let sync = GitRefSync::new()?;
-            let use_case = SyncYaks::new(&sync, &output);
+            // Apply retention and escalation first so their changes propagate with this sync
+            let config = GitConfig::new()?;
+            let archived = storage.archived();
+            ApplyRetention::new(&storage, &output, &log, &config, &storage, &archived).execute()?;
+            EscalateYaks::new(&storage, &output, &log, &config).execute_if_configured()?;
+
+            let sync = YakSync::new(storage.path())?;
+            let state = GitDirState::new()?;
+            let mut use_case = SyncYaks::new(&sync, &output).with_state(&state);
+            if let Some(format) = format {
+                use_case = use_case.with_format(format);
+            }
+            use_case.execute()
+        }
+        Commands::Completions { .. }
+        | Commands::Bug
+        | Commands::Init
+        | Commands::Prompt
+        | Commands::All { .. }
+        | Commands::External(_) => {
+            unreachable!("handled before adapters are initialized")
+        }
+    };
+
+    for (typed, name) in storage.fuzzy_matches() {
+        output.detail(&format!("'{typed}' matched '{name}'"));
+    }
+    result
+}
+
+/// Whether `command` changes the yaks, so it holds the lock from start to
+/// finish. Those that wait on an editor, a timer or a series of questions
+/// (context, open, edit-all, pom, plan) lock each write instead, and rm and
+/// prune lock their changes once confirmed, so other commands aren't kept
+/// waiting on the user
+fn changes_yaks(command: &Commands) -> bool {
+    matches!(
+        command,
+        Commands::Add { .. }
+            | Commands::Done { .. }
+            | Commands::Restore { .. }
+            | Commands::Archive { .. }
+            | Commands::Unarchive { .. }
+            | Commands::Move { .. }
+            | Commands::Split { .. }
+            | Commands::Merge { .. }
+            | Commands::Note { .. }
+            | Commands::Cp { .. }
+            | Commands::Link { .. }
+            | Commands::Title { .. }
+            | Commands::Doing { .. }
+            | Commands::State { .. }
+            | Commands::Priority { .. }
+            | Commands::Due { .. }
+            | Commands::SuggestOwner { .. }
+            | Commands::Escalate
+            | Commands::Estimate { .. }
+            | Commands::Effort { .. }
+            | Commands::Import { .. }
+            | Commands::Bulk { .. }
+            | Commands::Stash { .. }
+            | Commands::Trash { .. }
+            | Commands::Migrate { .. }
+            | Commands::VerifyNames { .. }
+            | Commands::Sync
+    )
+}
+
+/// How yak states are shown, from `yaks.display.*` config and the terminal locale
+fn state_labels() -> Result<StateLabels> {
+    let config = GitConfig::new()?;
+    let unicode = supports_unicode();
+    // A bad theme was already warned about when the output was set up
+    let theme = Theme::from_config(&config).unwrap_or_default();
+    let (open, done) = theme.markers(unicode);
+    Ok(StateLabels::from_config(&config, unicode).with_markers(open, done))
+}
+
+/// Terminal width from `$COLUMNS`, when the shell exports it
+fn terminal_width() -> Option<usize> {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse().ok())
+        .filter(|width| *width > 0)
+}
+
+/// Config key for what to do with a mistyped command (see `Autocorrect::parse`)
+const AUTOCORRECT_KEY: &str = "yaks.autocorrect";
+
+/// Run the `yx-<name>` plugin, or work out which command a typo meant
+fn resolve_external(args: Vec<String>) -> Result<Cli> {
+    let (name, rest) = args.split_first().context("missing command")?;
+    if let Some(plugin) = find_plugin(name) {
+        run_plugin(&plugin, rest);
+    }
+
+    let builtins: Vec<String> = Cli::command()
+        .get_subcommands()
+        .filter(|command| !command.is_hide_set())
+        .flat_map(|command| std::iter::once(command.get_name()).chain(command.get_all_aliases()))
+        .map(|name| name.to_string())
+        .collect();
+    let plugins = plugin_names();
+    let candidates: Vec<&str> = builtins
+        .iter()
+        .chain(&plugins)
+        .map(|name| name.as_str())
+        .collect();
+
+    let Some(suggestion) = closest_match(name, &candidates) else {
+        anyhow::bail!("'{name}' is not a yx command. See 'yx --help'.");
+    };
+
+    // Outside a repository there's still global config
+    let autocorrect = GitConfig::new()
+        .ok()
+        .and_then(|config| config.get(AUTOCORRECT_KEY))
+        .map(|value| Autocorrect::parse(&value))
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("{AUTOCORRECT_KEY}: {e}"))?
+        .unwrap_or(Autocorrect::Never);
+
+    match autocorrect {
+        Autocorrect::Never => {
+            anyhow::bail!("'{name}' is not a yx command. Did you mean '{suggestion}'?")
+        }
+        Autocorrect::Prompt => {
+            if !atty::is(atty::Stream::Stdin) {
+                anyhow::bail!("'{name}' is not a yx command. Did you mean '{suggestion}'?");
+            }
+            eprint!("'{name}' is not a yx command. Run '{suggestion}' instead? [y/N] ");
+            let mut answer = String::new();
+            std::io::stdin().read_line(&mut answer)?;
+            if !matches!(answer.trim(), "y" | "Y" | "yes") {
+                anyhow::bail!("'{name}' is not a yx command");
+            }
+        }
+        Autocorrect::After(delay) => {
+            eprintln!("WARNING: '{name}' is not a yx command.");
+            if delay.is_zero() {
+                eprintln!("Continuing under the assumption that you meant '{suggestion}'.");
+            } else {
+                eprintln!(
+                    "Continuing in {:.1} seconds, assuming that you meant '{suggestion}'.",
+                    delay.as_secs_f64()
+                );
+                std::thread::sleep(delay);
+            }
+        }
+    }
+
+    if !builtins.iter().any(|builtin| builtin == suggestion) {
+        if let Some(plugin) = find_plugin(suggestion) {
+            run_plugin(&plugin, rest);
+        }
+    }
+    let corrected = ["yx", suggestion]
+        .into_iter()
+        .map(String::from)
+        .chain(rest.iter().cloned());
+    Ok(Cli::parse_from(corrected))
+}
+
+/// Gitignore .yaks so yaks can be stored in this repository
+fn init_repo(output: &dyn OutputPort, config: &dyn ConfigPort) -> Result<()> {
+    if let Some(path) = DirectoryStorage::data_path(config, std::path::Path::new("")) {
+        output.info(&format!(
+            "Already set up - yaks are kept in {}, outside the work tree",
+            path.display()
+        ));
+    } else if DirectoryStorage::init()? {
+        output.success("Added .yaks to .gitignore - ready to add yaks");
+    } else {
+        output.info("Already set up - .yaks is gitignored");
+    }
+    Ok(())
+}
+
+fn show_prompt(porcelain: bool) -> Result<()> {
+    let storage = DirectoryStorage::new(&GitConfig::new()?)?;
+    let state = GitDirState::new()?;
+    ShowPrompt::new(&storage, &ConsoleOutput::default())
+        .with_state(&state)
+        .execute(porcelain)
+}
+
+/// Hand over to a plugin, exiting with its status
+fn run_plugin(plugin: &std::path::Path, args: &[String]) -> ! {
+    match std::process::Command::new(plugin).args(args).status() {
+        Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            eprintln!("Error: Failed to run {}: {e}", plugin.display());
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_all(
+    command: AllCommands,
+    format: Option<OutputFormat>,
+    output: &ConsoleOutput,
+) -> Result<()> {
+    let config = GitConfig::new()?;
+    let workspaces = configured_workspaces(&config);
+    let cipher: Rc<dyn CipherPort> = Rc::new(YakCipher::from_config(&config));
+    let storages: Vec<DirectoryStorage> = workspaces
+        .iter()
+        .map(|workspace| {
+            let config = GitConfig::for_work_tree(&workspace.path)?;
+            let storage = DirectoryStorage::for_work_tree(&config, &workspace.path)
+                .with_cipher(cipher.clone());
+            // Each workspace's yaks are upgraded as for a command run inside
+            // it; one that can't be still has its error reported when listed
+            let output = PrefixedOutput::new(&workspace.name, output);
+            let upgraded = YakLog::for_work_tree(&workspace.path, storage.path())
+                .and_then(|log| MigrateYaks::new(&storage, &storage, &output, &log).upgrade());
+            if let Err(e) = upgraded {
+                output.error(&e.to_string());
+            }
+            Ok(storage)
+        })
+        .collect::<Result<_>>()?;
+
+    match command {
+        AllCommands::List { only, mine } => {
+            let named = workspaces
+                .iter()
+                .zip(&storages)
+                .map(|(workspace, storage)| (workspace.name.clone(), storage as &dyn StoragePort))
+                .collect();
+            let mut use_case = ListAllYaks::new(named, output).with_state_labels(state_labels()?);
+            if mine {
+                let Some(email) = config.get("user.email") else {
+                    anyhow::bail!("--mine needs your git user.email to be set");
+                };
+                use_case = use_case.with_assignee(email);
+            }
+            use_case.execute(format.unwrap_or(OutputFormat::Markdown), only.as_deref())
+        }
+        AllCommands::Status => {
+            let adapters: Vec<Result<(YakSync, GitDirState)>> = workspaces
+                .iter()
+                .zip(&storages)
+                .map(|(workspace, storage)| {
+                    Ok((
+                        YakSync::for_work_tree(&workspace.path, storage.path())?,
+                        GitDirState::for_work_tree(&workspace.path)?,
+                    ))
+                })
+                .collect();
+            let named = workspaces
+                .iter()
+                .zip(&storages)
+                .zip(&adapters)
+                .map(|((workspace, storage), adapters)| {
+                    let ports = match adapters {
+                        Ok((sync, state)) => Ok(WorkspacePorts {
+                            storage,
+                            sync,
+                            state,
+                        }),
+                        Err(e) => Err(anyhow::anyhow!("{e}")),
+                    };
+                    (workspace.name.clone(), ports)
+                })
+                .collect();
+            let use_case = ShowAllStatus::new(named, output);
             use_case.execute()
         }
     }
@@ -0,0 +1,11 @@
+// Archive port - moves yaks out of the active list without deleting them
+
+use anyhow::Result;
+
+pub trait ArchivePort {
+    /// Move a yak and everything under it into the archive
+    fn archive(&self, name: &str) -> Result<()>;
+
+    /// Move an archived yak and everything under it back into the active list
+    fn unarchive(&self, name: &str) -> Result<()>;
+}
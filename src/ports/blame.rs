@@ -0,0 +1,19 @@
+// Blame port - who wrote which code, according to version control history
+
+use anyhow::Result;
+
+/// Someone who authored code
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Author {
+    pub name: String,
+    pub email: String,
+}
+
+pub trait BlamePort {
+    /// Authors of a file's current lines, with how many lines each one wrote
+    /// The path is relative to the repository root.
+    fn blame_file(&self, path: &str) -> Result<Vec<(Author, usize)>>;
+
+    /// Author of a commit, given a full or abbreviated hash
+    fn commit_author(&self, rev: &str) -> Result<Author>;
+}
@@ -0,0 +1,8 @@
+// Browser port - showing web pages to the user
+
+use anyhow::Result;
+
+pub trait BrowserPort {
+    /// Open a URL in the user's browser
+    fn open(&self, url: &str) -> Result<()>;
+}
@@ -0,0 +1,14 @@
+// Cipher port - encrypts the files of yaks kept secret, so they're stored and synced as ciphertext
+
+use anyhow::Result;
+
+pub trait CipherPort {
+    /// Whether the yak's files are to be stored encrypted
+    fn covers(&self, name: &str) -> bool;
+
+    /// `text` as ciphertext that's safe to store and sync as a text file
+    fn encrypt(&self, text: &str) -> Result<String>;
+
+    /// The text `ciphertext` was made from, or None without a key that opens it
+    fn decrypt(&self, ciphertext: &str) -> Result<Option<String>>;
+}
@@ -0,0 +1,10 @@
+// Config port - read-only access to per-repo yak settings
+
+pub trait ConfigPort {
+    /// Read a config value by key (e.g., "yaks.retention.doneDays")
+    /// Returns None if the key is not set
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Read every value of a multi-valued key (e.g., "yaks.workspace"), in config order
+    fn get_all(&self, key: &str) -> Vec<String>;
+}
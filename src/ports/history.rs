@@ -0,0 +1,65 @@
+// History port - queries over the yak operation history in refs/notes/yaks
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+/// One recorded operation in the yak history
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Revision {
+    pub id: String,
+    pub command: String,
+    pub author: String,
+    pub time: DateTime<Utc>,
+}
+
+/// A yak's files as they were in some revision, e.g. ("context.md", "...")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YakSnapshot {
+    pub name: String,
+    pub files: Vec<(String, String)>,
+}
+
+/// One of a yak's own files as a revision left it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileVersion {
+    pub revision: Revision,
+    pub content: String,
+}
+
+/// How a file changed in a revision
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(any(feature = "libgit2", feature = "git-cli")), allow(dead_code))]
+pub enum ChangeKind {
+    Added,
+    Modified,
+    Deleted,
+}
+
+/// A file under a yak's folder that a revision changed, e.g. ("context.md", Modified)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    pub path: String,
+    pub kind: ChangeKind,
+}
+
+pub trait HistoryPort {
+    /// Recorded operations, newest first; with a name, only those that changed
+    /// that yak or anything under it
+    fn revisions(&self, name: Option<&str>) -> Result<Vec<Revision>>;
+
+    /// Files under the yak's folder that a revision changed, relative to that folder
+    fn changed_files(&self, revision: &str, name: &str) -> Result<Vec<FileChange>>;
+
+    /// The newest revision that still had the yak, with the yak and everything
+    /// under it as they were then (parents before children)
+    fn last_snapshot(&self, name: &str) -> Result<Option<(Revision, Vec<YakSnapshot>)>>;
+
+    /// Each version of one of the yak's own files (e.g. "context.md"), newest
+    /// first: the revisions that changed it, with its text then; revisions
+    /// that removed it are left out
+    fn file_versions(&self, name: &str, file: &str) -> Result<Vec<FileVersion>>;
+
+    /// One of the yak's own files as origin had it when `yx sync` last fetched,
+    /// or None when nothing was fetched or origin doesn't have it
+    fn remote_file_version(&self, name: &str, file: &str) -> Result<Option<FileVersion>>;
+}
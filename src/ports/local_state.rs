@@ -0,0 +1,12 @@
+// Local state port - per-repo state that stays on this machine and is never synced
+
+use anyhow::Result;
+
+pub trait LocalStatePort {
+    /// Read a local state value by key
+    /// Returns None if nothing has been stored
+    fn read(&self, key: &str) -> Result<Option<String>>;
+
+    /// Write a local state value, or clear it when value is None
+    fn write(&self, key: &str, value: Option<&str>) -> Result<()>;
+}
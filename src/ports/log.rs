@@ -1,8 +1,19 @@
 // Log port - commits yak operations to git history
 
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+/// A logged command and when it was recorded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    pub command: String,
+    pub time: DateTime<Utc>,
+}
 
 pub trait LogPort {
     /// Log a command by committing current .yaks state to refs/notes/yaks
     fn log_command(&self, command: &str) -> Result<()>;
+
+    /// Read back all logged commands, oldest first
+    fn entries(&self) -> Result<Vec<LogEntry>>;
 }
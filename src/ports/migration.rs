@@ -0,0 +1,28 @@
+// Migration port - brings yaks kept in an older storage format up to date
+
+use anyhow::Result;
+
+/// One change to how yaks are kept, applied once to yaks stored before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Migration {
+    /// The format version the yaks are at once this has been applied
+    pub version: u32,
+    /// Short name, e.g. "state"
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+pub trait MigrationPort {
+    /// Every migration this storage knows, oldest first
+    fn migrations(&self) -> Vec<Migration>;
+
+    /// The storage format version the yaks are kept in
+    fn format_version(&self) -> Result<u32>;
+
+    /// Migrations the yaks haven't had yet, oldest first; fails for yaks
+    /// kept in a newer format than this build knows
+    fn pending_migrations(&self) -> Result<Vec<Migration>>;
+
+    /// Apply `migration` and record the version it brings the yaks to
+    fn apply_migration(&self, migration: &Migration) -> Result<()>;
+}
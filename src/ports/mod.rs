@@ -1,11 +1,37 @@
 // Port traits - define interfaces between domain and adapters
 
+pub mod archive;
+pub mod blame;
+pub mod browser;
+pub mod cipher;
+pub mod config;
+pub mod history;
+pub mod local_state;
 pub mod log;
+pub mod migration;
 pub mod output;
+pub mod pruned;
+pub mod stash;
 pub mod storage;
 pub mod sync;
+pub mod timer;
+pub mod trash;
+pub mod watcher;
 
-pub use log::LogPort;
-pub use output::OutputPort;
-pub use storage::StoragePort;
-pub use sync::SyncPort;
+pub use archive::ArchivePort;
+pub use blame::{Author, BlamePort};
+pub use browser::BrowserPort;
+pub use cipher::CipherPort;
+pub use config::ConfigPort;
+pub use history::{ChangeKind, FileChange, FileVersion, HistoryPort, Revision, YakSnapshot};
+pub use local_state::LocalStatePort;
+pub use log::{LogEntry, LogPort};
+pub use migration::{Migration, MigrationPort};
+pub use output::{OutputPort, Style, Verbosity};
+pub use pruned::{PrunedPort, PrunedYak};
+pub use stash::{StashEntry, StashPort};
+pub use storage::{StoragePort, YakIter};
+pub use sync::{IncomingChange, SyncPhase, SyncPort, SyncProgress, SyncStatus};
+pub use timer::TimerPort;
+pub use trash::{TrashEntry, TrashPort};
+pub use watcher::WatcherPort;
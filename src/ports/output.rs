@@ -1,7 +1,39 @@
 // Output port trait - abstraction for displaying results to user
 
+/// How much a command prints, chosen with the global `--quiet` and `--verbose`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Errors and results only: no success messages, hints or detail
+    Quiet,
+    #[default]
+    Normal,
+    /// Also extra detail, such as the yak a partial name resolved to
+    Verbose,
+}
+
+/// What a piece of text is, so the output can color it (or not)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// A done yak, shown dimmed
+    Done,
+    /// A yak name leading a search result
+    Name,
+    /// A line number in a search result
+    LineNumber,
+    /// The text a search matched
+    Match,
+    /// A warning
+    Warning,
+    /// A line a diff adds
+    Added,
+    /// A line a diff removes
+    Removed,
+    /// The header of a diff hunk, e.g. "@@ -1,4 +1,5 @@"
+    Hunk,
+}
+
 pub trait OutputPort {
-    /// Display success message
+    /// Display success message: what a command did, not its result
     #[allow(dead_code)]
     fn success(&self, message: &str);
 
@@ -9,6 +41,68 @@ pub trait OutputPort {
     #[allow(dead_code)]
     fn error(&self, message: &str);
 
-    /// Display informational message
+    /// Display a command's result: the lists, contexts and exports scripts pipe
     fn info(&self, message: &str);
+
+    /// Display a status message that isn't the command's result, e.g. "No
+    /// stashed yaks" or "Nothing removed"
+    /// Output that doesn't separate the two treats it as info, hence the default.
+    fn note(&self, message: &str) {
+        self.info(message);
+    }
+
+    /// Display a warning: something was skipped or unexpected, but the command
+    /// carried on (e.g. the yak already existed, the push to origin failed)
+    fn warn(&self, message: &str);
+
+    /// Suggest a next step to a person at the terminal
+    /// Output meant for scripts leaves hints out, so the default drops them.
+    fn hint(&self, _message: &str) {}
+
+    /// Extra detail for `--verbose`, such as the steps a sync takes
+    /// Only shown when asked for, so the default drops it.
+    fn detail(&self, _message: &str) {}
+
+    /// `text` marked up to show as `style`
+    /// Output that isn't a color terminal shows text as-is, so the default does nothing.
+    fn paint(&self, text: &str, _style: Style) -> String {
+        text.to_string()
+    }
+
+    /// `code` from a fenced block marked up as `language` source, e.g. "rust" or "sh"
+    /// Output that can't highlight, or doesn't know the language, shows code as-is.
+    fn highlight(&self, code: &str, _language: &str) -> String {
+        code.to_string()
+    }
+
+    /// The level this output prints at, so callers can skip work that only
+    /// verbose output needs
+    fn verbosity(&self) -> Verbosity {
+        Verbosity::Normal
+    }
+
+    /// Ask the user a yes/no question (shown with "[y/N]")
+    /// Returns None when nobody can answer, e.g. stdin isn't a terminal, so
+    /// callers go ahead as if the question hadn't been asked.
+    fn confirm(&self, _question: &str) -> Option<bool> {
+        None
+    }
+
+    /// Ask the user for a line of text, returned without the newline
+    /// Returns None when nobody can answer or input has ended.
+    fn ask(&self, _prompt: &str) -> Option<String> {
+        None
+    }
+
+    /// Show how far a long-running step has got, e.g. "Fetching from origin
+    /// (12/40 objects)", replacing the last progress shown
+    /// Output that isn't a terminal has nowhere to redraw, so the default drops it.
+    fn progress(&self, _message: &str) {}
+
+    /// Take down the progress shown, once the step is over
+    fn progress_done(&self) {}
+
+    /// Clear the screen before redrawing a live view
+    /// Output that isn't a terminal keeps every redraw, so the default does nothing.
+    fn clear(&self) {}
 }
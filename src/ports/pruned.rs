@@ -0,0 +1,22 @@
+// Pruned port - a lasting record of the done yaks `yx prune` removed
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+/// A done yak as it was when pruned
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrunedYak {
+    pub name: String,
+    pub title: Option<String>,
+    pub completed: Option<DateTime<Utc>>,
+    pub pruned: DateTime<Utc>,
+    pub context: String,
+}
+
+pub trait PrunedPort {
+    /// Add yaks to the record; earlier entries are never changed
+    fn record_pruned(&self, yaks: &[PrunedYak]) -> Result<()>;
+
+    /// Every recorded yak, in the order they were pruned
+    fn pruned(&self) -> Result<Vec<PrunedYak>>;
+}
@@ -0,0 +1,30 @@
+// Stash port - snapshots of yaks taken before risky operations
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+/// A stored snapshot, newest first when listed (`stash@{0}` is the latest)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StashEntry {
+    /// What the snapshot was taken before, e.g. "import --obsidian vault"
+    pub reason: String,
+    pub created: DateTime<Utc>,
+    /// Yaks captured in the snapshot
+    pub yaks: Vec<String>,
+}
+
+pub trait StashPort {
+    /// Snapshot the given yaks; yaks that don't exist yet are recorded as absent
+    /// so popping the stash removes them again
+    fn push(&self, reason: &str, names: &[String]) -> Result<()>;
+
+    /// All snapshots, newest first
+    fn list(&self) -> Result<Vec<StashEntry>>;
+
+    /// Restore the newest snapshot and drop it, returning it if there was one
+    fn pop(&self) -> Result<Option<StashEntry>>;
+
+    /// Drop the newest snapshot without restoring it, e.g. when the change it
+    /// was taken before was rolled back
+    fn discard(&self) -> Result<()>;
+}
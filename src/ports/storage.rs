@@ -2,6 +2,10 @@
 
 use crate::domain::Yak;
 use anyhow::Result;
+use std::path::PathBuf;
+
+/// Yaks read one at a time, as `StoragePort::iter_yaks` returns them
+pub type YakIter<'a> = Box<dyn Iterator<Item = Result<Yak>> + 'a>;
 
 pub trait StoragePort {
     /// Create a new yak
@@ -10,9 +14,30 @@ pub trait StoragePort {
     /// Get a yak by name
     fn get_yak(&self, name: &str) -> Result<Yak>;
 
-    /// List all yaks
+    /// List all yaks, without reading their contexts (left as None); most
+    /// commands never show them, and reading every context.md is most of
+    /// the work for large sets
     fn list_yaks(&self) -> Result<Vec<Yak>>;
 
+    /// List all yaks with their contexts, for commands that use them
+    fn list_yaks_with_context(&self) -> Result<Vec<Yak>> {
+        self.iter_yaks(true)?.collect()
+    }
+
+    /// All yaks, read as the iterator is advanced rather than up front;
+    /// without `context` their contexts aren't read and are left as None
+    fn iter_yaks(&self, context: bool) -> Result<YakIter<'_>> {
+        let yaks = self.list_yaks()?;
+        Ok(Box::new(yaks.into_iter().map(move |mut yak| {
+            if !context {
+                yak.context = None;
+            } else if yak.context.is_none() {
+                yak.context = self.read_context(&yak.name).ok();
+            }
+            Ok(yak)
+        })))
+    }
+
     /// Mark a yak as done or undone
     fn mark_done(&self, name: &str, done: bool) -> Result<()>;
 
@@ -22,12 +47,43 @@ pub trait StoragePort {
     /// Rename a yak
     fn rename_yak(&self, from: &str, to: &str) -> Result<()>;
 
+    /// Copy a yak's context, state and metadata to a new name
+    /// Children are not copied
+    fn copy_yak(&self, from: &str, to: &str) -> Result<()>;
+
     /// Read context for a yak
     fn read_context(&self, name: &str) -> Result<String>;
 
     /// Write context for a yak
     fn write_context(&self, name: &str, text: &str) -> Result<()>;
 
+    /// The file holding a yak's context, for storages that keep one on disk
+    fn context_file(&self, _name: &str) -> Option<PathBuf> {
+        None
+    }
+
+    /// A value that changes whenever any yak does, so watchers can skip
+    /// unchanged redraws; None when the storage can't tell
+    fn revision(&self) -> Result<Option<u64>> {
+        Ok(None)
+    }
+
+    /// Run `steps` so that either all of their changes apply or, when they
+    /// fail, none do; storages that can't put yaks back just run them
+    fn transaction(&self, steps: &mut dyn FnMut() -> Result<()>) -> Result<()> {
+        steps()
+    }
+
+    /// Read a metadata field for a yak (e.g., "links")
+    /// Returns None if the field has not been set
+    fn read_field(&self, name: &str, field: &str) -> Result<Option<String>>;
+
+    /// Write a metadata field for a yak, or clear it when value is None
+    fn write_field(&self, name: &str, field: &str, value: Option<&str>) -> Result<()>;
+
+    /// The metadata fields set for a yak, by name
+    fn list_fields(&self, name: &str) -> Result<Vec<String>>;
+
     /// Find a yak by name or fuzzy match
     /// Returns the exact name if found, or a unique fuzzy match
     /// Returns error if not found or ambiguous
@@ -2,6 +2,44 @@
 
 use anyhow::Result;
 
+/// How local yaks compare with the last synced state and the remote
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncStatus {
+    /// Whether .yaks has changes not yet committed to refs/notes/yaks
+    pub has_local_changes: bool,
+    /// (ahead, behind) commit counts against origin, or None without a remote yaks ref
+    pub remote: Option<(usize, usize)>,
+}
+
+/// How a sync would change one local yak
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(feature = "libgit2"), allow(dead_code))]
+pub enum IncomingChange {
+    Added,
+    Completed,
+    Reopened,
+    Updated,
+    Removed,
+}
+
+/// A step of a sync, in the order they run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(feature = "libgit2"), allow(dead_code))]
+pub enum SyncPhase {
+    Fetch,
+    Merge,
+    Push,
+}
+
+/// How far a sync has got: the step it's on and, while fetching or pushing,
+/// the objects transferred so far out of the total
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(feature = "libgit2"), allow(dead_code))]
+pub struct SyncProgress {
+    pub phase: SyncPhase,
+    pub objects: Option<(usize, usize)>,
+}
+
 pub trait SyncPort {
     /// Push local yaks to git refs
     #[allow(dead_code)]
@@ -11,6 +49,15 @@ pub trait SyncPort {
     #[allow(dead_code)]
     fn pull(&self) -> Result<()>;
 
-    /// Sync yaks (push + pull with merge)
-    fn sync(&self) -> Result<()>;
+    /// Sync yaks (push + pull with merge), calling `progress` as each step
+    /// starts and as objects are transferred
+    /// Returns warnings about steps that were skipped without failing the sync,
+    /// e.g. origin refusing the push.
+    fn sync(&self, progress: &dyn Fn(SyncProgress)) -> Result<Vec<String>>;
+
+    /// Compare local yaks with the last sync and the remote without changing anything
+    fn status(&self) -> Result<SyncStatus>;
+
+    /// What a sync would do to the local yaks, by yak name, without changing anything
+    fn preview(&self) -> Result<Vec<(String, IncomingChange)>>;
 }
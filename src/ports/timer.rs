@@ -0,0 +1,13 @@
+// Timer port - waiting on and announcing the end of focus sessions
+
+use anyhow::Result;
+use std::time::Duration;
+
+pub trait TimerPort {
+    /// Block until the duration has elapsed
+    fn wait(&self, duration: Duration) -> Result<()>;
+
+    /// Let the user know a timer finished (e.g., desktop notification)
+    /// Best effort - failing to notify must not fail the command
+    fn notify(&self, title: &str, message: &str);
+}
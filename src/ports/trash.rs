@@ -0,0 +1,23 @@
+// Trash port - keeps removed yaks around until they're restored
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+/// A yak moved to the trash, with everything that was under it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashEntry {
+    pub name: String,
+    pub removed: DateTime<Utc>,
+}
+
+pub trait TrashPort {
+    /// Move a yak and everything under it into the trash
+    fn trash(&self, name: &str) -> Result<()>;
+
+    /// Yaks in the trash, most recently removed first
+    fn trashed(&self) -> Result<Vec<TrashEntry>>;
+
+    /// Move the most recently removed yak called `name` (and what was under
+    /// it) back out of the trash
+    fn restore_trashed(&self, name: &str) -> Result<()>;
+}
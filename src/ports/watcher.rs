@@ -0,0 +1,10 @@
+// Watcher port - waiting for the yaks to change
+
+use anyhow::Result;
+use std::time::Duration;
+
+pub trait WatcherPort {
+    /// Block until the yaks may have changed, or at most `timeout`
+    /// Waking without a change is fine; callers check what changed.
+    fn wait_for_change(&self, timeout: Duration) -> Result<()>;
+}
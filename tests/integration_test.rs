@@ -1,8 +1,7 @@
 use anyhow::Result;
-use serial_test::serial;
-use std::env;
 use tempfile::TempDir;
-use yx::ports::{LogPort, StoragePort};
+use yx::adapters::storage::{DirectoryStorage, InMemoryStorage};
+use yx::ports::{LogEntry, LogPort, StoragePort};
 
 /// No-op log implementation for tests
 struct NoOpLog;
@@ -11,42 +10,38 @@ impl LogPort for NoOpLog {
     fn log_command(&self, _command: &str) -> Result<()> {
         Ok(())
     }
+
+    fn entries(&self) -> Result<Vec<LogEntry>> {
+        Ok(Vec::new())
+    }
 }
 
-/// Helper to run yx commands in a test environment
+/// A yaks folder of its own, for tests that check what lands on disk
 struct TestEnv {
-    _temp_dir: TempDir,
-    yak_path: String,
+    temp_dir: TempDir,
 }
 
 impl TestEnv {
     fn new() -> Self {
-        let temp_dir = TempDir::new().unwrap();
-        let yak_path = temp_dir.path().to_str().unwrap().to_string();
-
         Self {
-            _temp_dir: temp_dir,
-            yak_path,
+            temp_dir: TempDir::new().unwrap(),
         }
     }
 
+    fn storage(&self) -> DirectoryStorage {
+        DirectoryStorage::at(self.temp_dir.path().to_path_buf())
+    }
+
     fn yak_exists(&self, name: &str) -> bool {
-        let path = format!("{}/{}", self.yak_path, name);
-        std::path::Path::new(&path).exists()
+        self.temp_dir.path().join(name).exists()
     }
 }
 
 #[test]
-#[serial]
 fn test_add_yak_creates_directory() {
     let test_env = TestEnv::new();
-
-    // Set YAK_PATH for the test
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    // Create DirectoryStorage and ConsoleOutput
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = test_env.storage();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // Execute AddYak use case
     let use_case = yx::application::AddYak::new(&storage, &output, &NoOpLog);
@@ -57,13 +52,9 @@ fn test_add_yak_creates_directory() {
 }
 
 #[test]
-#[serial]
 fn test_add_yak_can_be_retrieved() {
-    let test_env = TestEnv::new();
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = InMemoryStorage::new();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // Add a yak
     let add_use_case = yx::application::AddYak::new(&storage, &output, &NoOpLog);
@@ -76,27 +67,21 @@ fn test_add_yak_can_be_retrieved() {
 }
 
 #[test]
-#[serial]
 fn test_list_empty_yaks() {
-    let test_env = TestEnv::new();
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = InMemoryStorage::new();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // List should succeed even with no yaks
     let list_use_case = yx::application::ListYaks::new(&storage, &output);
-    list_use_case.execute("plain", None).unwrap();
+    list_use_case
+        .execute(yx::application::OutputFormat::Plain, None)
+        .unwrap();
 }
 
 #[test]
-#[serial]
 fn test_list_shows_added_yaks() {
-    let test_env = TestEnv::new();
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = InMemoryStorage::new();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // Add some yaks
     let add_use_case = yx::application::AddYak::new(&storage, &output, &NoOpLog);
@@ -105,7 +90,9 @@ fn test_list_shows_added_yaks() {
 
     // List them
     let list_use_case = yx::application::ListYaks::new(&storage, &output);
-    list_use_case.execute("plain", None).unwrap();
+    list_use_case
+        .execute(yx::application::OutputFormat::Plain, None)
+        .unwrap();
 
     // Verify both yaks exist
     let yaks = storage.list_yaks().unwrap();
@@ -115,13 +102,9 @@ fn test_list_shows_added_yaks() {
 }
 
 #[test]
-#[serial]
 fn test_done_yak_marks_as_done() {
-    let test_env = TestEnv::new();
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = InMemoryStorage::new();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // Add a yak
     let add_use_case = yx::application::AddYak::new(&storage, &output, &NoOpLog);
@@ -129,7 +112,7 @@ fn test_done_yak_marks_as_done() {
 
     // Mark it as done
     let done_use_case = yx::application::DoneYak::new(&storage, &output, &NoOpLog);
-    done_use_case.execute("test-yak", false, false).unwrap();
+    done_use_case.execute(&["test-yak"], false, false).unwrap();
 
     // Verify it's marked as done
     let yak = storage.get_yak("test-yak").unwrap();
@@ -137,26 +120,22 @@ fn test_done_yak_marks_as_done() {
 }
 
 #[test]
-#[serial]
 fn test_done_yak_with_undo_marks_as_not_done() {
-    let test_env = TestEnv::new();
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = InMemoryStorage::new();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // Add a yak and mark it done
     let add_use_case = yx::application::AddYak::new(&storage, &output, &NoOpLog);
     add_use_case.execute("test-yak").unwrap();
     let done_use_case = yx::application::DoneYak::new(&storage, &output, &NoOpLog);
-    done_use_case.execute("test-yak", false, false).unwrap();
+    done_use_case.execute(&["test-yak"], false, false).unwrap();
 
     // Verify it's marked as done
     let yak = storage.get_yak("test-yak").unwrap();
     assert!(yak.done);
 
     // Mark it as not done using undo flag
-    done_use_case.execute("test-yak", true, false).unwrap();
+    done_use_case.execute(&["test-yak"], true, false).unwrap();
 
     // Verify it's no longer marked as done
     let yak = storage.get_yak("test-yak").unwrap();
@@ -164,29 +143,22 @@ fn test_done_yak_with_undo_marks_as_not_done() {
 }
 
 #[test]
-#[serial]
 fn test_done_yak_fails_for_nonexistent_yak() {
-    let test_env = TestEnv::new();
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = InMemoryStorage::new();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // Try to mark a non-existent yak as done
     let done_use_case = yx::application::DoneYak::new(&storage, &output, &NoOpLog);
-    let result = done_use_case.execute("nonexistent", false, false);
+    let result = done_use_case.execute(&["nonexistent"], false, false);
 
     assert!(result.is_err());
 }
 
 #[test]
-#[serial]
 fn test_remove_yak_deletes_directory() {
     let test_env = TestEnv::new();
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = test_env.storage();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // Add a yak
     let add_use_case = yx::application::AddYak::new(&storage, &output, &NoOpLog);
@@ -197,59 +169,49 @@ fn test_remove_yak_deletes_directory() {
 
     // Remove it
     let remove_use_case = yx::application::RemoveYak::new(&storage, &output, &NoOpLog);
-    remove_use_case.execute("test-yak").unwrap();
+    remove_use_case.execute("test-yak", false).unwrap();
 
     // Verify it no longer exists
     assert!(!test_env.yak_exists("test-yak"));
 }
 
 #[test]
-#[serial]
 fn test_remove_yak_fails_for_nonexistent_yak() {
-    let test_env = TestEnv::new();
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = InMemoryStorage::new();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // Try to remove a non-existent yak
     let remove_use_case = yx::application::RemoveYak::new(&storage, &output, &NoOpLog);
-    let result = remove_use_case.execute("nonexistent");
+    let result = remove_use_case.execute("nonexistent", false);
 
     assert!(result.is_err());
 }
 
 #[test]
-#[serial]
 fn test_remove_yak_can_remove_done_yak() {
     let test_env = TestEnv::new();
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = test_env.storage();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // Add a yak and mark it done
     let add_use_case = yx::application::AddYak::new(&storage, &output, &NoOpLog);
     add_use_case.execute("done-yak").unwrap();
     let done_use_case = yx::application::DoneYak::new(&storage, &output, &NoOpLog);
-    done_use_case.execute("done-yak", false, false).unwrap();
+    done_use_case.execute(&["done-yak"], false, false).unwrap();
 
     // Remove the done yak
     let remove_use_case = yx::application::RemoveYak::new(&storage, &output, &NoOpLog);
-    remove_use_case.execute("done-yak").unwrap();
+    remove_use_case.execute("done-yak", false).unwrap();
 
     // Verify it's gone
     assert!(!test_env.yak_exists("done-yak"));
 }
 
 #[test]
-#[serial]
 fn test_prune_removes_all_done_yaks() {
     let test_env = TestEnv::new();
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = test_env.storage();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // Add multiple yaks
     let add_use_case = yx::application::AddYak::new(&storage, &output, &NoOpLog);
@@ -259,8 +221,12 @@ fn test_prune_removes_all_done_yaks() {
 
     // Mark some as done
     let done_use_case = yx::application::DoneYak::new(&storage, &output, &NoOpLog);
-    done_use_case.execute("done-yak-1", false, false).unwrap();
-    done_use_case.execute("done-yak-2", false, false).unwrap();
+    done_use_case
+        .execute(&["done-yak-1"], false, false)
+        .unwrap();
+    done_use_case
+        .execute(&["done-yak-2"], false, false)
+        .unwrap();
 
     // Prune done yaks
     let prune_use_case = yx::application::PruneYaks::new(&storage, &output, &NoOpLog);
@@ -275,13 +241,10 @@ fn test_prune_removes_all_done_yaks() {
 }
 
 #[test]
-#[serial]
 fn test_prune_handles_no_done_yaks() {
     let test_env = TestEnv::new();
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = test_env.storage();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // Add only active yaks
     let add_use_case = yx::application::AddYak::new(&storage, &output, &NoOpLog);
@@ -298,13 +261,9 @@ fn test_prune_handles_no_done_yaks() {
 }
 
 #[test]
-#[serial]
 fn test_prune_handles_empty_list() {
-    let test_env = TestEnv::new();
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = InMemoryStorage::new();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // Prune when no yaks exist (should handle gracefully)
     let prune_use_case = yx::application::PruneYaks::new(&storage, &output, &NoOpLog);
@@ -312,13 +271,10 @@ fn test_prune_handles_empty_list() {
 }
 
 #[test]
-#[serial]
 fn test_move_yak_renames_directory() {
     let test_env = TestEnv::new();
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = test_env.storage();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // Add a yak
     let add_use_case = yx::application::AddYak::new(&storage, &output, &NoOpLog);
@@ -337,19 +293,15 @@ fn test_move_yak_renames_directory() {
 }
 
 #[test]
-#[serial]
 fn test_move_yak_preserves_done_status() {
-    let test_env = TestEnv::new();
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = InMemoryStorage::new();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // Add a yak and mark it done
     let add_use_case = yx::application::AddYak::new(&storage, &output, &NoOpLog);
     add_use_case.execute("done-yak").unwrap();
     let done_use_case = yx::application::DoneYak::new(&storage, &output, &NoOpLog);
-    done_use_case.execute("done-yak", false, false).unwrap();
+    done_use_case.execute(&["done-yak"], false, false).unwrap();
 
     // Move it
     let move_use_case = yx::application::MoveYak::new(&storage, &output, &NoOpLog);
@@ -363,13 +315,9 @@ fn test_move_yak_preserves_done_status() {
 }
 
 #[test]
-#[serial]
 fn test_move_yak_preserves_context() {
-    let test_env = TestEnv::new();
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = InMemoryStorage::new();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // Add a yak with context
     let add_use_case = yx::application::AddYak::new(&storage, &output, &NoOpLog);
@@ -390,13 +338,9 @@ fn test_move_yak_preserves_context() {
 }
 
 #[test]
-#[serial]
 fn test_move_yak_fails_for_nonexistent_yak() {
-    let test_env = TestEnv::new();
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = InMemoryStorage::new();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // Try to move a non-existent yak
     let move_use_case = yx::application::MoveYak::new(&storage, &output, &NoOpLog);
@@ -406,13 +350,9 @@ fn test_move_yak_fails_for_nonexistent_yak() {
 }
 
 #[test]
-#[serial]
 fn test_move_yak_fails_for_existing_target() {
-    let test_env = TestEnv::new();
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = InMemoryStorage::new();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // Add two yaks
     let add_use_case = yx::application::AddYak::new(&storage, &output, &NoOpLog);
@@ -427,13 +367,9 @@ fn test_move_yak_fails_for_existing_target() {
 }
 
 #[test]
-#[serial]
 fn test_edit_context_fails_for_nonexistent_yak() {
-    let test_env = TestEnv::new();
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = InMemoryStorage::new();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // Try to edit context for a non-existent yak
     let edit_context_use_case = yx::application::EditContext::new(&storage, &output, &NoOpLog);
@@ -449,13 +385,9 @@ fn test_edit_context_fails_for_nonexistent_yak() {
 // tested through end-to-end tests or manual testing.
 
 #[test]
-#[serial]
 fn test_show_context_fails_for_nonexistent_yak() {
-    let test_env = TestEnv::new();
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = InMemoryStorage::new();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // Try to show context for a non-existent yak
     let show_context_use_case = yx::application::ShowContext::new(&storage, &output);
@@ -465,13 +397,9 @@ fn test_show_context_fails_for_nonexistent_yak() {
 }
 
 #[test]
-#[serial]
 fn test_show_context_displays_empty_context() {
-    let test_env = TestEnv::new();
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = InMemoryStorage::new();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // Add a yak with no context
     let add_use_case = yx::application::AddYak::new(&storage, &output, &NoOpLog);
@@ -485,13 +413,9 @@ fn test_show_context_displays_empty_context() {
 }
 
 #[test]
-#[serial]
 fn test_show_context_displays_context_content() {
-    let test_env = TestEnv::new();
-    env::set_var("YAK_PATH", &test_env.yak_path);
-
-    let storage = yx::adapters::storage::DirectoryStorage::new().unwrap();
-    let output = yx::adapters::cli::ConsoleOutput;
+    let storage = InMemoryStorage::new();
+    let output = yx::adapters::cli::ConsoleOutput::default();
 
     // Add a yak
     let add_use_case = yx::application::AddYak::new(&storage, &output, &NoOpLog);
@@ -512,3 +436,25 @@ fn test_show_context_displays_context_content() {
     let context = storage.read_context("test-yak").unwrap();
     assert_eq!(context, "Test context content");
 }
+
+#[test]
+fn test_use_cases_run_against_in_memory_storage() {
+    let storage = InMemoryStorage::new();
+    let output = yx::adapters::cli::ConsoleOutput::default();
+
+    yx::application::AddYak::new(&storage, &output, &NoOpLog)
+        .execute("release/notes")
+        .unwrap();
+    yx::application::DoneYak::new(&storage, &output, &NoOpLog)
+        .execute(&["release/notes"], false, false)
+        .unwrap();
+    yx::application::MoveYak::new(&storage, &output, &NoOpLog)
+        .execute("release", "launch")
+        .unwrap();
+
+    let yaks = storage.list_yaks().unwrap();
+    let names: Vec<&str> = yaks.iter().map(|y| y.name.as_str()).collect();
+    assert_eq!(names, vec!["launch", "launch/notes"]);
+    assert!(yaks[1].done);
+    assert!(yaks[1].created_at.is_some());
+}